@@ -0,0 +1,375 @@
+use super::{
+    directive::Directive,
+    expression::{
+        ArrayExprEle, ArrayExpression, ArrowFunctionBody, ArrowFunctionExpression,
+        AssignmentExpression, AssignmentExpressionLeft, AwaitExpression, BinaryExpression,
+        BinaryOpeartorLeft, CallExprArgs, CallExprCallee, CallExpression, ChainExpression,
+        ChainExpressionElement, ClassExpression, ConditionalExpression, Expression,
+        FunctionExpression, Identifier, ImportExpression, Literal, LogicalExpression,
+        MemberExprObject, MemberExprProperty, MemberExpression, MetaProperty, NewExprArgs,
+        NewExpression, ObjectExpression, ObjectProperty, ParenthesizedExpression,
+        PrivateIdentifier, Property, SequenceExpression, SpreadElement, StaticBlock, Super,
+        TaggedTemplateExpression, TemplateLiteral, ThisExpression, UnaryExpression,
+        UpdateExpression, YieldExpression,
+    },
+    import_export_declaration::{
+        AnonymousDefaultExportedClassDeclaration, AnonymousDefaultExportedFunctionDeclaration,
+        ExportAllDeclaration, ExportAllExportedType, ExportDeclaration, ExportDefaultDeclaration,
+        ExportDefaultDeclarationType, ExportLocal, ExportNamedDeclaration, ExportSpecifier,
+        ExportedType, ImportDeclaration, ImportDefaultSpecifier, ImportNamespaceSpecifier,
+        ImportOrExportDeclaration, ImportSpecifier, ImportSpecifiers, ImportedType,
+    },
+    pattern::{
+        ArrayPattern, AssignmentPattern, AssignmentProperty, ObjectPattern, ObjectPatternProperty,
+        Pattern, RestElement,
+    },
+    statement::{
+        BlockStatement, BreakStatement, CatchClause, ClassBody, ClassBodyEl, ClassDeclaration,
+        ClassMethodKey, ClassPropertyKey, ContinueStatement, DebuggerStatement, DoWhileStatement,
+        EmptyStatement, ExpressionStatement, ForInOfStatementLeft, ForInStatement, ForOfStatement,
+        ForStatement, ForStatementInit, FunctionBody, FunctionBodyContent, FunctionDeclaration,
+        IfStatement, InvalidStatement, LabeledStatement, MethodDefinition, PropertyDefinition,
+        ReturnStatement, Statement, SwitchCase, SwitchStatement, ThrowStatement, TryStatement,
+        VariableDeclaration, VariableDeclarator, WhileStatement, WithStatement,
+    },
+    Box, Program, ProgramNode, SourceLocation,
+};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CommentKind {
+    /// `// ...`，以及非模块代码中的 `<!--` 和 `-->`
+    Line,
+    /// `/* ... */`
+    Block,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Comment<'a> {
+    #[serde(rename(serialize = "type"))]
+    pub kind: CommentKind,
+    /// 注释的内容，不包含注释的开始和结束标记
+    pub value: &'a str,
+    pub start: usize,
+    pub end: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+}
+
+/// 挂载在节点上的注释，只有开启 attach_comments 时才会有内容，所以没有分配在 arena 中
+pub type Comments<'a> = Vec<Comment<'a>>;
+
+/// 可以挂载注释的节点，即各种 Statement、Expression、ClassBodyEl，以及函数体和类的主体。
+/// 函数体和类的主体中的注释不会挂载到外面的节点上，比如函数名
+trait Attachable<'a> {
+    fn range(&self) -> (usize, usize);
+    fn lines(&self) -> (usize, usize);
+    fn comments_mut(&mut self) -> (&mut Comments<'a>, &mut Comments<'a>);
+    /// 没有子节点可以挂载时，节点内部的注释放在这里，只有语句块这样的节点有
+    fn inner_comments_mut(&mut self) -> Option<&mut Comments<'a>>;
+    /// 收集距离最近的可挂载注释的子孙节点
+    fn collect_children<'b>(&'b mut self, out: &mut Vec<&'b mut dyn Attachable<'a>>);
+}
+
+/// 收集可挂载注释的节点，不能挂载注释的节点（例如 Pattern）会继续收集其子节点
+trait CollectAttachable<'a> {
+    fn collect<'b>(&'b mut self, out: &mut Vec<&'b mut dyn Attachable<'a>>);
+}
+
+impl<'a, T: CollectAttachable<'a>> CollectAttachable<'a> for Box<'_, T> {
+    fn collect<'b>(&'b mut self, out: &mut Vec<&'b mut dyn Attachable<'a>>) {
+        self.as_mut().collect(out);
+    }
+}
+
+impl<'a, T: CollectAttachable<'a>> CollectAttachable<'a> for Option<T> {
+    fn collect<'b>(&'b mut self, out: &mut Vec<&'b mut dyn Attachable<'a>>) {
+        if let Some(node) = self {
+            node.collect(out);
+        }
+    }
+}
+
+// 节点列表分配在 arena 中，与收集结果使用的 std Vec 不同
+impl<'a, T: CollectAttachable<'a>> CollectAttachable<'a> for super::Vec<'_, T> {
+    fn collect<'b>(&'b mut self, out: &mut Vec<&'b mut dyn Attachable<'a>>) {
+        for node in self.iter_mut() {
+            node.collect(out);
+        }
+    }
+}
+
+macro_rules! inner_comments {
+    ($self:ident) => {
+        None
+    };
+    ($self:ident, $inner:ident) => {
+        Some(&mut $self.$inner)
+    };
+}
+
+macro_rules! attachable {
+    ($($ty:ident { $($field:ident),* } $(+ $inner:ident)?)*) => {
+        $(
+            impl<'a> Attachable<'a> for $ty<'a> {
+                fn range(&self) -> (usize, usize) {
+                    (self.start, self.end)
+                }
+
+                fn lines(&self) -> (usize, usize) {
+                    lines(&self.loc)
+                }
+
+                fn comments_mut(&mut self) -> (&mut Comments<'a>, &mut Comments<'a>) {
+                    (&mut self.leading_comments, &mut self.trailing_comments)
+                }
+
+                fn inner_comments_mut(&mut self) -> Option<&mut Comments<'a>> {
+                    inner_comments!(self $(, $inner)?)
+                }
+
+                #[allow(unused_variables)]
+                fn collect_children<'b>(&'b mut self, out: &mut Vec<&'b mut dyn Attachable<'a>>) {
+                    $(self.$field.collect(out);)*
+                }
+            }
+
+            impl<'a> CollectAttachable<'a> for $ty<'a> {
+                fn collect<'b>(&'b mut self, out: &mut Vec<&'b mut dyn Attachable<'a>>) {
+                    out.push(self);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! transparent {
+    ($($ty:ident { $($field:ident),* })*) => {
+        $(
+            impl<'a> CollectAttachable<'a> for $ty<'a> {
+                #[allow(unused_variables)]
+                fn collect<'b>(&'b mut self, out: &mut Vec<&'b mut dyn Attachable<'a>>) {
+                    $(self.$field.collect(out);)*
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! transparent_enum {
+    ($($ty:ident { $($variant:ident),* })*) => {
+        $(
+            impl<'a> CollectAttachable<'a> for $ty<'a> {
+                fn collect<'b>(&'b mut self, out: &mut Vec<&'b mut dyn Attachable<'a>>) {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        $($ty::$variant(node) => node.collect(out),)*
+                        _ => {}
+                    }
+                }
+            }
+        )*
+    };
+}
+
+attachable! {
+    Directive { expression }
+    ExpressionStatement { expression }
+    BlockStatement { body } + inner_comments
+    FunctionBody { body } + inner_comments
+    ClassBody { body } + inner_comments
+    EmptyStatement {}
+    DebuggerStatement {}
+    WithStatement { object, body }
+    ReturnStatement { argument }
+    LabeledStatement { label, body }
+    BreakStatement { label }
+    ContinueStatement { label }
+    IfStatement { test, consequent, alternate }
+    SwitchStatement { discriminant, cases }
+    ThrowStatement { argument }
+    TryStatement { block, handler, finalizer }
+    WhileStatement { test, body }
+    DoWhileStatement { body, test }
+    ForStatement { init, test, update, body }
+    ForInStatement { left, right, body }
+    ForOfStatement { left, right, body }
+    FunctionDeclaration { id, params, body }
+    AnonymousDefaultExportedFunctionDeclaration { params, body }
+    AnonymousDefaultExportedClassDeclaration { super_class, body }
+    VariableDeclaration { declarations }
+    ClassDeclaration { id, super_class, body }
+    InvalidStatement {}
+    ImportDeclaration { specifiers, source }
+    ExportNamedDeclaration { declaration, specifiers, source }
+    ExportDefaultDeclaration { declaration }
+    ExportAllDeclaration { exported, source }
+    Identifier {}
+    Literal {}
+    ThisExpression {}
+    ArrayExpression { elements }
+    ObjectExpression { properties }
+    FunctionExpression { id, params, body }
+    UnaryExpression { argument }
+    UpdateExpression { argument }
+    BinaryExpression { left, right }
+    AssignmentExpression { left, right }
+    LogicalExpression { left, right }
+    MemberExpression { object, property }
+    ConditionalExpression { test, consequent, alternate }
+    CallExpression { callee, arguments }
+    NewExpression { callee, arguments }
+    SequenceExpression { expressions }
+    ParenthesizedExpression { expression }
+    ArrowFunctionExpression { params, body }
+    YieldExpression { argument }
+    TemplateLiteral { expressions }
+    TaggedTemplateExpression { tag, quasi }
+    ClassExpression { id, super_class, body }
+    MetaProperty { meta, property }
+    AwaitExpression { argument }
+    ChainExpression { expression }
+    ImportExpression { source }
+    StaticBlock { body }
+    MethodDefinition { key, value }
+    PropertyDefinition { key, value }
+}
+
+transparent! {
+    SwitchCase { test, consequent }
+    CatchClause { param, body }
+    VariableDeclarator { id, init }
+    SpreadElement { argument }
+    Property { key, value }
+    Super {}
+    PrivateIdentifier {}
+    ImportSpecifier { imported, local }
+    ImportDefaultSpecifier { local }
+    ImportNamespaceSpecifier { local }
+    ExportSpecifier { local, exported }
+    RestElement { argument }
+    ArrayPattern { elements }
+    AssignmentProperty { key, value }
+    ObjectPattern { properties }
+    AssignmentPattern { left, right }
+}
+
+transparent_enum! {
+    ProgramNode { Directive, Statement, ImportOrExportDeclaration }
+    Statement {
+        ExpressionStatement, BlockStatement, EmptyStatement, DebuggerStatement, WithStatement,
+        ReturnStatement, LabeledStatement, BreakStatement, ContinueStatement, IfStatement,
+        SwitchStatement, ThrowStatement, TryStatement, WhileStatement, DoWhileStatement,
+        ForStatement, ForInStatement, ForOfStatement, FunctionDeclaration,
+        AnonymousDefaultExportedFunctionDeclaration, AnonymousDefaultExportedClassDeclaration,
+        VariableDeclaration, ClassDeclaration, InvalidStatement, ImportOrExportDeclaration
+    }
+    FunctionBodyContent { Directive, Statement }
+    ForStatementInit { VariableDeclaration, Expression }
+    ForInOfStatementLeft { VariableDeclaration, Pattern }
+    ClassBodyEl { MethodDefinition, PropertyDefinition, StaticBlock }
+    ClassMethodKey { Expression, PrivateIdentifier }
+    ClassPropertyKey { Expression, PrivateIdentifier }
+    Expression {
+        Identifier, Literal, ThisExpression, ArrayExpression, ObjectExpression,
+        FunctionExpression, UnaryExpression, UpdateExpression, BinaryExpression,
+        AssignmentExpression, LogicalExpression, MemberExpression, ConditionalExpression,
+        CallExpression, NewExpression, SequenceExpression, ParenthesizedExpression,
+        ArrowFunctionExpression, YieldExpression, TemplateLiteral, TaggedTemplateExpression,
+        ClassExpression, MetaProperty, AwaitExpression, ChainExpression, ImportExpression,
+        StaticBlock
+    }
+    ArrayExprEle { Expression, SpreadElement }
+    ObjectProperty { Property, SpreadElement }
+    BinaryOpeartorLeft { Expression, PrivateIdentifier }
+    AssignmentExpressionLeft { Pattern, Expression }
+    MemberExprObject { Expression, Super }
+    MemberExprProperty { Expression, PrivateIdentifier }
+    CallExprArgs { Expression, SpreadElement }
+    CallExprCallee { Expression, Super }
+    NewExprArgs { Expression, SpreadElement }
+    ArrowFunctionBody { FunctionBoby, Expression }
+    ChainExpressionElement { CallExpression, MemberExpression }
+    Pattern {
+        RestElement, ArrayPattern, ObjectPattern, Identifier, AssignmentPattern, MemberExpression
+    }
+    ObjectPatternProperty { AssignmentProperty, RestElement }
+    ImportOrExportDeclaration {
+        ImportDeclaration, ExportNamedDeclaration, ExportDefaultDeclaration, ExportAllDeclaration
+    }
+    ImportSpecifiers { ImportSpecifier, ImportDefaultSpecifier, ImportNamespaceSpecifier }
+    ImportedType { Identifier, Literal }
+    ExportDeclaration { FunctionDeclaration, VariableDeclaration, ClassDeclaration }
+    ExportLocal { Identifier, Literal }
+    ExportedType { Identifier, Literal }
+    ExportDefaultDeclarationType {
+        AnonymousDefaultExportedFunctionDeclaration, FunctionDeclaration,
+        AnonymousDefaultExportedClassDeclaration, ClassDeclaration, Expression
+    }
+    ExportAllExportedType { Identifier, Literal }
+}
+
+/// 开始和结束的行，注释在位置转换之前挂载，这时 loc 总是存在的
+fn lines(loc: &Option<SourceLocation>) -> (usize, usize) {
+    loc.as_ref()
+        .map_or((0, 0), |loc| (loc.start.line, loc.end.line))
+}
+
+/// 将 Program.comments 中的注释挂载到距离最近的节点的 leading_comments 或 trailing_comments 上
+pub fn attach_comments<'a>(program: &mut Program<'a>) {
+    let comments = program.comments.clone();
+    let mut children = vec![];
+    program.body.collect(&mut children);
+    attach_to_children(children, comments);
+}
+
+/// comments 都位于 children 的父节点范围内，并且按照出现的顺序排列
+fn attach_to_children<'a>(mut children: Vec<&mut dyn Attachable<'a>>, comments: Comments<'a>) {
+    if comments.is_empty() {
+        return;
+    }
+    children.sort_by_key(|child| child.range().0);
+    let mut inner_comments: Vec<Vec<Comment>> = children.iter().map(|_| vec![]).collect();
+
+    for comment in comments {
+        // 第一个在注释之后结束的子节点
+        let idx = children.partition_point(|child| child.range().1 <= comment.start);
+        if idx < children.len() && children[idx].range().0 < comment.start {
+            inner_comments[idx].push(comment);
+            continue;
+        }
+        let prev = idx.checked_sub(1);
+        let next = (idx < children.len()).then_some(idx);
+        // 与前一个节点位于同一行，并且后一个节点在之后的行开始的注释，例如 `a = 1; // comment`
+        let (start_line, end_line) = lines(&comment.loc);
+        let is_trailing = prev.is_some_and(|prev| {
+            children[prev].lines().1 == start_line
+                && next.is_none_or(|next| children[next].lines().0 > end_line)
+        });
+        match (prev, next) {
+            (Some(prev), _) if is_trailing => children[prev].comments_mut().1.push(comment),
+            (_, Some(next)) => children[next].comments_mut().0.push(comment),
+            (Some(prev), None) => children[prev].comments_mut().1.push(comment),
+            // 没有可以挂载的节点，例如只有注释的文件，只保留在 Program.comments 中
+            (None, None) => {}
+        }
+    }
+
+    for (child, comments) in children.into_iter().zip(inner_comments) {
+        if comments.is_empty() {
+            continue;
+        }
+        let mut grandchildren = vec![];
+        child.collect_children(&mut grandchildren);
+        // 空的对象字面量这样没有 inner_comments 的节点中的注释只保留在 Program.comments 中
+        if grandchildren.is_empty() {
+            if let Some(inner) = child.inner_comments_mut() {
+                inner.extend(comments);
+            }
+            continue;
+        }
+        attach_to_children(grandchildren, comments);
+    }
+}
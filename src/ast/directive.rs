@@ -1,29 +1,45 @@
+use std::borrow::Cow;
+
 use serde::Serialize;
 
 use super::{
+    comment::Comments,
     expression::{Literal, LiteralValue},
     NodeType, ProgramNode, SourceLocation,
 };
 
 #[derive(Serialize)]
-pub struct Directive {
+pub struct Directive<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType, // ExpressionStatement
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub expression: Literal,
-    pub directive: String, // raw string exclude the quotes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub expression: Literal<'a>,
+    pub directive: Cow<'a, str>, // raw string exclude the quotes
 }
 
-impl From<Literal> for Directive {
-    fn from(value: Literal) -> Self {
-        let expression = value.clone();
-        let directive = if let LiteralValue::String(v) = value.value {
-            v
-        } else {
+impl<'a> From<Literal<'a>> for Directive<'a> {
+    fn from(value: Literal<'a>) -> Self {
+        if !matches!(value.value, LiteralValue::String(..)) {
             panic!("Directive can only be String Literal")
-        };
+        }
+        let expression = value.clone();
+        // 与 acorn 一样保留转义，比如 `'a\n'` 的 directive 是 `a\n`
+        let directive = Cow::Borrowed(&value.raw[1..value.raw.len() - 1]);
         Self {
             _type: NodeType::ExpressionStatement,
             expression,
@@ -31,12 +47,15 @@ impl From<Literal> for Directive {
             start: value.start,
             end: value.end,
             loc: value.loc,
+            range: value.range,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
-impl From<Directive> for ProgramNode {
-    fn from(value: Directive) -> Self {
+impl<'a> From<Directive<'a>> for ProgramNode<'a> {
+    fn from(value: Directive<'a>) -> Self {
         Self::Directive(value)
     }
 }
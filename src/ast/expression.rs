@@ -1,369 +1,477 @@
 use super::{
+    comment::Comments,
     pattern::Pattern,
+    regexp::RegExpAst,
     statement::{ClassBody, FunctionBody, Statement},
-    AstNodePos, NodeType, SourceLocation,
+    AstNodePos, Box, NodeType, SourceLocation, Vec,
 };
-use regex::Regex;
-use serde::Serialize;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
 
 #[derive(Serialize)]
-pub enum Expression {
-    Identifier(Identifier),
-    Literal(Literal), // literal 的类型可以继续拆分为 number、string、bool、reg、bigint 等
-    ThisExpression(ThisExpression),
-    ArrayExpression(ArrayExpression),
-    ObjectExpression(ObjectExpression),
-    FunctionExpression(FunctionExpression),
-    UnaryExpression(UnaryExpression),
-    UpdateExpression(UpdateExpression),
-    BinaryExpression(BinaryExpression),
-    AssignmentExpression(AssignmentExpression),
-    LogicalExpression(LogicalExpression),
-    MemberExpression(MemberExpression), // like a[b]、a.b
-    ConditionalExpression(ConditionalExpression),
-    CallExpression(CallExpression), // a.func()
-    NewExpression(NewExpression),   // new Object()
-    SequenceExpression(SequenceExpression),
-    ParenthesizedExpression(ParenthesizedExpression),
+#[serde(untagged)]
+pub enum Expression<'a> {
+    Identifier(Identifier<'a>),
+    Literal(Literal<'a>), // literal 的类型可以继续拆分为 number、string、bool、reg、bigint 等
+    ThisExpression(ThisExpression<'a>),
+    ArrayExpression(ArrayExpression<'a>),
+    ObjectExpression(ObjectExpression<'a>),
+    FunctionExpression(FunctionExpression<'a>),
+    UnaryExpression(UnaryExpression<'a>),
+    UpdateExpression(UpdateExpression<'a>),
+    BinaryExpression(BinaryExpression<'a>),
+    AssignmentExpression(AssignmentExpression<'a>),
+    LogicalExpression(LogicalExpression<'a>),
+    MemberExpression(MemberExpression<'a>), // like a[b]、a.b
+    ConditionalExpression(ConditionalExpression<'a>),
+    CallExpression(CallExpression<'a>), // a.func()
+    NewExpression(NewExpression<'a>),   // new Object()
+    SequenceExpression(SequenceExpression<'a>),
+    ParenthesizedExpression(ParenthesizedExpression<'a>),
     // starts from es6
-    ArrowFunctionExpression(ArrowFunctionExpression), // let f = () => {}
-    YieldExpression(YieldExpression),                 // yield a;
-    TemplateLiteral(TemplateLiteral),                 // `some template ${val}`
-    TaggedTemplateExpression(TaggedTemplateExpression), // myTag`That ${person} is a ${age}.`
-    ClassExpression(ClassExpression),
-    MetaProperty(MetaProperty), // to check if function called with new operator
+    ArrowFunctionExpression(ArrowFunctionExpression<'a>), // let f = () => {}
+    YieldExpression(YieldExpression<'a>),                 // yield a;
+    TemplateLiteral(TemplateLiteral<'a>),                 // `some template ${val}`
+    TaggedTemplateExpression(TaggedTemplateExpression<'a>), // myTag`That ${person} is a ${age}.`
+    ClassExpression(ClassExpression<'a>),
+    MetaProperty(MetaProperty<'a>), // to check if function called with new operator
     // starts from es2017
-    AwaitExpression(AwaitExpression),
+    AwaitExpression(AwaitExpression<'a>),
     // starts from es2020
-    ChainExpression(ChainExpression),   // ?.
-    ImportExpression(ImportExpression), // import(source) represents dynamic import
+    ChainExpression(ChainExpression<'a>),   // ?.
+    ImportExpression(ImportExpression<'a>), // import(source) represents dynamic import
     // starts from es2022
-    StaticBlock(StaticBlock),
+    StaticBlock(StaticBlock<'a>),
 }
 
-impl From<Identifier> for Expression {
-    fn from(value: Identifier) -> Self {
+impl<'a> From<Identifier<'a>> for Expression<'a> {
+    fn from(value: Identifier<'a>) -> Self {
         Self::Identifier(value)
     }
 }
 
-impl From<Literal> for Expression {
-    fn from(value: Literal) -> Self {
+impl<'a> From<Literal<'a>> for Expression<'a> {
+    fn from(value: Literal<'a>) -> Self {
         Self::Literal(value)
     }
 }
 
-impl From<ThisExpression> for Expression {
-    fn from(value: ThisExpression) -> Self {
+impl<'a> From<ThisExpression<'a>> for Expression<'a> {
+    fn from(value: ThisExpression<'a>) -> Self {
         Self::ThisExpression(value)
     }
 }
 
-impl From<ArrayExpression> for Expression {
-    fn from(value: ArrayExpression) -> Self {
+impl<'a> From<ArrayExpression<'a>> for Expression<'a> {
+    fn from(value: ArrayExpression<'a>) -> Self {
         Self::ArrayExpression(value)
     }
 }
 
-impl From<ObjectExpression> for Expression {
-    fn from(value: ObjectExpression) -> Self {
+impl<'a> From<ObjectExpression<'a>> for Expression<'a> {
+    fn from(value: ObjectExpression<'a>) -> Self {
         Self::ObjectExpression(value)
     }
 }
 
-impl From<FunctionExpression> for Expression {
-    fn from(value: FunctionExpression) -> Self {
+impl<'a> From<FunctionExpression<'a>> for Expression<'a> {
+    fn from(value: FunctionExpression<'a>) -> Self {
         Self::FunctionExpression(value)
     }
 }
 
-impl From<UnaryExpression> for Expression {
-    fn from(value: UnaryExpression) -> Self {
+impl<'a> From<UnaryExpression<'a>> for Expression<'a> {
+    fn from(value: UnaryExpression<'a>) -> Self {
         Self::UnaryExpression(value)
     }
 }
 
-impl From<UpdateExpression> for Expression {
-    fn from(value: UpdateExpression) -> Self {
+impl<'a> From<UpdateExpression<'a>> for Expression<'a> {
+    fn from(value: UpdateExpression<'a>) -> Self {
         Self::UpdateExpression(value)
     }
 }
 
-impl From<BinaryExpression> for Expression {
-    fn from(value: BinaryExpression) -> Self {
+impl<'a> From<BinaryExpression<'a>> for Expression<'a> {
+    fn from(value: BinaryExpression<'a>) -> Self {
         Self::BinaryExpression(value)
     }
 }
 
-impl From<AssignmentExpression> for Expression {
-    fn from(value: AssignmentExpression) -> Self {
+impl<'a> From<AssignmentExpression<'a>> for Expression<'a> {
+    fn from(value: AssignmentExpression<'a>) -> Self {
         Self::AssignmentExpression(value)
     }
 }
 
-impl From<LogicalExpression> for Expression {
-    fn from(value: LogicalExpression) -> Self {
+impl<'a> From<LogicalExpression<'a>> for Expression<'a> {
+    fn from(value: LogicalExpression<'a>) -> Self {
         Self::LogicalExpression(value)
     }
 }
 
-impl From<MemberExpression> for Expression {
-    fn from(value: MemberExpression) -> Self {
+impl<'a> From<MemberExpression<'a>> for Expression<'a> {
+    fn from(value: MemberExpression<'a>) -> Self {
         Self::MemberExpression(value)
     }
 }
 
-impl From<ConditionalExpression> for Expression {
-    fn from(value: ConditionalExpression) -> Self {
+impl<'a> From<ConditionalExpression<'a>> for Expression<'a> {
+    fn from(value: ConditionalExpression<'a>) -> Self {
         Self::ConditionalExpression(value)
     }
 }
 
-impl From<CallExpression> for Expression {
-    fn from(value: CallExpression) -> Self {
+impl<'a> From<CallExpression<'a>> for Expression<'a> {
+    fn from(value: CallExpression<'a>) -> Self {
         Self::CallExpression(value)
     }
 }
 
-impl From<NewExpression> for Expression {
-    fn from(value: NewExpression) -> Self {
+impl<'a> From<NewExpression<'a>> for Expression<'a> {
+    fn from(value: NewExpression<'a>) -> Self {
         Self::NewExpression(value)
     }
 }
 
-impl From<SequenceExpression> for Expression {
-    fn from(value: SequenceExpression) -> Self {
+impl<'a> From<SequenceExpression<'a>> for Expression<'a> {
+    fn from(value: SequenceExpression<'a>) -> Self {
         Self::SequenceExpression(value)
     }
 }
 
-impl From<ArrowFunctionExpression> for Expression {
-    fn from(value: ArrowFunctionExpression) -> Self {
+impl<'a> From<ArrowFunctionExpression<'a>> for Expression<'a> {
+    fn from(value: ArrowFunctionExpression<'a>) -> Self {
         Self::ArrowFunctionExpression(value)
     }
 }
 
-impl From<YieldExpression> for Expression {
-    fn from(value: YieldExpression) -> Self {
+impl<'a> From<YieldExpression<'a>> for Expression<'a> {
+    fn from(value: YieldExpression<'a>) -> Self {
         Self::YieldExpression(value)
     }
 }
 
-impl From<TemplateLiteral> for Expression {
-    fn from(value: TemplateLiteral) -> Self {
+impl<'a> From<TemplateLiteral<'a>> for Expression<'a> {
+    fn from(value: TemplateLiteral<'a>) -> Self {
         Self::TemplateLiteral(value)
     }
 }
 
-impl From<TaggedTemplateExpression> for Expression {
-    fn from(value: TaggedTemplateExpression) -> Self {
+impl<'a> From<TaggedTemplateExpression<'a>> for Expression<'a> {
+    fn from(value: TaggedTemplateExpression<'a>) -> Self {
         Self::TaggedTemplateExpression(value)
     }
 }
 
-impl From<ClassExpression> for Expression {
-    fn from(value: ClassExpression) -> Self {
+impl<'a> From<ClassExpression<'a>> for Expression<'a> {
+    fn from(value: ClassExpression<'a>) -> Self {
         Self::ClassExpression(value)
     }
 }
 
-impl From<MetaProperty> for Expression {
-    fn from(value: MetaProperty) -> Self {
+impl<'a> From<MetaProperty<'a>> for Expression<'a> {
+    fn from(value: MetaProperty<'a>) -> Self {
         Self::MetaProperty(value)
     }
 }
 
-impl From<AwaitExpression> for Expression {
-    fn from(value: AwaitExpression) -> Self {
+impl<'a> From<AwaitExpression<'a>> for Expression<'a> {
+    fn from(value: AwaitExpression<'a>) -> Self {
         Self::AwaitExpression(value)
     }
 }
 
-impl From<ChainExpression> for Expression {
-    fn from(value: ChainExpression) -> Self {
+impl<'a> From<ChainExpression<'a>> for Expression<'a> {
+    fn from(value: ChainExpression<'a>) -> Self {
         Self::ChainExpression(value)
     }
 }
 
-impl From<ImportExpression> for Expression {
-    fn from(value: ImportExpression) -> Self {
+impl<'a> From<ImportExpression<'a>> for Expression<'a> {
+    fn from(value: ImportExpression<'a>) -> Self {
         Self::ImportExpression(value)
     }
 }
 
-impl From<StaticBlock> for Expression {
-    fn from(value: StaticBlock) -> Self {
+impl<'a> From<StaticBlock<'a>> for Expression<'a> {
+    fn from(value: StaticBlock<'a>) -> Self {
         Self::StaticBlock(value)
     }
 }
 
-#[derive(Clone, Serialize)]
-pub enum LiteralValue {
-    String(String),
+#[derive(Clone)]
+pub enum LiteralValue<'a> {
+    String(Cow<'a, str>),
     Boolean(bool),
     Null,
-    Number(i64),
-    // use regex lib as js regexp's value
-    #[serde(skip_serializing)]
-    Regx(Option<Regex>),
+    Number(f64),
+    // 正则的 pattern 与 flags 记录在 `Literal::reg` 中
+    Regx,
     // from es11, add bigint type, if language env didn't support BigInt, leave it none.
     BigInt,
 }
 
+// 与 acorn 生成的 JSON 保持一致：RegExp 对象序列化为 `{}`，BigInt 无法序列化，使用 null
+impl<'a> Serialize for LiteralValue<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::String(value) => serializer.serialize_str(value),
+            Self::Boolean(value) => serializer.serialize_bool(*value),
+            // 整数按 JSON.stringify 的方式输出，即 `1` 而不是 `1.0`
+            Self::Number(value) if value.fract() == 0.0 && value.abs() < 1e16 => {
+                serializer.serialize_i64(*value as i64)
+            }
+            Self::Number(value) => serializer.serialize_f64(*value),
+            Self::Regx => serializer.serialize_map(Some(0))?.end(),
+            Self::Null | Self::BigInt => serializer.serialize_unit(),
+        }
+    }
+}
+
 #[derive(Clone, Serialize)]
-pub struct Reg {
-    pub pattern: String,
-    pub flags: String,
+pub struct Reg<'a> {
+    pub pattern: &'a str,
+    pub flags: &'a str,
+    // 只有开启 `parse_regex_ast` 时才会解析
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ast: Option<RegExpAst>,
 }
 
 #[derive(Clone, Serialize)]
-pub struct Literal {
+pub struct Literal<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
-    pub value: LiteralValue,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub value: LiteralValue<'a>,
+    // 源码中的原始文本，比如 `'a'`、`0x10`
+    pub raw: &'a str,
     // 由于 reg 字面量拓展了字段，使用 Option 来展示
-    pub reg: Option<Reg>,
+    #[serde(rename(serialize = "regex"), skip_serializing_if = "Option::is_none")]
+    pub reg: Option<Reg<'a>>,
     // from es 11, raw string exclude numberic separators.
-    pub bigint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bigint: Option<Cow<'a, str>>,
 }
 
-impl Literal {
+impl<'a> Literal<'a> {
     pub fn new(
-        value: LiteralValue,
-        reg: Option<Reg>,
-        bigint: Option<String>,
-        (start_loc, end_loc, source_file): AstNodePos,
+        value: LiteralValue<'a>,
+        raw: &'a str,
+        reg: Option<Reg<'a>>,
+        bigint: Option<Cow<'a, str>>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::Literal,
             value,
+            raw,
             reg,
             bigint,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct ParenthesizedExpression {
+pub struct ParenthesizedExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub expression: Box<Expression>,
-}
-
-impl ParenthesizedExpression {
-    pub fn new(expression: Box<Expression>, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub expression: Box<'a, Expression<'a>>,
+}
+
+impl<'a> ParenthesizedExpression<'a> {
+    pub fn new(
+        expression: Box<'a, Expression<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::ParenthesizedExpression,
             expression,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct ThisExpression {
+pub struct ThisExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-}
-
-impl ThisExpression {
-    pub fn new((start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+}
+
+impl<'a> ThisExpression<'a> {
+    pub fn new((start_loc, end_loc, source): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::ThisExpression,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct SpreadElement {
+pub struct SpreadElement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub argument: Expression,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub argument: Expression<'a>,
 }
 
-impl SpreadElement {
-    pub fn new(argument: Expression, (start_loc, end_loc, source_file): AstNodePos) -> Self {
+impl<'a> SpreadElement<'a> {
+    pub fn new(
+        argument: Expression<'a>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::SpreadElement,
             argument,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum ArrayExprEle {
-    Expression(Expression),
-    SpreadElement(SpreadElement),
+#[serde(untagged)]
+pub enum ArrayExprEle<'a> {
+    Expression(Expression<'a>),
+    SpreadElement(SpreadElement<'a>),
     // use Null for case like E.g. [1,,2], None represents null
     Null,
 }
 
 #[derive(Serialize)]
-pub struct ArrayExpression {
+pub struct ArrayExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub elements: Vec<ArrayExprEle>,
-}
-
-impl ArrayExpression {
-    pub fn new(elements: Vec<ArrayExprEle>, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub elements: Vec<'a, ArrayExprEle<'a>>,
+}
+
+impl<'a> ArrayExpression<'a> {
+    pub fn new(
+        elements: Vec<'a, ArrayExprEle<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::ArrayExpression,
             elements,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(PartialEq, Eq, Clone, Serialize)]
+#[serde(rename_all(serialize = "lowercase"))]
 pub enum PropertyKind {
     Init,
     Get,
@@ -372,30 +480,33 @@ pub enum PropertyKind {
 
 // define object property structure
 #[derive(Serialize)]
-pub struct Property {
+pub struct Property<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType, // Property
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub kind: PropertyKind,
-    pub value: Expression,
-    pub key: Expression,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
     // extends from es6
     pub method: bool,
     pub shorthand: bool,
     pub computed: bool,
+    pub key: Expression<'a>,
+    pub kind: PropertyKind,
+    pub value: Expression<'a>,
 }
 
-impl Property {
+impl<'a> Property<'a> {
     pub fn new(
-        key: Expression,
-        value: Expression,
+        key: Expression<'a>,
+        value: Expression<'a>,
         kind: PropertyKind,
         method: bool,
         shorthand: bool,
         computed: bool,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::Property,
@@ -407,102 +518,155 @@ impl Property {
             computed,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 // from es9, supports spread element as properties, e.g., {a: 1, ...obj, b: 2}.
 #[derive(Serialize)]
-pub enum ObjectProperty {
-    Property(Property),
-    SpreadElement(SpreadElement),
+#[serde(untagged)]
+pub enum ObjectProperty<'a> {
+    Property(Property<'a>),
+    SpreadElement(SpreadElement<'a>),
 }
 
 #[derive(Serialize)]
-pub struct ObjectExpression {
+pub struct ObjectExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub properties: Vec<ObjectProperty>,
-}
-
-impl ObjectExpression {
-    pub fn new(properties: Vec<ObjectProperty>, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub properties: Vec<'a, ObjectProperty<'a>>,
+}
+
+impl<'a> ObjectExpression<'a> {
+    pub fn new(
+        properties: Vec<'a, ObjectProperty<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::ObjectExpression,
             properties,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 // ast nodes definition
 #[derive(Clone, Serialize)]
-pub struct Identifier {
+pub struct Identifier<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
-    pub name: String,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-}
-
-impl Identifier {
-    pub fn new(name: String, (start_loc, end_loc, source_file): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub name: Cow<'a, str>,
+}
+
+impl<'a> Identifier<'a> {
+    pub fn new(name: Cow<'a, str>, (start_loc, end_loc, source_file): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::Identifier,
             name,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct FunctionExpression {
+pub struct FunctionExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub id: Option<Identifier>,
-    pub params: Vec<Pattern>,
-    pub body: FunctionBody,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub id: Option<Identifier<'a>>,
+    pub expression: bool,
     // starts from es6
     pub generator: bool,
     // from es8
+    #[serde(rename(serialize = "async"))]
     pub is_async: bool,
+    pub params: Vec<'a, Pattern<'a>>,
+    pub body: FunctionBody<'a>,
 }
 
-impl FunctionExpression {
+impl<'a> FunctionExpression<'a> {
     pub fn new(
-        id: Option<Identifier>,
-        params: Vec<Pattern>,
-        body: FunctionBody,
+        id: Option<Identifier<'a>>,
+        params: Vec<'a, Pattern<'a>>,
+        body: FunctionBody<'a>,
         generator: bool,
         is_async: bool,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::FunctionExpression,
+            expression: false,
             id,
             params,
             body,
@@ -510,16 +674,18 @@ impl FunctionExpression {
             is_async,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
-#[derive(Serialize)]
 pub enum UnaryOperator {
     Minus,       // '-'
     Plus,        // '+'
@@ -530,16 +696,16 @@ pub enum UnaryOperator {
     Delete,      // 'delete'
 }
 
-impl From<String> for UnaryOperator {
-    fn from(value: String) -> Self {
-        match value.as_str() {
+impl From<&str> for UnaryOperator {
+    fn from(value: &str) -> Self {
+        match value {
             "-" => Self::Minus,
             "+" => Self::Plus,
             "!" => Self::Exclamation,
             "~" => Self::Tilde,
             "typeof" => Self::Typeof,
             "void" => Self::Void,
-            "delte" => Self::Delete,
+            "delete" => Self::Delete,
             _ => {
                 panic!("Unexpected operator {}", value);
             }
@@ -547,24 +713,51 @@ impl From<String> for UnaryOperator {
     }
 }
 
+impl UnaryOperator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Minus => "-",
+            Self::Plus => "+",
+            Self::Exclamation => "!",
+            Self::Tilde => "~",
+            Self::Typeof => "typeof",
+            Self::Void => "void",
+            Self::Delete => "delete",
+        }
+    }
+}
+
 #[derive(Serialize)]
-pub struct UnaryExpression {
+pub struct UnaryExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
     pub operator: UnaryOperator,
     pub prefix: bool,
-    pub argument: Box<Expression>,
+    pub argument: Box<'a, Expression<'a>>,
 }
 
-impl UnaryExpression {
+impl<'a> UnaryExpression<'a> {
     pub fn new(
         operator: UnaryOperator,
-        argument: Box<Expression>,
+        argument: Box<'a, Expression<'a>>,
         prefix: bool,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::UnaryExpression,
@@ -573,24 +766,26 @@ impl UnaryExpression {
             prefix,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
-#[derive(Serialize)]
 pub enum UpdateOperator {
     PlusPlus,
     MinusMinus,
 }
 
-impl From<String> for UpdateOperator {
-    fn from(value: String) -> Self {
-        match value.as_str() {
+impl From<&str> for UpdateOperator {
+    fn from(value: &str) -> Self {
+        match value {
             "++" => UpdateOperator::PlusPlus,
             "--" => UpdateOperator::MinusMinus,
             _ => {
@@ -600,24 +795,46 @@ impl From<String> for UpdateOperator {
     }
 }
 
+impl UpdateOperator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PlusPlus => "++",
+            Self::MinusMinus => "--",
+        }
+    }
+}
+
 #[derive(Serialize)]
-pub struct UpdateExpression {
+pub struct UpdateExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
     pub operator: UpdateOperator,
-    pub argument: Box<Expression>,
     pub prefix: bool,
+    pub argument: Box<'a, Expression<'a>>,
 }
 
-impl UpdateExpression {
+impl<'a> UpdateExpression<'a> {
     pub fn new(
         operator: UpdateOperator,
-        argument: Box<Expression>,
+        argument: Box<'a, Expression<'a>>,
         prefix: bool,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::UpdateExpression,
@@ -626,16 +843,18 @@ impl UpdateExpression {
             prefix,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
-#[derive(Serialize)]
 pub enum BinaryOperator {
     Equality,           // '=='
     InEquality,         // '!='
@@ -662,9 +881,9 @@ pub enum BinaryOperator {
     Exponentiation, // '**'
 }
 
-impl From<String> for BinaryOperator {
-    fn from(value: String) -> Self {
-        match value.as_str() {
+impl From<&str> for BinaryOperator {
+    fn from(value: &str) -> Self {
+        match value {
             "==" => Self::Equality,
             "!=" => Self::InEquality,
             "===" => Self::StrictEquality,
@@ -694,31 +913,74 @@ impl From<String> for BinaryOperator {
     }
 }
 
+impl BinaryOperator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Equality => "==",
+            Self::InEquality => "!=",
+            Self::StrictEquality => "===",
+            Self::StrictInEquality => "!==",
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::RightShift => ">>",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::LeftShift => "<<",
+            Self::UnsignedRightShift => ">>>",
+            Self::Plus => "+",
+            Self::Minus => "-",
+            Self::Multipl => "*",
+            Self::Division => "/",
+            Self::Reminder => "%",
+            Self::BitwiseOr => "|",
+            Self::BitwiseAnd => "&",
+            Self::BitwiseXor => "^",
+            Self::In => "in",
+            Self::InstanceOf => "instanceof",
+            Self::Exponentiation => "**",
+        }
+    }
+}
+
 // left could be PrivateIdentifier when operator is 'in'
 #[derive(Serialize)]
-pub enum BinaryOpeartorLeft {
-    Expression(Box<Expression>),
-    PrivateIdentifier(PrivateIdentifier),
+#[serde(untagged)]
+pub enum BinaryOpeartorLeft<'a> {
+    Expression(Box<'a, Expression<'a>>),
+    PrivateIdentifier(PrivateIdentifier<'a>),
 }
 
 #[derive(Serialize)]
-pub struct BinaryExpression {
+pub struct BinaryExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub left: BinaryOpeartorLeft<'a>,
     pub operator: BinaryOperator,
-    pub left: BinaryOpeartorLeft,
-    pub right: Box<Expression>,
+    pub right: Box<'a, Expression<'a>>,
 }
 
-impl BinaryExpression {
+impl<'a> BinaryExpression<'a> {
     pub fn new(
-        left: BinaryOpeartorLeft,
+        left: BinaryOpeartorLeft<'a>,
         operator: BinaryOperator,
-        right: Box<Expression>,
-        (start_loc, end_loc, source): AstNodePos,
+        right: Box<'a, Expression<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::BinaryExpression,
@@ -727,16 +989,18 @@ impl BinaryExpression {
             right,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
-#[derive(Serialize)]
 pub enum AssignmentOperator {
     Assignment,               // '='
     AdditionAssign,           // "+="
@@ -758,31 +1022,31 @@ pub enum AssignmentOperator {
 }
 
 impl AssignmentOperator {
-    pub fn as_str(&self) -> String {
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Self::Assignment => "=".to_string(),
-            Self::AdditionAssign => "+=".to_string(),
-            Self::SubtractionAssign => "-=".to_string(),
-            Self::MultiplAssign => "*=".to_string(),
-            Self::DivisionAssign => "/=".to_string(),
-            Self::RemainderAssign => "%=".to_string(),
-            Self::LeftShiftAssign => "<<=".to_string(),
-            Self::RightShiftAssign => ">>=".to_string(),
-            Self::UnsignedRightShiftAssign => ">>>=".to_string(),
-            Self::BitwiseORAssign => "|=".to_string(),
-            Self::BitwiseANDAssign => "&=".to_string(),
-            Self::BitwiseXORAssign => "^=".to_string(),
-            Self::LogicalANDAssign => "&&=".to_string(),
-            Self::LogicalORAssign => "||=".to_string(),
-            Self::NullishCoalescingAssign => "??=".to_string(),
-            Self::ExponentiationAssign => "**=".to_string(),
+            Self::Assignment => "=",
+            Self::AdditionAssign => "+=",
+            Self::SubtractionAssign => "-=",
+            Self::MultiplAssign => "*=",
+            Self::DivisionAssign => "/=",
+            Self::RemainderAssign => "%=",
+            Self::LeftShiftAssign => "<<=",
+            Self::RightShiftAssign => ">>=",
+            Self::UnsignedRightShiftAssign => ">>>=",
+            Self::BitwiseORAssign => "|=",
+            Self::BitwiseANDAssign => "&=",
+            Self::BitwiseXORAssign => "^=",
+            Self::LogicalANDAssign => "&&=",
+            Self::LogicalORAssign => "||=",
+            Self::NullishCoalescingAssign => "??=",
+            Self::ExponentiationAssign => "**=",
         }
     }
 }
 
-impl From<String> for AssignmentOperator {
-    fn from(value: String) -> Self {
-        match value.as_str() {
+impl From<&str> for AssignmentOperator {
+    fn from(value: &str) -> Self {
+        match value {
             "=" => Self::Assignment,
             "+=" => Self::AdditionAssign,
             "-=" => Self::SubtractionAssign,
@@ -807,41 +1071,55 @@ impl From<String> for AssignmentOperator {
 }
 
 #[derive(Serialize)]
-pub enum AssignmentExpressionLeft {
-    Pattern(Pattern),
-    Expression(Box<Expression>),
+#[serde(untagged)]
+pub enum AssignmentExpressionLeft<'a> {
+    Pattern(Pattern<'a>),
+    Expression(Box<'a, Expression<'a>>),
 }
 
-impl From<Pattern> for AssignmentExpressionLeft {
-    fn from(value: Pattern) -> Self {
+impl<'a> From<Pattern<'a>> for AssignmentExpressionLeft<'a> {
+    fn from(value: Pattern<'a>) -> Self {
         Self::Pattern(value)
     }
 }
 
-impl From<Expression> for AssignmentExpressionLeft {
-    fn from(value: Expression) -> Self {
-        Self::Expression(Box::new(value))
+impl<'a> From<Box<'a, Expression<'a>>> for AssignmentExpressionLeft<'a> {
+    fn from(value: Box<'a, Expression<'a>>) -> Self {
+        Self::Expression(value)
     }
 }
 
 #[derive(Serialize)]
-pub struct AssignmentExpression {
+pub struct AssignmentExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
     pub operator: AssignmentOperator,
-    pub left: AssignmentExpressionLeft,
-    pub right: Box<Expression>,
+    pub left: AssignmentExpressionLeft<'a>,
+    pub right: Box<'a, Expression<'a>>,
 }
 
-impl AssignmentExpression {
+impl<'a> AssignmentExpression<'a> {
     pub fn new(
-        left: AssignmentExpressionLeft,
+        left: AssignmentExpressionLeft<'a>,
         operator: AssignmentOperator,
-        right: Box<Expression>,
-        (start_loc, end_loc, source): AstNodePos,
+        right: Box<'a, Expression<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::AssignmentExpression,
@@ -850,25 +1128,27 @@ impl AssignmentExpression {
             right,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
-#[derive(Serialize)]
 pub enum LogicalOperator {
     And,     // '&&'
     Or,      // '||'
     Nullish, // '??'
 }
 
-impl From<String> for LogicalOperator {
-    fn from(value: String) -> Self {
-        match value.as_str() {
+impl From<&str> for LogicalOperator {
+    fn from(value: &str) -> Self {
+        match value {
             "&&" => LogicalOperator::And,
             "||" => LogicalOperator::Or,
             "??" => LogicalOperator::Nullish,
@@ -879,24 +1159,47 @@ impl From<String> for LogicalOperator {
     }
 }
 
+impl LogicalOperator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::And => "&&",
+            Self::Or => "||",
+            Self::Nullish => "??",
+        }
+    }
+}
+
 #[derive(Serialize)]
-pub struct LogicalExpression {
+pub struct LogicalExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub left: Box<'a, Expression<'a>>,
     pub operator: LogicalOperator,
-    pub left: Box<Expression>,
-    pub right: Box<Expression>,
+    pub right: Box<'a, Expression<'a>>,
 }
 
-impl LogicalExpression {
+impl<'a> LogicalExpression<'a> {
     pub fn new(
-        left: Box<Expression>,
+        left: Box<'a, Expression<'a>>,
         operator: LogicalOperator,
-        right: Box<Expression>,
-        (start_loc, end_loc, source): AstNodePos,
+        right: Box<'a, Expression<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::LogicalExpression,
@@ -905,96 +1208,118 @@ impl LogicalExpression {
             right,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct Super {
+pub struct Super<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
 }
 
-impl Super {
-    pub fn new((start_loc, end_loc, source_file): AstNodePos) -> Self {
+impl<'a> Super<'a> {
+    pub fn new((start_loc, end_loc, source_file): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::Super,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum MemberExprObject {
-    Expression(Box<Expression>),
-    Super(Super),
+#[serde(untagged)]
+pub enum MemberExprObject<'a> {
+    Expression(Box<'a, Expression<'a>>),
+    Super(Super<'a>),
 }
 
-impl From<Super> for MemberExprObject {
-    fn from(value: Super) -> Self {
+impl<'a> From<Super<'a>> for MemberExprObject<'a> {
+    fn from(value: Super<'a>) -> Self {
         Self::Super(value)
     }
 }
 
-impl From<Expression> for MemberExprObject {
-    fn from(value: Expression) -> Self {
-        Self::Expression(Box::new(value))
+impl<'a> From<Box<'a, Expression<'a>>> for MemberExprObject<'a> {
+    fn from(value: Box<'a, Expression<'a>>) -> Self {
+        Self::Expression(value)
     }
 }
 
 #[derive(Serialize)]
-pub enum MemberExprProperty {
-    Expression(Box<Expression>),
-    PrivateIdentifier(PrivateIdentifier),
+#[serde(untagged)]
+pub enum MemberExprProperty<'a> {
+    Expression(Box<'a, Expression<'a>>),
+    PrivateIdentifier(PrivateIdentifier<'a>),
 }
 
-impl From<PrivateIdentifier> for MemberExprProperty {
-    fn from(value: PrivateIdentifier) -> Self {
+impl<'a> From<PrivateIdentifier<'a>> for MemberExprProperty<'a> {
+    fn from(value: PrivateIdentifier<'a>) -> Self {
         Self::PrivateIdentifier(value)
     }
 }
 
-impl From<Expression> for MemberExprProperty {
-    fn from(value: Expression) -> Self {
-        Self::Expression(Box::new(value))
+impl<'a> From<Box<'a, Expression<'a>>> for MemberExprProperty<'a> {
+    fn from(value: Box<'a, Expression<'a>>) -> Self {
+        Self::Expression(value)
     }
 }
 
 #[derive(Serialize)]
-pub struct MemberExpression {
+pub struct MemberExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub object: MemberExprObject,
-    pub property: MemberExprProperty,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub object: MemberExprObject<'a>,
+    pub property: MemberExprProperty<'a>,
     pub computed: bool,
     // from es11, for optinal chaining
     pub optional: bool,
 }
 
-impl MemberExpression {
+impl<'a> MemberExpression<'a> {
     pub fn new(
-        object: MemberExprObject,
-        property: MemberExprProperty,
+        object: MemberExprObject<'a>,
+        property: MemberExprProperty<'a>,
         computed: bool,
         optional: bool,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::MemberExpression,
@@ -1004,34 +1329,50 @@ impl MemberExpression {
             optional,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 // ternary ?/: expression
 #[derive(Serialize)]
-pub struct ConditionalExpression {
+pub struct ConditionalExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub test: Box<Expression>,
-    pub alternate: Box<Expression>,
-    pub consequent: Box<Expression>,
-}
-
-impl ConditionalExpression {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub test: Box<'a, Expression<'a>>,
+    pub consequent: Box<'a, Expression<'a>>,
+    pub alternate: Box<'a, Expression<'a>>,
+}
+
+impl<'a> ConditionalExpression<'a> {
     pub fn new(
-        test: Box<Expression>,
-        consequent: Box<Expression>,
-        alternate: Box<Expression>,
-        (start_loc, end_loc, source): AstNodePos,
+        test: Box<'a, Expression<'a>>,
+        consequent: Box<'a, Expression<'a>>,
+        alternate: Box<'a, Expression<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ConditionalExpression,
@@ -1040,58 +1381,76 @@ impl ConditionalExpression {
             alternate,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum CallExprArgs {
-    Expression(Expression),
-    SpreadElement(SpreadElement),
+#[serde(untagged)]
+pub enum CallExprArgs<'a> {
+    Expression(Expression<'a>),
+    SpreadElement(SpreadElement<'a>),
 }
 
 #[derive(Serialize)]
-pub enum CallExprCallee {
-    Expression(Box<Expression>),
-    Super(Super),
+#[serde(untagged)]
+pub enum CallExprCallee<'a> {
+    Expression(Box<'a, Expression<'a>>),
+    Super(Super<'a>),
 }
 
-impl From<Super> for CallExprCallee {
-    fn from(value: Super) -> Self {
+impl<'a> From<Super<'a>> for CallExprCallee<'a> {
+    fn from(value: Super<'a>) -> Self {
         Self::Super(value)
     }
 }
 
-impl From<Expression> for CallExprCallee {
-    fn from(value: Expression) -> Self {
-        Self::Expression(Box::new(value))
+impl<'a> From<Box<'a, Expression<'a>>> for CallExprCallee<'a> {
+    fn from(value: Box<'a, Expression<'a>>) -> Self {
+        Self::Expression(value)
     }
 }
 
 #[derive(Serialize)]
-pub struct CallExpression {
+pub struct CallExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub callee: CallExprCallee,
-    pub arguments: Vec<CallExprArgs>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub callee: CallExprCallee<'a>,
+    pub arguments: Vec<'a, CallExprArgs<'a>>,
     // from es11, for optinal chianing
     pub optional: bool,
 }
 
-impl CallExpression {
+impl<'a> CallExpression<'a> {
     pub fn new(
-        callee: CallExprCallee,
-        arguments: Vec<CallExprArgs>,
+        callee: CallExprCallee<'a>,
+        arguments: Vec<'a, CallExprArgs<'a>>,
         optional: bool,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::CallExpression,
@@ -1100,49 +1459,66 @@ impl CallExpression {
             optional,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum NewExprArgs {
-    Expression(Expression),
-    SpreadElement(SpreadElement),
+#[serde(untagged)]
+pub enum NewExprArgs<'a> {
+    Expression(Expression<'a>),
+    SpreadElement(SpreadElement<'a>),
 }
 
-impl From<Expression> for NewExprArgs {
-    fn from(value: Expression) -> Self {
+impl<'a> From<Expression<'a>> for NewExprArgs<'a> {
+    fn from(value: Expression<'a>) -> Self {
         Self::Expression(value)
     }
 }
 
-impl From<SpreadElement> for NewExprArgs {
-    fn from(value: SpreadElement) -> Self {
+impl<'a> From<SpreadElement<'a>> for NewExprArgs<'a> {
+    fn from(value: SpreadElement<'a>) -> Self {
         Self::SpreadElement(value)
     }
 }
 
 #[derive(Serialize)]
-pub struct NewExpression {
+pub struct NewExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub callee: Box<Expression>,
-    pub arguments: Vec<NewExprArgs>,
-}
-
-impl NewExpression {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub callee: Box<'a, Expression<'a>>,
+    pub arguments: Vec<'a, NewExprArgs<'a>>,
+}
+
+impl<'a> NewExpression<'a> {
     pub fn new(
-        callee: Box<Expression>,
-        arguments: Vec<NewExprArgs>,
-        (start_loc, end_loc, source): AstNodePos,
+        callee: Box<'a, Expression<'a>>,
+        arguments: Vec<'a, NewExprArgs<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::NewExpression,
@@ -1150,103 +1526,160 @@ impl NewExpression {
             arguments,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 // comma-separated sequence of expressions, eg. a,b,c
 #[derive(Serialize)]
-pub struct SequenceExpression {
+pub struct SequenceExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub expressions: Vec<Expression>,
-}
-
-impl SequenceExpression {
-    pub fn new(expressions: Vec<Expression>, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub expressions: Vec<'a, Expression<'a>>,
+}
+
+impl<'a> SequenceExpression<'a> {
+    pub fn new(
+        expressions: Vec<'a, Expression<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::SequenceExpression,
             expressions,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum ArrowFunctionBody {
-    FunctionBoby(FunctionBody),
-    Expression(Box<Expression>),
+#[serde(untagged)]
+pub enum ArrowFunctionBody<'a> {
+    FunctionBoby(FunctionBody<'a>),
+    Expression(Box<'a, Expression<'a>>),
 }
 
 // Note: there is not generator arrow function expression.
 #[derive(Serialize)]
-pub struct ArrowFunctionExpression {
+pub struct ArrowFunctionExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub params: Vec<Pattern>,
-    pub body: ArrowFunctionBody,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub id: Option<Identifier<'a>>,
     pub expression: bool,
+    pub generator: bool,
+    #[serde(rename(serialize = "async"))]
     pub is_async: bool,
+    pub params: Vec<'a, Pattern<'a>>,
+    pub body: ArrowFunctionBody<'a>,
 }
 
-impl ArrowFunctionExpression {
+impl<'a> ArrowFunctionExpression<'a> {
     pub fn new(
-        params: Vec<Pattern>,
-        body: ArrowFunctionBody,
+        params: Vec<'a, Pattern<'a>>,
+        body: ArrowFunctionBody<'a>,
         expression: bool,
         is_async: bool,
-        (start_loc, end_loc, source_file): AstNodePos,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ArrowFunctionExpression,
+            id: None,
+            generator: false,
             params,
             body,
             expression,
             is_async,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct YieldExpression {
+pub struct YieldExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub argument: Option<Box<Expression>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
     pub delegate: bool,
+    pub argument: Option<Box<'a, Expression<'a>>>,
 }
 
-impl YieldExpression {
+impl<'a> YieldExpression<'a> {
     pub fn new(
         delegate: bool,
-        argument: Option<Box<Expression>>,
-        (start_loc, end_loc, source_file): AstNodePos,
+        argument: Option<Box<'a, Expression<'a>>>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::YieldExpression,
@@ -1254,40 +1687,47 @@ impl YieldExpression {
             argument,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 // from es9:
-// if it's tagged and there is invalid escape, raw should be null
+// if it's tagged and there is invalid escape, cooked should be null
 // eg. tag`\unicode and \u{55}`
+// raw 是源码中的原始文本，其中的 \r\n 与 \r 被规范化为 \n
 #[derive(Serialize)]
-pub struct TemplateValue {
-    pub cooked: Option<String>,
-    pub raw: String,
+pub struct TemplateValue<'a> {
+    pub raw: Cow<'a, str>,
+    pub cooked: Option<Cow<'a, str>>,
 }
 
 #[derive(Serialize)]
-pub struct TemplateElement {
+pub struct TemplateElement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub value: TemplateValue<'a>,
     pub tail: bool,
-    pub value: TemplateValue,
 }
 
-impl TemplateElement {
+impl<'a> TemplateElement<'a> {
     pub fn new(
-        value: TemplateValue,
+        value: TemplateValue<'a>,
         tail: bool,
-        (start_loc, end_loc, source_file): AstNodePos,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::TemplateElement,
@@ -1295,31 +1735,45 @@ impl TemplateElement {
             tail,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct TemplateLiteral {
+pub struct TemplateLiteral<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub quasis: Vec<TemplateElement>,
-    pub expressions: Vec<Expression>,
-}
-
-impl TemplateLiteral {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub expressions: Vec<'a, Expression<'a>>,
+    pub quasis: Vec<'a, TemplateElement<'a>>,
+}
+
+impl<'a> TemplateLiteral<'a> {
     pub fn new(
-        quasis: Vec<TemplateElement>,
-        expressions: Vec<Expression>,
-        (start_loc, end_loc, source_file): AstNodePos,
+        quasis: Vec<'a, TemplateElement<'a>>,
+        expressions: Vec<'a, Expression<'a>>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::TemplateLiteral,
@@ -1327,31 +1781,47 @@ impl TemplateLiteral {
             expressions,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct TaggedTemplateExpression {
+pub struct TaggedTemplateExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub tag: Box<Expression>,
-    pub quasi: TemplateLiteral,
-}
-
-impl TaggedTemplateExpression {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub tag: Box<'a, Expression<'a>>,
+    pub quasi: TemplateLiteral<'a>,
+}
+
+impl<'a> TaggedTemplateExpression<'a> {
     pub fn new(
-        tag: Box<Expression>,
-        quasi: TemplateLiteral,
-        (start_loc, end_loc, source_file): AstNodePos,
+        tag: Box<'a, Expression<'a>>,
+        quasi: TemplateLiteral<'a>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::TaggedTemplateExpression,
@@ -1359,33 +1829,50 @@ impl TaggedTemplateExpression {
             quasi,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct ClassExpression {
+pub struct ClassExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub id: Option<Identifier>,
-    pub super_class: Option<Box<Expression>>,
-    pub body: ClassBody,
-}
-
-impl ClassExpression {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub id: Option<Identifier<'a>>,
+    #[serde(rename(serialize = "superClass"))]
+    pub super_class: Option<Box<'a, Expression<'a>>>,
+    pub body: ClassBody<'a>,
+}
+
+impl<'a> ClassExpression<'a> {
     pub fn new(
-        id: Option<Identifier>,
-        super_class: Option<Box<Expression>>,
-        body: ClassBody,
-        (start_loc, end_loc, source_file): AstNodePos,
+        id: Option<Identifier<'a>>,
+        super_class: Option<Box<'a, Expression<'a>>>,
+        body: ClassBody<'a>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ClassExpression,
@@ -1394,32 +1881,48 @@ impl ClassExpression {
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 // MetaProperty node represents new.target
 #[derive(Serialize)]
-pub struct MetaProperty {
+pub struct MetaProperty<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub meta: Identifier,
-    pub property: Identifier,
-}
-
-impl MetaProperty {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub meta: Identifier<'a>,
+    pub property: Identifier<'a>,
+}
+
+impl<'a> MetaProperty<'a> {
     pub fn new(
-        meta: Identifier,
-        property: Identifier,
-        (start_loc, end_loc, source_file): AstNodePos,
+        meta: Identifier<'a>,
+        property: Identifier<'a>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::MetaProperty,
@@ -1427,37 +1930,59 @@ impl MetaProperty {
             property,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct AwaitExpression {
+pub struct AwaitExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub argument: Box<Expression>,
-}
-
-impl AwaitExpression {
-    pub fn new(argument: Box<Expression>, (start_loc, end_loc, source_file): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub argument: Box<'a, Expression<'a>>,
+}
+
+impl<'a> AwaitExpression<'a> {
+    pub fn new(
+        argument: Box<'a, Expression<'a>>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::AwaitExpression,
             argument,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
@@ -1467,9 +1992,10 @@ pub struct ChainElement {
 }
 
 #[derive(Serialize)]
-pub enum ChainExpressionElement {
-    CallExpression(CallExpression),
-    MemberExpression(MemberExpression),
+#[serde(untagged)]
+pub enum ChainExpressionElement<'a> {
+    CallExpression(CallExpression<'a>),
+    MemberExpression(MemberExpression<'a>),
 }
 
 // from es11
@@ -1492,110 +2018,176 @@ pub enum ChainExpressionElement {
 // }
 // Which should MemberExpression should be the root node.
 #[derive(Serialize)]
-pub struct ChainExpression {
+pub struct ChainExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub expression: ChainExpressionElement,
-}
-
-impl ChainExpression {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub expression: ChainExpressionElement<'a>,
+}
+
+impl<'a> ChainExpression<'a> {
     pub fn new(
-        expression: ChainExpressionElement,
-        (start_loc, end_loc, source_file): AstNodePos,
+        expression: ChainExpressionElement<'a>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ChainExpression,
             expression,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 // for dynamic import such as import(source)
 #[derive(Serialize)]
-pub struct ImportExpression {
+pub struct ImportExpression<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub source: Box<Expression>,
-}
-
-impl ImportExpression {
-    pub fn new(source: Box<Expression>, (start_loc, end_loc, source_file): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub source: Box<'a, Expression<'a>>,
+}
+
+impl<'a> ImportExpression<'a> {
+    pub fn new(
+        source: Box<'a, Expression<'a>>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::ImportExpression,
             source,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct StaticBlock {
+pub struct StaticBlock<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub body: Vec<Statement>,
-}
-
-impl StaticBlock {
-    pub fn new(body: Vec<Statement>, (start_loc, end_loc, source_file): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+impl<'a> StaticBlock<'a> {
+    pub fn new(
+        body: Vec<'a, Statement<'a>>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::StaticBlock,
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 // class field whichs name starts with #, For a private name #a, its name is a.
 #[derive(Serialize)]
-pub struct PrivateIdentifier {
+pub struct PrivateIdentifier<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub name: Cow<'a, str>,
 }
 
-impl PrivateIdentifier {
-    pub fn new(name: String, (start_loc, end_loc, source_file): AstNodePos) -> Self {
+impl<'a> PrivateIdentifier<'a> {
+    pub fn new(name: Cow<'a, str>, (start_loc, end_loc, source_file): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::PrivateIdentifier,
             name,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
+
+serialize_as_str!(
+    UnaryOperator,
+    UpdateOperator,
+    BinaryOperator,
+    AssignmentOperator,
+    LogicalOperator
+);
@@ -0,0 +1,134 @@
+use std::{borrow::Cow, mem};
+
+use super::{
+    expression::{Expression, Identifier, StaticBlock, ThisExpression},
+    get_location_at,
+    pattern::Pattern,
+    statement::{
+        BlockStatement, EmptyStatement, FunctionBody, FunctionBodyContent, Statement, SwitchCase,
+    },
+    visit_mut::{
+        walk_block_statement, walk_expression, walk_function_body, walk_pattern, walk_program,
+        walk_statement, walk_static_block, walk_switch_case, VisitMut,
+    },
+    AstNodePos, Position, Program, ProgramNode, Vec,
+};
+
+/// Rewrites a `Program` by value, from the leaves up.
+///
+/// `fold_program` hands every `Expression`, `Statement` and `Pattern` to the
+/// method of the same kind once its children have been folded, and puts the
+/// returned node in its place, so a pass can e.g. swap a `CallExpression` for
+/// a `MemberExpression`. Statement lists are handed over as a whole after
+/// their items, to remove a statement or splice several in its place. Nodes
+/// returned by a method are not folded again.
+pub trait Fold<'a> {
+    fn fold_expression(&mut self, node: Expression<'a>) -> Expression<'a> {
+        node
+    }
+
+    fn fold_statement(&mut self, node: Statement<'a>) -> Statement<'a> {
+        node
+    }
+
+    fn fold_pattern(&mut self, node: Pattern<'a>) -> Pattern<'a> {
+        node
+    }
+
+    /// `BlockStatement::body`, `SwitchCase::consequent` and `StaticBlock::body`.
+    fn fold_statements(&mut self, node: Vec<'a, Statement<'a>>) -> Vec<'a, Statement<'a>> {
+        node
+    }
+
+    /// `FunctionBody::body`, which may start with directives.
+    fn fold_function_body_contents(
+        &mut self,
+        node: Vec<'a, FunctionBodyContent<'a>>,
+    ) -> Vec<'a, FunctionBodyContent<'a>> {
+        node
+    }
+
+    /// `Program::body`.
+    fn fold_program_nodes(&mut self, node: Vec<'a, ProgramNode<'a>>) -> Vec<'a, ProgramNode<'a>> {
+        node
+    }
+}
+
+pub fn fold_program<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    mut program: Program<'a>,
+) -> Program<'a> {
+    Folder(folder).visit_program(&mut program);
+    program
+}
+
+// 借助 VisitMut 找到树中的每一个位置，先折叠子节点再把节点本身交给 Fold
+struct Folder<'f, F: ?Sized>(&'f mut F);
+
+impl<'a, F: Fold<'a> + ?Sized> VisitMut<'a> for Folder<'_, F> {
+    fn visit_program(&mut self, node: &mut Program<'a>) {
+        walk_program(self, node);
+        node.body = self.0.fold_program_nodes(take(&mut node.body));
+    }
+
+    fn visit_statement(&mut self, node: &mut Statement<'a>) {
+        walk_statement(self, node);
+        let statement = mem::replace(
+            node,
+            Statement::EmptyStatement(EmptyStatement::new(dummy_pos())),
+        );
+        *node = self.0.fold_statement(statement);
+    }
+
+    fn visit_block_statement(&mut self, node: &mut BlockStatement<'a>) {
+        walk_block_statement(self, node);
+        node.body = self.0.fold_statements(take(&mut node.body));
+    }
+
+    fn visit_switch_case(&mut self, node: &mut SwitchCase<'a>) {
+        walk_switch_case(self, node);
+        node.consequent = self.0.fold_statements(take(&mut node.consequent));
+    }
+
+    fn visit_static_block(&mut self, node: &mut StaticBlock<'a>) {
+        walk_static_block(self, node);
+        node.body = self.0.fold_statements(take(&mut node.body));
+    }
+
+    fn visit_function_body(&mut self, node: &mut FunctionBody<'a>) {
+        walk_function_body(self, node);
+        node.body = self.0.fold_function_body_contents(take(&mut node.body));
+    }
+
+    fn visit_expression(&mut self, node: &mut Expression<'a>) {
+        walk_expression(self, node);
+        let expression = mem::replace(
+            node,
+            Expression::ThisExpression(ThisExpression::new(dummy_pos())),
+        );
+        *node = self.0.fold_expression(expression);
+    }
+
+    fn visit_pattern(&mut self, node: &mut Pattern<'a>) {
+        walk_pattern(self, node);
+        let pattern = mem::replace(
+            node,
+            Pattern::Identifier(Identifier::new(Cow::Borrowed(""), dummy_pos())),
+        );
+        *node = self.0.fold_pattern(pattern);
+    }
+}
+
+fn take<'a, T>(list: &mut Vec<'a, T>) -> Vec<'a, T> {
+    let arena = list.bump();
+    mem::replace(list, Vec::new_in(arena))
+}
+
+// 节点被取出来交给 Fold 时临时占位用的，不会留在树中
+fn dummy_pos<'a>() -> AstNodePos<'a> {
+    (
+        get_location_at(0, Position::default()),
+        get_location_at(0, Position::default()),
+        None,
+    )
+}
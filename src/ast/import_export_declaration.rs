@@ -1,85 +1,92 @@
 use serde::Serialize;
 
 use super::{
+    comment::Comments,
     expression::{Expression, Identifier, Literal},
     pattern::Pattern,
     statement::{
         ClassBody, ClassDeclaration, ClassDeclarationType, FunctionBody, FunctionDeclaration,
         FunctionDeclarationType, VariableDeclaration,
     },
-    AstNodePos, NodeType, ProgramNode, SourceLocation,
+    AstNodePos, NodeType, ProgramNode, SourceLocation, Vec,
 };
 
 #[derive(Serialize)]
-pub enum ImportOrExportDeclaration {
-    ImportDeclaration(ImportDeclaration),
-    ExportNamedDeclaration(ExportNamedDeclaration),
-    ExportDefaultDeclaration(ExportDefaultDeclaration), // export default 1 or export default function() {}
-    ExportAllDeclaration(ExportAllDeclaration),         // export * from 'mod'
+#[serde(untagged)]
+pub enum ImportOrExportDeclaration<'a> {
+    ImportDeclaration(ImportDeclaration<'a>),
+    ExportNamedDeclaration(ExportNamedDeclaration<'a>),
+    ExportDefaultDeclaration(ExportDefaultDeclaration<'a>), // export default 1 or export default function() {}
+    ExportAllDeclaration(ExportAllDeclaration<'a>),         // export * from 'mod'
 }
 
-impl From<ImportOrExportDeclaration> for ProgramNode {
-    fn from(value: ImportOrExportDeclaration) -> Self {
+impl<'a> From<ImportOrExportDeclaration<'a>> for ProgramNode<'a> {
+    fn from(value: ImportOrExportDeclaration<'a>) -> Self {
         Self::ImportOrExportDeclaration(value)
     }
 }
 
-impl From<ImportDeclaration> for ImportOrExportDeclaration {
-    fn from(value: ImportDeclaration) -> Self {
+impl<'a> From<ImportDeclaration<'a>> for ImportOrExportDeclaration<'a> {
+    fn from(value: ImportDeclaration<'a>) -> Self {
         Self::ImportDeclaration(value)
     }
 }
 
-impl From<ExportNamedDeclaration> for ImportOrExportDeclaration {
-    fn from(value: ExportNamedDeclaration) -> Self {
+impl<'a> From<ExportNamedDeclaration<'a>> for ImportOrExportDeclaration<'a> {
+    fn from(value: ExportNamedDeclaration<'a>) -> Self {
         Self::ExportNamedDeclaration(value)
     }
 }
 
-impl From<ExportDefaultDeclaration> for ImportOrExportDeclaration {
-    fn from(value: ExportDefaultDeclaration) -> Self {
+impl<'a> From<ExportDefaultDeclaration<'a>> for ImportOrExportDeclaration<'a> {
+    fn from(value: ExportDefaultDeclaration<'a>) -> Self {
         Self::ExportDefaultDeclaration(value)
     }
 }
 
-impl From<ExportAllDeclaration> for ImportOrExportDeclaration {
-    fn from(value: ExportAllDeclaration) -> Self {
+impl<'a> From<ExportAllDeclaration<'a>> for ImportOrExportDeclaration<'a> {
+    fn from(value: ExportAllDeclaration<'a>) -> Self {
         Self::ExportAllDeclaration(value)
     }
 }
 
 #[derive(Serialize)]
-pub enum ImportSpecifiers {
-    ImportSpecifier(ImportSpecifier),
-    ImportDefaultSpecifier(ImportDefaultSpecifier),
-    ImportNamespaceSpecifier(ImportNamespaceSpecifier),
+#[serde(untagged)]
+pub enum ImportSpecifiers<'a> {
+    ImportSpecifier(ImportSpecifier<'a>),
+    ImportDefaultSpecifier(ImportDefaultSpecifier<'a>),
+    ImportNamespaceSpecifier(ImportNamespaceSpecifier<'a>),
 }
 
 // from es13, support imported's value could be literal without lone surrogate
 // see: https://github.com/tc39/ecma262/pull/2154
 #[derive(Serialize)]
-pub enum ImportedType {
-    Identifier(Identifier),
-    Literal(Literal),
+#[serde(untagged)]
+pub enum ImportedType<'a> {
+    Identifier(Identifier<'a>),
+    Literal(Literal<'a>),
 }
 
 // for case like: import {foo} from "mod" or {foo as bar} in import {foo as bar} from "mod"
 #[derive(Serialize)]
-pub struct ImportSpecifier {
+pub struct ImportSpecifier<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub imported: ImportedType,
-    pub local: Identifier,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub imported: ImportedType<'a>,
+    pub local: Identifier<'a>,
 }
 
-impl ImportSpecifier {
+impl<'a> ImportSpecifier<'a> {
     pub fn new(
-        imported: ImportedType,
-        local: Identifier,
-        (start_loc, end_loc, source_file): AstNodePos,
+        imported: ImportedType<'a>,
+        local: Identifier<'a>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ImportSpecifier,
@@ -87,85 +94,107 @@ impl ImportSpecifier {
             local,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 // for case: import foo from "mod.js".
 #[derive(Serialize)]
-pub struct ImportDefaultSpecifier {
+pub struct ImportDefaultSpecifier<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub local: Identifier,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub local: Identifier<'a>,
 }
 
-impl ImportDefaultSpecifier {
-    pub fn new(local: Identifier, (start_loc, end_loc, source_file): AstNodePos) -> Self {
+impl<'a> ImportDefaultSpecifier<'a> {
+    pub fn new(local: Identifier<'a>, (start_loc, end_loc, source_file): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::ImportDefaultSpecifier,
             local,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 // for case: import * as foo from "mod.js"
 #[derive(Serialize)]
-pub struct ImportNamespaceSpecifier {
+pub struct ImportNamespaceSpecifier<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub local: Identifier,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub local: Identifier<'a>,
 }
 
-impl ImportNamespaceSpecifier {
-    pub fn new(local: Identifier, (start_loc, end_loc, source_file): AstNodePos) -> Self {
+impl<'a> ImportNamespaceSpecifier<'a> {
+    pub fn new(local: Identifier<'a>, (start_loc, end_loc, source_file): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::ImportNamespaceSpecifier,
             local,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct ImportDeclaration {
+pub struct ImportDeclaration<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub specifiers: Vec<ImportSpecifiers>,
-    pub source: Literal,
-}
-
-impl ImportDeclaration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub specifiers: Vec<'a, ImportSpecifiers<'a>>,
+    pub source: Literal<'a>,
+}
+
+impl<'a> ImportDeclaration<'a> {
     pub fn new(
-        source: Literal,
-        specifiers: Vec<ImportSpecifiers>,
-        (start_loc, end_loc, source_file): AstNodePos,
+        source: Literal<'a>,
+        specifiers: Vec<'a, ImportSpecifiers<'a>>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ImportDeclaration,
@@ -173,51 +202,60 @@ impl ImportDeclaration {
             specifiers,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum ExportDeclaration {
-    FunctionDeclaration(FunctionDeclaration),
-    VariableDeclaration(VariableDeclaration),
-    ClassDeclaration(ClassDeclaration),
+#[serde(untagged)]
+pub enum ExportDeclaration<'a> {
+    FunctionDeclaration(FunctionDeclaration<'a>),
+    VariableDeclaration(VariableDeclaration<'a>),
+    ClassDeclaration(ClassDeclaration<'a>),
 }
 
 // if the type of local value is literal, then the type of source must be Some.
 #[derive(Serialize)]
-pub enum ExportLocal {
-    Identifier(Identifier),
-    Literal(Literal), // without lone surrogate
+#[serde(untagged)]
+pub enum ExportLocal<'a> {
+    Identifier(Identifier<'a>),
+    Literal(Literal<'a>), // without lone surrogate
 }
 
 #[derive(Serialize)]
-pub enum ExportedType {
-    Identifier(Identifier),
-    Literal(Literal), // without lone surrogate
+#[serde(untagged)]
+pub enum ExportedType<'a> {
+    Identifier(Identifier<'a>),
+    Literal(Literal<'a>), // without lone surrogate
 }
 
 #[derive(Serialize)]
-pub struct ExportSpecifier {
+pub struct ExportSpecifier<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub local: ExportLocal,
-    pub exported: ExportedType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub local: ExportLocal<'a>,
+    pub exported: ExportedType<'a>,
 }
 
-impl ExportSpecifier {
+impl<'a> ExportSpecifier<'a> {
     pub fn new(
-        local: ExportLocal,
-        exported: ExportedType,
-        (start_loc, end_loc, file_source): AstNodePos,
+        local: ExportLocal<'a>,
+        exported: ExportedType<'a>,
+        (start_loc, end_loc, file_source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ExportSpecifier,
@@ -225,11 +263,12 @@ impl ExportSpecifier {
             exported,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: file_source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
@@ -237,23 +276,36 @@ impl ExportSpecifier {
 // When declaration is Some, for case like: export var foo = 1.
 // And there can not be specifiers or source.
 #[derive(Serialize)]
-pub struct ExportNamedDeclaration {
+pub struct ExportNamedDeclaration<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub declaration: Option<ExportDeclaration>,
-    pub specifiers: Option<Vec<ExportSpecifier>>,
-    pub source: Option<Literal>,
-}
-
-impl ExportNamedDeclaration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub declaration: Option<ExportDeclaration<'a>>,
+    pub specifiers: Vec<'a, ExportSpecifier<'a>>,
+    pub source: Option<Literal<'a>>,
+}
+
+impl<'a> ExportNamedDeclaration<'a> {
     pub fn new(
-        declaration: Option<ExportDeclaration>,
-        specifiers: Option<Vec<ExportSpecifier>>,
-        source: Option<Literal>,
-        (start_loc, end_loc, file_source): AstNodePos,
+        declaration: Option<ExportDeclaration<'a>>,
+        specifiers: Vec<'a, ExportSpecifier<'a>>,
+        source: Option<Literal<'a>>,
+        (start_loc, end_loc, file_source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ExportNamedDeclaration,
@@ -262,11 +314,14 @@ impl ExportNamedDeclaration {
             source,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: file_source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
@@ -274,125 +329,182 @@ impl ExportNamedDeclaration {
 // Note: Although anonymouse decalaration seems like a expression,
 // here still need to use same type value with normal decalaration
 #[derive(Serialize)]
-pub enum ExportDefaultDeclarationType {
-    AnonymousDefaultExportedFunctionDeclaration(AnonymousDefaultExportedFunctionDeclaration),
-    FunctionDeclaration(FunctionDeclaration),
-    AnonymousDefaultExportedClassDeclaration(AnonymousDefaultExportedClassDeclaration),
-    ClassDeclaration(ClassDeclaration),
-    Expression(Expression),
+#[serde(untagged)]
+pub enum ExportDefaultDeclarationType<'a> {
+    AnonymousDefaultExportedFunctionDeclaration(AnonymousDefaultExportedFunctionDeclaration<'a>),
+    FunctionDeclaration(FunctionDeclaration<'a>),
+    AnonymousDefaultExportedClassDeclaration(AnonymousDefaultExportedClassDeclaration<'a>),
+    ClassDeclaration(ClassDeclaration<'a>),
+    Expression(Expression<'a>),
 }
 
 #[derive(Serialize)]
-pub struct AnonymousDefaultExportedFunctionDeclaration {
+pub struct AnonymousDefaultExportedFunctionDeclaration<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType, // "FunctionDeclaration"
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    // pub id: None; // the value is constant, ignore it directly
-    pub params: Vec<Pattern>,
-    pub body: FunctionBody,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    // 匿名函数的 id 始终为 None
+    pub id: Option<Identifier<'a>>,
+    pub expression: bool,
     pub generator: bool,
     // from es8
+    #[serde(rename(serialize = "async"))]
     pub is_async: bool,
+    pub params: Vec<'a, Pattern<'a>>,
+    pub body: FunctionBody<'a>,
 }
 
-impl AnonymousDefaultExportedFunctionDeclaration {
+impl<'a> AnonymousDefaultExportedFunctionDeclaration<'a> {
     pub fn new(
-        params: Vec<Pattern>,
-        body: FunctionBody,
+        params: Vec<'a, Pattern<'a>>,
+        body: FunctionBody<'a>,
         is_generator: bool,
         is_async: bool,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::FunctionDeclaration,
+            id: None,
+            expression: false,
             params,
             body,
             generator: is_generator,
             is_async,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
-impl From<AnonymousDefaultExportedFunctionDeclaration> for FunctionDeclarationType {
-    fn from(value: AnonymousDefaultExportedFunctionDeclaration) -> Self {
+impl<'a> From<AnonymousDefaultExportedFunctionDeclaration<'a>> for FunctionDeclarationType<'a> {
+    fn from(value: AnonymousDefaultExportedFunctionDeclaration<'a>) -> Self {
         Self::AnonymousDefaultExportedFunctionDeclaration(value)
     }
 }
 
 #[derive(Serialize)]
-pub struct AnonymousDefaultExportedClassDeclaration {
+pub struct AnonymousDefaultExportedClassDeclaration<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    // pub id: Identifier, // the value is constant, ignore it directly
-    pub super_class: Option<Expression>,
-    pub body: ClassBody,
-}
-
-impl AnonymousDefaultExportedClassDeclaration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    // 匿名类的 id 始终为 None
+    pub id: Option<Identifier<'a>>,
+    #[serde(rename(serialize = "superClass"))]
+    pub super_class: Option<Expression<'a>>,
+    pub body: ClassBody<'a>,
+}
+
+impl<'a> AnonymousDefaultExportedClassDeclaration<'a> {
     pub fn new(
-        super_class: Option<Expression>,
-        body: ClassBody,
-        (start_loc, end_loc, source_file): AstNodePos,
+        super_class: Option<Expression<'a>>,
+        body: ClassBody<'a>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ClassDeclaration,
+            id: None,
             super_class,
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
-impl From<AnonymousDefaultExportedClassDeclaration> for ClassDeclarationType {
-    fn from(value: AnonymousDefaultExportedClassDeclaration) -> Self {
+impl<'a> From<AnonymousDefaultExportedClassDeclaration<'a>> for ClassDeclarationType<'a> {
+    fn from(value: AnonymousDefaultExportedClassDeclaration<'a>) -> Self {
         Self::AnonymousDefaultExportedClassDeclaration(value)
     }
 }
 
 // e.g., export default function () {}; or export default 1;.
 #[derive(Serialize)]
-pub struct ExportDefaultDeclaration {
+pub struct ExportDefaultDeclaration<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub declaration: ExportDefaultDeclarationType,
-}
-
-impl ExportDefaultDeclaration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub declaration: ExportDefaultDeclarationType<'a>,
+}
+
+impl<'a> ExportDefaultDeclaration<'a> {
     pub fn new(
-        declaration: ExportDefaultDeclarationType,
-        (start_loc, end_loc, source_file): AstNodePos,
+        declaration: ExportDefaultDeclarationType<'a>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ExportDefaultDeclaration,
             declaration,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
@@ -400,29 +512,43 @@ impl ExportDefaultDeclaration {
 // from es11, eg. export * as foo from "mod";
 // from es13, support literal without lone surrogate
 #[derive(Serialize)]
-pub enum ExportAllExportedType {
-    Identifier(Identifier),
-    Literal(Literal),
+#[serde(untagged)]
+pub enum ExportAllExportedType<'a> {
+    Identifier(Identifier<'a>),
+    Literal(Literal<'a>),
     Null,
 }
 
 // e.g., export * from "mod";.
 #[derive(Serialize)]
-pub struct ExportAllDeclaration {
+pub struct ExportAllDeclaration<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub source: Literal,
-    pub exported: ExportAllExportedType,
-}
-
-impl ExportAllDeclaration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub exported: ExportAllExportedType<'a>,
+    pub source: Literal<'a>,
+}
+
+impl<'a> ExportAllDeclaration<'a> {
     pub fn new(
-        exported: ExportAllExportedType,
-        source: Literal,
-        (start_loc, end_loc, source_file): AstNodePos,
+        exported: ExportAllExportedType<'a>,
+        source: Literal<'a>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ExportAllDeclaration,
@@ -430,11 +556,14 @@ impl ExportAllDeclaration {
             source,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
@@ -0,0 +1,418 @@
+use super::{
+    comment::Comment,
+    directive::Directive,
+    expression::{
+        ArrayExprEle, ArrayExpression, ArrowFunctionBody, ArrowFunctionExpression,
+        AssignmentExpression, AssignmentExpressionLeft, AwaitExpression, BinaryExpression,
+        BinaryOpeartorLeft, CallExprArgs, CallExprCallee, CallExpression, ChainExpression,
+        ChainExpressionElement, ClassExpression, ConditionalExpression, Expression,
+        FunctionExpression, Identifier, ImportExpression, Literal, LogicalExpression,
+        MemberExprObject, MemberExprProperty, MemberExpression, MetaProperty, NewExprArgs,
+        NewExpression, ObjectExpression, ObjectProperty, ParenthesizedExpression,
+        PrivateIdentifier, Property, Reg, SequenceExpression, SpreadElement, StaticBlock, Super,
+        TaggedTemplateExpression, TemplateElement, TemplateLiteral, ThisExpression,
+        UnaryExpression, UpdateExpression, YieldExpression,
+    },
+    import_export_declaration::{
+        AnonymousDefaultExportedClassDeclaration, AnonymousDefaultExportedFunctionDeclaration,
+        ExportAllDeclaration, ExportAllExportedType, ExportDeclaration, ExportDefaultDeclaration,
+        ExportDefaultDeclarationType, ExportLocal, ExportNamedDeclaration, ExportSpecifier,
+        ExportedType, ImportDeclaration, ImportDefaultSpecifier, ImportNamespaceSpecifier,
+        ImportOrExportDeclaration, ImportSpecifier, ImportSpecifiers, ImportedType,
+    },
+    pattern::{
+        ArrayPattern, AssignmentPattern, AssignmentProperty, ObjectPattern, ObjectPatternProperty,
+        Pattern, RestElement,
+    },
+    regexp::{
+        Alternative, Assertion, Backreference, CapturingGroup, Character, CharacterClass,
+        CharacterClassRange, CharacterSet, ClassElement, ClassIntersection, ClassStringDisjunction,
+        ClassSubtraction, ExpressionCharacterClass, Group, Quantifier, RegExpAst, RegExpElement,
+        StringAlternative,
+    },
+    statement::{
+        BlockStatement, BreakStatement, CatchClause, ClassBody, ClassBodyEl, ClassDeclaration,
+        ClassMethodKey, ClassPropertyKey, ContinueStatement, DebuggerStatement, DoWhileStatement,
+        EmptyStatement, ExpressionStatement, ForInOfStatementLeft, ForInStatement, ForOfStatement,
+        ForStatement, ForStatementInit, FunctionBody, FunctionBodyContent, FunctionDeclaration,
+        IfStatement, InvalidStatement, LabeledStatement, MethodDefinition, PropertyDefinition,
+        ReturnStatement, Statement, SwitchCase, SwitchStatement, ThrowStatement, TryStatement,
+        VariableDeclaration, VariableDeclarator, WhileStatement, WithStatement,
+    },
+    Box, Position, Program, ProgramNode, SourceLocation,
+};
+use crate::error::ParseError;
+use crate::options::{ParserOptions, PositionEncoding};
+use crate::tokenizer::js_token::Token;
+
+/// 将解析时使用的字节位置转换为 `ParserOptions::position_encoding` 指定的单位
+#[derive(Debug)]
+pub struct OffsetEncoder {
+    /// 每个多字节字符结束处的字节位置，以及到此为止累计多出的字节数，按位置排列
+    wide_chars: std::vec::Vec<(usize, usize)>,
+}
+
+impl OffsetEncoder {
+    /// 不需要转换时（UTF-8 或者纯 ASCII 源码）返回 None
+    pub fn new(source: &str, encoding: PositionEncoding) -> Option<Self> {
+        if encoding == PositionEncoding::Utf8 || source.is_ascii() {
+            return None;
+        }
+        let mut wide_chars = vec![];
+        let mut extra = 0;
+        for (i, c) in source.char_indices() {
+            if c.is_ascii() {
+                continue;
+            }
+            let units = match encoding {
+                PositionEncoding::Utf16 => c.len_utf16(),
+                _ => 1,
+            };
+            extra += c.len_utf8() - units;
+            wide_chars.push((i + c.len_utf8(), extra));
+        }
+        Some(Self { wide_chars })
+    }
+
+    /// 字节位置 pos 转换后的位置
+    pub fn offset(&self, pos: usize) -> usize {
+        let idx = self.wide_chars.partition_point(|&(end, _)| end <= pos);
+        match idx.checked_sub(1) {
+            Some(idx) => pos - self.wide_chars[idx].1,
+            None => pos,
+        }
+    }
+
+    /// 同时转换位置和它所在的行列，列号依赖转换前的位置，所以两者要一起转换
+    pub fn encode(&self, pos: &mut usize, position: &mut Position) {
+        let line_start = self.offset(*pos - position.col);
+        *pos = self.offset(*pos);
+        position.col = *pos - line_start;
+    }
+
+    pub fn encode_error(&self, err: &mut ParseError) {
+        self.encode(&mut err.pos, &mut err.loc);
+    }
+}
+
+/// 解析完成后统一处理所有的位置信息：转换位置的单位，去掉 loc 或者加上 range
+#[derive(Debug)]
+pub struct LocationConfig {
+    pub encoder: Option<OffsetEncoder>,
+    locations: bool,
+    ranges: bool,
+}
+
+impl LocationConfig {
+    /// 使用默认配置，不需要做任何处理时返回 None
+    pub fn new(source: &str, options: &ParserOptions) -> Option<Self> {
+        let encoder = OffsetEncoder::new(source, options.position_encoding);
+        if encoder.is_none() && options.locations && !options.ranges {
+            return None;
+        }
+        Some(Self {
+            encoder,
+            locations: options.locations,
+            ranges: options.ranges,
+        })
+    }
+
+    fn offset(&self, pos: usize) -> usize {
+        self.encoder
+            .as_ref()
+            .map_or(pos, |encoder| encoder.offset(pos))
+    }
+
+    fn finish(
+        &self,
+        start: &mut usize,
+        end: &mut usize,
+        loc: &mut Option<SourceLocation>,
+        range: &mut Option<[usize; 2]>,
+    ) {
+        if let (Some(encoder), Some(loc)) = (&self.encoder, loc.as_mut()) {
+            encoder.encode(start, &mut loc.start);
+            encoder.encode(end, &mut loc.end);
+        }
+        if !self.locations {
+            *loc = None;
+        }
+        if self.ranges {
+            *range = Some([*start, *end]);
+        }
+    }
+
+    pub fn finish_error(&self, err: &mut ParseError) {
+        if let Some(encoder) = &self.encoder {
+            encoder.encode_error(err);
+        }
+    }
+}
+
+/// 包含位置信息的节点，处理自身以及所有子孙节点的位置
+pub trait FinishLocations {
+    fn finish_locations(&mut self, config: &LocationConfig);
+}
+
+impl<T: FinishLocations> FinishLocations for Box<'_, T> {
+    fn finish_locations(&mut self, config: &LocationConfig) {
+        self.as_mut().finish_locations(config);
+    }
+}
+
+impl<T: FinishLocations> FinishLocations for std::boxed::Box<T> {
+    fn finish_locations(&mut self, config: &LocationConfig) {
+        self.as_mut().finish_locations(config);
+    }
+}
+
+impl<T: FinishLocations> FinishLocations for Option<T> {
+    fn finish_locations(&mut self, config: &LocationConfig) {
+        if let Some(node) = self {
+            node.finish_locations(config);
+        }
+    }
+}
+
+impl<T: FinishLocations> FinishLocations for super::Vec<'_, T> {
+    fn finish_locations(&mut self, config: &LocationConfig) {
+        for node in self.iter_mut() {
+            node.finish_locations(config);
+        }
+    }
+}
+
+// 正则的 AST 和注释列表不在 arena 中
+impl<T: FinishLocations> FinishLocations for std::vec::Vec<T> {
+    fn finish_locations(&mut self, config: &LocationConfig) {
+        for node in self.iter_mut() {
+            node.finish_locations(config);
+        }
+    }
+}
+
+impl FinishLocations for Reg<'_> {
+    fn finish_locations(&mut self, config: &LocationConfig) {
+        self.ast.finish_locations(config);
+    }
+}
+
+/// ESTree 节点，start、end 与 loc 一一对应
+macro_rules! located {
+    ($($ty:ident { $($field:ident),* })*) => {
+        $(
+            impl FinishLocations for $ty<'_> {
+                fn finish_locations(&mut self, config: &LocationConfig) {
+                    config.finish(&mut self.start, &mut self.end, &mut self.loc, &mut self.range);
+                    $(self.$field.finish_locations(config);)*
+                }
+            }
+        )*
+    };
+}
+
+/// 可以挂载注释的节点，挂载的注释是 Program.comments 的拷贝，需要单独处理
+macro_rules! commented {
+    ($($ty:ident { $($field:ident),* })*) => {
+        located! { $($ty { leading_comments, trailing_comments $(, $field)* })* }
+    };
+}
+
+/// 正则的 AST 节点只有 start、end
+macro_rules! ranged {
+    ($($ty:ident { $($field:ident),* })*) => {
+        $(
+            impl FinishLocations for $ty {
+                fn finish_locations(&mut self, config: &LocationConfig) {
+                    self.start = config.offset(self.start);
+                    self.end = config.offset(self.end);
+                    $(self.$field.finish_locations(config);)*
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! transparent_enum {
+    ($($ty:ident $(<$lt:lifetime>)? { $($variant:ident),* })*) => {
+        $(
+            impl FinishLocations for $ty $(<$lt>)? {
+                fn finish_locations(&mut self, config: &LocationConfig) {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        $($ty::$variant(node) => node.finish_locations(config),)*
+                        _ => {}
+                    }
+                }
+            }
+        )*
+    };
+}
+
+located! {
+    Program { body, comments }
+    Comment {}
+    Token {}
+    SwitchCase { test, consequent }
+    CatchClause { param, body }
+    VariableDeclarator { id, init }
+    ImportSpecifier { imported, local }
+    ImportDefaultSpecifier { local }
+    ImportNamespaceSpecifier { local }
+    ExportSpecifier { local, exported }
+    PrivateIdentifier {}
+    Super {}
+    Property { key, value }
+    SpreadElement { argument }
+    TemplateElement {}
+    RestElement { argument }
+    ArrayPattern { elements }
+    ObjectPattern { properties }
+    AssignmentProperty { key, value }
+    AssignmentPattern { left, right }
+}
+
+commented! {
+    Directive { expression }
+    ExpressionStatement { expression }
+    BlockStatement { inner_comments, body }
+    FunctionBody { inner_comments, body }
+    ClassBody { inner_comments, body }
+    EmptyStatement {}
+    DebuggerStatement {}
+    WithStatement { object, body }
+    ReturnStatement { argument }
+    LabeledStatement { label, body }
+    BreakStatement { label }
+    ContinueStatement { label }
+    IfStatement { test, consequent, alternate }
+    SwitchStatement { discriminant, cases }
+    ThrowStatement { argument }
+    TryStatement { block, handler, finalizer }
+    WhileStatement { test, body }
+    DoWhileStatement { body, test }
+    ForStatement { init, test, update, body }
+    ForInStatement { left, right, body }
+    ForOfStatement { left, right, body }
+    FunctionDeclaration { id, params, body }
+    AnonymousDefaultExportedFunctionDeclaration { params, body }
+    AnonymousDefaultExportedClassDeclaration { super_class, body }
+    VariableDeclaration { declarations }
+    ClassDeclaration { id, super_class, body }
+    MethodDefinition { key, value }
+    PropertyDefinition { key, value }
+    StaticBlock { body }
+    InvalidStatement {}
+    ImportDeclaration { specifiers, source }
+    ExportNamedDeclaration { declaration, specifiers, source }
+    ExportDefaultDeclaration { declaration }
+    ExportAllDeclaration { exported, source }
+    Identifier {}
+    Literal { reg }
+    ThisExpression {}
+    ArrayExpression { elements }
+    ObjectExpression { properties }
+    FunctionExpression { id, params, body }
+    UnaryExpression { argument }
+    UpdateExpression { argument }
+    BinaryExpression { left, right }
+    AssignmentExpression { left, right }
+    LogicalExpression { left, right }
+    MemberExpression { object, property }
+    ConditionalExpression { test, consequent, alternate }
+    CallExpression { callee, arguments }
+    NewExpression { callee, arguments }
+    SequenceExpression { expressions }
+    ParenthesizedExpression { expression }
+    ArrowFunctionExpression { params, body }
+    YieldExpression { argument }
+    TemplateLiteral { quasis, expressions }
+    TaggedTemplateExpression { tag, quasi }
+    ClassExpression { id, super_class, body }
+    MetaProperty { meta, property }
+    AwaitExpression { argument }
+    ChainExpression { expression }
+    ImportExpression { source }
+}
+
+ranged! {
+    RegExpAst { alternatives }
+    Alternative { elements }
+    Group { alternatives }
+    CapturingGroup { alternatives }
+    Quantifier { element }
+    Assertion { alternatives }
+    CharacterSet {}
+    Character {}
+    Backreference {}
+    CharacterClass { elements }
+    CharacterClassRange { min, max }
+    ExpressionCharacterClass { expression }
+    ClassIntersection { left, right }
+    ClassSubtraction { left, right }
+    ClassStringDisjunction { alternatives }
+    StringAlternative { elements }
+}
+
+transparent_enum! {
+    ProgramNode<'_> { Directive, Statement, ImportOrExportDeclaration }
+    Statement<'_> {
+        ExpressionStatement, BlockStatement, EmptyStatement, DebuggerStatement, WithStatement,
+        ReturnStatement, LabeledStatement, BreakStatement, ContinueStatement, IfStatement,
+        SwitchStatement, ThrowStatement, TryStatement, WhileStatement, DoWhileStatement,
+        ForStatement, ForInStatement, ForOfStatement, FunctionDeclaration,
+        AnonymousDefaultExportedFunctionDeclaration, AnonymousDefaultExportedClassDeclaration,
+        VariableDeclaration, ClassDeclaration, InvalidStatement, ImportOrExportDeclaration
+    }
+    FunctionBodyContent<'_> { Directive, Statement }
+    ForStatementInit<'_> { VariableDeclaration, Expression }
+    ForInOfStatementLeft<'_> { VariableDeclaration, Pattern }
+    ClassBodyEl<'_> { MethodDefinition, PropertyDefinition, StaticBlock }
+    ClassMethodKey<'_> { Expression, PrivateIdentifier }
+    ClassPropertyKey<'_> { Expression, PrivateIdentifier }
+    Expression<'_> {
+        Identifier, Literal, ThisExpression, ArrayExpression, ObjectExpression,
+        FunctionExpression, UnaryExpression, UpdateExpression, BinaryExpression,
+        AssignmentExpression, LogicalExpression, MemberExpression, ConditionalExpression,
+        CallExpression, NewExpression, SequenceExpression, ParenthesizedExpression,
+        ArrowFunctionExpression, YieldExpression, TemplateLiteral, TaggedTemplateExpression,
+        ClassExpression, MetaProperty, AwaitExpression, ChainExpression, ImportExpression,
+        StaticBlock
+    }
+    ArrayExprEle<'_> { Expression, SpreadElement }
+    ObjectProperty<'_> { Property, SpreadElement }
+    BinaryOpeartorLeft<'_> { Expression, PrivateIdentifier }
+    AssignmentExpressionLeft<'_> { Pattern, Expression }
+    MemberExprObject<'_> { Expression, Super }
+    MemberExprProperty<'_> { Expression, PrivateIdentifier }
+    CallExprArgs<'_> { Expression, SpreadElement }
+    CallExprCallee<'_> { Expression, Super }
+    NewExprArgs<'_> { Expression, SpreadElement }
+    ArrowFunctionBody<'_> { FunctionBoby, Expression }
+    ChainExpressionElement<'_> { CallExpression, MemberExpression }
+    Pattern<'_> {
+        RestElement, ArrayPattern, ObjectPattern, Identifier, AssignmentPattern, MemberExpression
+    }
+    ObjectPatternProperty<'_> { AssignmentProperty, RestElement }
+    ImportOrExportDeclaration<'_> {
+        ImportDeclaration, ExportNamedDeclaration, ExportDefaultDeclaration, ExportAllDeclaration
+    }
+    ImportSpecifiers<'_> { ImportSpecifier, ImportDefaultSpecifier, ImportNamespaceSpecifier }
+    ImportedType<'_> { Identifier, Literal }
+    ExportDeclaration<'_> { FunctionDeclaration, VariableDeclaration, ClassDeclaration }
+    ExportLocal<'_> { Identifier, Literal }
+    ExportedType<'_> { Identifier, Literal }
+    ExportDefaultDeclarationType<'_> {
+        AnonymousDefaultExportedFunctionDeclaration, FunctionDeclaration,
+        AnonymousDefaultExportedClassDeclaration, ClassDeclaration, Expression
+    }
+    ExportAllExportedType<'_> { Identifier, Literal }
+    RegExpElement {
+        Assertion, Quantifier, Group, CapturingGroup, CharacterClass, ExpressionCharacterClass,
+        CharacterSet, Character, Backreference
+    }
+    ClassElement {
+        Character, CharacterClassRange, CharacterSet, CharacterClass, ExpressionCharacterClass,
+        ClassIntersection, ClassSubtraction, ClassStringDisjunction
+    }
+}
@@ -1,15 +1,39 @@
+/// 使用 `as_str` 的结果序列化，用于各种操作符
+macro_rules! serialize_as_str {
+    ($($ty:ident),*) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.as_str())
+                }
+            }
+        )*
+    };
+}
+
+pub mod comment;
 pub mod directive;
 pub mod expression;
+pub mod fold;
 pub mod import_export_declaration;
+pub(crate) mod location;
 pub mod pattern;
+pub mod regexp;
 pub mod statement;
+pub mod visit;
+pub mod visit_mut;
 
 use self::{
-    directive::Directive, import_export_declaration::ImportOrExportDeclaration,
+    comment::Comments, directive::Directive, import_export_declaration::ImportOrExportDeclaration,
     statement::Statement,
 };
+use crate::options::SourceType;
+use bumpalo::Bump;
 use serde::Serialize;
 
+// 节点的子节点以及子节点列表都分配在 arena 中，整棵树随 arena 一起释放
+pub use bumpalo::{boxed::Box, collections::Vec};
+
 // Node 类型包含所有节点的类型，大部分是 StatementType
 #[derive(Debug, Clone, Serialize)]
 pub enum NodeType {
@@ -35,10 +59,11 @@ pub enum NodeType {
     ForOfStatement,
     FunctionDeclaration,
     VariableDeclaration,
-    VaraiableDeclarator,
+    VariableDeclarator,
     ClassDeclaration,
     SwitchCase,
     CatchClause,
+    InvalidStatement,
     // Class Related
     Super,
     SpreadElement,
@@ -87,6 +112,7 @@ pub enum NodeType {
     AssignmentPattern,
     ClassBody,
     MethodDefinition,
+    PropertyDefinition,
     ClassExpression,
     MetaProperty,
     ImportDeclaration,
@@ -104,17 +130,21 @@ pub enum NodeType {
     StaticBlock,
 }
 
-pub fn create_program_node() -> Program {
+pub fn create_program_node<'a>(arena: &'a Bump, source_type: SourceType) -> Program<'a> {
     Program {
         _type: NodeType::Program,
         start: 0,
         end: 0,
-        loc: SourceLocation {
+        loc: Some(SourceLocation {
             source: None,
             start: Position { line: 1, col: 0 },
             end: Position { line: 0, col: 0 },
-        },
-        body: vec![],
+        }),
+        range: None,
+        body: Vec::new_in(arena),
+        source_type,
+        hashbang: None,
+        comments: vec![],
     }
 }
 
@@ -125,23 +155,25 @@ pub fn get_location_at(start: usize, start_loc: Position) -> _LocationNode {
     }
 }
 
-pub type AstNodePos = (_LocationNode, _LocationNode, Option<String>);
+pub type AstNodePos<'a> = (_LocationNode, _LocationNode, Option<&'a str>);
 
 // Rust 中没有继承，只有组合，关于为什么 Rust 不支持继承的原因，社区的理由是认为 OOP 会带来很多不必要的丑陋的编译后的代码的原因，
 // 以及后续维护代码时灵活性不够难以拆解导致的耦合过高，然而使用组合的方式可以让代码实现更加简洁，解除继承带来的耦合。
 // 问题讨论可见:
 // https://users.rust-lang.org/t/how-to-think-without-field-inheritance/78116/11
 // https://henrietteharmse.com/2015/04/18/the-rectanglesquare-controversy/
-#[derive(Clone, Debug, Serialize)]
-pub struct SourceLocation {
-    pub source: Option<String>,
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SourceLocation<'a> {
     pub start: Position,
     pub end: Position,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<&'a str>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
 pub struct Position {
     pub line: usize,
+    #[serde(rename(serialize = "column"))]
     pub col: usize,
 }
 
@@ -153,26 +185,38 @@ pub struct _LocationNode {
 }
 
 #[derive(Serialize)]
-pub enum ProgramNode {
-    Directive(Directive),
-    Statement(Statement),
+#[serde(untagged)]
+pub enum ProgramNode<'a> {
+    Directive(Directive<'a>),
+    Statement(Statement<'a>),
     // starts from es6
-    ImportOrExportDeclaration(ImportOrExportDeclaration),
+    ImportOrExportDeclaration(ImportOrExportDeclaration<'a>),
 }
 
-impl From<Statement> for ProgramNode {
-    fn from(value: Statement) -> Self {
+impl<'a> From<Statement<'a>> for ProgramNode<'a> {
+    fn from(value: Statement<'a>) -> Self {
         Self::Statement(value)
     }
 }
 
 #[derive(Serialize)]
-pub struct Program {
+pub struct Program<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
     // 包含所有 programe node
-    pub body: Vec<ProgramNode>,
+    pub body: Vec<'a, ProgramNode<'a>>,
+    #[serde(rename(serialize = "sourceType"))]
+    pub source_type: SourceType,
+    /// 文件开头 `#!` 之后直到行尾的内容，例如 `/usr/bin/env node`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashbang: Option<&'a str>,
+    /// 源码中所有的注释，按照出现的顺序排列，ESTree 中没有这个字段，所以不参与序列化
+    #[serde(skip)]
+    pub comments: Comments<'a>,
 }
@@ -2,118 +2,134 @@ use serde::Serialize;
 
 use super::{
     expression::{Expression, Identifier, MemberExpression},
-    AstNodePos, NodeType, SourceLocation,
+    AstNodePos, Box, NodeType, SourceLocation, Vec,
 };
 
 #[derive(Serialize)]
-pub enum Pattern {
-    RestElement(RestElement),
-    ArrayPattern(ArrayPattern),
-    ObjectPattern(ObjectPattern),
-    Identifier(Identifier),
-    AssignmentPattern(AssignmentPattern),
-    MemberExpression(MemberExpression),
-}
-
-impl From<Identifier> for Pattern {
-    fn from(value: Identifier) -> Self {
+#[serde(untagged)]
+pub enum Pattern<'a> {
+    RestElement(RestElement<'a>),
+    ArrayPattern(ArrayPattern<'a>),
+    ObjectPattern(ObjectPattern<'a>),
+    Identifier(Identifier<'a>),
+    AssignmentPattern(AssignmentPattern<'a>),
+    MemberExpression(MemberExpression<'a>),
+}
+
+impl<'a> From<Identifier<'a>> for Pattern<'a> {
+    fn from(value: Identifier<'a>) -> Self {
         Self::Identifier(value)
     }
 }
 
-impl From<RestElement> for Pattern {
-    fn from(value: RestElement) -> Self {
+impl<'a> From<RestElement<'a>> for Pattern<'a> {
+    fn from(value: RestElement<'a>) -> Self {
         Self::RestElement(value)
     }
 }
 
-impl From<ArrayPattern> for Pattern {
-    fn from(value: ArrayPattern) -> Self {
+impl<'a> From<ArrayPattern<'a>> for Pattern<'a> {
+    fn from(value: ArrayPattern<'a>) -> Self {
         Self::ArrayPattern(value)
     }
 }
 
 #[derive(Serialize)]
-pub struct RestElement {
+pub struct RestElement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub argument: Box<Pattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub argument: Box<'a, Pattern<'a>>,
 }
 
-impl RestElement {
-    pub fn new(arg: Box<Pattern>, (start_loc, end_loc, source): AstNodePos) -> Self {
+impl<'a> RestElement<'a> {
+    pub fn new(arg: Box<'a, Pattern<'a>>, (start_loc, end_loc, source): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::RestElement,
             argument: arg,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct ArrayPattern {
+pub struct ArrayPattern<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub elements: Vec<Option<Pattern>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub elements: Vec<'a, Option<Pattern<'a>>>,
 }
 
-impl ArrayPattern {
-    pub fn new(elements: Vec<Option<Pattern>>, (start_loc, end_loc, source): AstNodePos) -> Self {
+impl<'a> ArrayPattern<'a> {
+    pub fn new(
+        elements: Vec<'a, Option<Pattern<'a>>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::ArrayPattern,
             elements,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 #[derive(Serialize)]
+#[serde(rename_all(serialize = "lowercase"))]
 pub enum AssignmentPropertyKind {
     Init,
 }
 
 // comment the redundant property which inherited from Property
 #[derive(Serialize)]
-pub struct AssignmentProperty {
+pub struct AssignmentProperty<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType, // Property
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub kind: AssignmentPropertyKind,
-    pub value: Pattern,
-    pub key: Expression,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
     pub method: bool, // has to be false
     // if shorthand is true, computed must be false.
     pub shorthand: bool,
     pub computed: bool,
+    pub key: Expression<'a>,
+    pub kind: AssignmentPropertyKind,
+    pub value: Pattern<'a>,
 }
 
-impl AssignmentProperty {
+impl<'a> AssignmentProperty<'a> {
     pub fn new(
-        key: Expression,
-        value: Pattern,
+        key: Expression<'a>,
+        value: Pattern<'a>,
         shorthand: bool,
         computed: bool,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::Property,
@@ -125,11 +141,12 @@ impl AssignmentProperty {
             computed,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
@@ -137,68 +154,76 @@ impl AssignmentProperty {
 // from es9, object pattern property support RestElement,
 // eg.{a, ...rest} = obj
 #[derive(Serialize)]
-pub enum ObjectPatternProperty {
-    AssignmentProperty(AssignmentProperty),
-    RestElement(RestElement),
+#[serde(untagged)]
+pub enum ObjectPatternProperty<'a> {
+    AssignmentProperty(AssignmentProperty<'a>),
+    RestElement(RestElement<'a>),
 }
 
-impl From<AssignmentProperty> for ObjectPatternProperty {
-    fn from(value: AssignmentProperty) -> Self {
+impl<'a> From<AssignmentProperty<'a>> for ObjectPatternProperty<'a> {
+    fn from(value: AssignmentProperty<'a>) -> Self {
         Self::AssignmentProperty(value)
     }
 }
 
-impl From<RestElement> for ObjectPatternProperty {
-    fn from(value: RestElement) -> Self {
+impl<'a> From<RestElement<'a>> for ObjectPatternProperty<'a> {
+    fn from(value: RestElement<'a>) -> Self {
         Self::RestElement(value)
     }
 }
 
 #[derive(Serialize)]
-pub struct ObjectPattern {
+pub struct ObjectPattern<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub properties: Vec<ObjectPatternProperty>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub properties: Vec<'a, ObjectPatternProperty<'a>>,
 }
 
-impl ObjectPattern {
+impl<'a> ObjectPattern<'a> {
     pub fn new(
-        properties: Vec<ObjectPatternProperty>,
-        (start_loc, end_loc, source): AstNodePos,
+        properties: Vec<'a, ObjectPatternProperty<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ObjectPattern,
             properties,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct AssignmentPattern {
+pub struct AssignmentPattern<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub left: Box<Pattern>,
-    pub right: Box<Expression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub left: Box<'a, Pattern<'a>>,
+    pub right: Box<'a, Expression<'a>>,
 }
 
-impl AssignmentPattern {
+impl<'a> AssignmentPattern<'a> {
     pub fn new(
-        left: Box<Pattern>,
-        right: Box<Expression>,
-        (start_loc, end_loc, source_file): AstNodePos,
+        left: Box<'a, Pattern<'a>>,
+        right: Box<'a, Expression<'a>>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::AssignmentPattern,
@@ -206,11 +231,12 @@ impl AssignmentPattern {
             right,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
@@ -0,0 +1,323 @@
+// 正则表达式 pattern 的 AST，节点的结构参考 regexpp，但是不包含 parent 等循环引用的字段
+// start 和 end 与其它节点一样，是在整个源码中的字符位置
+use serde::Serialize;
+
+/// The root of a parsed regular expression pattern.
+pub type RegExpAst = Pattern;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RegExpNodeType {
+    Pattern,
+    Alternative,
+    Group,
+    CapturingGroup,
+    Quantifier,
+    CharacterClass,
+    CharacterClassRange,
+    ExpressionCharacterClass,
+    ClassIntersection,
+    ClassSubtraction,
+    ClassStringDisjunction,
+    StringAlternative,
+    Assertion,
+    CharacterSet,
+    Character,
+    Backreference,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Pattern {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub alternatives: Vec<Alternative>,
+}
+
+/// `a|b` 中的 a 与 b
+#[derive(Debug, Clone, Serialize)]
+pub struct Alternative {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub elements: Vec<RegExpElement>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum RegExpElement {
+    Assertion(Assertion),
+    Quantifier(Quantifier),
+    Group(Group),
+    CapturingGroup(CapturingGroup),
+    CharacterClass(CharacterClass),
+    ExpressionCharacterClass(ExpressionCharacterClass),
+    CharacterSet(CharacterSet),
+    Character(Character),
+    Backreference(Backreference),
+}
+
+impl RegExpElement {
+    pub fn start(&self) -> usize {
+        match self {
+            Self::Assertion(node) => node.start,
+            Self::Quantifier(node) => node.start,
+            Self::Group(node) => node.start,
+            Self::CapturingGroup(node) => node.start,
+            Self::CharacterClass(node) => node.start,
+            Self::ExpressionCharacterClass(node) => node.start,
+            Self::CharacterSet(node) => node.start,
+            Self::Character(node) => node.start,
+            Self::Backreference(node) => node.start,
+        }
+    }
+}
+
+/// `(?:a)`
+#[derive(Debug, Clone, Serialize)]
+pub struct Group {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub alternatives: Vec<Alternative>,
+}
+
+/// `(a)` or `(?<name>a)`
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturingGroup {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub name: Option<String>,
+    pub alternatives: Vec<Alternative>,
+}
+
+/// `a*`、`a+?`、`a{1,2}`，max 为 None 时表示没有上限
+#[derive(Debug, Clone, Serialize)]
+pub struct Quantifier {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub min: u32,
+    pub max: Option<u32>,
+    pub greedy: bool,
+    pub element: Box<RegExpElement>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all(serialize = "lowercase"))]
+pub enum AssertionKind {
+    /// `^`
+    Start,
+    /// `$`
+    End,
+    /// `\b` or `\B`
+    Word,
+    /// `(?=a)` or `(?!a)`
+    Lookahead,
+    /// `(?<=a)` or `(?<!a)`
+    Lookbehind,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Assertion {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub kind: AssertionKind,
+    // `^`、`$` 没有 negate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub negate: Option<bool>,
+    // 只有 lookahead 与 lookbehind 有 alternatives
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alternatives: Option<Vec<Alternative>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all(serialize = "lowercase"))]
+pub enum CharacterSetKind {
+    /// `.`
+    Any,
+    /// `\d` or `\D`
+    Digit,
+    /// `\s` or `\S`
+    Space,
+    /// `\w` or `\W`
+    Word,
+    /// `\p{...}` or `\P{...}`
+    Property,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CharacterSet {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub kind: CharacterSetKind,
+    // `.` 没有 negate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub negate: Option<bool>,
+    // 以下字段只用于 `\p{key=value}`，value 为 None 时表示 `\p{key}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    // 是否可能匹配字符串，比如 `\p{RGI_Emoji}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strings: Option<bool>,
+}
+
+/// A single character, `value` is its code point.
+#[derive(Debug, Clone, Serialize)]
+pub struct Character {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub value: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum BackreferenceRef {
+    Number(u32),
+    Name(String),
+}
+
+/// `\1` or `\k<name>`
+#[derive(Debug, Clone, Serialize)]
+pub struct Backreference {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    #[serde(rename(serialize = "ref"))]
+    pub reference: BackreferenceRef,
+}
+
+/// `[a-z]`，`v` 模式下可以嵌套
+#[derive(Debug, Clone, Serialize)]
+pub struct CharacterClass {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    #[serde(rename(serialize = "unicodeSets"))]
+    pub unicode_sets: bool,
+    pub negate: bool,
+    pub elements: Vec<ClassElement>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ClassElement {
+    Character(Character),
+    CharacterClassRange(CharacterClassRange),
+    CharacterSet(CharacterSet),
+    // 以下只出现在 `v` 模式下
+    CharacterClass(CharacterClass),
+    ExpressionCharacterClass(ExpressionCharacterClass),
+    ClassIntersection(ClassIntersection),
+    ClassSubtraction(ClassSubtraction),
+    ClassStringDisjunction(ClassStringDisjunction),
+}
+
+impl ClassElement {
+    pub fn start(&self) -> usize {
+        match self {
+            Self::Character(node) => node.start,
+            Self::CharacterClassRange(node) => node.start,
+            Self::CharacterSet(node) => node.start,
+            Self::CharacterClass(node) => node.start,
+            Self::ExpressionCharacterClass(node) => node.start,
+            Self::ClassIntersection(node) => node.start,
+            Self::ClassSubtraction(node) => node.start,
+            Self::ClassStringDisjunction(node) => node.start,
+        }
+    }
+}
+
+/// `a-z`
+#[derive(Debug, Clone, Serialize)]
+pub struct CharacterClassRange {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub min: Character,
+    pub max: Character,
+}
+
+/// `[a&&b]` or `[a--b]`
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpressionCharacterClass {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub negate: bool,
+    pub expression: Box<ClassElement>,
+}
+
+/// `a&&b`
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassIntersection {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub left: Box<ClassElement>,
+    pub right: Box<ClassElement>,
+}
+
+/// `a--b`
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassSubtraction {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub left: Box<ClassElement>,
+    pub right: Box<ClassElement>,
+}
+
+/// `\q{abc|d}`
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassStringDisjunction {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub alternatives: Vec<StringAlternative>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StringAlternative {
+    #[serde(rename(serialize = "type"))]
+    pub _type: RegExpNodeType,
+    pub start: usize,
+    pub end: usize,
+    pub raw: String,
+    pub elements: Vec<Character>,
+}
@@ -1,295 +1,439 @@
 use serde::Serialize;
 
 use super::{
+    comment::Comments,
     directive::Directive,
     expression::{Expression, FunctionExpression, Identifier, PrivateIdentifier, StaticBlock},
     import_export_declaration::{
         AnonymousDefaultExportedClassDeclaration, AnonymousDefaultExportedFunctionDeclaration,
+        ImportOrExportDeclaration,
     },
     pattern::Pattern,
-    AstNodePos, NodeType, SourceLocation,
+    AstNodePos, Box, NodeType, SourceLocation, Vec,
 };
 
 #[derive(Serialize)]
-pub enum Statement {
-    ExpressionStatement(ExpressionStatement),
-    BlockStatement(BlockStatement),
-    EmptyStatement(EmptyStatement),
-    DebuggerStatement(DebuggerStatement),
-    WithStatement(WithStatement),
-    ReturnStatement(ReturnStatement),
-    LabeledStatement(LabeledStatement),
-    BreakStatement(BreakStatement),
-    ContinueStatement(ContinueStatement),
-    IfStatement(IfStatement),
-    SwitchStatement(SwitchStatement),
-    ThrowStatement(ThrowStatement),
-    TryStatement(TryStatement),
-    WhileStatement(WhileStatement),
-    DoWhileStatement(DoWhileStatement),
-    ForStatement(ForStatement),
-    ForInStatement(ForInStatement),
+#[serde(untagged)]
+pub enum Statement<'a> {
+    ExpressionStatement(ExpressionStatement<'a>),
+    BlockStatement(BlockStatement<'a>),
+    EmptyStatement(EmptyStatement<'a>),
+    DebuggerStatement(DebuggerStatement<'a>),
+    WithStatement(WithStatement<'a>),
+    ReturnStatement(ReturnStatement<'a>),
+    LabeledStatement(LabeledStatement<'a>),
+    BreakStatement(BreakStatement<'a>),
+    ContinueStatement(ContinueStatement<'a>),
+    IfStatement(IfStatement<'a>),
+    SwitchStatement(SwitchStatement<'a>),
+    ThrowStatement(ThrowStatement<'a>),
+    TryStatement(TryStatement<'a>),
+    WhileStatement(WhileStatement<'a>),
+    DoWhileStatement(DoWhileStatement<'a>),
+    ForStatement(ForStatement<'a>),
+    ForInStatement(ForInStatement<'a>),
     // starts from es6
-    ForOfStatement(ForOfStatement),
+    ForOfStatement(ForOfStatement<'a>),
     // 在 ESTree 的定义里，declaration 也属于 Statement
-    FunctionDeclaration(FunctionDeclaration),
-    AnonymousDefaultExportedFunctionDeclaration(AnonymousDefaultExportedFunctionDeclaration),
-    AnonymousDefaultExportedClassDeclaration(AnonymousDefaultExportedClassDeclaration),
-    VariableDeclaration(VariableDeclaration),
+    FunctionDeclaration(FunctionDeclaration<'a>),
+    AnonymousDefaultExportedFunctionDeclaration(AnonymousDefaultExportedFunctionDeclaration<'a>),
+    AnonymousDefaultExportedClassDeclaration(AnonymousDefaultExportedClassDeclaration<'a>),
+    VariableDeclaration(VariableDeclaration<'a>),
     // starts from es6
-    ClassDeclaration(ClassDeclaration),
+    ClassDeclaration(ClassDeclaration<'a>),
+    // 容错模式下无法解析而被跳过的语句
+    InvalidStatement(InvalidStatement<'a>),
+    // 开启 allow_import_export_everywhere 后，出现在语句位置的 import/export
+    ImportOrExportDeclaration(ImportOrExportDeclaration<'a>),
 }
 
-impl From<ExpressionStatement> for Statement {
-    fn from(value: ExpressionStatement) -> Self {
+impl<'a> From<ExpressionStatement<'a>> for Statement<'a> {
+    fn from(value: ExpressionStatement<'a>) -> Self {
         Self::ExpressionStatement(value)
     }
 }
 
-impl From<BlockStatement> for Statement {
-    fn from(value: BlockStatement) -> Self {
+impl<'a> From<BlockStatement<'a>> for Statement<'a> {
+    fn from(value: BlockStatement<'a>) -> Self {
         Self::BlockStatement(value)
     }
 }
 
-impl From<EmptyStatement> for Statement {
-    fn from(value: EmptyStatement) -> Self {
+impl<'a> From<EmptyStatement<'a>> for Statement<'a> {
+    fn from(value: EmptyStatement<'a>) -> Self {
         Self::EmptyStatement(value)
     }
 }
 
-impl From<DebuggerStatement> for Statement {
-    fn from(value: DebuggerStatement) -> Self {
+impl<'a> From<InvalidStatement<'a>> for Statement<'a> {
+    fn from(value: InvalidStatement<'a>) -> Self {
+        Self::InvalidStatement(value)
+    }
+}
+
+impl<'a> From<ImportOrExportDeclaration<'a>> for Statement<'a> {
+    fn from(value: ImportOrExportDeclaration<'a>) -> Self {
+        Self::ImportOrExportDeclaration(value)
+    }
+}
+
+impl<'a> From<DebuggerStatement<'a>> for Statement<'a> {
+    fn from(value: DebuggerStatement<'a>) -> Self {
         Self::DebuggerStatement(value)
     }
 }
 
-impl From<WithStatement> for Statement {
-    fn from(value: WithStatement) -> Self {
+impl<'a> From<WithStatement<'a>> for Statement<'a> {
+    fn from(value: WithStatement<'a>) -> Self {
         Self::WithStatement(value)
     }
 }
 
-impl From<ReturnStatement> for Statement {
-    fn from(value: ReturnStatement) -> Self {
+impl<'a> From<ReturnStatement<'a>> for Statement<'a> {
+    fn from(value: ReturnStatement<'a>) -> Self {
         Self::ReturnStatement(value)
     }
 }
 
-impl From<LabeledStatement> for Statement {
-    fn from(value: LabeledStatement) -> Self {
+impl<'a> From<LabeledStatement<'a>> for Statement<'a> {
+    fn from(value: LabeledStatement<'a>) -> Self {
         Self::LabeledStatement(value)
     }
 }
 
-impl From<BreakStatement> for Statement {
-    fn from(value: BreakStatement) -> Self {
+impl<'a> From<BreakStatement<'a>> for Statement<'a> {
+    fn from(value: BreakStatement<'a>) -> Self {
         Self::BreakStatement(value)
     }
 }
 
-impl From<ContinueStatement> for Statement {
-    fn from(value: ContinueStatement) -> Self {
+impl<'a> From<ContinueStatement<'a>> for Statement<'a> {
+    fn from(value: ContinueStatement<'a>) -> Self {
         Self::ContinueStatement(value)
     }
 }
 
-impl From<IfStatement> for Statement {
-    fn from(value: IfStatement) -> Self {
+impl<'a> From<IfStatement<'a>> for Statement<'a> {
+    fn from(value: IfStatement<'a>) -> Self {
         Self::IfStatement(value)
     }
 }
 
-impl From<SwitchStatement> for Statement {
-    fn from(value: SwitchStatement) -> Self {
+impl<'a> From<SwitchStatement<'a>> for Statement<'a> {
+    fn from(value: SwitchStatement<'a>) -> Self {
         Self::SwitchStatement(value)
     }
 }
 
-impl From<ThrowStatement> for Statement {
-    fn from(value: ThrowStatement) -> Self {
+impl<'a> From<ThrowStatement<'a>> for Statement<'a> {
+    fn from(value: ThrowStatement<'a>) -> Self {
         Self::ThrowStatement(value)
     }
 }
 
-impl From<TryStatement> for Statement {
-    fn from(value: TryStatement) -> Self {
+impl<'a> From<TryStatement<'a>> for Statement<'a> {
+    fn from(value: TryStatement<'a>) -> Self {
         Self::TryStatement(value)
     }
 }
 
-impl From<WhileStatement> for Statement {
-    fn from(value: WhileStatement) -> Self {
+impl<'a> From<WhileStatement<'a>> for Statement<'a> {
+    fn from(value: WhileStatement<'a>) -> Self {
         Self::WhileStatement(value)
     }
 }
 
-impl From<DoWhileStatement> for Statement {
-    fn from(value: DoWhileStatement) -> Self {
+impl<'a> From<DoWhileStatement<'a>> for Statement<'a> {
+    fn from(value: DoWhileStatement<'a>) -> Self {
         Self::DoWhileStatement(value)
     }
 }
 
-impl From<ForStatement> for Statement {
-    fn from(value: ForStatement) -> Self {
+impl<'a> From<ForStatement<'a>> for Statement<'a> {
+    fn from(value: ForStatement<'a>) -> Self {
         Self::ForStatement(value)
     }
 }
 
-impl From<ForInStatement> for Statement {
-    fn from(value: ForInStatement) -> Self {
+impl<'a> From<ForInStatement<'a>> for Statement<'a> {
+    fn from(value: ForInStatement<'a>) -> Self {
         Self::ForInStatement(value)
     }
 }
 
-impl From<ForOfStatement> for Statement {
-    fn from(value: ForOfStatement) -> Self {
+impl<'a> From<ForOfStatement<'a>> for Statement<'a> {
+    fn from(value: ForOfStatement<'a>) -> Self {
         Self::ForOfStatement(value)
     }
 }
 
-impl From<FunctionDeclaration> for Statement {
-    fn from(value: FunctionDeclaration) -> Self {
+impl<'a> From<FunctionDeclaration<'a>> for Statement<'a> {
+    fn from(value: FunctionDeclaration<'a>) -> Self {
         Self::FunctionDeclaration(value)
     }
 }
 
-impl From<AnonymousDefaultExportedFunctionDeclaration> for Statement {
-    fn from(value: AnonymousDefaultExportedFunctionDeclaration) -> Self {
+impl<'a> From<AnonymousDefaultExportedFunctionDeclaration<'a>> for Statement<'a> {
+    fn from(value: AnonymousDefaultExportedFunctionDeclaration<'a>) -> Self {
         Self::AnonymousDefaultExportedFunctionDeclaration(value)
     }
 }
 
-impl From<VariableDeclaration> for Statement {
-    fn from(value: VariableDeclaration) -> Self {
+impl<'a> From<VariableDeclaration<'a>> for Statement<'a> {
+    fn from(value: VariableDeclaration<'a>) -> Self {
         Self::VariableDeclaration(value)
     }
 }
 
-impl From<ClassDeclaration> for Statement {
-    fn from(value: ClassDeclaration) -> Self {
+impl<'a> From<ClassDeclaration<'a>> for Statement<'a> {
+    fn from(value: ClassDeclaration<'a>) -> Self {
         Self::ClassDeclaration(value)
     }
 }
 
 // statements definition starts
 #[derive(Serialize)]
-pub struct ExpressionStatement {
+pub struct ExpressionStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub expression: Expression,
-}
-
-impl ExpressionStatement {
-    pub fn new(expr: Expression, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub expression: Expression<'a>,
+}
+
+impl<'a> ExpressionStatement<'a> {
+    pub fn new(expr: Expression<'a>, (start_loc, end_loc, source): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::ExpressionStatement,
             expression: expr,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct BlockStatement {
+pub struct BlockStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub body: Vec<Statement>,
-}
-
-impl BlockStatement {
-    pub fn new(body: Vec<Statement>, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    /// 空的语句块中的注释，例如 `function f() { /* c */ }`
+    #[serde(
+        rename(serialize = "innerComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub inner_comments: Comments<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+impl<'a> BlockStatement<'a> {
+    pub fn new(body: Vec<'a, Statement<'a>>, (start_loc, end_loc, source): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::BlockStatement,
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
+            inner_comments: vec![],
         }
     }
 }
 
 // for a solitary semicolon
 #[derive(Serialize)]
-pub struct EmptyStatement {
+pub struct EmptyStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-}
-
-impl EmptyStatement {
-    pub fn new((start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+}
+
+impl<'a> EmptyStatement<'a> {
+    pub fn new((start_loc, end_loc, source): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::EmptyStatement,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
+// the source range skipped by the parser after a syntax error in error-recovery mode
 #[derive(Serialize)]
-pub struct DebuggerStatement {
+pub struct InvalidStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+}
+
+impl<'a> InvalidStatement<'a> {
+    pub fn new((start_loc, end_loc, source): AstNodePos<'a>) -> Self {
+        Self {
+            _type: NodeType::InvalidStatement,
+            start: start_loc.pos,
+            end: end_loc.pos,
+            loc: Some(SourceLocation {
+                source,
+                start: start_loc.loc,
+                end: end_loc.loc,
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
+        }
+    }
 }
 
-impl DebuggerStatement {
-    pub fn new((start_loc, end_loc, source): AstNodePos) -> Self {
+#[derive(Serialize)]
+pub struct DebuggerStatement<'a> {
+    #[serde(rename(serialize = "type"))]
+    pub _type: NodeType,
+    pub start: usize,
+    pub end: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+}
+
+impl<'a> DebuggerStatement<'a> {
+    pub fn new((start_loc, end_loc, source): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::DebuggerStatement,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct WithStatement {
+pub struct WithStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub object: Expression,
-    pub body: Box<Statement>,
-}
-
-impl WithStatement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub object: Expression<'a>,
+    pub body: Box<'a, Statement<'a>>,
+}
+
+impl<'a> WithStatement<'a> {
     pub fn new(
-        object: Expression,
-        body: Box<Statement>,
-        (start_loc, end_loc, source): AstNodePos,
+        object: Expression<'a>,
+        body: Box<'a, Statement<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::WithStatement,
@@ -297,57 +441,92 @@ impl WithStatement {
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct ReturnStatement {
+pub struct ReturnStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub argument: Option<Expression>,
-}
-
-impl ReturnStatement {
-    pub fn new(argument: Option<Expression>, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub argument: Option<Expression<'a>>,
+}
+
+impl<'a> ReturnStatement<'a> {
+    pub fn new(
+        argument: Option<Expression<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::ReturnStatement,
             argument,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct LabeledStatement {
+pub struct LabeledStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub label: Identifier,
-    pub body: Box<Statement>,
-}
-
-impl LabeledStatement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub body: Box<'a, Statement<'a>>,
+    pub label: Identifier<'a>,
+}
+
+impl<'a> LabeledStatement<'a> {
     pub fn new(
-        label: Identifier,
-        body: Box<Statement>,
-        (start_loc, end_loc, source): AstNodePos,
+        label: Identifier<'a>,
+        body: Box<'a, Statement<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::LabeledStatement,
@@ -355,85 +534,139 @@ impl LabeledStatement {
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct BreakStatement {
+pub struct BreakStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub label: Option<Identifier>,
-}
-
-impl BreakStatement {
-    pub fn new(label: Option<Identifier>, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub label: Option<Identifier<'a>>,
+}
+
+impl<'a> BreakStatement<'a> {
+    pub fn new(
+        label: Option<Identifier<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::BreakStatement,
             label,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct ContinueStatement {
+pub struct ContinueStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub label: Option<Identifier>,
-}
-
-impl ContinueStatement {
-    pub fn new(label: Option<Identifier>, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub label: Option<Identifier<'a>>,
+}
+
+impl<'a> ContinueStatement<'a> {
+    pub fn new(
+        label: Option<Identifier<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::ContinueStatement,
             label,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct IfStatement {
+pub struct IfStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub test: Expression,
-    pub consequent: Box<Statement>,
-    pub alternate: Option<Box<Statement>>,
-}
-
-impl IfStatement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub test: Expression<'a>,
+    pub consequent: Box<'a, Statement<'a>>,
+    pub alternate: Option<Box<'a, Statement<'a>>>,
+}
+
+impl<'a> IfStatement<'a> {
     pub fn new(
-        test: Expression,
-        consequent: Box<Statement>,
-        alternate: Option<Box<Statement>>,
-        (start_loc, end_loc, source): AstNodePos,
+        test: Expression<'a>,
+        consequent: Box<'a, Statement<'a>>,
+        alternate: Option<Box<'a, Statement<'a>>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::IfStatement,
@@ -442,32 +675,38 @@ impl IfStatement {
             alternate,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct SwitchCase {
+pub struct SwitchCase<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub consequent: Vec<'a, Statement<'a>>,
     // test == None indicates it's a defualt clause
-    pub test: Option<Expression>,
-    pub consequent: Vec<Statement>,
+    pub test: Option<Expression<'a>>,
 }
 
-impl SwitchCase {
+impl<'a> SwitchCase<'a> {
     pub fn new(
-        test: Option<Expression>,
-        consequent: Vec<Statement>,
-        (start_loc, end_loc, source): AstNodePos,
+        test: Option<Expression<'a>>,
+        consequent: Vec<'a, Statement<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::SwitchCase,
@@ -475,31 +714,45 @@ impl SwitchCase {
             consequent,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct SwitchStatement {
+pub struct SwitchStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub discriminant: Expression,
-    pub cases: Vec<SwitchCase>,
-}
-
-impl SwitchStatement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub discriminant: Expression<'a>,
+    pub cases: Vec<'a, SwitchCase<'a>>,
+}
+
+impl<'a> SwitchStatement<'a> {
     pub fn new(
-        discriminant: Expression,
-        cases: Vec<SwitchCase>,
-        (start_loc, end_loc, source): AstNodePos,
+        discriminant: Expression<'a>,
+        cases: Vec<'a, SwitchCase<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::SwitchStatement,
@@ -507,58 +760,80 @@ impl SwitchStatement {
             cases,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct ThrowStatement {
+pub struct ThrowStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub argument: Expression,
-}
-
-impl ThrowStatement {
-    pub fn new(argument: Expression, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub argument: Expression<'a>,
+}
+
+impl<'a> ThrowStatement<'a> {
+    pub fn new(argument: Expression<'a>, (start_loc, end_loc, source): AstNodePos<'a>) -> Self {
         Self {
             _type: NodeType::ThrowStatement,
             argument,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 // from es10, catch clause's param could be null
 #[derive(Serialize)]
-pub struct CatchClause {
+pub struct CatchClause<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub param: Option<Pattern>,
-    pub body: BlockStatement,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub param: Option<Pattern<'a>>,
+    pub body: BlockStatement<'a>,
 }
 
-impl CatchClause {
+impl<'a> CatchClause<'a> {
     pub fn new(
-        param: Option<Pattern>,
-        body: BlockStatement,
-        (start_loc, end_loc, source): AstNodePos,
+        param: Option<Pattern<'a>>,
+        body: BlockStatement<'a>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::CatchClause,
@@ -566,33 +841,47 @@ impl CatchClause {
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct TryStatement {
+pub struct TryStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub block: BlockStatement,
-    pub handler: Option<CatchClause>,
-    pub finalizer: Option<BlockStatement>,
-}
-
-impl TryStatement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub block: BlockStatement<'a>,
+    pub handler: Option<CatchClause<'a>>,
+    pub finalizer: Option<BlockStatement<'a>>,
+}
+
+impl<'a> TryStatement<'a> {
     pub fn new(
-        block: BlockStatement,
-        handler: Option<CatchClause>,
-        finalizer: Option<BlockStatement>,
-        (start_loc, end_loc, source): AstNodePos,
+        block: BlockStatement<'a>,
+        handler: Option<CatchClause<'a>>,
+        finalizer: Option<BlockStatement<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::TryStatement,
@@ -601,31 +890,47 @@ impl TryStatement {
             finalizer,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct WhileStatement {
+pub struct WhileStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub test: Expression,
-    pub body: Box<Statement>,
-}
-
-impl WhileStatement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub test: Expression<'a>,
+    pub body: Box<'a, Statement<'a>>,
+}
+
+impl<'a> WhileStatement<'a> {
     pub fn new(
-        test: Expression,
-        body: Box<Statement>,
-        (start_loc, end_loc, source): AstNodePos,
+        test: Expression<'a>,
+        body: Box<'a, Statement<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::WhileStatement,
@@ -633,31 +938,47 @@ impl WhileStatement {
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct DoWhileStatement {
+pub struct DoWhileStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub body: Box<Statement>,
-    pub test: Expression,
-}
-
-impl DoWhileStatement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub body: Box<'a, Statement<'a>>,
+    pub test: Expression<'a>,
+}
+
+impl<'a> DoWhileStatement<'a> {
     pub fn new(
-        body: Box<Statement>,
-        test: Expression,
-        (start_loc, end_loc, source): AstNodePos,
+        body: Box<'a, Statement<'a>>,
+        test: Expression<'a>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::DoWhileStatement,
@@ -665,75 +986,120 @@ impl DoWhileStatement {
             test,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum FunctionBodyContent {
-    Directive(Directive),
-    Statement(Box<Statement>),
+#[serde(untagged)]
+pub enum FunctionBodyContent<'a> {
+    Directive(Directive<'a>),
+    Statement(Box<'a, Statement<'a>>),
 }
 
 // Function body is the same with BlockStatement except it could contain Directive in the beginning.
 #[derive(Serialize)]
-pub struct FunctionBody {
+pub struct FunctionBody<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub body: Vec<FunctionBodyContent>,
-}
-
-impl FunctionBody {
-    pub fn new(body: Vec<FunctionBodyContent>, (start_loc, end_loc, source): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "innerComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub inner_comments: Comments<'a>,
+    pub body: Vec<'a, FunctionBodyContent<'a>>,
+}
+
+impl<'a> FunctionBody<'a> {
+    pub fn new(
+        body: Vec<'a, FunctionBodyContent<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::BlockStatement,
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
+            inner_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct FunctionDeclaration {
+pub struct FunctionDeclaration<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub id: Identifier,
-    pub params: Vec<Pattern>,
-    pub body: FunctionBody,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub id: Identifier<'a>,
+    pub expression: bool,
     // starts from es6
     pub generator: bool,
     // starts from es8, async is a reserved word, so prefix it with is_
+    #[serde(rename(serialize = "async"))]
     pub is_async: bool,
+    pub params: Vec<'a, Pattern<'a>>,
+    pub body: FunctionBody<'a>,
 }
 
-impl FunctionDeclaration {
+impl<'a> FunctionDeclaration<'a> {
     pub fn new(
-        id: Identifier,
-        params: Vec<Pattern>,
-        body: FunctionBody,
+        id: Identifier<'a>,
+        params: Vec<'a, Pattern<'a>>,
+        body: FunctionBody<'a>,
         is_generator: bool,
         is_async: bool,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::FunctionDeclaration,
+            expression: false,
             id,
             params,
             body,
@@ -741,48 +1107,56 @@ impl FunctionDeclaration {
             is_async,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct VariableDeclarator {
+pub struct VariableDeclarator<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub id: Pattern,
-    pub init: Option<Expression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    pub id: Pattern<'a>,
+    pub init: Option<Expression<'a>>,
 }
 
-impl VariableDeclarator {
+impl<'a> VariableDeclarator<'a> {
     pub fn new(
-        id: Pattern,
-        init: Option<Expression>,
-        (start_loc, end_loc, source): AstNodePos,
+        id: Pattern<'a>,
+        init: Option<Expression<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
-            _type: NodeType::VaraiableDeclarator,
+            _type: NodeType::VariableDeclarator,
             id,
             init,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
         }
     }
 }
 
 #[derive(Clone, Serialize)]
+#[serde(rename_all(serialize = "lowercase"))]
 pub enum VariableKind {
     Var,
     Let,
@@ -790,21 +1164,34 @@ pub enum VariableKind {
 }
 
 #[derive(Serialize)]
-pub struct VariableDeclaration {
+pub struct VariableDeclaration<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub declarations: Vec<VariableDeclarator>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub declarations: Vec<'a, VariableDeclarator<'a>>,
     pub kind: VariableKind,
 }
 
-impl VariableDeclaration {
+impl<'a> VariableDeclaration<'a> {
     pub fn new(
-        declarators: Vec<VariableDeclarator>,
+        declarators: Vec<'a, VariableDeclarator<'a>>,
         kind: VariableKind,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::VariableDeclaration,
@@ -812,54 +1199,71 @@ impl VariableDeclaration {
             kind,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum ForStatementInit {
-    VariableDeclaration(VariableDeclaration),
-    Expression(Expression),
+#[serde(untagged)]
+pub enum ForStatementInit<'a> {
+    VariableDeclaration(VariableDeclaration<'a>),
+    Expression(Expression<'a>),
     Null,
 }
 
-impl From<VariableDeclaration> for ForStatementInit {
-    fn from(value: VariableDeclaration) -> Self {
+impl<'a> From<VariableDeclaration<'a>> for ForStatementInit<'a> {
+    fn from(value: VariableDeclaration<'a>) -> Self {
         Self::VariableDeclaration(value)
     }
 }
 
-impl From<Expression> for ForStatementInit {
-    fn from(value: Expression) -> Self {
+impl<'a> From<Expression<'a>> for ForStatementInit<'a> {
+    fn from(value: Expression<'a>) -> Self {
         Self::Expression(value)
     }
 }
 
 #[derive(Serialize)]
-pub struct ForStatement {
+pub struct ForStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub init: ForStatementInit,
-    pub test: Option<Expression>,
-    pub update: Option<Expression>,
-    pub body: Box<Statement>,
-}
-
-impl ForStatement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub init: ForStatementInit<'a>,
+    pub test: Option<Expression<'a>>,
+    pub update: Option<Expression<'a>>,
+    pub body: Box<'a, Statement<'a>>,
+}
+
+impl<'a> ForStatement<'a> {
     pub fn new(
-        init: ForStatementInit,
-        test: Option<Expression>,
-        update: Option<Expression>,
-        body: Box<Statement>,
-        (start_loc, end_loc, source): AstNodePos,
+        init: ForStatementInit<'a>,
+        test: Option<Expression<'a>>,
+        update: Option<Expression<'a>>,
+        body: Box<'a, Statement<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ForStatement,
@@ -869,51 +1273,68 @@ impl ForStatement {
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum ForInOfStatementLeft {
-    VariableDeclaration(VariableDeclaration),
-    Pattern(Pattern),
+#[serde(untagged)]
+pub enum ForInOfStatementLeft<'a> {
+    VariableDeclaration(VariableDeclaration<'a>),
+    Pattern(Pattern<'a>),
 }
 
-impl From<VariableDeclaration> for ForInOfStatementLeft {
-    fn from(value: VariableDeclaration) -> Self {
+impl<'a> From<VariableDeclaration<'a>> for ForInOfStatementLeft<'a> {
+    fn from(value: VariableDeclaration<'a>) -> Self {
         Self::VariableDeclaration(value)
     }
 }
 
-impl From<Pattern> for ForInOfStatementLeft {
-    fn from(value: Pattern) -> Self {
+impl<'a> From<Pattern<'a>> for ForInOfStatementLeft<'a> {
+    fn from(value: Pattern<'a>) -> Self {
         Self::Pattern(value)
     }
 }
 
 #[derive(Serialize)]
-pub struct ForInStatement {
+pub struct ForInStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub left: ForInOfStatementLeft,
-    pub right: Expression,
-    pub body: Box<Statement>,
-}
-
-impl ForInStatement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub left: ForInOfStatementLeft<'a>,
+    pub right: Expression<'a>,
+    pub body: Box<'a, Statement<'a>>,
+}
+
+impl<'a> ForInStatement<'a> {
     pub fn new(
-        left: ForInOfStatementLeft,
-        right: Expression,
-        body: Box<Statement>,
-        (start_loc, end_loc, source): AstNodePos,
+        left: ForInOfStatementLeft<'a>,
+        right: Expression<'a>,
+        body: Box<'a, Statement<'a>>,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ForInStatement,
@@ -922,37 +1343,54 @@ impl ForInStatement {
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 // following definition all have to greater than es5
 #[derive(Serialize)]
-pub struct ForOfStatement {
+pub struct ForOfStatement<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub left: ForInOfStatementLeft,
-    pub right: Expression,
-    pub body: Box<Statement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
     // from es9
+    #[serde(rename(serialize = "await"))]
     pub is_await: bool,
+    pub left: ForInOfStatementLeft<'a>,
+    pub right: Expression<'a>,
+    pub body: Box<'a, Statement<'a>>,
 }
 
-impl ForOfStatement {
+impl<'a> ForOfStatement<'a> {
     pub fn new(
-        left: ForInOfStatementLeft,
-        right: Expression,
-        body: Box<Statement>,
+        left: ForInOfStatementLeft<'a>,
+        right: Expression<'a>,
+        body: Box<'a, Statement<'a>>,
         is_await: bool,
-        (start_loc, end_loc, source): AstNodePos,
+        (start_loc, end_loc, source): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ForOfStatement,
@@ -962,30 +1400,36 @@ impl ForOfStatement {
             is_await,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum ClassMethodKey {
-    Expression(Expression),
-    PrivateIdentifier(PrivateIdentifier),
+#[serde(untagged)]
+pub enum ClassMethodKey<'a> {
+    Expression(Expression<'a>),
+    PrivateIdentifier(PrivateIdentifier<'a>),
 }
 
 // from es13, class body supports property definition and staticblock
 #[derive(Serialize)]
-pub enum ClassBodyEl {
-    MethodDefinition(MethodDefinition),
-    PropertyDefinition(PropertyDefinition),
-    StaticBlock(StaticBlock),
+#[serde(untagged)]
+pub enum ClassBodyEl<'a> {
+    MethodDefinition(MethodDefinition<'a>),
+    PropertyDefinition(PropertyDefinition<'a>),
+    StaticBlock(StaticBlock<'a>),
 }
 
 #[derive(PartialEq, Eq, Serialize)]
+#[serde(rename_all(serialize = "lowercase"))]
 pub enum MethodKind {
     Constructor,
     Method,
@@ -994,28 +1438,42 @@ pub enum MethodKind {
 }
 
 #[derive(Serialize)]
-pub struct MethodDefinition {
+pub struct MethodDefinition<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub key: ClassMethodKey,
-    pub value: FunctionExpression,
-    pub kind: MethodKind,
-    pub computed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
     // static is reserved word, use is_static here
+    #[serde(rename(serialize = "static"))]
     pub is_static: bool,
+    pub computed: bool,
+    pub key: ClassMethodKey<'a>,
+    pub kind: MethodKind,
+    pub value: FunctionExpression<'a>,
 }
 
-impl MethodDefinition {
+impl<'a> MethodDefinition<'a> {
     pub fn new(
-        key: ClassMethodKey,
-        value: FunctionExpression,
+        key: ClassMethodKey<'a>,
+        value: FunctionExpression<'a>,
         kind: MethodKind,
         computed: bool,
         is_static: bool,
-        (start_loc, end_loc, source_file): AstNodePos,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::MethodDefinition,
@@ -1026,104 +1484,164 @@ impl MethodDefinition {
             is_static,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub enum ClassPropertyKey {
-    Expression(Expression),
-    PrivateIdentifier(PrivateIdentifier),
+#[serde(untagged)]
+pub enum ClassPropertyKey<'a> {
+    Expression(Expression<'a>),
+    PrivateIdentifier(PrivateIdentifier<'a>),
 }
 
 // if key is PrivateIdentifier, computed must be false.
 #[derive(Serialize)]
-pub struct PropertyDefinition {
+pub struct PropertyDefinition<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub key: ClassPropertyKey,
-    pub value: Option<Expression>,
-    pub computed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    #[serde(rename(serialize = "static"))]
     pub is_static: bool,
+    pub computed: bool,
+    pub key: ClassPropertyKey<'a>,
+    pub value: Option<Expression<'a>>,
 }
 
-impl PropertyDefinition {
+impl<'a> PropertyDefinition<'a> {
     pub fn new(
-        key: ClassPropertyKey,
-        value: Option<Expression>,
+        key: ClassPropertyKey<'a>,
+        value: Option<Expression<'a>>,
         computed: bool,
         is_static: bool,
-        (start_loc, end_loc, source_file): AstNodePos,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
-            _type: NodeType::Property,
+            _type: NodeType::PropertyDefinition,
             key,
             value,
             computed,
             is_static,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct ClassBody {
+pub struct ClassBody<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub body: Vec<ClassBodyEl>,
-}
-
-impl ClassBody {
-    pub fn new(body: Vec<ClassBodyEl>, (start_loc, end_loc, source_file): AstNodePos) -> Self {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "innerComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub inner_comments: Comments<'a>,
+    pub body: Vec<'a, ClassBodyEl<'a>>,
+}
+
+impl<'a> ClassBody<'a> {
+    pub fn new(
+        body: Vec<'a, ClassBodyEl<'a>>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
+    ) -> Self {
         Self {
             _type: NodeType::ClassBody,
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
+            inner_comments: vec![],
         }
     }
 }
 
 #[derive(Serialize)]
-pub struct ClassDeclaration {
+pub struct ClassDeclaration<'a> {
     #[serde(rename(serialize = "type"))]
     pub _type: NodeType,
     pub start: usize,
     pub end: usize,
-    pub loc: SourceLocation,
-    pub id: Identifier,
-    pub super_class: Option<Expression>,
-    pub body: ClassBody,
-}
-
-impl ClassDeclaration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[usize; 2]>,
+    #[serde(
+        rename(serialize = "leadingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub leading_comments: Comments<'a>,
+    #[serde(
+        rename(serialize = "trailingComments"),
+        skip_serializing_if = "Comments::is_empty"
+    )]
+    pub trailing_comments: Comments<'a>,
+    pub id: Identifier<'a>,
+    #[serde(rename(serialize = "superClass"))]
+    pub super_class: Option<Expression<'a>>,
+    pub body: ClassBody<'a>,
+}
+
+impl<'a> ClassDeclaration<'a> {
     pub fn new(
-        id: Identifier,
-        super_class: Option<Expression>,
-        body: ClassBody,
-        (start_loc, end_loc, source_file): AstNodePos,
+        id: Identifier<'a>,
+        super_class: Option<Expression<'a>>,
+        body: ClassBody<'a>,
+        (start_loc, end_loc, source_file): AstNodePos<'a>,
     ) -> Self {
         Self {
             _type: NodeType::ClassDeclaration,
@@ -1132,11 +1650,14 @@ impl ClassDeclaration {
             body,
             start: start_loc.pos,
             end: end_loc.pos,
-            loc: SourceLocation {
+            loc: Some(SourceLocation {
                 source: source_file,
                 start: start_loc.loc,
                 end: end_loc.loc,
-            },
+            }),
+            range: None,
+            leading_comments: vec![],
+            trailing_comments: vec![],
         }
     }
 }
@@ -1146,19 +1667,19 @@ impl ClassDeclaration {
 //     pub _type: NodeType,
 //     pub start: usize,
 //     pub end: usize,
-//     pub loc: SourceLocation,
+//     pub loc: SourceLocation<'a>,
 //     pub id: Option<Identifier>,
 //     pub super_class: Option<Expression>,
 //     pub body: ClassBody,
 // }
 
-pub enum FunctionDeclarationType {
-    FunctionDeclaration(FunctionDeclaration),
-    AnonymousDefaultExportedFunctionDeclaration(AnonymousDefaultExportedFunctionDeclaration),
+pub enum FunctionDeclarationType<'a> {
+    FunctionDeclaration(FunctionDeclaration<'a>),
+    AnonymousDefaultExportedFunctionDeclaration(AnonymousDefaultExportedFunctionDeclaration<'a>),
 }
 
-impl From<FunctionDeclarationType> for Statement {
-    fn from(value: FunctionDeclarationType) -> Self {
+impl<'a> From<FunctionDeclarationType<'a>> for Statement<'a> {
+    fn from(value: FunctionDeclarationType<'a>) -> Self {
         match value {
             FunctionDeclarationType::FunctionDeclaration(v) => v.into(),
             FunctionDeclarationType::AnonymousDefaultExportedFunctionDeclaration(v) => v.into(),
@@ -1166,25 +1687,25 @@ impl From<FunctionDeclarationType> for Statement {
     }
 }
 
-impl From<FunctionDeclaration> for FunctionDeclarationType {
-    fn from(value: FunctionDeclaration) -> Self {
+impl<'a> From<FunctionDeclaration<'a>> for FunctionDeclarationType<'a> {
+    fn from(value: FunctionDeclaration<'a>) -> Self {
         Self::FunctionDeclaration(value)
     }
 }
 
-pub enum ClassDeclarationType {
-    AnonymousDefaultExportedClassDeclaration(AnonymousDefaultExportedClassDeclaration),
-    ClassDeclaration(ClassDeclaration),
+pub enum ClassDeclarationType<'a> {
+    AnonymousDefaultExportedClassDeclaration(AnonymousDefaultExportedClassDeclaration<'a>),
+    ClassDeclaration(ClassDeclaration<'a>),
 }
 
-impl From<ClassDeclaration> for ClassDeclarationType {
-    fn from(value: ClassDeclaration) -> Self {
+impl<'a> From<ClassDeclaration<'a>> for ClassDeclarationType<'a> {
+    fn from(value: ClassDeclaration<'a>) -> Self {
         Self::ClassDeclaration(value)
     }
 }
 
-impl From<ClassDeclarationType> for Statement {
-    fn from(value: ClassDeclarationType) -> Self {
+impl<'a> From<ClassDeclarationType<'a>> for Statement<'a> {
+    fn from(value: ClassDeclarationType<'a>) -> Self {
         match value {
             ClassDeclarationType::AnonymousDefaultExportedClassDeclaration(v) => {
                 Statement::AnonymousDefaultExportedClassDeclaration(v)
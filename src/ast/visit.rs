@@ -0,0 +1,1159 @@
+use super::{
+    directive::Directive,
+    expression::{
+        ArrayExprEle, ArrayExpression, ArrowFunctionBody, ArrowFunctionExpression,
+        AssignmentExpression, AssignmentExpressionLeft, AwaitExpression, BinaryExpression,
+        BinaryOpeartorLeft, CallExprArgs, CallExprCallee, CallExpression, ChainExpression,
+        ChainExpressionElement, ClassExpression, ConditionalExpression, Expression,
+        FunctionExpression, Identifier, ImportExpression, Literal, LogicalExpression,
+        MemberExprObject, MemberExprProperty, MemberExpression, MetaProperty, NewExprArgs,
+        NewExpression, ObjectExpression, ObjectProperty, ParenthesizedExpression,
+        PrivateIdentifier, Property, SequenceExpression, SpreadElement, StaticBlock, Super,
+        TaggedTemplateExpression, TemplateElement, TemplateLiteral, ThisExpression,
+        UnaryExpression, UpdateExpression, YieldExpression,
+    },
+    import_export_declaration::{
+        AnonymousDefaultExportedClassDeclaration, AnonymousDefaultExportedFunctionDeclaration,
+        ExportAllDeclaration, ExportAllExportedType, ExportDeclaration, ExportDefaultDeclaration,
+        ExportDefaultDeclarationType, ExportLocal, ExportNamedDeclaration, ExportSpecifier,
+        ExportedType, ImportDeclaration, ImportDefaultSpecifier, ImportNamespaceSpecifier,
+        ImportOrExportDeclaration, ImportSpecifier, ImportSpecifiers, ImportedType,
+    },
+    pattern::{
+        ArrayPattern, AssignmentPattern, AssignmentProperty, ObjectPattern, ObjectPatternProperty,
+        Pattern, RestElement,
+    },
+    statement::{
+        BlockStatement, BreakStatement, CatchClause, ClassBody, ClassBodyEl, ClassDeclaration,
+        ClassMethodKey, ClassPropertyKey, ContinueStatement, DebuggerStatement, DoWhileStatement,
+        EmptyStatement, ExpressionStatement, ForInOfStatementLeft, ForInStatement, ForOfStatement,
+        ForStatement, ForStatementInit, FunctionBody, FunctionBodyContent, FunctionDeclaration,
+        IfStatement, InvalidStatement, LabeledStatement, MethodDefinition, PropertyDefinition,
+        ReturnStatement, Statement, SwitchCase, SwitchStatement, ThrowStatement, TryStatement,
+        VariableDeclaration, VariableDeclarator, WhileStatement, WithStatement,
+    },
+    Program, ProgramNode,
+};
+
+/// Walks a `Program` immutably.
+///
+/// Every method visits one kind of node, and by default calls the `walk_*`
+/// function of the same name to visit its children in source order. Override
+/// a method to act on the node, and call the `walk_*` function from it to keep
+/// walking into the children.
+#[allow(unused_variables)]
+pub trait Visit<'a> {
+    fn visit_program(&mut self, node: &Program<'a>) {
+        walk_program(self, node);
+    }
+
+    fn visit_directive(&mut self, node: &Directive<'a>) {
+        walk_directive(self, node);
+    }
+
+    // statements
+    fn visit_statement(&mut self, node: &Statement<'a>) {
+        walk_statement(self, node);
+    }
+
+    fn visit_expression_statement(&mut self, node: &ExpressionStatement<'a>) {
+        walk_expression_statement(self, node);
+    }
+
+    fn visit_block_statement(&mut self, node: &BlockStatement<'a>) {
+        walk_block_statement(self, node);
+    }
+
+    fn visit_empty_statement(&mut self, node: &EmptyStatement<'a>) {}
+
+    fn visit_debugger_statement(&mut self, node: &DebuggerStatement<'a>) {}
+
+    fn visit_with_statement(&mut self, node: &WithStatement<'a>) {
+        walk_with_statement(self, node);
+    }
+
+    fn visit_return_statement(&mut self, node: &ReturnStatement<'a>) {
+        walk_return_statement(self, node);
+    }
+
+    fn visit_labeled_statement(&mut self, node: &LabeledStatement<'a>) {
+        walk_labeled_statement(self, node);
+    }
+
+    fn visit_break_statement(&mut self, node: &BreakStatement<'a>) {
+        walk_break_statement(self, node);
+    }
+
+    fn visit_continue_statement(&mut self, node: &ContinueStatement<'a>) {
+        walk_continue_statement(self, node);
+    }
+
+    fn visit_if_statement(&mut self, node: &IfStatement<'a>) {
+        walk_if_statement(self, node);
+    }
+
+    fn visit_switch_statement(&mut self, node: &SwitchStatement<'a>) {
+        walk_switch_statement(self, node);
+    }
+
+    fn visit_switch_case(&mut self, node: &SwitchCase<'a>) {
+        walk_switch_case(self, node);
+    }
+
+    fn visit_throw_statement(&mut self, node: &ThrowStatement<'a>) {
+        walk_throw_statement(self, node);
+    }
+
+    fn visit_try_statement(&mut self, node: &TryStatement<'a>) {
+        walk_try_statement(self, node);
+    }
+
+    fn visit_catch_clause(&mut self, node: &CatchClause<'a>) {
+        walk_catch_clause(self, node);
+    }
+
+    fn visit_while_statement(&mut self, node: &WhileStatement<'a>) {
+        walk_while_statement(self, node);
+    }
+
+    fn visit_do_while_statement(&mut self, node: &DoWhileStatement<'a>) {
+        walk_do_while_statement(self, node);
+    }
+
+    fn visit_for_statement(&mut self, node: &ForStatement<'a>) {
+        walk_for_statement(self, node);
+    }
+
+    fn visit_for_in_statement(&mut self, node: &ForInStatement<'a>) {
+        walk_for_in_statement(self, node);
+    }
+
+    fn visit_for_of_statement(&mut self, node: &ForOfStatement<'a>) {
+        walk_for_of_statement(self, node);
+    }
+
+    fn visit_function_declaration(&mut self, node: &FunctionDeclaration<'a>) {
+        walk_function_declaration(self, node);
+    }
+
+    fn visit_function_body(&mut self, node: &FunctionBody<'a>) {
+        walk_function_body(self, node);
+    }
+
+    fn visit_variable_declaration(&mut self, node: &VariableDeclaration<'a>) {
+        walk_variable_declaration(self, node);
+    }
+
+    fn visit_variable_declarator(&mut self, node: &VariableDeclarator<'a>) {
+        walk_variable_declarator(self, node);
+    }
+
+    fn visit_class_declaration(&mut self, node: &ClassDeclaration<'a>) {
+        walk_class_declaration(self, node);
+    }
+
+    fn visit_class_body(&mut self, node: &ClassBody<'a>) {
+        walk_class_body(self, node);
+    }
+
+    fn visit_class_body_el(&mut self, node: &ClassBodyEl<'a>) {
+        walk_class_body_el(self, node);
+    }
+
+    fn visit_method_definition(&mut self, node: &MethodDefinition<'a>) {
+        walk_method_definition(self, node);
+    }
+
+    fn visit_property_definition(&mut self, node: &PropertyDefinition<'a>) {
+        walk_property_definition(self, node);
+    }
+
+    fn visit_invalid_statement(&mut self, node: &InvalidStatement<'a>) {}
+
+    // expressions
+    fn visit_expression(&mut self, node: &Expression<'a>) {
+        walk_expression(self, node);
+    }
+
+    fn visit_identifier(&mut self, node: &Identifier<'a>) {}
+
+    fn visit_private_identifier(&mut self, node: &PrivateIdentifier<'a>) {}
+
+    fn visit_literal(&mut self, node: &Literal<'a>) {}
+
+    fn visit_this_expression(&mut self, node: &ThisExpression<'a>) {}
+
+    fn visit_super(&mut self, node: &Super<'a>) {}
+
+    fn visit_array_expression(&mut self, node: &ArrayExpression<'a>) {
+        walk_array_expression(self, node);
+    }
+
+    fn visit_spread_element(&mut self, node: &SpreadElement<'a>) {
+        walk_spread_element(self, node);
+    }
+
+    fn visit_object_expression(&mut self, node: &ObjectExpression<'a>) {
+        walk_object_expression(self, node);
+    }
+
+    fn visit_property(&mut self, node: &Property<'a>) {
+        walk_property(self, node);
+    }
+
+    fn visit_function_expression(&mut self, node: &FunctionExpression<'a>) {
+        walk_function_expression(self, node);
+    }
+
+    fn visit_unary_expression(&mut self, node: &UnaryExpression<'a>) {
+        walk_unary_expression(self, node);
+    }
+
+    fn visit_update_expression(&mut self, node: &UpdateExpression<'a>) {
+        walk_update_expression(self, node);
+    }
+
+    fn visit_binary_expression(&mut self, node: &BinaryExpression<'a>) {
+        walk_binary_expression(self, node);
+    }
+
+    fn visit_assignment_expression(&mut self, node: &AssignmentExpression<'a>) {
+        walk_assignment_expression(self, node);
+    }
+
+    fn visit_logical_expression(&mut self, node: &LogicalExpression<'a>) {
+        walk_logical_expression(self, node);
+    }
+
+    fn visit_member_expression(&mut self, node: &MemberExpression<'a>) {
+        walk_member_expression(self, node);
+    }
+
+    fn visit_conditional_expression(&mut self, node: &ConditionalExpression<'a>) {
+        walk_conditional_expression(self, node);
+    }
+
+    fn visit_call_expression(&mut self, node: &CallExpression<'a>) {
+        walk_call_expression(self, node);
+    }
+
+    fn visit_new_expression(&mut self, node: &NewExpression<'a>) {
+        walk_new_expression(self, node);
+    }
+
+    fn visit_sequence_expression(&mut self, node: &SequenceExpression<'a>) {
+        walk_sequence_expression(self, node);
+    }
+
+    fn visit_parenthesized_expression(&mut self, node: &ParenthesizedExpression<'a>) {
+        walk_parenthesized_expression(self, node);
+    }
+
+    fn visit_arrow_function_expression(&mut self, node: &ArrowFunctionExpression<'a>) {
+        walk_arrow_function_expression(self, node);
+    }
+
+    fn visit_yield_expression(&mut self, node: &YieldExpression<'a>) {
+        walk_yield_expression(self, node);
+    }
+
+    fn visit_template_literal(&mut self, node: &TemplateLiteral<'a>) {
+        walk_template_literal(self, node);
+    }
+
+    fn visit_template_element(&mut self, node: &TemplateElement<'a>) {}
+
+    fn visit_tagged_template_expression(&mut self, node: &TaggedTemplateExpression<'a>) {
+        walk_tagged_template_expression(self, node);
+    }
+
+    fn visit_class_expression(&mut self, node: &ClassExpression<'a>) {
+        walk_class_expression(self, node);
+    }
+
+    fn visit_meta_property(&mut self, node: &MetaProperty<'a>) {
+        walk_meta_property(self, node);
+    }
+
+    fn visit_await_expression(&mut self, node: &AwaitExpression<'a>) {
+        walk_await_expression(self, node);
+    }
+
+    fn visit_chain_expression(&mut self, node: &ChainExpression<'a>) {
+        walk_chain_expression(self, node);
+    }
+
+    fn visit_import_expression(&mut self, node: &ImportExpression<'a>) {
+        walk_import_expression(self, node);
+    }
+
+    fn visit_static_block(&mut self, node: &StaticBlock<'a>) {
+        walk_static_block(self, node);
+    }
+
+    // patterns
+    fn visit_pattern(&mut self, node: &Pattern<'a>) {
+        walk_pattern(self, node);
+    }
+
+    fn visit_rest_element(&mut self, node: &RestElement<'a>) {
+        walk_rest_element(self, node);
+    }
+
+    fn visit_array_pattern(&mut self, node: &ArrayPattern<'a>) {
+        walk_array_pattern(self, node);
+    }
+
+    fn visit_object_pattern(&mut self, node: &ObjectPattern<'a>) {
+        walk_object_pattern(self, node);
+    }
+
+    fn visit_assignment_property(&mut self, node: &AssignmentProperty<'a>) {
+        walk_assignment_property(self, node);
+    }
+
+    fn visit_assignment_pattern(&mut self, node: &AssignmentPattern<'a>) {
+        walk_assignment_pattern(self, node);
+    }
+
+    // modules
+    fn visit_import_or_export_declaration(&mut self, node: &ImportOrExportDeclaration<'a>) {
+        walk_import_or_export_declaration(self, node);
+    }
+
+    fn visit_import_declaration(&mut self, node: &ImportDeclaration<'a>) {
+        walk_import_declaration(self, node);
+    }
+
+    fn visit_import_specifier(&mut self, node: &ImportSpecifier<'a>) {
+        walk_import_specifier(self, node);
+    }
+
+    fn visit_import_default_specifier(&mut self, node: &ImportDefaultSpecifier<'a>) {
+        walk_import_default_specifier(self, node);
+    }
+
+    fn visit_import_namespace_specifier(&mut self, node: &ImportNamespaceSpecifier<'a>) {
+        walk_import_namespace_specifier(self, node);
+    }
+
+    fn visit_export_named_declaration(&mut self, node: &ExportNamedDeclaration<'a>) {
+        walk_export_named_declaration(self, node);
+    }
+
+    fn visit_export_specifier(&mut self, node: &ExportSpecifier<'a>) {
+        walk_export_specifier(self, node);
+    }
+
+    fn visit_export_default_declaration(&mut self, node: &ExportDefaultDeclaration<'a>) {
+        walk_export_default_declaration(self, node);
+    }
+
+    fn visit_anonymous_default_exported_function_declaration(
+        &mut self,
+        node: &AnonymousDefaultExportedFunctionDeclaration<'a>,
+    ) {
+        walk_anonymous_default_exported_function_declaration(self, node);
+    }
+
+    fn visit_anonymous_default_exported_class_declaration(
+        &mut self,
+        node: &AnonymousDefaultExportedClassDeclaration<'a>,
+    ) {
+        walk_anonymous_default_exported_class_declaration(self, node);
+    }
+
+    fn visit_export_all_declaration(&mut self, node: &ExportAllDeclaration<'a>) {
+        walk_export_all_declaration(self, node);
+    }
+}
+
+pub fn walk_program<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &Program<'a>) {
+    for item in node.body.iter() {
+        match item {
+            ProgramNode::Directive(node) => visitor.visit_directive(node),
+            ProgramNode::Statement(node) => visitor.visit_statement(node),
+            ProgramNode::ImportOrExportDeclaration(node) => {
+                visitor.visit_import_or_export_declaration(node)
+            }
+        }
+    }
+}
+
+pub fn walk_directive<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &Directive<'a>) {
+    visitor.visit_literal(&node.expression);
+}
+
+pub fn walk_statement<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &Statement<'a>) {
+    match node {
+        Statement::ExpressionStatement(node) => visitor.visit_expression_statement(node),
+        Statement::BlockStatement(node) => visitor.visit_block_statement(node),
+        Statement::EmptyStatement(node) => visitor.visit_empty_statement(node),
+        Statement::DebuggerStatement(node) => visitor.visit_debugger_statement(node),
+        Statement::WithStatement(node) => visitor.visit_with_statement(node),
+        Statement::ReturnStatement(node) => visitor.visit_return_statement(node),
+        Statement::LabeledStatement(node) => visitor.visit_labeled_statement(node),
+        Statement::BreakStatement(node) => visitor.visit_break_statement(node),
+        Statement::ContinueStatement(node) => visitor.visit_continue_statement(node),
+        Statement::IfStatement(node) => visitor.visit_if_statement(node),
+        Statement::SwitchStatement(node) => visitor.visit_switch_statement(node),
+        Statement::ThrowStatement(node) => visitor.visit_throw_statement(node),
+        Statement::TryStatement(node) => visitor.visit_try_statement(node),
+        Statement::WhileStatement(node) => visitor.visit_while_statement(node),
+        Statement::DoWhileStatement(node) => visitor.visit_do_while_statement(node),
+        Statement::ForStatement(node) => visitor.visit_for_statement(node),
+        Statement::ForInStatement(node) => visitor.visit_for_in_statement(node),
+        Statement::ForOfStatement(node) => visitor.visit_for_of_statement(node),
+        Statement::FunctionDeclaration(node) => visitor.visit_function_declaration(node),
+        Statement::AnonymousDefaultExportedFunctionDeclaration(node) => {
+            visitor.visit_anonymous_default_exported_function_declaration(node)
+        }
+        Statement::AnonymousDefaultExportedClassDeclaration(node) => {
+            visitor.visit_anonymous_default_exported_class_declaration(node)
+        }
+        Statement::VariableDeclaration(node) => visitor.visit_variable_declaration(node),
+        Statement::ClassDeclaration(node) => visitor.visit_class_declaration(node),
+        Statement::InvalidStatement(node) => visitor.visit_invalid_statement(node),
+        Statement::ImportOrExportDeclaration(node) => {
+            visitor.visit_import_or_export_declaration(node)
+        }
+    }
+}
+
+pub fn walk_expression_statement<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ExpressionStatement<'a>,
+) {
+    visitor.visit_expression(&node.expression);
+}
+
+pub fn walk_block_statement<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &BlockStatement<'a>) {
+    for stmt in node.body.iter() {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_with_statement<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &WithStatement<'a>) {
+    visitor.visit_expression(&node.object);
+    visitor.visit_statement(&node.body);
+}
+
+pub fn walk_return_statement<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ReturnStatement<'a>,
+) {
+    if let Some(argument) = &node.argument {
+        visitor.visit_expression(argument);
+    }
+}
+
+pub fn walk_labeled_statement<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &LabeledStatement<'a>,
+) {
+    visitor.visit_identifier(&node.label);
+    visitor.visit_statement(&node.body);
+}
+
+pub fn walk_break_statement<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &BreakStatement<'a>) {
+    if let Some(label) = &node.label {
+        visitor.visit_identifier(label);
+    }
+}
+
+pub fn walk_continue_statement<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ContinueStatement<'a>,
+) {
+    if let Some(label) = &node.label {
+        visitor.visit_identifier(label);
+    }
+}
+
+pub fn walk_if_statement<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &IfStatement<'a>) {
+    visitor.visit_expression(&node.test);
+    visitor.visit_statement(&node.consequent);
+    if let Some(alternate) = &node.alternate {
+        visitor.visit_statement(alternate);
+    }
+}
+
+pub fn walk_switch_statement<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &SwitchStatement<'a>,
+) {
+    visitor.visit_expression(&node.discriminant);
+    for case in node.cases.iter() {
+        visitor.visit_switch_case(case);
+    }
+}
+
+pub fn walk_switch_case<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &SwitchCase<'a>) {
+    if let Some(test) = &node.test {
+        visitor.visit_expression(test);
+    }
+    for stmt in node.consequent.iter() {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_throw_statement<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &ThrowStatement<'a>) {
+    visitor.visit_expression(&node.argument);
+}
+
+pub fn walk_try_statement<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &TryStatement<'a>) {
+    visitor.visit_block_statement(&node.block);
+    if let Some(handler) = &node.handler {
+        visitor.visit_catch_clause(handler);
+    }
+    if let Some(finalizer) = &node.finalizer {
+        visitor.visit_block_statement(finalizer);
+    }
+}
+
+pub fn walk_catch_clause<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &CatchClause<'a>) {
+    if let Some(param) = &node.param {
+        visitor.visit_pattern(param);
+    }
+    visitor.visit_block_statement(&node.body);
+}
+
+pub fn walk_while_statement<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &WhileStatement<'a>) {
+    visitor.visit_expression(&node.test);
+    visitor.visit_statement(&node.body);
+}
+
+pub fn walk_do_while_statement<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &DoWhileStatement<'a>,
+) {
+    visitor.visit_statement(&node.body);
+    visitor.visit_expression(&node.test);
+}
+
+pub fn walk_for_statement<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &ForStatement<'a>) {
+    match &node.init {
+        ForStatementInit::VariableDeclaration(init) => visitor.visit_variable_declaration(init),
+        ForStatementInit::Expression(init) => visitor.visit_expression(init),
+        ForStatementInit::Null => {}
+    }
+    if let Some(test) = &node.test {
+        visitor.visit_expression(test);
+    }
+    if let Some(update) = &node.update {
+        visitor.visit_expression(update);
+    }
+    visitor.visit_statement(&node.body);
+}
+
+fn walk_for_in_of_left<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    left: &ForInOfStatementLeft<'a>,
+) {
+    match left {
+        ForInOfStatementLeft::VariableDeclaration(left) => visitor.visit_variable_declaration(left),
+        ForInOfStatementLeft::Pattern(left) => visitor.visit_pattern(left),
+    }
+}
+
+pub fn walk_for_in_statement<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ForInStatement<'a>,
+) {
+    walk_for_in_of_left(visitor, &node.left);
+    visitor.visit_expression(&node.right);
+    visitor.visit_statement(&node.body);
+}
+
+pub fn walk_for_of_statement<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ForOfStatement<'a>,
+) {
+    walk_for_in_of_left(visitor, &node.left);
+    visitor.visit_expression(&node.right);
+    visitor.visit_statement(&node.body);
+}
+
+pub fn walk_function_declaration<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &FunctionDeclaration<'a>,
+) {
+    visitor.visit_identifier(&node.id);
+    for param in node.params.iter() {
+        visitor.visit_pattern(param);
+    }
+    visitor.visit_function_body(&node.body);
+}
+
+pub fn walk_function_body<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &FunctionBody<'a>) {
+    for item in node.body.iter() {
+        match item {
+            FunctionBodyContent::Directive(node) => visitor.visit_directive(node),
+            FunctionBodyContent::Statement(node) => visitor.visit_statement(node),
+        }
+    }
+}
+
+pub fn walk_variable_declaration<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &VariableDeclaration<'a>,
+) {
+    for declarator in node.declarations.iter() {
+        visitor.visit_variable_declarator(declarator);
+    }
+}
+
+pub fn walk_variable_declarator<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &VariableDeclarator<'a>,
+) {
+    visitor.visit_pattern(&node.id);
+    if let Some(init) = &node.init {
+        visitor.visit_expression(init);
+    }
+}
+
+pub fn walk_class_declaration<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ClassDeclaration<'a>,
+) {
+    visitor.visit_identifier(&node.id);
+    if let Some(super_class) = &node.super_class {
+        visitor.visit_expression(super_class);
+    }
+    visitor.visit_class_body(&node.body);
+}
+
+pub fn walk_class_body<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &ClassBody<'a>) {
+    for el in node.body.iter() {
+        visitor.visit_class_body_el(el);
+    }
+}
+
+pub fn walk_class_body_el<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &ClassBodyEl<'a>) {
+    match node {
+        ClassBodyEl::MethodDefinition(node) => visitor.visit_method_definition(node),
+        ClassBodyEl::PropertyDefinition(node) => visitor.visit_property_definition(node),
+        ClassBodyEl::StaticBlock(node) => visitor.visit_static_block(node),
+    }
+}
+
+pub fn walk_method_definition<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &MethodDefinition<'a>,
+) {
+    match &node.key {
+        ClassMethodKey::Expression(key) => visitor.visit_expression(key),
+        ClassMethodKey::PrivateIdentifier(key) => visitor.visit_private_identifier(key),
+    }
+    visitor.visit_function_expression(&node.value);
+}
+
+pub fn walk_property_definition<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &PropertyDefinition<'a>,
+) {
+    match &node.key {
+        ClassPropertyKey::Expression(key) => visitor.visit_expression(key),
+        ClassPropertyKey::PrivateIdentifier(key) => visitor.visit_private_identifier(key),
+    }
+    if let Some(value) = &node.value {
+        visitor.visit_expression(value);
+    }
+}
+
+pub fn walk_expression<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &Expression<'a>) {
+    match node {
+        Expression::Identifier(node) => visitor.visit_identifier(node),
+        Expression::Literal(node) => visitor.visit_literal(node),
+        Expression::ThisExpression(node) => visitor.visit_this_expression(node),
+        Expression::ArrayExpression(node) => visitor.visit_array_expression(node),
+        Expression::ObjectExpression(node) => visitor.visit_object_expression(node),
+        Expression::FunctionExpression(node) => visitor.visit_function_expression(node),
+        Expression::UnaryExpression(node) => visitor.visit_unary_expression(node),
+        Expression::UpdateExpression(node) => visitor.visit_update_expression(node),
+        Expression::BinaryExpression(node) => visitor.visit_binary_expression(node),
+        Expression::AssignmentExpression(node) => visitor.visit_assignment_expression(node),
+        Expression::LogicalExpression(node) => visitor.visit_logical_expression(node),
+        Expression::MemberExpression(node) => visitor.visit_member_expression(node),
+        Expression::ConditionalExpression(node) => visitor.visit_conditional_expression(node),
+        Expression::CallExpression(node) => visitor.visit_call_expression(node),
+        Expression::NewExpression(node) => visitor.visit_new_expression(node),
+        Expression::SequenceExpression(node) => visitor.visit_sequence_expression(node),
+        Expression::ParenthesizedExpression(node) => visitor.visit_parenthesized_expression(node),
+        Expression::ArrowFunctionExpression(node) => visitor.visit_arrow_function_expression(node),
+        Expression::YieldExpression(node) => visitor.visit_yield_expression(node),
+        Expression::TemplateLiteral(node) => visitor.visit_template_literal(node),
+        Expression::TaggedTemplateExpression(node) => {
+            visitor.visit_tagged_template_expression(node)
+        }
+        Expression::ClassExpression(node) => visitor.visit_class_expression(node),
+        Expression::MetaProperty(node) => visitor.visit_meta_property(node),
+        Expression::AwaitExpression(node) => visitor.visit_await_expression(node),
+        Expression::ChainExpression(node) => visitor.visit_chain_expression(node),
+        Expression::ImportExpression(node) => visitor.visit_import_expression(node),
+        Expression::StaticBlock(node) => visitor.visit_static_block(node),
+    }
+}
+
+pub fn walk_array_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ArrayExpression<'a>,
+) {
+    for element in node.elements.iter() {
+        match element {
+            ArrayExprEle::Expression(element) => visitor.visit_expression(element),
+            ArrayExprEle::SpreadElement(element) => visitor.visit_spread_element(element),
+            ArrayExprEle::Null => {}
+        }
+    }
+}
+
+pub fn walk_spread_element<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &SpreadElement<'a>) {
+    visitor.visit_expression(&node.argument);
+}
+
+pub fn walk_object_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ObjectExpression<'a>,
+) {
+    for property in node.properties.iter() {
+        match property {
+            ObjectProperty::Property(property) => visitor.visit_property(property),
+            ObjectProperty::SpreadElement(property) => visitor.visit_spread_element(property),
+        }
+    }
+}
+
+pub fn walk_property<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &Property<'a>) {
+    visitor.visit_expression(&node.key);
+    visitor.visit_expression(&node.value);
+}
+
+pub fn walk_function_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &FunctionExpression<'a>,
+) {
+    if let Some(id) = &node.id {
+        visitor.visit_identifier(id);
+    }
+    for param in node.params.iter() {
+        visitor.visit_pattern(param);
+    }
+    visitor.visit_function_body(&node.body);
+}
+
+pub fn walk_unary_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &UnaryExpression<'a>,
+) {
+    visitor.visit_expression(&node.argument);
+}
+
+pub fn walk_update_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &UpdateExpression<'a>,
+) {
+    visitor.visit_expression(&node.argument);
+}
+
+pub fn walk_binary_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &BinaryExpression<'a>,
+) {
+    match &node.left {
+        BinaryOpeartorLeft::Expression(left) => visitor.visit_expression(left),
+        BinaryOpeartorLeft::PrivateIdentifier(left) => visitor.visit_private_identifier(left),
+    }
+    visitor.visit_expression(&node.right);
+}
+
+pub fn walk_assignment_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &AssignmentExpression<'a>,
+) {
+    match &node.left {
+        AssignmentExpressionLeft::Pattern(left) => visitor.visit_pattern(left),
+        AssignmentExpressionLeft::Expression(left) => visitor.visit_expression(left),
+    }
+    visitor.visit_expression(&node.right);
+}
+
+pub fn walk_logical_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &LogicalExpression<'a>,
+) {
+    visitor.visit_expression(&node.left);
+    visitor.visit_expression(&node.right);
+}
+
+pub fn walk_member_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &MemberExpression<'a>,
+) {
+    match &node.object {
+        MemberExprObject::Expression(object) => visitor.visit_expression(object),
+        MemberExprObject::Super(object) => visitor.visit_super(object),
+    }
+    match &node.property {
+        MemberExprProperty::Expression(property) => visitor.visit_expression(property),
+        MemberExprProperty::PrivateIdentifier(property) => {
+            visitor.visit_private_identifier(property)
+        }
+    }
+}
+
+pub fn walk_conditional_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ConditionalExpression<'a>,
+) {
+    visitor.visit_expression(&node.test);
+    visitor.visit_expression(&node.consequent);
+    visitor.visit_expression(&node.alternate);
+}
+
+pub fn walk_call_expression<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &CallExpression<'a>) {
+    match &node.callee {
+        CallExprCallee::Expression(callee) => visitor.visit_expression(callee),
+        CallExprCallee::Super(callee) => visitor.visit_super(callee),
+    }
+    for argument in node.arguments.iter() {
+        match argument {
+            CallExprArgs::Expression(argument) => visitor.visit_expression(argument),
+            CallExprArgs::SpreadElement(argument) => visitor.visit_spread_element(argument),
+        }
+    }
+}
+
+pub fn walk_new_expression<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &NewExpression<'a>) {
+    visitor.visit_expression(&node.callee);
+    for argument in node.arguments.iter() {
+        match argument {
+            NewExprArgs::Expression(argument) => visitor.visit_expression(argument),
+            NewExprArgs::SpreadElement(argument) => visitor.visit_spread_element(argument),
+        }
+    }
+}
+
+pub fn walk_sequence_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &SequenceExpression<'a>,
+) {
+    for expression in node.expressions.iter() {
+        visitor.visit_expression(expression);
+    }
+}
+
+pub fn walk_parenthesized_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ParenthesizedExpression<'a>,
+) {
+    visitor.visit_expression(&node.expression);
+}
+
+pub fn walk_arrow_function_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ArrowFunctionExpression<'a>,
+) {
+    for param in node.params.iter() {
+        visitor.visit_pattern(param);
+    }
+    match &node.body {
+        ArrowFunctionBody::FunctionBoby(body) => visitor.visit_function_body(body),
+        ArrowFunctionBody::Expression(body) => visitor.visit_expression(body),
+    }
+}
+
+pub fn walk_yield_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &YieldExpression<'a>,
+) {
+    if let Some(argument) = &node.argument {
+        visitor.visit_expression(argument);
+    }
+}
+
+pub fn walk_template_literal<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &TemplateLiteral<'a>,
+) {
+    // quasis 比 expressions 多一个，按照源码中的顺序交替访问
+    for (idx, quasi) in node.quasis.iter().enumerate() {
+        visitor.visit_template_element(quasi);
+        if let Some(expression) = node.expressions.get(idx) {
+            visitor.visit_expression(expression);
+        }
+    }
+}
+
+pub fn walk_tagged_template_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &TaggedTemplateExpression<'a>,
+) {
+    visitor.visit_expression(&node.tag);
+    visitor.visit_template_literal(&node.quasi);
+}
+
+pub fn walk_class_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ClassExpression<'a>,
+) {
+    if let Some(id) = &node.id {
+        visitor.visit_identifier(id);
+    }
+    if let Some(super_class) = &node.super_class {
+        visitor.visit_expression(super_class);
+    }
+    visitor.visit_class_body(&node.body);
+}
+
+pub fn walk_meta_property<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &MetaProperty<'a>) {
+    visitor.visit_identifier(&node.meta);
+    visitor.visit_identifier(&node.property);
+}
+
+pub fn walk_await_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &AwaitExpression<'a>,
+) {
+    visitor.visit_expression(&node.argument);
+}
+
+pub fn walk_chain_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ChainExpression<'a>,
+) {
+    match &node.expression {
+        ChainExpressionElement::CallExpression(expression) => {
+            visitor.visit_call_expression(expression)
+        }
+        ChainExpressionElement::MemberExpression(expression) => {
+            visitor.visit_member_expression(expression)
+        }
+    }
+}
+
+pub fn walk_import_expression<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ImportExpression<'a>,
+) {
+    visitor.visit_expression(&node.source);
+}
+
+pub fn walk_static_block<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &StaticBlock<'a>) {
+    for stmt in node.body.iter() {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_pattern<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &Pattern<'a>) {
+    match node {
+        Pattern::RestElement(node) => visitor.visit_rest_element(node),
+        Pattern::ArrayPattern(node) => visitor.visit_array_pattern(node),
+        Pattern::ObjectPattern(node) => visitor.visit_object_pattern(node),
+        Pattern::Identifier(node) => visitor.visit_identifier(node),
+        Pattern::AssignmentPattern(node) => visitor.visit_assignment_pattern(node),
+        Pattern::MemberExpression(node) => visitor.visit_member_expression(node),
+    }
+}
+
+pub fn walk_rest_element<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &RestElement<'a>) {
+    visitor.visit_pattern(&node.argument);
+}
+
+pub fn walk_array_pattern<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &ArrayPattern<'a>) {
+    for element in node.elements.iter().flatten() {
+        visitor.visit_pattern(element);
+    }
+}
+
+pub fn walk_object_pattern<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, node: &ObjectPattern<'a>) {
+    for property in node.properties.iter() {
+        match property {
+            ObjectPatternProperty::AssignmentProperty(property) => {
+                visitor.visit_assignment_property(property)
+            }
+            ObjectPatternProperty::RestElement(property) => visitor.visit_rest_element(property),
+        }
+    }
+}
+
+pub fn walk_assignment_property<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &AssignmentProperty<'a>,
+) {
+    visitor.visit_expression(&node.key);
+    visitor.visit_pattern(&node.value);
+}
+
+pub fn walk_assignment_pattern<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &AssignmentPattern<'a>,
+) {
+    visitor.visit_pattern(&node.left);
+    visitor.visit_expression(&node.right);
+}
+
+pub fn walk_import_or_export_declaration<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ImportOrExportDeclaration<'a>,
+) {
+    match node {
+        ImportOrExportDeclaration::ImportDeclaration(node) => {
+            visitor.visit_import_declaration(node)
+        }
+        ImportOrExportDeclaration::ExportNamedDeclaration(node) => {
+            visitor.visit_export_named_declaration(node)
+        }
+        ImportOrExportDeclaration::ExportDefaultDeclaration(node) => {
+            visitor.visit_export_default_declaration(node)
+        }
+        ImportOrExportDeclaration::ExportAllDeclaration(node) => {
+            visitor.visit_export_all_declaration(node)
+        }
+    }
+}
+
+pub fn walk_import_declaration<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ImportDeclaration<'a>,
+) {
+    for specifier in node.specifiers.iter() {
+        match specifier {
+            ImportSpecifiers::ImportSpecifier(specifier) => {
+                visitor.visit_import_specifier(specifier)
+            }
+            ImportSpecifiers::ImportDefaultSpecifier(specifier) => {
+                visitor.visit_import_default_specifier(specifier)
+            }
+            ImportSpecifiers::ImportNamespaceSpecifier(specifier) => {
+                visitor.visit_import_namespace_specifier(specifier)
+            }
+        }
+    }
+    visitor.visit_literal(&node.source);
+}
+
+pub fn walk_import_specifier<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ImportSpecifier<'a>,
+) {
+    match &node.imported {
+        ImportedType::Identifier(imported) => visitor.visit_identifier(imported),
+        ImportedType::Literal(imported) => visitor.visit_literal(imported),
+    }
+    visitor.visit_identifier(&node.local);
+}
+
+pub fn walk_import_default_specifier<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ImportDefaultSpecifier<'a>,
+) {
+    visitor.visit_identifier(&node.local);
+}
+
+pub fn walk_import_namespace_specifier<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ImportNamespaceSpecifier<'a>,
+) {
+    visitor.visit_identifier(&node.local);
+}
+
+pub fn walk_export_named_declaration<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ExportNamedDeclaration<'a>,
+) {
+    match &node.declaration {
+        Some(ExportDeclaration::FunctionDeclaration(declaration)) => {
+            visitor.visit_function_declaration(declaration)
+        }
+        Some(ExportDeclaration::VariableDeclaration(declaration)) => {
+            visitor.visit_variable_declaration(declaration)
+        }
+        Some(ExportDeclaration::ClassDeclaration(declaration)) => {
+            visitor.visit_class_declaration(declaration)
+        }
+        None => {}
+    }
+    for specifier in node.specifiers.iter() {
+        visitor.visit_export_specifier(specifier);
+    }
+    if let Some(source) = &node.source {
+        visitor.visit_literal(source);
+    }
+}
+
+pub fn walk_export_specifier<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ExportSpecifier<'a>,
+) {
+    match &node.local {
+        ExportLocal::Identifier(local) => visitor.visit_identifier(local),
+        ExportLocal::Literal(local) => visitor.visit_literal(local),
+    }
+    match &node.exported {
+        ExportedType::Identifier(exported) => visitor.visit_identifier(exported),
+        ExportedType::Literal(exported) => visitor.visit_literal(exported),
+    }
+}
+
+pub fn walk_export_default_declaration<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ExportDefaultDeclaration<'a>,
+) {
+    match &node.declaration {
+        ExportDefaultDeclarationType::AnonymousDefaultExportedFunctionDeclaration(declaration) => {
+            visitor.visit_anonymous_default_exported_function_declaration(declaration)
+        }
+        ExportDefaultDeclarationType::FunctionDeclaration(declaration) => {
+            visitor.visit_function_declaration(declaration)
+        }
+        ExportDefaultDeclarationType::AnonymousDefaultExportedClassDeclaration(declaration) => {
+            visitor.visit_anonymous_default_exported_class_declaration(declaration)
+        }
+        ExportDefaultDeclarationType::ClassDeclaration(declaration) => {
+            visitor.visit_class_declaration(declaration)
+        }
+        ExportDefaultDeclarationType::Expression(declaration) => {
+            visitor.visit_expression(declaration)
+        }
+    }
+}
+
+pub fn walk_anonymous_default_exported_function_declaration<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &AnonymousDefaultExportedFunctionDeclaration<'a>,
+) {
+    if let Some(id) = &node.id {
+        visitor.visit_identifier(id);
+    }
+    for param in node.params.iter() {
+        visitor.visit_pattern(param);
+    }
+    visitor.visit_function_body(&node.body);
+}
+
+pub fn walk_anonymous_default_exported_class_declaration<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &AnonymousDefaultExportedClassDeclaration<'a>,
+) {
+    if let Some(id) = &node.id {
+        visitor.visit_identifier(id);
+    }
+    if let Some(super_class) = &node.super_class {
+        visitor.visit_expression(super_class);
+    }
+    visitor.visit_class_body(&node.body);
+}
+
+pub fn walk_export_all_declaration<'a, V: Visit<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &ExportAllDeclaration<'a>,
+) {
+    match &node.exported {
+        ExportAllExportedType::Identifier(exported) => visitor.visit_identifier(exported),
+        ExportAllExportedType::Literal(exported) => visitor.visit_literal(exported),
+        ExportAllExportedType::Null => {}
+    }
+    visitor.visit_literal(&node.source);
+}
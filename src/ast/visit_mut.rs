@@ -0,0 +1,1215 @@
+use super::{
+    directive::Directive,
+    expression::{
+        ArrayExprEle, ArrayExpression, ArrowFunctionBody, ArrowFunctionExpression,
+        AssignmentExpression, AssignmentExpressionLeft, AwaitExpression, BinaryExpression,
+        BinaryOpeartorLeft, CallExprArgs, CallExprCallee, CallExpression, ChainExpression,
+        ChainExpressionElement, ClassExpression, ConditionalExpression, Expression,
+        FunctionExpression, Identifier, ImportExpression, Literal, LogicalExpression,
+        MemberExprObject, MemberExprProperty, MemberExpression, MetaProperty, NewExprArgs,
+        NewExpression, ObjectExpression, ObjectProperty, ParenthesizedExpression,
+        PrivateIdentifier, Property, SequenceExpression, SpreadElement, StaticBlock, Super,
+        TaggedTemplateExpression, TemplateElement, TemplateLiteral, ThisExpression,
+        UnaryExpression, UpdateExpression, YieldExpression,
+    },
+    import_export_declaration::{
+        AnonymousDefaultExportedClassDeclaration, AnonymousDefaultExportedFunctionDeclaration,
+        ExportAllDeclaration, ExportAllExportedType, ExportDeclaration, ExportDefaultDeclaration,
+        ExportDefaultDeclarationType, ExportLocal, ExportNamedDeclaration, ExportSpecifier,
+        ExportedType, ImportDeclaration, ImportDefaultSpecifier, ImportNamespaceSpecifier,
+        ImportOrExportDeclaration, ImportSpecifier, ImportSpecifiers, ImportedType,
+    },
+    pattern::{
+        ArrayPattern, AssignmentPattern, AssignmentProperty, ObjectPattern, ObjectPatternProperty,
+        Pattern, RestElement,
+    },
+    statement::{
+        BlockStatement, BreakStatement, CatchClause, ClassBody, ClassBodyEl, ClassDeclaration,
+        ClassMethodKey, ClassPropertyKey, ContinueStatement, DebuggerStatement, DoWhileStatement,
+        EmptyStatement, ExpressionStatement, ForInOfStatementLeft, ForInStatement, ForOfStatement,
+        ForStatement, ForStatementInit, FunctionBody, FunctionBodyContent, FunctionDeclaration,
+        IfStatement, InvalidStatement, LabeledStatement, MethodDefinition, PropertyDefinition,
+        ReturnStatement, Statement, SwitchCase, SwitchStatement, ThrowStatement, TryStatement,
+        VariableDeclaration, VariableDeclarator, WhileStatement, WithStatement,
+    },
+    Program, ProgramNode,
+};
+
+/// Walks a `Program` mutably, the `&mut` counterpart of `Visit`.
+///
+/// A method can rewrite the node it is handed in place, including replacing
+/// a whole `Expression` or `Statement`, or inserting and removing items of a
+/// statement list such as `BlockStatement::body`.
+#[allow(unused_variables)]
+pub trait VisitMut<'a> {
+    fn visit_program(&mut self, node: &mut Program<'a>) {
+        walk_program(self, node);
+    }
+
+    fn visit_directive(&mut self, node: &mut Directive<'a>) {
+        walk_directive(self, node);
+    }
+
+    // statements
+    fn visit_statement(&mut self, node: &mut Statement<'a>) {
+        walk_statement(self, node);
+    }
+
+    fn visit_expression_statement(&mut self, node: &mut ExpressionStatement<'a>) {
+        walk_expression_statement(self, node);
+    }
+
+    fn visit_block_statement(&mut self, node: &mut BlockStatement<'a>) {
+        walk_block_statement(self, node);
+    }
+
+    fn visit_empty_statement(&mut self, node: &mut EmptyStatement<'a>) {}
+
+    fn visit_debugger_statement(&mut self, node: &mut DebuggerStatement<'a>) {}
+
+    fn visit_with_statement(&mut self, node: &mut WithStatement<'a>) {
+        walk_with_statement(self, node);
+    }
+
+    fn visit_return_statement(&mut self, node: &mut ReturnStatement<'a>) {
+        walk_return_statement(self, node);
+    }
+
+    fn visit_labeled_statement(&mut self, node: &mut LabeledStatement<'a>) {
+        walk_labeled_statement(self, node);
+    }
+
+    fn visit_break_statement(&mut self, node: &mut BreakStatement<'a>) {
+        walk_break_statement(self, node);
+    }
+
+    fn visit_continue_statement(&mut self, node: &mut ContinueStatement<'a>) {
+        walk_continue_statement(self, node);
+    }
+
+    fn visit_if_statement(&mut self, node: &mut IfStatement<'a>) {
+        walk_if_statement(self, node);
+    }
+
+    fn visit_switch_statement(&mut self, node: &mut SwitchStatement<'a>) {
+        walk_switch_statement(self, node);
+    }
+
+    fn visit_switch_case(&mut self, node: &mut SwitchCase<'a>) {
+        walk_switch_case(self, node);
+    }
+
+    fn visit_throw_statement(&mut self, node: &mut ThrowStatement<'a>) {
+        walk_throw_statement(self, node);
+    }
+
+    fn visit_try_statement(&mut self, node: &mut TryStatement<'a>) {
+        walk_try_statement(self, node);
+    }
+
+    fn visit_catch_clause(&mut self, node: &mut CatchClause<'a>) {
+        walk_catch_clause(self, node);
+    }
+
+    fn visit_while_statement(&mut self, node: &mut WhileStatement<'a>) {
+        walk_while_statement(self, node);
+    }
+
+    fn visit_do_while_statement(&mut self, node: &mut DoWhileStatement<'a>) {
+        walk_do_while_statement(self, node);
+    }
+
+    fn visit_for_statement(&mut self, node: &mut ForStatement<'a>) {
+        walk_for_statement(self, node);
+    }
+
+    fn visit_for_in_statement(&mut self, node: &mut ForInStatement<'a>) {
+        walk_for_in_statement(self, node);
+    }
+
+    fn visit_for_of_statement(&mut self, node: &mut ForOfStatement<'a>) {
+        walk_for_of_statement(self, node);
+    }
+
+    fn visit_function_declaration(&mut self, node: &mut FunctionDeclaration<'a>) {
+        walk_function_declaration(self, node);
+    }
+
+    fn visit_function_body(&mut self, node: &mut FunctionBody<'a>) {
+        walk_function_body(self, node);
+    }
+
+    fn visit_variable_declaration(&mut self, node: &mut VariableDeclaration<'a>) {
+        walk_variable_declaration(self, node);
+    }
+
+    fn visit_variable_declarator(&mut self, node: &mut VariableDeclarator<'a>) {
+        walk_variable_declarator(self, node);
+    }
+
+    fn visit_class_declaration(&mut self, node: &mut ClassDeclaration<'a>) {
+        walk_class_declaration(self, node);
+    }
+
+    fn visit_class_body(&mut self, node: &mut ClassBody<'a>) {
+        walk_class_body(self, node);
+    }
+
+    fn visit_class_body_el(&mut self, node: &mut ClassBodyEl<'a>) {
+        walk_class_body_el(self, node);
+    }
+
+    fn visit_method_definition(&mut self, node: &mut MethodDefinition<'a>) {
+        walk_method_definition(self, node);
+    }
+
+    fn visit_property_definition(&mut self, node: &mut PropertyDefinition<'a>) {
+        walk_property_definition(self, node);
+    }
+
+    fn visit_invalid_statement(&mut self, node: &mut InvalidStatement<'a>) {}
+
+    // expressions
+    fn visit_expression(&mut self, node: &mut Expression<'a>) {
+        walk_expression(self, node);
+    }
+
+    fn visit_identifier(&mut self, node: &mut Identifier<'a>) {}
+
+    fn visit_private_identifier(&mut self, node: &mut PrivateIdentifier<'a>) {}
+
+    fn visit_literal(&mut self, node: &mut Literal<'a>) {}
+
+    fn visit_this_expression(&mut self, node: &mut ThisExpression<'a>) {}
+
+    fn visit_super(&mut self, node: &mut Super<'a>) {}
+
+    fn visit_array_expression(&mut self, node: &mut ArrayExpression<'a>) {
+        walk_array_expression(self, node);
+    }
+
+    fn visit_spread_element(&mut self, node: &mut SpreadElement<'a>) {
+        walk_spread_element(self, node);
+    }
+
+    fn visit_object_expression(&mut self, node: &mut ObjectExpression<'a>) {
+        walk_object_expression(self, node);
+    }
+
+    fn visit_property(&mut self, node: &mut Property<'a>) {
+        walk_property(self, node);
+    }
+
+    fn visit_function_expression(&mut self, node: &mut FunctionExpression<'a>) {
+        walk_function_expression(self, node);
+    }
+
+    fn visit_unary_expression(&mut self, node: &mut UnaryExpression<'a>) {
+        walk_unary_expression(self, node);
+    }
+
+    fn visit_update_expression(&mut self, node: &mut UpdateExpression<'a>) {
+        walk_update_expression(self, node);
+    }
+
+    fn visit_binary_expression(&mut self, node: &mut BinaryExpression<'a>) {
+        walk_binary_expression(self, node);
+    }
+
+    fn visit_assignment_expression(&mut self, node: &mut AssignmentExpression<'a>) {
+        walk_assignment_expression(self, node);
+    }
+
+    fn visit_logical_expression(&mut self, node: &mut LogicalExpression<'a>) {
+        walk_logical_expression(self, node);
+    }
+
+    fn visit_member_expression(&mut self, node: &mut MemberExpression<'a>) {
+        walk_member_expression(self, node);
+    }
+
+    fn visit_conditional_expression(&mut self, node: &mut ConditionalExpression<'a>) {
+        walk_conditional_expression(self, node);
+    }
+
+    fn visit_call_expression(&mut self, node: &mut CallExpression<'a>) {
+        walk_call_expression(self, node);
+    }
+
+    fn visit_new_expression(&mut self, node: &mut NewExpression<'a>) {
+        walk_new_expression(self, node);
+    }
+
+    fn visit_sequence_expression(&mut self, node: &mut SequenceExpression<'a>) {
+        walk_sequence_expression(self, node);
+    }
+
+    fn visit_parenthesized_expression(&mut self, node: &mut ParenthesizedExpression<'a>) {
+        walk_parenthesized_expression(self, node);
+    }
+
+    fn visit_arrow_function_expression(&mut self, node: &mut ArrowFunctionExpression<'a>) {
+        walk_arrow_function_expression(self, node);
+    }
+
+    fn visit_yield_expression(&mut self, node: &mut YieldExpression<'a>) {
+        walk_yield_expression(self, node);
+    }
+
+    fn visit_template_literal(&mut self, node: &mut TemplateLiteral<'a>) {
+        walk_template_literal(self, node);
+    }
+
+    fn visit_template_element(&mut self, node: &mut TemplateElement<'a>) {}
+
+    fn visit_tagged_template_expression(&mut self, node: &mut TaggedTemplateExpression<'a>) {
+        walk_tagged_template_expression(self, node);
+    }
+
+    fn visit_class_expression(&mut self, node: &mut ClassExpression<'a>) {
+        walk_class_expression(self, node);
+    }
+
+    fn visit_meta_property(&mut self, node: &mut MetaProperty<'a>) {
+        walk_meta_property(self, node);
+    }
+
+    fn visit_await_expression(&mut self, node: &mut AwaitExpression<'a>) {
+        walk_await_expression(self, node);
+    }
+
+    fn visit_chain_expression(&mut self, node: &mut ChainExpression<'a>) {
+        walk_chain_expression(self, node);
+    }
+
+    fn visit_import_expression(&mut self, node: &mut ImportExpression<'a>) {
+        walk_import_expression(self, node);
+    }
+
+    fn visit_static_block(&mut self, node: &mut StaticBlock<'a>) {
+        walk_static_block(self, node);
+    }
+
+    // patterns
+    fn visit_pattern(&mut self, node: &mut Pattern<'a>) {
+        walk_pattern(self, node);
+    }
+
+    fn visit_rest_element(&mut self, node: &mut RestElement<'a>) {
+        walk_rest_element(self, node);
+    }
+
+    fn visit_array_pattern(&mut self, node: &mut ArrayPattern<'a>) {
+        walk_array_pattern(self, node);
+    }
+
+    fn visit_object_pattern(&mut self, node: &mut ObjectPattern<'a>) {
+        walk_object_pattern(self, node);
+    }
+
+    fn visit_assignment_property(&mut self, node: &mut AssignmentProperty<'a>) {
+        walk_assignment_property(self, node);
+    }
+
+    fn visit_assignment_pattern(&mut self, node: &mut AssignmentPattern<'a>) {
+        walk_assignment_pattern(self, node);
+    }
+
+    // modules
+    fn visit_import_or_export_declaration(&mut self, node: &mut ImportOrExportDeclaration<'a>) {
+        walk_import_or_export_declaration(self, node);
+    }
+
+    fn visit_import_declaration(&mut self, node: &mut ImportDeclaration<'a>) {
+        walk_import_declaration(self, node);
+    }
+
+    fn visit_import_specifier(&mut self, node: &mut ImportSpecifier<'a>) {
+        walk_import_specifier(self, node);
+    }
+
+    fn visit_import_default_specifier(&mut self, node: &mut ImportDefaultSpecifier<'a>) {
+        walk_import_default_specifier(self, node);
+    }
+
+    fn visit_import_namespace_specifier(&mut self, node: &mut ImportNamespaceSpecifier<'a>) {
+        walk_import_namespace_specifier(self, node);
+    }
+
+    fn visit_export_named_declaration(&mut self, node: &mut ExportNamedDeclaration<'a>) {
+        walk_export_named_declaration(self, node);
+    }
+
+    fn visit_export_specifier(&mut self, node: &mut ExportSpecifier<'a>) {
+        walk_export_specifier(self, node);
+    }
+
+    fn visit_export_default_declaration(&mut self, node: &mut ExportDefaultDeclaration<'a>) {
+        walk_export_default_declaration(self, node);
+    }
+
+    fn visit_anonymous_default_exported_function_declaration(
+        &mut self,
+        node: &mut AnonymousDefaultExportedFunctionDeclaration<'a>,
+    ) {
+        walk_anonymous_default_exported_function_declaration(self, node);
+    }
+
+    fn visit_anonymous_default_exported_class_declaration(
+        &mut self,
+        node: &mut AnonymousDefaultExportedClassDeclaration<'a>,
+    ) {
+        walk_anonymous_default_exported_class_declaration(self, node);
+    }
+
+    fn visit_export_all_declaration(&mut self, node: &mut ExportAllDeclaration<'a>) {
+        walk_export_all_declaration(self, node);
+    }
+}
+
+pub fn walk_program<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, node: &mut Program<'a>) {
+    for item in node.body.iter_mut() {
+        match item {
+            ProgramNode::Directive(node) => visitor.visit_directive(node),
+            ProgramNode::Statement(node) => visitor.visit_statement(node),
+            ProgramNode::ImportOrExportDeclaration(node) => {
+                visitor.visit_import_or_export_declaration(node)
+            }
+        }
+    }
+}
+
+pub fn walk_directive<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, node: &mut Directive<'a>) {
+    visitor.visit_literal(&mut node.expression);
+}
+
+pub fn walk_statement<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, node: &mut Statement<'a>) {
+    match node {
+        Statement::ExpressionStatement(node) => visitor.visit_expression_statement(node),
+        Statement::BlockStatement(node) => visitor.visit_block_statement(node),
+        Statement::EmptyStatement(node) => visitor.visit_empty_statement(node),
+        Statement::DebuggerStatement(node) => visitor.visit_debugger_statement(node),
+        Statement::WithStatement(node) => visitor.visit_with_statement(node),
+        Statement::ReturnStatement(node) => visitor.visit_return_statement(node),
+        Statement::LabeledStatement(node) => visitor.visit_labeled_statement(node),
+        Statement::BreakStatement(node) => visitor.visit_break_statement(node),
+        Statement::ContinueStatement(node) => visitor.visit_continue_statement(node),
+        Statement::IfStatement(node) => visitor.visit_if_statement(node),
+        Statement::SwitchStatement(node) => visitor.visit_switch_statement(node),
+        Statement::ThrowStatement(node) => visitor.visit_throw_statement(node),
+        Statement::TryStatement(node) => visitor.visit_try_statement(node),
+        Statement::WhileStatement(node) => visitor.visit_while_statement(node),
+        Statement::DoWhileStatement(node) => visitor.visit_do_while_statement(node),
+        Statement::ForStatement(node) => visitor.visit_for_statement(node),
+        Statement::ForInStatement(node) => visitor.visit_for_in_statement(node),
+        Statement::ForOfStatement(node) => visitor.visit_for_of_statement(node),
+        Statement::FunctionDeclaration(node) => visitor.visit_function_declaration(node),
+        Statement::AnonymousDefaultExportedFunctionDeclaration(node) => {
+            visitor.visit_anonymous_default_exported_function_declaration(node)
+        }
+        Statement::AnonymousDefaultExportedClassDeclaration(node) => {
+            visitor.visit_anonymous_default_exported_class_declaration(node)
+        }
+        Statement::VariableDeclaration(node) => visitor.visit_variable_declaration(node),
+        Statement::ClassDeclaration(node) => visitor.visit_class_declaration(node),
+        Statement::InvalidStatement(node) => visitor.visit_invalid_statement(node),
+        Statement::ImportOrExportDeclaration(node) => {
+            visitor.visit_import_or_export_declaration(node)
+        }
+    }
+}
+
+pub fn walk_expression_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ExpressionStatement<'a>,
+) {
+    visitor.visit_expression(&mut node.expression);
+}
+
+pub fn walk_block_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut BlockStatement<'a>,
+) {
+    for stmt in node.body.iter_mut() {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_with_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut WithStatement<'a>,
+) {
+    visitor.visit_expression(&mut node.object);
+    visitor.visit_statement(&mut node.body);
+}
+
+pub fn walk_return_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ReturnStatement<'a>,
+) {
+    if let Some(argument) = &mut node.argument {
+        visitor.visit_expression(argument);
+    }
+}
+
+pub fn walk_labeled_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut LabeledStatement<'a>,
+) {
+    visitor.visit_identifier(&mut node.label);
+    visitor.visit_statement(&mut node.body);
+}
+
+pub fn walk_break_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut BreakStatement<'a>,
+) {
+    if let Some(label) = &mut node.label {
+        visitor.visit_identifier(label);
+    }
+}
+
+pub fn walk_continue_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ContinueStatement<'a>,
+) {
+    if let Some(label) = &mut node.label {
+        visitor.visit_identifier(label);
+    }
+}
+
+pub fn walk_if_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut IfStatement<'a>,
+) {
+    visitor.visit_expression(&mut node.test);
+    visitor.visit_statement(&mut node.consequent);
+    if let Some(alternate) = &mut node.alternate {
+        visitor.visit_statement(alternate);
+    }
+}
+
+pub fn walk_switch_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut SwitchStatement<'a>,
+) {
+    visitor.visit_expression(&mut node.discriminant);
+    for case in node.cases.iter_mut() {
+        visitor.visit_switch_case(case);
+    }
+}
+
+pub fn walk_switch_case<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, node: &mut SwitchCase<'a>) {
+    if let Some(test) = &mut node.test {
+        visitor.visit_expression(test);
+    }
+    for stmt in node.consequent.iter_mut() {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_throw_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ThrowStatement<'a>,
+) {
+    visitor.visit_expression(&mut node.argument);
+}
+
+pub fn walk_try_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut TryStatement<'a>,
+) {
+    visitor.visit_block_statement(&mut node.block);
+    if let Some(handler) = &mut node.handler {
+        visitor.visit_catch_clause(handler);
+    }
+    if let Some(finalizer) = &mut node.finalizer {
+        visitor.visit_block_statement(finalizer);
+    }
+}
+
+pub fn walk_catch_clause<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut CatchClause<'a>,
+) {
+    if let Some(param) = &mut node.param {
+        visitor.visit_pattern(param);
+    }
+    visitor.visit_block_statement(&mut node.body);
+}
+
+pub fn walk_while_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut WhileStatement<'a>,
+) {
+    visitor.visit_expression(&mut node.test);
+    visitor.visit_statement(&mut node.body);
+}
+
+pub fn walk_do_while_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut DoWhileStatement<'a>,
+) {
+    visitor.visit_statement(&mut node.body);
+    visitor.visit_expression(&mut node.test);
+}
+
+pub fn walk_for_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ForStatement<'a>,
+) {
+    match &mut node.init {
+        ForStatementInit::VariableDeclaration(init) => visitor.visit_variable_declaration(init),
+        ForStatementInit::Expression(init) => visitor.visit_expression(init),
+        ForStatementInit::Null => {}
+    }
+    if let Some(test) = &mut node.test {
+        visitor.visit_expression(test);
+    }
+    if let Some(update) = &mut node.update {
+        visitor.visit_expression(update);
+    }
+    visitor.visit_statement(&mut node.body);
+}
+
+fn walk_for_in_of_left<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    left: &mut ForInOfStatementLeft<'a>,
+) {
+    match left {
+        ForInOfStatementLeft::VariableDeclaration(left) => visitor.visit_variable_declaration(left),
+        ForInOfStatementLeft::Pattern(left) => visitor.visit_pattern(left),
+    }
+}
+
+pub fn walk_for_in_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ForInStatement<'a>,
+) {
+    walk_for_in_of_left(visitor, &mut node.left);
+    visitor.visit_expression(&mut node.right);
+    visitor.visit_statement(&mut node.body);
+}
+
+pub fn walk_for_of_statement<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ForOfStatement<'a>,
+) {
+    walk_for_in_of_left(visitor, &mut node.left);
+    visitor.visit_expression(&mut node.right);
+    visitor.visit_statement(&mut node.body);
+}
+
+pub fn walk_function_declaration<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut FunctionDeclaration<'a>,
+) {
+    visitor.visit_identifier(&mut node.id);
+    for param in node.params.iter_mut() {
+        visitor.visit_pattern(param);
+    }
+    visitor.visit_function_body(&mut node.body);
+}
+
+pub fn walk_function_body<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut FunctionBody<'a>,
+) {
+    for item in node.body.iter_mut() {
+        match item {
+            FunctionBodyContent::Directive(node) => visitor.visit_directive(node),
+            FunctionBodyContent::Statement(node) => visitor.visit_statement(node),
+        }
+    }
+}
+
+pub fn walk_variable_declaration<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut VariableDeclaration<'a>,
+) {
+    for declarator in node.declarations.iter_mut() {
+        visitor.visit_variable_declarator(declarator);
+    }
+}
+
+pub fn walk_variable_declarator<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut VariableDeclarator<'a>,
+) {
+    visitor.visit_pattern(&mut node.id);
+    if let Some(init) = &mut node.init {
+        visitor.visit_expression(init);
+    }
+}
+
+pub fn walk_class_declaration<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ClassDeclaration<'a>,
+) {
+    visitor.visit_identifier(&mut node.id);
+    if let Some(super_class) = &mut node.super_class {
+        visitor.visit_expression(super_class);
+    }
+    visitor.visit_class_body(&mut node.body);
+}
+
+pub fn walk_class_body<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, node: &mut ClassBody<'a>) {
+    for el in node.body.iter_mut() {
+        visitor.visit_class_body_el(el);
+    }
+}
+
+pub fn walk_class_body_el<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ClassBodyEl<'a>,
+) {
+    match node {
+        ClassBodyEl::MethodDefinition(node) => visitor.visit_method_definition(node),
+        ClassBodyEl::PropertyDefinition(node) => visitor.visit_property_definition(node),
+        ClassBodyEl::StaticBlock(node) => visitor.visit_static_block(node),
+    }
+}
+
+pub fn walk_method_definition<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut MethodDefinition<'a>,
+) {
+    match &mut node.key {
+        ClassMethodKey::Expression(key) => visitor.visit_expression(key),
+        ClassMethodKey::PrivateIdentifier(key) => visitor.visit_private_identifier(key),
+    }
+    visitor.visit_function_expression(&mut node.value);
+}
+
+pub fn walk_property_definition<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut PropertyDefinition<'a>,
+) {
+    match &mut node.key {
+        ClassPropertyKey::Expression(key) => visitor.visit_expression(key),
+        ClassPropertyKey::PrivateIdentifier(key) => visitor.visit_private_identifier(key),
+    }
+    if let Some(value) = &mut node.value {
+        visitor.visit_expression(value);
+    }
+}
+
+pub fn walk_expression<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, node: &mut Expression<'a>) {
+    match node {
+        Expression::Identifier(node) => visitor.visit_identifier(node),
+        Expression::Literal(node) => visitor.visit_literal(node),
+        Expression::ThisExpression(node) => visitor.visit_this_expression(node),
+        Expression::ArrayExpression(node) => visitor.visit_array_expression(node),
+        Expression::ObjectExpression(node) => visitor.visit_object_expression(node),
+        Expression::FunctionExpression(node) => visitor.visit_function_expression(node),
+        Expression::UnaryExpression(node) => visitor.visit_unary_expression(node),
+        Expression::UpdateExpression(node) => visitor.visit_update_expression(node),
+        Expression::BinaryExpression(node) => visitor.visit_binary_expression(node),
+        Expression::AssignmentExpression(node) => visitor.visit_assignment_expression(node),
+        Expression::LogicalExpression(node) => visitor.visit_logical_expression(node),
+        Expression::MemberExpression(node) => visitor.visit_member_expression(node),
+        Expression::ConditionalExpression(node) => visitor.visit_conditional_expression(node),
+        Expression::CallExpression(node) => visitor.visit_call_expression(node),
+        Expression::NewExpression(node) => visitor.visit_new_expression(node),
+        Expression::SequenceExpression(node) => visitor.visit_sequence_expression(node),
+        Expression::ParenthesizedExpression(node) => visitor.visit_parenthesized_expression(node),
+        Expression::ArrowFunctionExpression(node) => visitor.visit_arrow_function_expression(node),
+        Expression::YieldExpression(node) => visitor.visit_yield_expression(node),
+        Expression::TemplateLiteral(node) => visitor.visit_template_literal(node),
+        Expression::TaggedTemplateExpression(node) => {
+            visitor.visit_tagged_template_expression(node)
+        }
+        Expression::ClassExpression(node) => visitor.visit_class_expression(node),
+        Expression::MetaProperty(node) => visitor.visit_meta_property(node),
+        Expression::AwaitExpression(node) => visitor.visit_await_expression(node),
+        Expression::ChainExpression(node) => visitor.visit_chain_expression(node),
+        Expression::ImportExpression(node) => visitor.visit_import_expression(node),
+        Expression::StaticBlock(node) => visitor.visit_static_block(node),
+    }
+}
+
+pub fn walk_array_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ArrayExpression<'a>,
+) {
+    for element in node.elements.iter_mut() {
+        match element {
+            ArrayExprEle::Expression(element) => visitor.visit_expression(element),
+            ArrayExprEle::SpreadElement(element) => visitor.visit_spread_element(element),
+            ArrayExprEle::Null => {}
+        }
+    }
+}
+
+pub fn walk_spread_element<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut SpreadElement<'a>,
+) {
+    visitor.visit_expression(&mut node.argument);
+}
+
+pub fn walk_object_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ObjectExpression<'a>,
+) {
+    for property in node.properties.iter_mut() {
+        match property {
+            ObjectProperty::Property(property) => visitor.visit_property(property),
+            ObjectProperty::SpreadElement(property) => visitor.visit_spread_element(property),
+        }
+    }
+}
+
+pub fn walk_property<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, node: &mut Property<'a>) {
+    visitor.visit_expression(&mut node.key);
+    visitor.visit_expression(&mut node.value);
+}
+
+pub fn walk_function_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut FunctionExpression<'a>,
+) {
+    if let Some(id) = &mut node.id {
+        visitor.visit_identifier(id);
+    }
+    for param in node.params.iter_mut() {
+        visitor.visit_pattern(param);
+    }
+    visitor.visit_function_body(&mut node.body);
+}
+
+pub fn walk_unary_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut UnaryExpression<'a>,
+) {
+    visitor.visit_expression(&mut node.argument);
+}
+
+pub fn walk_update_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut UpdateExpression<'a>,
+) {
+    visitor.visit_expression(&mut node.argument);
+}
+
+pub fn walk_binary_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut BinaryExpression<'a>,
+) {
+    match &mut node.left {
+        BinaryOpeartorLeft::Expression(left) => visitor.visit_expression(left),
+        BinaryOpeartorLeft::PrivateIdentifier(left) => visitor.visit_private_identifier(left),
+    }
+    visitor.visit_expression(&mut node.right);
+}
+
+pub fn walk_assignment_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut AssignmentExpression<'a>,
+) {
+    match &mut node.left {
+        AssignmentExpressionLeft::Pattern(left) => visitor.visit_pattern(left),
+        AssignmentExpressionLeft::Expression(left) => visitor.visit_expression(left),
+    }
+    visitor.visit_expression(&mut node.right);
+}
+
+pub fn walk_logical_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut LogicalExpression<'a>,
+) {
+    visitor.visit_expression(&mut node.left);
+    visitor.visit_expression(&mut node.right);
+}
+
+pub fn walk_member_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut MemberExpression<'a>,
+) {
+    match &mut node.object {
+        MemberExprObject::Expression(object) => visitor.visit_expression(object),
+        MemberExprObject::Super(object) => visitor.visit_super(object),
+    }
+    match &mut node.property {
+        MemberExprProperty::Expression(property) => visitor.visit_expression(property),
+        MemberExprProperty::PrivateIdentifier(property) => {
+            visitor.visit_private_identifier(property)
+        }
+    }
+}
+
+pub fn walk_conditional_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ConditionalExpression<'a>,
+) {
+    visitor.visit_expression(&mut node.test);
+    visitor.visit_expression(&mut node.consequent);
+    visitor.visit_expression(&mut node.alternate);
+}
+
+pub fn walk_call_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut CallExpression<'a>,
+) {
+    match &mut node.callee {
+        CallExprCallee::Expression(callee) => visitor.visit_expression(callee),
+        CallExprCallee::Super(callee) => visitor.visit_super(callee),
+    }
+    for argument in node.arguments.iter_mut() {
+        match argument {
+            CallExprArgs::Expression(argument) => visitor.visit_expression(argument),
+            CallExprArgs::SpreadElement(argument) => visitor.visit_spread_element(argument),
+        }
+    }
+}
+
+pub fn walk_new_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut NewExpression<'a>,
+) {
+    visitor.visit_expression(&mut node.callee);
+    for argument in node.arguments.iter_mut() {
+        match argument {
+            NewExprArgs::Expression(argument) => visitor.visit_expression(argument),
+            NewExprArgs::SpreadElement(argument) => visitor.visit_spread_element(argument),
+        }
+    }
+}
+
+pub fn walk_sequence_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut SequenceExpression<'a>,
+) {
+    for expression in node.expressions.iter_mut() {
+        visitor.visit_expression(expression);
+    }
+}
+
+pub fn walk_parenthesized_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ParenthesizedExpression<'a>,
+) {
+    visitor.visit_expression(&mut node.expression);
+}
+
+pub fn walk_arrow_function_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ArrowFunctionExpression<'a>,
+) {
+    for param in node.params.iter_mut() {
+        visitor.visit_pattern(param);
+    }
+    match &mut node.body {
+        ArrowFunctionBody::FunctionBoby(body) => visitor.visit_function_body(body),
+        ArrowFunctionBody::Expression(body) => visitor.visit_expression(body),
+    }
+}
+
+pub fn walk_yield_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut YieldExpression<'a>,
+) {
+    if let Some(argument) = &mut node.argument {
+        visitor.visit_expression(argument);
+    }
+}
+
+pub fn walk_template_literal<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut TemplateLiteral<'a>,
+) {
+    // quasis 比 expressions 多一个，按照源码中的顺序交替访问
+    for (idx, quasi) in node.quasis.iter_mut().enumerate() {
+        visitor.visit_template_element(quasi);
+        if let Some(expression) = node.expressions.get_mut(idx) {
+            visitor.visit_expression(expression);
+        }
+    }
+}
+
+pub fn walk_tagged_template_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut TaggedTemplateExpression<'a>,
+) {
+    visitor.visit_expression(&mut node.tag);
+    visitor.visit_template_literal(&mut node.quasi);
+}
+
+pub fn walk_class_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ClassExpression<'a>,
+) {
+    if let Some(id) = &mut node.id {
+        visitor.visit_identifier(id);
+    }
+    if let Some(super_class) = &mut node.super_class {
+        visitor.visit_expression(super_class);
+    }
+    visitor.visit_class_body(&mut node.body);
+}
+
+pub fn walk_meta_property<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut MetaProperty<'a>,
+) {
+    visitor.visit_identifier(&mut node.meta);
+    visitor.visit_identifier(&mut node.property);
+}
+
+pub fn walk_await_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut AwaitExpression<'a>,
+) {
+    visitor.visit_expression(&mut node.argument);
+}
+
+pub fn walk_chain_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ChainExpression<'a>,
+) {
+    match &mut node.expression {
+        ChainExpressionElement::CallExpression(expression) => {
+            visitor.visit_call_expression(expression)
+        }
+        ChainExpressionElement::MemberExpression(expression) => {
+            visitor.visit_member_expression(expression)
+        }
+    }
+}
+
+pub fn walk_import_expression<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ImportExpression<'a>,
+) {
+    visitor.visit_expression(&mut node.source);
+}
+
+pub fn walk_static_block<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut StaticBlock<'a>,
+) {
+    for stmt in node.body.iter_mut() {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_pattern<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, node: &mut Pattern<'a>) {
+    match node {
+        Pattern::RestElement(node) => visitor.visit_rest_element(node),
+        Pattern::ArrayPattern(node) => visitor.visit_array_pattern(node),
+        Pattern::ObjectPattern(node) => visitor.visit_object_pattern(node),
+        Pattern::Identifier(node) => visitor.visit_identifier(node),
+        Pattern::AssignmentPattern(node) => visitor.visit_assignment_pattern(node),
+        Pattern::MemberExpression(node) => visitor.visit_member_expression(node),
+    }
+}
+
+pub fn walk_rest_element<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut RestElement<'a>,
+) {
+    visitor.visit_pattern(&mut node.argument);
+}
+
+pub fn walk_array_pattern<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ArrayPattern<'a>,
+) {
+    for element in node.elements.iter_mut().flatten() {
+        visitor.visit_pattern(element);
+    }
+}
+
+pub fn walk_object_pattern<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ObjectPattern<'a>,
+) {
+    for property in node.properties.iter_mut() {
+        match property {
+            ObjectPatternProperty::AssignmentProperty(property) => {
+                visitor.visit_assignment_property(property)
+            }
+            ObjectPatternProperty::RestElement(property) => visitor.visit_rest_element(property),
+        }
+    }
+}
+
+pub fn walk_assignment_property<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut AssignmentProperty<'a>,
+) {
+    visitor.visit_expression(&mut node.key);
+    visitor.visit_pattern(&mut node.value);
+}
+
+pub fn walk_assignment_pattern<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut AssignmentPattern<'a>,
+) {
+    visitor.visit_pattern(&mut node.left);
+    visitor.visit_expression(&mut node.right);
+}
+
+pub fn walk_import_or_export_declaration<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ImportOrExportDeclaration<'a>,
+) {
+    match node {
+        ImportOrExportDeclaration::ImportDeclaration(node) => {
+            visitor.visit_import_declaration(node)
+        }
+        ImportOrExportDeclaration::ExportNamedDeclaration(node) => {
+            visitor.visit_export_named_declaration(node)
+        }
+        ImportOrExportDeclaration::ExportDefaultDeclaration(node) => {
+            visitor.visit_export_default_declaration(node)
+        }
+        ImportOrExportDeclaration::ExportAllDeclaration(node) => {
+            visitor.visit_export_all_declaration(node)
+        }
+    }
+}
+
+pub fn walk_import_declaration<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ImportDeclaration<'a>,
+) {
+    for specifier in node.specifiers.iter_mut() {
+        match specifier {
+            ImportSpecifiers::ImportSpecifier(specifier) => {
+                visitor.visit_import_specifier(specifier)
+            }
+            ImportSpecifiers::ImportDefaultSpecifier(specifier) => {
+                visitor.visit_import_default_specifier(specifier)
+            }
+            ImportSpecifiers::ImportNamespaceSpecifier(specifier) => {
+                visitor.visit_import_namespace_specifier(specifier)
+            }
+        }
+    }
+    visitor.visit_literal(&mut node.source);
+}
+
+pub fn walk_import_specifier<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ImportSpecifier<'a>,
+) {
+    match &mut node.imported {
+        ImportedType::Identifier(imported) => visitor.visit_identifier(imported),
+        ImportedType::Literal(imported) => visitor.visit_literal(imported),
+    }
+    visitor.visit_identifier(&mut node.local);
+}
+
+pub fn walk_import_default_specifier<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ImportDefaultSpecifier<'a>,
+) {
+    visitor.visit_identifier(&mut node.local);
+}
+
+pub fn walk_import_namespace_specifier<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ImportNamespaceSpecifier<'a>,
+) {
+    visitor.visit_identifier(&mut node.local);
+}
+
+pub fn walk_export_named_declaration<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ExportNamedDeclaration<'a>,
+) {
+    match &mut node.declaration {
+        Some(ExportDeclaration::FunctionDeclaration(declaration)) => {
+            visitor.visit_function_declaration(declaration)
+        }
+        Some(ExportDeclaration::VariableDeclaration(declaration)) => {
+            visitor.visit_variable_declaration(declaration)
+        }
+        Some(ExportDeclaration::ClassDeclaration(declaration)) => {
+            visitor.visit_class_declaration(declaration)
+        }
+        None => {}
+    }
+    for specifier in node.specifiers.iter_mut() {
+        visitor.visit_export_specifier(specifier);
+    }
+    if let Some(source) = &mut node.source {
+        visitor.visit_literal(source);
+    }
+}
+
+pub fn walk_export_specifier<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ExportSpecifier<'a>,
+) {
+    match &mut node.local {
+        ExportLocal::Identifier(local) => visitor.visit_identifier(local),
+        ExportLocal::Literal(local) => visitor.visit_literal(local),
+    }
+    match &mut node.exported {
+        ExportedType::Identifier(exported) => visitor.visit_identifier(exported),
+        ExportedType::Literal(exported) => visitor.visit_literal(exported),
+    }
+}
+
+pub fn walk_export_default_declaration<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ExportDefaultDeclaration<'a>,
+) {
+    match &mut node.declaration {
+        ExportDefaultDeclarationType::AnonymousDefaultExportedFunctionDeclaration(declaration) => {
+            visitor.visit_anonymous_default_exported_function_declaration(declaration)
+        }
+        ExportDefaultDeclarationType::FunctionDeclaration(declaration) => {
+            visitor.visit_function_declaration(declaration)
+        }
+        ExportDefaultDeclarationType::AnonymousDefaultExportedClassDeclaration(declaration) => {
+            visitor.visit_anonymous_default_exported_class_declaration(declaration)
+        }
+        ExportDefaultDeclarationType::ClassDeclaration(declaration) => {
+            visitor.visit_class_declaration(declaration)
+        }
+        ExportDefaultDeclarationType::Expression(declaration) => {
+            visitor.visit_expression(declaration)
+        }
+    }
+}
+
+pub fn walk_anonymous_default_exported_function_declaration<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut AnonymousDefaultExportedFunctionDeclaration<'a>,
+) {
+    if let Some(id) = &mut node.id {
+        visitor.visit_identifier(id);
+    }
+    for param in node.params.iter_mut() {
+        visitor.visit_pattern(param);
+    }
+    visitor.visit_function_body(&mut node.body);
+}
+
+pub fn walk_anonymous_default_exported_class_declaration<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut AnonymousDefaultExportedClassDeclaration<'a>,
+) {
+    if let Some(id) = &mut node.id {
+        visitor.visit_identifier(id);
+    }
+    if let Some(super_class) = &mut node.super_class {
+        visitor.visit_expression(super_class);
+    }
+    visitor.visit_class_body(&mut node.body);
+}
+
+pub fn walk_export_all_declaration<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    node: &mut ExportAllDeclaration<'a>,
+) {
+    match &mut node.exported {
+        ExportAllExportedType::Identifier(exported) => visitor.visit_identifier(exported),
+        ExportAllExportedType::Literal(exported) => visitor.visit_literal(exported),
+        ExportAllExportedType::Null => {}
+    }
+    visitor.visit_literal(&mut node.source);
+}
@@ -0,0 +1,300 @@
+use std::ops::Range;
+
+use super::source_map::{PendingMapping, SourceMapBuilder};
+
+// 打印时生成的指令序列，文本之外的指令标出了可以换行的位置，由 render 根据行宽决定是否换行
+pub(crate) enum Doc {
+    // Codegen::code 中的一段文本
+    Text(Range<usize>),
+    // 注释，不计入 Codegen::pos
+    Comment(String),
+    Line(Line),
+    GroupStart(Group),
+    GroupEnd,
+    IndentStart,
+    IndentEnd,
+    // 所在的 group 换行时才输出，比如尾随逗号
+    IfBreak(&'static str),
+    // 推迟到下一次换行之前输出，用于行尾的单行注释
+    LineSuffix(String),
+    Mapping(PendingMapping),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Line {
+    // 不换行时为空
+    Soft,
+    // 不换行时为一个空格
+    Space,
+    // 总是换行
+    Hard,
+}
+
+pub(crate) struct Group {
+    // 总是换行，比如 `codegen::print` 中的对象字面量
+    pub expand: bool,
+    // 放不下时先尝试只让从这里开始的最后一个子节点换行，比如 `foo(a, () => {...})` 中的回调函数
+    pub hug_from: Option<usize>,
+}
+
+pub(crate) struct Layout {
+    // 为 None 时不考虑行宽，只有 expand 的 group 会换行
+    pub width: Option<usize>,
+    pub indent_width: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+    // group 自身的换行位置不换行，由最后一个子节点换行
+    Hug,
+}
+
+// group 中包含强制换行的内容，此时 group 本身也必须换行
+const HARD: u8 = 1;
+// 强制换行的内容只出现在 hug_from 之后
+const HARD_IN_LAST: u8 = 2;
+
+struct Renderer<'d, 's, 'm> {
+    docs: &'d [Doc],
+    code: &'d str,
+    layout: &'d Layout,
+    // 每个 GroupStart 对应的 GroupEnd 的位置，以及强制换行的标记
+    ends: Vec<usize>,
+    flags: Vec<u8>,
+    out: String,
+    col: usize,
+    indent: usize,
+    at_line_start: bool,
+    // 推迟输出的注释以及加入时的缩进
+    line_suffix: Vec<(&'d str, usize)>,
+    source_map: Option<&'m mut SourceMapBuilder<'s>>,
+}
+
+pub(crate) fn render(
+    docs: &[Doc],
+    code: &str,
+    layout: &Layout,
+    source_map: Option<&mut SourceMapBuilder>,
+) -> String {
+    let mut renderer = Renderer {
+        docs,
+        code,
+        layout,
+        ends: vec![0; docs.len()],
+        flags: vec![0; docs.len()],
+        out: String::with_capacity(code.len()),
+        col: 0,
+        indent: 0,
+        at_line_start: true,
+        line_suffix: vec![],
+        source_map,
+    };
+    renderer.measure_groups();
+    renderer.render();
+    renderer.out
+}
+
+impl<'d> Renderer<'d, '_, '_> {
+    fn measure_groups(&mut self) {
+        let mut open: Vec<usize> = vec![];
+        for (idx, doc) in self.docs.iter().enumerate() {
+            match doc {
+                Doc::GroupStart(_) => open.push(idx),
+                Doc::GroupEnd => {
+                    let start = open.pop().unwrap();
+                    self.ends[start] = idx;
+                    let expand =
+                        matches!(&self.docs[start], Doc::GroupStart(group) if group.expand);
+                    if expand || self.flags[start] != 0 {
+                        if let Some(&parent) = open.last() {
+                            self.mark_hard(parent, start);
+                        }
+                    }
+                }
+                Doc::Line(Line::Hard) | Doc::LineSuffix(_) => {
+                    if let Some(&group) = open.last() {
+                        self.mark_hard(group, idx);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn mark_hard(&mut self, group: usize, at: usize) {
+        match &self.docs[group] {
+            Doc::GroupStart(Group {
+                hug_from: Some(hug_from),
+                ..
+            }) if at >= *hug_from => self.flags[group] |= HARD_IN_LAST,
+            _ => self.flags[group] |= HARD,
+        }
+    }
+
+    fn render(&mut self) {
+        // 每个 group 的换行方式以及 hug_from
+        let mut modes = vec![(Mode::Break, usize::MAX)];
+        // 每个 IndentStart 是否生效，只有换行的 group 中的缩进才生效。hug 时 group 自身的缩进不生效，
+        // 否则最后一个子节点会多缩进一层
+        let mut indents = vec![];
+        for idx in 0..self.docs.len() {
+            let (mode, hug_from) = *modes.last().unwrap();
+            match &self.docs[idx] {
+                Doc::Text(range) => {
+                    self.write_indent();
+                    // 只有代码本身的文本才对应节点的位置，空格、尾随逗号和注释都不算
+                    if let Some(source_map) = &mut self.source_map {
+                        source_map.flush();
+                    }
+                    self.push_str(&self.code[range.clone()]);
+                }
+                Doc::Comment(text) => self.write(text),
+                Doc::Line(line) => match (line, mode) {
+                    (Line::Hard, _) | (_, Mode::Break) => self.newline(),
+                    (Line::Soft, _) => {}
+                    (Line::Space, _) => self.write(" "),
+                },
+                Doc::GroupStart(group) => {
+                    let mode = self.group_mode(idx, group, mode);
+                    modes.push((mode, group.hug_from.unwrap_or(usize::MAX)));
+                }
+                Doc::GroupEnd => {
+                    modes.pop();
+                }
+                Doc::IndentStart => {
+                    let effective = match mode {
+                        Mode::Flat => false,
+                        Mode::Break => true,
+                        Mode::Hug => idx >= hug_from,
+                    };
+                    if effective {
+                        self.indent += 1;
+                    }
+                    indents.push(effective);
+                }
+                Doc::IndentEnd => {
+                    if indents.pop().unwrap() {
+                        self.indent -= 1;
+                    }
+                }
+                Doc::IfBreak(text) => {
+                    if mode == Mode::Break {
+                        self.write(text);
+                    }
+                }
+                Doc::LineSuffix(text) => self.line_suffix.push((text, self.indent)),
+                Doc::Mapping(mapping) => {
+                    if let Some(source_map) = &mut self.source_map {
+                        source_map.mark(mapping);
+                    }
+                }
+            }
+        }
+        self.flush_line_suffix();
+    }
+
+    fn group_mode(&self, idx: usize, group: &Group, parent: Mode) -> Mode {
+        let Some(width) = self.layout.width else {
+            return if group.expand {
+                Mode::Break
+            } else {
+                Mode::Flat
+            };
+        };
+        if parent == Mode::Flat {
+            return Mode::Flat;
+        }
+        let flags = self.flags[idx];
+        if group.expand || flags & HARD != 0 {
+            return Mode::Break;
+        }
+        let col = if self.at_line_start {
+            self.indent * self.layout.indent_width
+        } else {
+            self.col
+        };
+        let remaining = width as isize - col as isize;
+        if flags & HARD_IN_LAST == 0 && self.fits(idx, self.ends[idx], remaining) {
+            return Mode::Flat;
+        }
+        match group.hug_from {
+            Some(hug_from) if self.fits(idx, hug_from, remaining) => Mode::Hug,
+            _ => Mode::Break,
+        }
+    }
+
+    // 从 start 开始不换行地打印到 flat_until，再到下一个可以换行的位置为止，是否能放在剩余的宽度中
+    fn fits(&self, start: usize, flat_until: usize, mut remaining: isize) -> bool {
+        for (idx, doc) in self.docs.iter().enumerate().skip(start) {
+            let text = match doc {
+                Doc::Text(range) => &self.code[range.clone()],
+                Doc::Comment(text) => text,
+                Doc::Line(_) if idx >= flat_until => return true,
+                Doc::Line(Line::Hard) => return true,
+                Doc::Line(Line::Space) => " ",
+                _ => "",
+            };
+            match text.split_once('\n') {
+                Some((first_line, _)) => return remaining >= first_line.chars().count() as isize,
+                None => remaining -= text.chars().count() as isize,
+            }
+            if remaining < 0 {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn write(&mut self, text: &str) {
+        self.write_indent();
+        self.push_str(text);
+    }
+
+    // 缩进等到这一行有内容时才输出，这样空行中不会留下空格
+    fn write_indent(&mut self) {
+        if self.at_line_start {
+            self.at_line_start = false;
+            let indent = " ".repeat(self.indent * self.layout.indent_width);
+            self.push_str(&indent);
+        }
+    }
+
+    fn newline(&mut self) {
+        self.flush_line_suffix();
+        self.push_str("\n");
+        self.at_line_start = true;
+    }
+
+    fn flush_line_suffix(&mut self) {
+        let mut after_line_comment = false;
+        let current_indent = self.indent;
+        for (text, indent) in std::mem::take(&mut self.line_suffix) {
+            // 单行注释之后的内容都会被当成注释，只能另起一行，按照注释所在的位置缩进
+            let text = if after_line_comment {
+                self.push_str("\n");
+                self.at_line_start = true;
+                self.indent = indent;
+                text.trim_start()
+            } else {
+                text
+            };
+            self.write_indent();
+            self.push_str(text);
+            after_line_comment = text.trim_start().starts_with("//");
+        }
+        self.indent = current_indent;
+    }
+
+    fn push_str(&mut self, text: &str) {
+        self.out.push_str(text);
+        match text.rsplit_once('\n') {
+            Some((_, last_line)) => self.col = last_line.chars().count(),
+            None => self.col += text.chars().count(),
+        }
+        if let Some(source_map) = &mut self.source_map {
+            source_map.advance(text);
+        }
+    }
+}
@@ -0,0 +1,1172 @@
+use std::mem;
+
+use super::{
+    format::{
+        end, has_comments_before, print_comma_list_item, print_dangling_comments,
+        print_inline_dangling_comments, print_leading_comments, print_list_item, print_string,
+        start,
+    },
+    source_map::{expression_loc, pattern_loc},
+    statement::{
+        print_argument, print_class, print_function, print_function_body, print_function_params,
+    },
+    BinaryLayout, Codegen,
+};
+use crate::ast::{
+    expression::{
+        ArrayExprEle, ArrowFunctionBody, ArrowFunctionExpression, AssignmentExpression,
+        AssignmentExpressionLeft, BinaryExpression, BinaryOpeartorLeft, BinaryOperator,
+        CallExprArgs, CallExprCallee, CallExpression, ChainExpressionElement, Expression,
+        Identifier, Literal, LiteralValue, LogicalExpression, LogicalOperator, MemberExprObject,
+        MemberExprProperty, MemberExpression, NewExprArgs, ObjectExpression, ObjectProperty,
+        Property, PropertyKind, SpreadElement, TemplateLiteral, UnaryOperator,
+    },
+    pattern::{ObjectPattern, ObjectPatternProperty, Pattern},
+    visit::{walk_binary_expression, Visit},
+    Position, SourceLocation,
+};
+
+// 运算符的优先级，从低到高排列，打印时优先级低于上下文要求的表达式需要加上括号
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precedence {
+    Lowest,
+    Comma,
+    Assign,
+    Conditional,
+    NullishCoalescing,
+    LogicalOr,
+    LogicalAnd,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    Equality,
+    Relational,
+    Shift,
+    Additive,
+    Multiplicative,
+    Exponentiation,
+    Prefix,
+    Postfix,
+    Call,
+    Member,
+    Primary,
+}
+
+impl Precedence {
+    fn next(self) -> Self {
+        match self {
+            Self::Lowest => Self::Comma,
+            Self::Comma => Self::Assign,
+            Self::Assign => Self::Conditional,
+            Self::Conditional => Self::NullishCoalescing,
+            Self::NullishCoalescing => Self::LogicalOr,
+            Self::LogicalOr => Self::LogicalAnd,
+            Self::LogicalAnd => Self::BitwiseOr,
+            Self::BitwiseOr => Self::BitwiseXor,
+            Self::BitwiseXor => Self::BitwiseAnd,
+            Self::BitwiseAnd => Self::Equality,
+            Self::Equality => Self::Relational,
+            Self::Relational => Self::Shift,
+            Self::Shift => Self::Additive,
+            Self::Additive => Self::Multiplicative,
+            Self::Multiplicative => Self::Exponentiation,
+            Self::Exponentiation => Self::Prefix,
+            Self::Prefix => Self::Postfix,
+            Self::Postfix => Self::Call,
+            Self::Call => Self::Member,
+            Self::Member | Self::Primary => Self::Primary,
+        }
+    }
+}
+
+fn binary_precedence(operator: &BinaryOperator) -> Precedence {
+    match operator {
+        BinaryOperator::Equality
+        | BinaryOperator::InEquality
+        | BinaryOperator::StrictEquality
+        | BinaryOperator::StrictInEquality => Precedence::Equality,
+        BinaryOperator::Greater
+        | BinaryOperator::GreaterOrEqual
+        | BinaryOperator::Less
+        | BinaryOperator::LessOrEqual
+        | BinaryOperator::In
+        | BinaryOperator::InstanceOf => Precedence::Relational,
+        BinaryOperator::LeftShift
+        | BinaryOperator::RightShift
+        | BinaryOperator::UnsignedRightShift => Precedence::Shift,
+        BinaryOperator::Plus | BinaryOperator::Minus => Precedence::Additive,
+        BinaryOperator::Multipl | BinaryOperator::Division | BinaryOperator::Reminder => {
+            Precedence::Multiplicative
+        }
+        BinaryOperator::BitwiseOr => Precedence::BitwiseOr,
+        BinaryOperator::BitwiseXor => Precedence::BitwiseXor,
+        BinaryOperator::BitwiseAnd => Precedence::BitwiseAnd,
+        BinaryOperator::Exponentiation => Precedence::Exponentiation,
+    }
+}
+
+fn logical_precedence(operator: &LogicalOperator) -> Precedence {
+    match operator {
+        LogicalOperator::Or => Precedence::LogicalOr,
+        LogicalOperator::And => Precedence::LogicalAnd,
+        LogicalOperator::Nullish => Precedence::NullishCoalescing,
+    }
+}
+
+fn precedence(node: &Expression) -> Precedence {
+    match node {
+        Expression::SequenceExpression(_) => Precedence::Comma,
+        Expression::ArrowFunctionExpression(_)
+        | Expression::YieldExpression(_)
+        | Expression::AssignmentExpression(_) => Precedence::Assign,
+        Expression::ConditionalExpression(_) => Precedence::Conditional,
+        Expression::LogicalExpression(node) => logical_precedence(&node.operator),
+        Expression::BinaryExpression(node) => binary_precedence(&node.operator),
+        Expression::UnaryExpression(_) | Expression::AwaitExpression(_) => Precedence::Prefix,
+        Expression::UpdateExpression(node) if node.prefix => Precedence::Prefix,
+        Expression::UpdateExpression(_) => Precedence::Postfix,
+        Expression::CallExpression(_) | Expression::ChainExpression(_) => Precedence::Call,
+        // new 表达式总是带着参数列表打印，因此和成员表达式的优先级相同
+        Expression::MemberExpression(_)
+        | Expression::NewExpression(_)
+        | Expression::TaggedTemplateExpression(_) => Precedence::Member,
+        Expression::Literal(Literal {
+            value: LiteralValue::Number(value),
+            raw: "",
+            ..
+        }) if value.is_sign_negative() => Precedence::Prefix,
+        _ => Precedence::Primary,
+    }
+}
+
+pub fn print_expression(ctx: &mut Codegen, node: &Expression, level: Precedence) {
+    let binary_layout = mem::take(&mut ctx.binary_layout);
+    print_leading_comments(ctx, expression_loc(node));
+    ctx.add_mapping(expression_loc(node));
+    ctx.wrap(precedence(node) < level, |ctx| match node {
+        Expression::Identifier(node) => print_identifier(ctx, node),
+        Expression::Literal(node) => print_literal(ctx, node),
+        Expression::ThisExpression(_) => ctx.push("this"),
+        Expression::ArrayExpression(node) => {
+            // 末尾的空位需要多一个逗号，否则会被当成尾随逗号
+            let trailing_comma = match node.elements.last() {
+                Some(ArrayExprEle::Null) => TrailingComma::Always,
+                _ => TrailingComma::IfBreak,
+            };
+            print_list(
+                ctx,
+                ["[", "]"],
+                &node.elements,
+                ListLayout {
+                    hug_last: false,
+                    trailing_comma,
+                    end: end(node.loc.as_ref()),
+                },
+                |element| match element {
+                    ArrayExprEle::Expression(element) => expression_loc(element),
+                    ArrayExprEle::SpreadElement(element) => element.loc.as_ref(),
+                    ArrayExprEle::Null => None,
+                },
+                |ctx, element| match element {
+                    ArrayExprEle::Expression(element) => {
+                        print_expression(ctx, element, Precedence::Assign)
+                    }
+                    ArrayExprEle::SpreadElement(element) => print_spread_element(ctx, element),
+                    ArrayExprEle::Null => {}
+                },
+            );
+        }
+        Expression::ObjectExpression(node) => {
+            let at_start = ctx.pos() == ctx.stmt_start || ctx.pos() == ctx.arrow_body_start;
+            ctx.wrap(at_start, |ctx| print_object(ctx, node));
+        }
+        Expression::FunctionExpression(node) => {
+            let at_start = ctx.pos() == ctx.stmt_start || ctx.pos() == ctx.export_default_start;
+            ctx.wrap(at_start, |ctx| {
+                print_function(
+                    ctx,
+                    node.is_async,
+                    node.generator,
+                    node.id.as_ref(),
+                    &node.params,
+                    &node.body,
+                )
+            });
+        }
+        Expression::UnaryExpression(node) => {
+            ctx.push(node.operator.as_str());
+            if matches!(
+                node.operator,
+                UnaryOperator::Typeof | UnaryOperator::Void | UnaryOperator::Delete
+            ) {
+                ctx.push(" ");
+            }
+            print_expression(ctx, &node.argument, Precedence::Prefix);
+        }
+        Expression::UpdateExpression(node) => {
+            if node.prefix {
+                ctx.push(node.operator.as_str());
+                print_expression(ctx, &node.argument, Precedence::Postfix);
+            } else {
+                print_expression(ctx, &node.argument, Precedence::Postfix);
+                ctx.push(node.operator.as_str());
+            }
+        }
+        Expression::BinaryExpression(node) => {
+            print_binary_like(ctx, binary_layout, |ctx, indent| {
+                print_binary(ctx, node, indent)
+            })
+        }
+        Expression::AssignmentExpression(node) => print_assignment(ctx, node),
+        Expression::LogicalExpression(node) => {
+            print_binary_like(ctx, binary_layout, |ctx, indent| {
+                print_logical(ctx, node, indent)
+            })
+        }
+        Expression::MemberExpression(node) => {
+            if !print_member_chain(ctx, ChainLink::Member(node)) {
+                print_member(ctx, node);
+            }
+        }
+        Expression::ConditionalExpression(node) => ctx.group(false, |ctx| {
+            print_expression(ctx, &node.test, Precedence::Conditional.next());
+            ctx.indent(|ctx| {
+                ctx.line();
+                // 分支中换行的部分和 `?`、`:` 之后的内容对齐
+                ctx.push("? ");
+                ctx.indent(|ctx| print_expression(ctx, &node.consequent, Precedence::Assign));
+                ctx.line();
+                ctx.push(": ");
+                ctx.indent(|ctx| print_expression(ctx, &node.alternate, Precedence::Assign));
+            });
+        }),
+        Expression::CallExpression(node) => {
+            if !print_member_chain(ctx, ChainLink::Call(node)) {
+                print_call(ctx, node);
+            }
+        }
+        Expression::NewExpression(node) => {
+            ctx.push("new ");
+            // callee 中出现的调用会被当成 new 的参数列表，比如 `new (a())()`
+            let wrap = precedence(&node.callee) < Precedence::Member || has_call(&node.callee);
+            ctx.wrap(wrap, |ctx| {
+                print_expression(ctx, &node.callee, Precedence::Lowest)
+            });
+            let arguments: Vec<_> = node
+                .arguments
+                .iter()
+                .map(|argument| match argument {
+                    NewExprArgs::Expression(argument) => Ok(argument),
+                    NewExprArgs::SpreadElement(argument) => Err(argument),
+                })
+                .collect();
+            print_arguments(ctx, &arguments, end(node.loc.as_ref()));
+        }
+        Expression::SequenceExpression(node) => {
+            for (idx, expression) in node.expressions.iter().enumerate() {
+                if idx > 0 {
+                    ctx.push(", ");
+                }
+                print_expression(ctx, expression, Precedence::Assign);
+            }
+        }
+        Expression::ParenthesizedExpression(node) => {
+            ctx.wrap(true, |ctx| {
+                print_expression(ctx, &node.expression, Precedence::Lowest)
+            });
+        }
+        Expression::ArrowFunctionExpression(node) => print_arrow_function(ctx, node),
+        Expression::YieldExpression(node) => {
+            ctx.push("yield");
+            if node.delegate {
+                ctx.push("*");
+            }
+            if let Some(argument) = &node.argument {
+                ctx.push(" ");
+                print_argument(ctx, argument, Precedence::Assign);
+            }
+        }
+        Expression::TemplateLiteral(node) => print_template_literal(ctx, node),
+        Expression::TaggedTemplateExpression(node) => {
+            print_callee(ctx, &node.tag);
+            print_template_literal(ctx, &node.quasi);
+        }
+        Expression::ClassExpression(node) => {
+            let at_start = ctx.pos() == ctx.stmt_start || ctx.pos() == ctx.export_default_start;
+            ctx.wrap(at_start, |ctx| {
+                print_class(
+                    ctx,
+                    node.id.as_ref(),
+                    node.super_class.as_deref(),
+                    &node.body,
+                )
+            });
+        }
+        Expression::MetaProperty(node) => {
+            print_identifier(ctx, &node.meta);
+            ctx.push(".");
+            print_identifier(ctx, &node.property);
+        }
+        Expression::AwaitExpression(node) => {
+            ctx.push("await ");
+            print_expression(ctx, &node.argument, Precedence::Prefix);
+        }
+        Expression::ChainExpression(node) => match &node.expression {
+            ChainExpressionElement::CallExpression(node) => {
+                if !print_member_chain(ctx, ChainLink::Call(node)) {
+                    print_call(ctx, node);
+                }
+            }
+            ChainExpressionElement::MemberExpression(node) => {
+                if !print_member_chain(ctx, ChainLink::Member(node)) {
+                    print_member(ctx, node);
+                }
+            }
+        },
+        Expression::ImportExpression(node) => {
+            ctx.push("import(");
+            print_expression(ctx, &node.source, Precedence::Assign);
+            ctx.push(")");
+        }
+        Expression::StaticBlock(node) => {
+            ctx.push("static ");
+            super::statement::print_block(ctx, &node.body, node.loc.as_ref());
+        }
+    });
+}
+
+pub fn print_identifier(ctx: &mut Codegen, node: &Identifier) {
+    // `let [` 开头的语句会被当成声明，`for (async of` 也是不允许的
+    let wrap = match node.name.as_ref() {
+        "let" => ctx.pos() == ctx.stmt_start || ctx.pos() == ctx.for_init_start,
+        "async" => ctx.pos() == ctx.for_init_start,
+        _ => false,
+    };
+    ctx.wrap(wrap, |ctx| print_name(ctx, node));
+}
+
+// 不需要考虑语句开头歧义的标识符，比如函数名、属性名和标签
+pub fn print_name(ctx: &mut Codegen, node: &Identifier) {
+    ctx.add_name_mapping(node);
+    ctx.push(&node.name);
+}
+
+pub fn print_literal(ctx: &mut Codegen, node: &Literal) {
+    // 尽量保留源码中的写法，没有原始文本的节点通常是代码转换时新建的
+    let is_string = matches!(node.value, LiteralValue::String(_));
+    if !node.raw.is_empty() {
+        match is_string && ctx.is_formatting() {
+            true => print_string(ctx, node.raw, false),
+            false => ctx.push(node.raw),
+        }
+        return;
+    }
+    match &node.value {
+        LiteralValue::String(value) => print_quoted(ctx, value),
+        LiteralValue::Boolean(value) => ctx.push(if *value { "true" } else { "false" }),
+        LiteralValue::Null => ctx.push("null"),
+        LiteralValue::Number(value) => {
+            if value.is_nan() {
+                ctx.push("NaN");
+            } else if value.is_infinite() {
+                ctx.push(if *value > 0.0 {
+                    "Infinity"
+                } else {
+                    "-Infinity"
+                });
+            } else {
+                ctx.push(&value.to_string());
+            }
+        }
+        LiteralValue::Regx => {
+            if let Some(reg) = &node.reg {
+                ctx.push("/");
+                ctx.push(reg.pattern);
+                ctx.push("/");
+                ctx.push(reg.flags);
+            }
+        }
+        LiteralValue::BigInt => {
+            if let Some(bigint) = &node.bigint {
+                ctx.push(bigint);
+                ctx.push("n");
+            }
+        }
+    }
+}
+
+fn print_quoted(ctx: &mut Codegen, value: &str) {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\u{2028}' => quoted.push_str("\\u2028"),
+            '\u{2029}' => quoted.push_str("\\u2029"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    match ctx.is_formatting() {
+        true => print_string(ctx, &quoted, false),
+        false => ctx.push(&quoted),
+    }
+}
+
+fn print_spread_element(ctx: &mut Codegen, node: &SpreadElement) {
+    ctx.push("...");
+    print_expression(ctx, &node.argument, Precedence::Assign);
+}
+
+pub fn print_property_key(ctx: &mut Codegen, key: &Expression, computed: bool) {
+    if computed {
+        ctx.push("[");
+        print_expression(ctx, key, Precedence::Assign);
+        ctx.push("]");
+    } else {
+        match key {
+            // 属性名的位置不会有语句开头的歧义
+            Expression::Identifier(key) => print_name(ctx, key),
+            key => print_expression(ctx, key, Precedence::Assign),
+        }
+    }
+}
+
+fn print_property(ctx: &mut Codegen, node: &Property) {
+    if let Expression::FunctionExpression(value) = &node.value {
+        let prefix = match node.kind {
+            PropertyKind::Get => "get ",
+            PropertyKind::Set => "set ",
+            PropertyKind::Init => "",
+        };
+        if node.method || !prefix.is_empty() {
+            ctx.push(prefix);
+            if value.is_async {
+                ctx.push("async ");
+            }
+            if value.generator {
+                ctx.push("*");
+            }
+            print_property_key(ctx, &node.key, node.computed);
+            print_function_params(ctx, &value.params, start(value.body.loc.as_ref()));
+            ctx.push(" ");
+            print_function_body(ctx, &value.body);
+            return;
+        }
+    }
+    if node.shorthand && is_same_identifier(&node.key, &node.value) {
+        print_property_key(ctx, &node.key, false);
+        return;
+    }
+    print_property_key(ctx, &node.key, node.computed);
+    ctx.push(": ");
+    print_expression(ctx, &node.value, Precedence::Assign);
+}
+
+fn is_same_identifier(key: &Expression, value: &Expression) -> bool {
+    matches!((key, value), (Expression::Identifier(key), Expression::Identifier(value)) if key.name == value.name)
+}
+
+pub fn is_binary_like(node: &Expression) -> bool {
+    matches!(
+        node,
+        Expression::BinaryExpression(_) | Expression::LogicalExpression(_)
+    )
+}
+
+// 二元表达式和逻辑表达式放不下时在运算符之后换行，优先级相同的一串运算一起换行
+fn print_binary_like<'s>(
+    ctx: &mut Codegen<'s>,
+    layout: BinaryLayout,
+    print: impl FnOnce(&mut Codegen<'s>, bool),
+) {
+    if layout.group {
+        ctx.group(false, |ctx| print(ctx, layout.indent));
+    } else {
+        print(ctx, layout.indent);
+    }
+}
+
+// 左边的操作数是同样优先级的运算时，和当前的运算一起换行
+fn chain_left(ctx: &mut Codegen, left: &Expression, level: Precedence, indent: bool) {
+    if is_binary_like(left) && precedence(left) == level {
+        ctx.binary_layout = BinaryLayout {
+            group: false,
+            indent,
+        };
+    }
+}
+
+// 运算符和右边的操作数，换行时操作数缩进
+fn print_operator_right(
+    ctx: &mut Codegen,
+    operator: &str,
+    indent: bool,
+    print: impl FnOnce(&mut Codegen),
+) {
+    ctx.push(" ");
+    ctx.push(operator);
+    if indent {
+        ctx.indent(|ctx| {
+            ctx.line();
+            print(ctx);
+        });
+    } else {
+        ctx.line();
+        print(ctx);
+    }
+}
+
+fn print_binary(ctx: &mut Codegen, node: &BinaryExpression, indent: bool) {
+    let level = binary_precedence(&node.operator);
+    let is_exponent = matches!(node.operator, BinaryOperator::Exponentiation);
+    match &node.left {
+        BinaryOpeartorLeft::Expression(left) => {
+            // `**` 是右结合的，且左边不能是一元表达式，比如 `(-a) ** b`
+            let wrap = is_exponent
+                && matches!(
+                    &**left,
+                    Expression::UnaryExpression(_) | Expression::AwaitExpression(_)
+                );
+            let left_level = if is_exponent { level.next() } else { level };
+            if !is_exponent {
+                chain_left(ctx, left, level, indent);
+            }
+            ctx.wrap(wrap, |ctx| print_expression(ctx, left, left_level));
+        }
+        BinaryOpeartorLeft::PrivateIdentifier(left) => {
+            ctx.push("#");
+            ctx.push(&left.name);
+        }
+    }
+    let right_level = if is_exponent { level } else { level.next() };
+    print_operator_right(ctx, node.operator.as_str(), indent, |ctx| {
+        print_expression(ctx, &node.right, right_level)
+    });
+}
+
+fn print_logical(ctx: &mut Codegen, node: &LogicalExpression, indent: bool) {
+    let level = logical_precedence(&node.operator);
+    // `??` 不能和 `||`、`&&` 直接混用，需要用括号隔开
+    let is_mixed = |operand: &Expression| match operand {
+        Expression::LogicalExpression(operand) => {
+            matches!(node.operator, LogicalOperator::Nullish)
+                != matches!(operand.operator, LogicalOperator::Nullish)
+        }
+        _ => false,
+    };
+    if !is_mixed(&node.left) {
+        chain_left(ctx, &node.left, level, indent);
+    }
+    ctx.wrap(is_mixed(&node.left), |ctx| {
+        print_expression(ctx, &node.left, level)
+    });
+    print_operator_right(ctx, node.operator.as_str(), indent, |ctx| {
+        ctx.wrap(is_mixed(&node.right), |ctx| {
+            print_expression(ctx, &node.right, level.next())
+        });
+    });
+}
+
+fn print_assignment(ctx: &mut Codegen, node: &AssignmentExpression) {
+    // `({ a } = b)` 的对象解构出现在语句开头时，需要把整个赋值表达式括起来
+    let is_object = match &node.left {
+        AssignmentExpressionLeft::Pattern(left) => matches!(left, Pattern::ObjectPattern(_)),
+        AssignmentExpressionLeft::Expression(left) => {
+            matches!(&**left, Expression::ObjectExpression(_))
+        }
+    };
+    let wrap = is_object && (ctx.pos() == ctx.stmt_start || ctx.pos() == ctx.arrow_body_start);
+    ctx.wrap(wrap, |ctx| {
+        match &node.left {
+            AssignmentExpressionLeft::Pattern(left) => print_pattern(ctx, left),
+            AssignmentExpressionLeft::Expression(left) => {
+                print_expression(ctx, left, Precedence::Postfix)
+            }
+        }
+        ctx.push(" ");
+        ctx.push(node.operator.as_str());
+        print_assignment_right(ctx, &node.right);
+    });
+}
+
+// 赋值表达式和变量声明中 `=` 右边的部分，格式化时放不下的二元表达式以及条件是二元表达式的
+// 条件表达式整体换到下一行
+pub fn print_assignment_right(ctx: &mut Codegen, right: &Expression) {
+    let binary_like = is_binary_like(right);
+    let break_after_operator = binary_like
+        || matches!(right, Expression::ConditionalExpression(right) if is_binary_like(&right.test));
+    if !(ctx.is_formatting() && break_after_operator) {
+        ctx.push(" ");
+        print_expression(ctx, right, Precedence::Assign);
+        return;
+    }
+    ctx.group(false, |ctx| {
+        ctx.indent(|ctx| {
+            ctx.line();
+            if binary_like {
+                ctx.binary_layout = BinaryLayout {
+                    group: true,
+                    indent: false,
+                };
+            }
+            print_expression(ctx, right, Precedence::Assign);
+        });
+    });
+}
+
+// 成员表达式的对象、调用表达式的 callee 以及模板字符串的 tag
+fn print_callee(ctx: &mut Codegen, node: &Expression) {
+    // 可选链只能整体作为对象，`(a?.b).c` 的括号不能省略；`1.toString()` 中的 `.` 会被当成小数点
+    let wrap = matches!(node, Expression::ChainExpression(_))
+        || matches!(node, Expression::Literal(node) if is_integer_literal(node));
+    ctx.wrap(wrap, |ctx| print_expression(ctx, node, Precedence::Call));
+}
+
+fn is_integer_literal(node: &Literal) -> bool {
+    match node.value {
+        LiteralValue::Number(value) if node.raw.is_empty() => value.fract() == 0.0,
+        LiteralValue::Number(_) => node.raw.bytes().all(|c| c.is_ascii_digit() || c == b'_'),
+        _ => false,
+    }
+}
+
+fn has_call(node: &Expression) -> bool {
+    match node {
+        Expression::CallExpression(_) | Expression::ChainExpression(_) => true,
+        Expression::MemberExpression(node) => match &node.object {
+            MemberExprObject::Expression(object) => has_call(object),
+            MemberExprObject::Super(_) => false,
+        },
+        Expression::TaggedTemplateExpression(node) => has_call(&node.tag),
+        _ => false,
+    }
+}
+
+pub fn print_member(ctx: &mut Codegen, node: &MemberExpression) {
+    match &node.object {
+        MemberExprObject::Expression(object) => print_callee(ctx, object),
+        MemberExprObject::Super(_) => ctx.push("super"),
+    }
+    print_member_property(ctx, node);
+}
+
+fn print_member_property(ctx: &mut Codegen, node: &MemberExpression) {
+    if node.optional {
+        ctx.push("?.");
+    }
+    match &node.property {
+        MemberExprProperty::Expression(property) => match &**property {
+            Expression::Identifier(property) if !node.computed => {
+                if !node.optional {
+                    ctx.push(".");
+                }
+                print_name(ctx, property);
+            }
+            // 只有标识符才能用 `.` 访问，其余的属性都用 `[]` 打印
+            property => {
+                ctx.push("[");
+                print_expression(ctx, property, Precedence::Lowest);
+                ctx.push("]");
+            }
+        },
+        MemberExprProperty::PrivateIdentifier(property) => {
+            if !node.optional {
+                ctx.push(".");
+            }
+            ctx.push("#");
+            ctx.push(&property.name);
+        }
+    }
+}
+
+fn print_call(ctx: &mut Codegen, node: &CallExpression) {
+    match &node.callee {
+        CallExprCallee::Expression(callee) => print_callee(ctx, callee),
+        CallExprCallee::Super(_) => ctx.push("super"),
+    }
+    print_call_arguments(ctx, node);
+}
+
+fn print_call_arguments(ctx: &mut Codegen, node: &CallExpression) {
+    if node.optional {
+        ctx.push("?.");
+    }
+    let arguments: Vec<_> = node
+        .arguments
+        .iter()
+        .map(|argument| match argument {
+            CallExprArgs::Expression(argument) => Ok(argument),
+            CallExprArgs::SpreadElement(argument) => Err(argument),
+        })
+        .collect();
+    print_arguments(ctx, &arguments, end(node.loc.as_ref()));
+}
+
+// 调用和 new 表达式的参数列表，Err 表示展开的参数。call_end 是整个表达式结束的位置
+fn print_arguments(
+    ctx: &mut Codegen,
+    arguments: &[Result<&Expression, &SpreadElement>],
+    call_end: Option<&Position>,
+) {
+    let is_function = |argument: &Result<&Expression, &SpreadElement>| {
+        matches!(
+            argument,
+            Ok(Expression::FunctionExpression(_) | Expression::ArrowFunctionExpression(_))
+        )
+    };
+    // 最后一个参数是函数、对象或者数组，且其他参数中没有函数时，放不下时先只让最后一个参数换行，
+    // 比如 `it("works", () => {...})`。最后一个参数之前有注释时不这样做，注释可能需要单独成行
+    let hug_last = match arguments.split_last() {
+        Some((last, rest)) => {
+            let hug = match last {
+                Ok(Expression::ObjectExpression(last)) => !last.properties.is_empty(),
+                Ok(Expression::ArrayExpression(last)) => !last.elements.is_empty(),
+                // 箭头函数的表达式不能换行时，只会把参数列表拆开，比如 `(r) => r.target`
+                Ok(Expression::ArrowFunctionExpression(last)) => match &last.body {
+                    ArrowFunctionBody::FunctionBoby(_) => true,
+                    ArrowFunctionBody::Expression(body) => {
+                        is_binary_like(body)
+                            || matches!(
+                                &**body,
+                                Expression::CallExpression(_)
+                                    | Expression::NewExpression(_)
+                                    | Expression::ObjectExpression(_)
+                                    | Expression::ArrayExpression(_)
+                                    | Expression::ConditionalExpression(_)
+                                    | Expression::ArrowFunctionExpression(_)
+                            )
+                    }
+                },
+                last => is_function(last),
+            };
+            let last_start = match last {
+                Ok(last) => start(expression_loc(last)),
+                Err(last) => start(last.loc.as_ref()),
+            };
+            hug && !rest.iter().any(is_function) && !has_comments_before(ctx, last_start)
+        }
+        None => false,
+    };
+    print_list(
+        ctx,
+        ["(", ")"],
+        arguments,
+        ListLayout {
+            hug_last,
+            trailing_comma: TrailingComma::IfBreak,
+            end: call_end,
+        },
+        |argument| match argument {
+            Ok(argument) => expression_loc(argument),
+            Err(argument) => argument.loc.as_ref(),
+        },
+        |ctx, argument| match argument {
+            Ok(argument) => print_expression(ctx, argument, Precedence::Assign),
+            Err(argument) => print_spread_element(ctx, argument),
+        },
+    );
+}
+
+#[derive(Clone, Copy)]
+enum ChainLink<'b, 'a> {
+    Member(&'b MemberExpression<'a>),
+    Call(&'b CallExpression<'a>),
+}
+
+// 格式化时至少有三次调用的链式调用，放不下时在每次调用之后的 `.` 之前换行。不是这样的调用时返回 false
+fn print_member_chain(ctx: &mut Codegen, node: ChainLink) -> bool {
+    if !ctx.is_formatting() {
+        return false;
+    }
+    let mut links = vec![node];
+    let head = loop {
+        let next = match links.last().unwrap() {
+            ChainLink::Call(call) => match &call.callee {
+                CallExprCallee::Expression(callee) => callee,
+                CallExprCallee::Super(_) => return false,
+            },
+            ChainLink::Member(member) => match &member.object {
+                MemberExprObject::Expression(object) => object,
+                MemberExprObject::Super(_) => return false,
+            },
+        };
+        match &**next {
+            Expression::CallExpression(call) => links.push(ChainLink::Call(call)),
+            Expression::MemberExpression(member) => links.push(ChainLink::Member(member)),
+            head => break head,
+        }
+    };
+    links.reverse();
+    let calls = links
+        .iter()
+        .filter(|link| matches!(link, ChainLink::Call(_)))
+        .count();
+    if calls < 3 {
+        return false;
+    }
+    let is_dot = |link: &ChainLink| matches!(link, ChainLink::Member(member) if !member.computed);
+    // 和开头连在一起的部分：紧跟着的调用和 `[]`，以及 `a.b.c()` 中的 `.b`
+    let mut first = links.iter().take_while(|link| !is_dot(link)).count();
+    if first == 0 {
+        first = links
+            .windows(2)
+            .take_while(|pair| matches!(pair, [ChainLink::Member(_), ChainLink::Member(_)]))
+            .count();
+    }
+    // 开头是 `this`、首字母大写或者很短的标识符时，第一次调用也不换行，比如 `z.object().min().max()`
+    let short = ctx
+        .format
+        .as_ref()
+        .map_or(0, |format| format.options.indent_width);
+    let keep_first = first == 0
+        && match head {
+            Expression::ThisExpression(_) => true,
+            Expression::Identifier(head) => {
+                head.name.starts_with(|c: char| c.is_ascii_uppercase())
+                    || head.name.chars().count() <= short
+            }
+            _ => false,
+        };
+    ctx.group(false, |ctx| {
+        print_callee(ctx, head);
+        ctx.indent(|ctx| {
+            let mut after_call = false;
+            for (idx, link) in links.iter().enumerate() {
+                match link {
+                    ChainLink::Member(member) => {
+                        if is_dot(link) && (after_call || (idx == first && !keep_first)) {
+                            ctx.soft_line();
+                        }
+                        print_member_property(ctx, member);
+                        after_call = false;
+                    }
+                    ChainLink::Call(call) => {
+                        print_call_arguments(ctx, call);
+                        after_call = true;
+                    }
+                }
+            }
+        });
+    });
+    true
+}
+
+fn print_arrow_function(ctx: &mut Codegen, node: &ArrowFunctionExpression) {
+    if node.is_async {
+        ctx.push("async ");
+    }
+    let body_start = match &node.body {
+        ArrowFunctionBody::FunctionBoby(body) => start(body.loc.as_ref()),
+        ArrowFunctionBody::Expression(body) => start(expression_loc(body)),
+    };
+    print_function_params(ctx, &node.params, body_start);
+    match &node.body {
+        ArrowFunctionBody::FunctionBoby(body) => {
+            ctx.push(" => ");
+            print_function_body(ctx, body);
+        }
+        // 放不下的运算换到下一行，其余的表达式放在 `=>` 之后
+        ArrowFunctionBody::Expression(body)
+            if is_binary_like(body) || matches!(&**body, Expression::ConditionalExpression(_)) =>
+        {
+            ctx.push(" =>");
+            ctx.group(false, |ctx| {
+                ctx.indent(|ctx| {
+                    ctx.line();
+                    print_expression(ctx, body, Precedence::Assign);
+                });
+            });
+        }
+        ArrowFunctionBody::Expression(body) => {
+            ctx.push(" => ");
+            ctx.arrow_body_start = ctx.pos();
+            print_expression(ctx, body, Precedence::Assign);
+        }
+    }
+}
+
+fn print_template_literal(ctx: &mut Codegen, node: &TemplateLiteral) {
+    ctx.push("`");
+    for (idx, quasi) in node.quasis.iter().enumerate() {
+        ctx.push(&quasi.value.raw);
+        if let Some(expression) = node.expressions.get(idx) {
+            ctx.push("${");
+            print_expression(ctx, expression, Precedence::Lowest);
+            ctx.push("}");
+        }
+    }
+    ctx.push("`");
+}
+
+pub fn print_pattern(ctx: &mut Codegen, node: &Pattern) {
+    print_leading_comments(ctx, pattern_loc(node));
+    ctx.add_mapping(pattern_loc(node));
+    match node {
+        Pattern::Identifier(node) => print_identifier(ctx, node),
+        Pattern::MemberExpression(node) => print_member(ctx, node),
+        Pattern::RestElement(node) => {
+            ctx.push("...");
+            print_pattern(ctx, &node.argument);
+        }
+        Pattern::AssignmentPattern(node) => {
+            print_pattern(ctx, &node.left);
+            ctx.push(" = ");
+            print_expression(ctx, &node.right, Precedence::Assign);
+        }
+        Pattern::ArrayPattern(node) => {
+            let trailing_comma = match node.elements.last() {
+                Some(None) => TrailingComma::Always,
+                Some(Some(Pattern::RestElement(_))) => TrailingComma::Never,
+                _ => TrailingComma::IfBreak,
+            };
+            print_list(
+                ctx,
+                ["[", "]"],
+                &node.elements,
+                ListLayout {
+                    hug_last: false,
+                    trailing_comma,
+                    end: end(node.loc.as_ref()),
+                },
+                |element| element.as_ref().and_then(pattern_loc),
+                |ctx, element| {
+                    if let Some(element) = element {
+                        print_pattern(ctx, element);
+                    }
+                },
+            );
+        }
+        Pattern::ObjectPattern(node) => print_object_pattern(ctx, node),
+    }
+}
+
+fn print_object_pattern(ctx: &mut Codegen, node: &ObjectPattern) {
+    if node.properties.is_empty() && !has_comments_before(ctx, end(node.loc.as_ref())) {
+        ctx.push("{}");
+        return;
+    }
+    let last = node.properties.len().saturating_sub(1);
+    ctx.group(false, |ctx| {
+        ctx.push("{");
+        ctx.indent(|ctx| {
+            let mut prev_line = None;
+            for (idx, property) in node.properties.iter().enumerate() {
+                let item_end = match node.properties.get(idx + 1) {
+                    Some(next) => start(object_pattern_property_loc(next)),
+                    None => end(node.loc.as_ref()),
+                };
+                ctx.line();
+                print_list_item(ctx, object_pattern_property_loc(property), item_end, &mut prev_line, |ctx| {
+                    match property {
+                        ObjectPatternProperty::AssignmentProperty(property) => {
+                            // 简写时 value 就是 key 本身，或者是带默认值的 key
+                            let shorthand_value = match &property.value {
+                                Pattern::AssignmentPattern(value) => &*value.left,
+                                value => value,
+                            };
+                            let is_shorthand = property.shorthand
+                                && matches!(
+                                    (&property.key, shorthand_value),
+                                    (Expression::Identifier(key), Pattern::Identifier(value)) if key.name == value.name
+                                );
+                            if is_shorthand {
+                                print_pattern(ctx, &property.value);
+                            } else {
+                                print_property_key(ctx, &property.key, property.computed);
+                                ctx.push(": ");
+                                print_pattern(ctx, &property.value);
+                            }
+                        }
+                        ObjectPatternProperty::RestElement(property) => {
+                            ctx.push("...");
+                            print_pattern(ctx, &property.argument);
+                        }
+                    }
+                    // rest 元素之后不能有逗号
+                    if idx < last {
+                        ctx.push(",");
+                    } else if !matches!(property, ObjectPatternProperty::RestElement(_)) {
+                        ctx.if_break(",");
+                    }
+                });
+            }
+            print_object_end(ctx, node.loc.as_ref(), prev_line);
+        });
+        ctx.line();
+        ctx.push("}");
+    });
+}
+
+fn object_pattern_property_loc<'b, 'a>(
+    property: &'b ObjectPatternProperty<'a>,
+) -> Option<&'b SourceLocation<'a>> {
+    match property {
+        ObjectPatternProperty::AssignmentProperty(property) => property.loc.as_ref(),
+        ObjectPatternProperty::RestElement(property) => property.loc.as_ref(),
+    }
+}
+
+fn print_object(ctx: &mut Codegen, node: &ObjectExpression) {
+    let loc = node.loc.as_ref();
+    if node.properties.is_empty() && !has_comments_before(ctx, end(loc)) {
+        ctx.push("{}");
+        return;
+    }
+    // 格式化时和 Prettier 一样，第一个属性原来就换行的对象保持展开
+    let expand = !ctx.is_formatting()
+        || match (loc, node.properties.first().and_then(object_property_loc)) {
+            (Some(loc), Some(first)) => first.start.line > loc.start.line,
+            _ => false,
+        };
+    let last = node.properties.len().saturating_sub(1);
+    ctx.group(expand, |ctx| {
+        ctx.push("{");
+        ctx.indent(|ctx| {
+            let mut prev_line = None;
+            for (idx, property) in node.properties.iter().enumerate() {
+                let item_end = match node.properties.get(idx + 1) {
+                    Some(next) => start(object_property_loc(next)),
+                    None => end(loc),
+                };
+                ctx.line();
+                print_list_item(
+                    ctx,
+                    object_property_loc(property),
+                    item_end,
+                    &mut prev_line,
+                    |ctx| {
+                        match property {
+                            ObjectProperty::Property(property) => print_property(ctx, property),
+                            ObjectProperty::SpreadElement(property) => {
+                                print_spread_element(ctx, property)
+                            }
+                        }
+                        if idx < last {
+                            ctx.push(",");
+                        } else {
+                            ctx.if_break(",");
+                        }
+                    },
+                );
+            }
+            print_object_end(ctx, loc, prev_line);
+        });
+        ctx.line();
+        ctx.push("}");
+    });
+}
+
+fn object_property_loc<'b, 'a>(property: &'b ObjectProperty<'a>) -> Option<&'b SourceLocation<'a>> {
+    match property {
+        ObjectProperty::Property(property) => property.loc.as_ref(),
+        ObjectProperty::SpreadElement(property) => property.loc.as_ref(),
+    }
+}
+
+// 对象结束之前剩下的注释，注释之后一定换行
+fn print_object_end(ctx: &mut Codegen, loc: Option<&SourceLocation>, prev_line: Option<usize>) {
+    if has_comments_before(ctx, end(loc)) {
+        ctx.newline();
+        print_dangling_comments(ctx, end(loc), prev_line);
+    }
+}
+
+// 列表末尾的逗号
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TrailingComma {
+    Never,
+    // 换行时才加上
+    IfBreak,
+    // 数组末尾的空位
+    Always,
+}
+
+// print_list 的布局
+pub struct ListLayout<'b> {
+    // 放不下时先只让最后一项换行
+    pub hug_last: bool,
+    pub trailing_comma: TrailingComma,
+    // 列表结束的位置，在这之前剩下的注释放在列表的末尾
+    pub end: Option<&'b Position>,
+}
+
+// 用逗号分隔的列表，比如数组和参数，放不下时每项一行。item_loc 用于打印每一项前后的注释
+pub fn print_list<'s, 'b, T>(
+    ctx: &mut Codegen<'s>,
+    [open, close]: [&str; 2],
+    items: &'b [T],
+    layout: ListLayout,
+    item_loc: impl Fn(&'b T) -> Option<&'b SourceLocation<'b>>,
+    mut print: impl FnMut(&mut Codegen<'s>, &T),
+) {
+    ctx.group(false, |ctx| {
+        ctx.push(open);
+        if items.is_empty() && print_inline_dangling_comments(ctx, layout.end) {
+            ctx.push(close);
+            return;
+        }
+        ctx.indent(|ctx| {
+            let mut prev_line = None;
+            if items.is_empty() {
+                ctx.newline();
+            } else {
+                ctx.soft_line();
+            }
+            for (idx, item) in items.iter().enumerate() {
+                if idx > 0 {
+                    ctx.push(",");
+                    ctx.line();
+                }
+                if layout.hug_last && idx + 1 == items.len() {
+                    ctx.hug_last();
+                }
+                let next = items[idx + 1..]
+                    .iter()
+                    .find_map(&item_loc)
+                    .map(|loc| &loc.start);
+                print_comma_list_item(
+                    ctx,
+                    item_loc(item),
+                    next,
+                    layout.end,
+                    &mut prev_line,
+                    |ctx| print(ctx, item),
+                );
+            }
+            if !items.is_empty() {
+                match layout.trailing_comma {
+                    TrailingComma::Never => {}
+                    TrailingComma::IfBreak => ctx.if_break(","),
+                    TrailingComma::Always => ctx.push(","),
+                }
+                if has_comments_before(ctx, layout.end) {
+                    ctx.newline();
+                }
+            }
+            print_dangling_comments(ctx, layout.end, prev_line);
+        });
+        ctx.soft_line();
+        ctx.push(close);
+    });
+}
+
+// for 语句的初始化部分不能直接出现 `in` 运算符，否则会被当成 for-in 语句
+pub fn contains_in(node: &Expression) -> bool {
+    struct ContainsIn(bool);
+
+    impl<'a> Visit<'a> for ContainsIn {
+        fn visit_binary_expression(&mut self, node: &BinaryExpression<'a>) {
+            self.0 |= matches!(node.operator, BinaryOperator::In);
+            walk_binary_expression(self, node);
+        }
+    }
+
+    let mut visitor = ContainsIn(false);
+    visitor.visit_expression(node);
+    visitor.0
+}
@@ -0,0 +1,441 @@
+use std::mem;
+
+use super::{doc::Doc, Codegen};
+use crate::ast::{
+    comment::{Comment, CommentKind},
+    Position, Program, SourceLocation,
+};
+
+/// Options of `codegen::format`.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// The line width to fit the code in. Defaults to 80.
+    pub print_width: usize,
+    /// The number of spaces per indentation level. Defaults to 2.
+    pub indent_width: usize,
+    /// Quote strings with `'` instead of `"`, unless that needs more escapes.
+    pub single_quote: bool,
+    /// End statements with semicolons. Without them, a `;` is only printed
+    /// where the next line would otherwise continue the statement, such as
+    /// before a statement starting with `(`. Defaults to true.
+    pub semicolons: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            print_width: 80,
+            indent_width: 2,
+            single_quote: false,
+            semicolons: true,
+        }
+    }
+}
+
+pub(crate) struct Format<'s> {
+    pub options: FormatOptions,
+    comments: &'s [Comment<'s>],
+    // 下一个还没有打印的注释，注释按照位置排列，打印代码时依次穿插在节点之间
+    next_comment: usize,
+}
+
+impl<'s> Format<'s> {
+    pub fn new(program: &'s Program, options: FormatOptions) -> Self {
+        Self {
+            options,
+            comments: &program.comments,
+            // hashbang 也被记录为第一个注释，由 print_program 单独打印
+            next_comment: usize::from(program.hashbang.is_some()),
+        }
+    }
+}
+
+pub fn start<'b>(loc: Option<&'b SourceLocation>) -> Option<&'b Position> {
+    loc.map(|loc| &loc.start)
+}
+
+pub fn end<'b>(loc: Option<&'b SourceLocation>) -> Option<&'b Position> {
+    loc.map(|loc| &loc.end)
+}
+
+fn key(position: &Position) -> (usize, usize) {
+    (position.line, position.col)
+}
+
+// 下一个在 before 之前开始的注释，before 为 None 时不限制位置
+fn next_comment<'s>(ctx: &Codegen<'s>, before: Option<&Position>) -> Option<&'s Comment<'s>> {
+    let format = ctx.format.as_ref()?;
+    let comment = format.comments.get(format.next_comment)?;
+    match (before, &comment.loc) {
+        (None, _) => Some(comment),
+        (Some(before), Some(loc)) if key(&loc.start) < key(before) => Some(comment),
+        _ => None,
+    }
+}
+
+fn take_comment(ctx: &mut Codegen) {
+    if let Some(format) = &mut ctx.format {
+        format.next_comment += 1;
+    }
+}
+
+fn comment_text(comment: &Comment) -> String {
+    match comment.kind {
+        CommentKind::Line => format!("//{}", comment.value),
+        CommentKind::Block => format!("/*{}*/", comment.value),
+    }
+}
+
+fn comment_line(comment: &Comment, end: bool) -> usize {
+    comment.loc.as_ref().map_or(0, |loc| match end {
+        true => loc.end.line,
+        false => loc.start.line,
+    })
+}
+
+// 单独成行的注释，`/**` 开头且每行都以 `*` 开头的块注释按照当前的缩进重新对齐
+fn push_comment(ctx: &mut Codegen, comment: &Comment) {
+    let text = comment_text(comment);
+    let lines: Vec<_> = text.lines().collect();
+    let is_doc = comment.kind == CommentKind::Block
+        && lines.len() > 1
+        && lines[1..]
+            .iter()
+            .all(|line| line.trim_start().starts_with('*'));
+    if !is_doc {
+        ctx.docs.push(Doc::Comment(text));
+        return;
+    }
+    for (idx, line) in lines.iter().enumerate() {
+        if idx == 0 {
+            ctx.docs.push(Doc::Comment(line.trim_end().to_string()));
+        } else {
+            ctx.newline();
+            ctx.docs.push(Doc::Comment(format!(" {}", line.trim())));
+        }
+    }
+}
+
+// before 之前单独成行的注释，每个注释之后换行；before 为 None 时打印剩下的所有注释
+fn print_own_line_comments(ctx: &mut Codegen, before: Option<&Position>) {
+    while let Some(comment) = next_comment(ctx, before) {
+        take_comment(ctx);
+        let end_line = comment_line(comment, true);
+        // 接下来的注释或者节点开始的行
+        let next_line = match next_comment(ctx, before) {
+            Some(next) => Some(comment_line(next, false)),
+            None => before.map(|before| before.line),
+        };
+        if comment.kind == CommentKind::Block && next_line == Some(end_line) {
+            // `/* a */ b` 保持在同一行
+            ctx.docs
+                .push(Doc::Comment(format!("{} ", comment_text(comment))));
+            ctx.last_char = Some(' ');
+            continue;
+        }
+        push_comment(ctx, comment);
+        ctx.newline();
+        if next_line.is_some_and(|line| line > end_line + 1) {
+            ctx.newline();
+        }
+    }
+}
+
+// 保留和上一项之间的空行，连续的空行只保留一个
+fn print_blank_line(ctx: &mut Codegen, prev_line: Option<usize>, next: &Position) {
+    let Some(prev_line) = prev_line else {
+        return;
+    };
+    let line =
+        next_comment(ctx, Some(next)).map_or(next.line, |comment| comment_line(comment, false));
+    if line > prev_line + 1 {
+        ctx.newline();
+    }
+}
+
+// 语句、类成员、对象属性这样的列表项，同时打印它之前单独成行的注释和之后同一行的注释，
+// 并保留和上一项之间的空行。end 是下一项开始的位置，最后一项时是整个列表结束的位置，
+// 从这里开始的注释属于后面的节点，比如 `f(a) // b` 中的注释不属于参数 `a`
+pub fn print_list_item<'s, F: FnOnce(&mut Codegen<'s>)>(
+    ctx: &mut Codegen<'s>,
+    loc: Option<&SourceLocation>,
+    end: Option<&Position>,
+    prev_line: &mut Option<usize>,
+    print: F,
+) {
+    print_item(ctx, loc, end, false, prev_line, print);
+}
+
+// 逗号分隔的列表项，比如参数和数组元素。next 是下一项开始的位置，最后一项时为 None，
+// 注释的范围到 list_end 为止
+pub fn print_comma_list_item<'s, F: FnOnce(&mut Codegen<'s>)>(
+    ctx: &mut Codegen<'s>,
+    loc: Option<&SourceLocation>,
+    next: Option<&Position>,
+    list_end: Option<&Position>,
+    prev_line: &mut Option<usize>,
+    print: F,
+) {
+    let end = match (loc, next) {
+        (Some(loc), Some(next)) => next_item_comments_start(ctx, &loc.end, next).or(Some(next)),
+        _ => next.or(list_end),
+    };
+    print_item(ctx, loc, end, next.is_some(), prev_line, print);
+}
+
+fn print_item<'s, F: FnOnce(&mut Codegen<'s>)>(
+    ctx: &mut Codegen<'s>,
+    loc: Option<&SourceLocation>,
+    end: Option<&Position>,
+    before_comma: bool,
+    prev_line: &mut Option<usize>,
+    print: F,
+) {
+    let Some(loc) = loc.filter(|_| ctx.is_formatting()) else {
+        print(ctx);
+        return;
+    };
+    print_blank_line(ctx, *prev_line, &loc.start);
+    print_own_line_comments(ctx, Some(&loc.start));
+    print(ctx);
+    // 跨行的尾随注释之后的空行从注释结束的行开始计算
+    let end_line = print_trailing_comments(ctx, loc, end, before_comma);
+    *prev_line = Some(end_line);
+}
+
+// 节点之后同一行的注释，以及节点内部没有机会打印的注释，比如 `f(a /* b */)` 中的注释。
+// 返回节点或者最后一个注释结束的行
+fn print_trailing_comments(
+    ctx: &mut Codegen,
+    loc: &SourceLocation,
+    end: Option<&Position>,
+    before_comma: bool,
+) -> usize {
+    let mut end_line = loc.end.line;
+    while let Some(comment) = next_comment(ctx, end) {
+        let start = comment.loc.as_ref().map(|loc| &loc.start);
+        if !start.is_some_and(|start| key(start) < key(&loc.end) || start.line == loc.end.line) {
+            break;
+        }
+        take_comment(ctx);
+        end_line = end_line.max(comment_line(comment, true));
+        let text = comment_text(comment);
+        // 下一项在后面的行时，逗号之前的块注释也放在行尾，比如 `a, /* b */\n c`，
+        // 否则这一行放得下时会变成 `a /* b */, c`，再次格式化时注释就属于下一项了
+        let at_line_end = before_comma && end.is_some_and(|end| end.line > end_line);
+        if comment.kind == CommentKind::Line || text.contains('\n') || at_line_end {
+            ctx.docs.push(Doc::LineSuffix(format!(" {}", text)));
+        } else {
+            ctx.docs.push(Doc::Comment(format!(" {}", text)));
+        }
+    }
+    end_line
+}
+
+// if 语句中 consequent 和 alternate 之间的注释放在 else 之前，比如 `} // c\nelse {`，
+// 和 consequent 的结尾在同一行的注释跟在后面，其它的注释单独成行。返回 else 是否需要换行
+pub fn print_comments_before_else(
+    ctx: &mut Codegen,
+    consequent: Option<&SourceLocation>,
+    alternate: Option<&Position>,
+) -> bool {
+    let Some(loc) = consequent.filter(|_| ctx.is_formatting()) else {
+        return false;
+    };
+    let mut break_line = false;
+    while let Some(comment) = next_comment(ctx, alternate) {
+        take_comment(ctx);
+        let text = comment_text(comment);
+        if comment_line(comment, false) != loc.end.line {
+            ctx.newline();
+            push_comment(ctx, comment);
+            break_line = true;
+        } else if comment.kind == CommentKind::Line || text.contains('\n') {
+            ctx.docs.push(Doc::LineSuffix(format!(" {}", text)));
+            break_line = true;
+        } else {
+            ctx.docs.push(Doc::Comment(format!(" {}", text)));
+        }
+    }
+    break_line
+}
+
+// 逗号分隔的列表中，after 之后、和下一项 next 在同一行的块注释属于下一项，比如 `a, /* b */ c`，
+// 返回这些注释开始的位置。没有源码无法知道注释在逗号的哪一边，统一放在下一项之前
+fn next_item_comments_start<'s>(
+    ctx: &Codegen<'s>,
+    after: &Position,
+    next: &Position,
+) -> Option<&'s Position> {
+    let format = ctx.format.as_ref()?;
+    let mut start = None;
+    for comment in &format.comments[format.next_comment..] {
+        let loc = comment.loc.as_ref()?;
+        if key(&loc.start) >= key(next) {
+            break;
+        }
+        if key(&loc.start) < key(after) {
+            continue;
+        }
+        if comment.kind == CommentKind::Block
+            && loc.start.line == next.line
+            && loc.end.line == next.line
+        {
+            start.get_or_insert(&loc.start);
+        } else {
+            start = None;
+        }
+    }
+    start
+}
+
+// 表达式和模式之前的注释。单独成行的注释之后换行，所在的 group 也会随之换行；
+// 其它的块注释留在同一行，但是包含换行的块注释要推迟到行尾，否则 `return` 之后会插入分号
+pub fn print_leading_comments(ctx: &mut Codegen, loc: Option<&SourceLocation>) {
+    let Some(loc) = loc else {
+        return;
+    };
+    while let Some(comment) = next_comment(ctx, Some(&loc.start)) {
+        take_comment(ctx);
+        let text = comment_text(comment);
+        let next_line = next_comment(ctx, Some(&loc.start))
+            .map_or(loc.start.line, |next| comment_line(next, false));
+        if comment_line(comment, true) < next_line {
+            push_comment(ctx, comment);
+            ctx.newline();
+        } else if text.contains('\n') {
+            ctx.docs.push(Doc::LineSuffix(format!(" {}", text)));
+        } else {
+            ctx.docs.push(Doc::Comment(format!("{} ", text)));
+            ctx.last_char = Some(' ');
+        }
+    }
+}
+
+// end 之前是否还有没有打印的注释，比如代码块中剩下的注释
+pub fn has_comments_before(ctx: &Codegen, end: Option<&Position>) -> bool {
+    end.is_some_and(|end| next_comment(ctx, Some(end)).is_some())
+}
+
+// 空列表中只有不换行的块注释时，注释留在括号之间，比如 `function f(/* c */) {}`。
+// 返回是否已经打印了所有的注释
+pub fn print_inline_dangling_comments(ctx: &mut Codegen, end: Option<&Position>) -> bool {
+    let (Some(format), Some(end)) = (&ctx.format, end) else {
+        return true;
+    };
+    let comments: Vec<_> = format.comments[format.next_comment..]
+        .iter()
+        .take_while(|comment| {
+            comment
+                .loc
+                .as_ref()
+                .is_some_and(|loc| key(&loc.start) < key(end))
+        })
+        .collect();
+    if comments
+        .iter()
+        .any(|comment| comment.kind == CommentKind::Line || comment.value.contains('\n'))
+    {
+        return false;
+    }
+    for (idx, comment) in comments.into_iter().enumerate() {
+        take_comment(ctx);
+        if idx > 0 {
+            ctx.push(" ");
+        }
+        ctx.docs.push(Doc::Comment(comment_text(comment)));
+    }
+    true
+}
+
+// 代码块、类和对象等结束之前剩下的注释，调用前已经换行，最后一个注释之后不换行
+pub fn print_dangling_comments(
+    ctx: &mut Codegen,
+    end: Option<&Position>,
+    prev_line: Option<usize>,
+) {
+    let Some(end) = end else {
+        return;
+    };
+    let mut prev_line = prev_line;
+    let mut first = true;
+    while let Some(comment) = next_comment(ctx, Some(end)) {
+        take_comment(ctx);
+        if !mem::take(&mut first) {
+            ctx.newline();
+        }
+        if prev_line.is_some_and(|line| comment_line(comment, false) > line + 1) {
+            ctx.newline();
+        }
+        push_comment(ctx, comment);
+        prev_line = Some(comment_line(comment, true));
+    }
+}
+
+// 文件末尾剩下的注释
+pub fn print_remaining_comments(ctx: &mut Codegen, prev_line: Option<usize>) {
+    if let Some(comment) = next_comment(ctx, None) {
+        let start = Position {
+            line: comment_line(comment, false),
+            col: 0,
+        };
+        print_blank_line(ctx, prev_line, &start);
+        print_own_line_comments(ctx, None);
+    }
+}
+
+// 用统一的引号打印字符串字面量，内容中的引号需要更多转义时改用另一种引号。
+// directive 的内容中有引号或者转义时保持原样，因为改变写法可能会改变它的含义，比如 `'use strict'`
+pub fn print_string(ctx: &mut Codegen, raw: &str, is_directive: bool) {
+    let (preferred, alternate) = match &ctx.format {
+        Some(format) if format.options.single_quote => ('\'', '"'),
+        _ => ('"', '\''),
+    };
+    let content = &raw[1..raw.len() - 1];
+    if is_directive && content.contains(['"', '\'', '\\']) {
+        ctx.push(raw);
+        return;
+    }
+    let count = |quote: char| content.chars().filter(|&c| c == quote).count();
+    let quote = if count(preferred) > count(alternate) {
+        alternate
+    } else {
+        preferred
+    };
+    let mut quoted = String::with_capacity(raw.len());
+    quoted.push(quote);
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                // 去掉不需要的引号转义
+                Some(escaped @ ('"' | '\'')) if escaped != quote => quoted.push(escaped),
+                Some(escaped) => {
+                    quoted.push('\\');
+                    quoted.push(escaped);
+                }
+                None => quoted.push('\\'),
+            },
+            c if c == quote => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push(quote);
+    ctx.push(&quoted);
+}
+
+// 不打印分号时，以这些字符开头的语句会和上一行连在一起，比如 `a\n(b)` 会被当成调用，
+// 需要在语句开头加上分号。start 是语句开始打印时 docs 和 code 的长度
+pub fn protect_statement_start(ctx: &mut Codegen, start: (usize, usize)) {
+    if ctx.semicolons() {
+        return;
+    }
+    let (doc_idx, code_idx) = start;
+    if let Some('(' | '[' | '`' | '+' | '-' | '/') = ctx.code[code_idx..].chars().next() {
+        let range = ctx.push_code(";");
+        ctx.docs.insert(doc_idx, Doc::Text(range));
+    }
+}
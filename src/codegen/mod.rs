@@ -0,0 +1,268 @@
+use crate::ast::{expression::Identifier, Program, SourceLocation};
+
+mod doc;
+mod expression;
+mod format;
+mod source_map;
+mod statement;
+
+use doc::{Doc, Group, Layout, Line};
+use format::Format;
+pub use format::FormatOptions;
+use source_map::SourceMapBuilder;
+pub use source_map::{SourceMap, SourceMapOptions};
+
+/// Print a `Program` back to JavaScript source code.
+///
+/// Parentheses are only added where the grammar needs them, so parsing the
+/// output gives the same tree again, apart from node positions and comments,
+/// which are not printed.
+pub fn print(program: &Program) -> String {
+    let mut ctx = Codegen::new();
+    statement::print_program(&mut ctx, program);
+    ctx.render(None, &PLAIN)
+}
+
+/// Print a `Program` like `print`, together with a source map from the
+/// printed code back to the `loc` of the nodes, so `ParserOptions::locations`
+/// should be enabled. `source` is the code the `Program` was parsed from, and
+/// the `ParserOptions::source_file` it was parsed with becomes the `sources`
+/// entry of the map.
+///
+/// Nodes without a `loc`, or created with a line of 0, are left out of the
+/// mappings. An `Identifier` whose name differs from its original text is
+/// mapped with the original name in `names`.
+pub fn print_with_source_map(
+    program: &Program,
+    source: &str,
+    options: SourceMapOptions,
+) -> (String, SourceMap) {
+    let mut ctx = Codegen::new();
+    ctx.source_map = Some(SourceMapBuilder::new(source, options));
+    statement::print_program(&mut ctx, program);
+    let mut source_map = ctx.source_map.take().unwrap();
+    let code = ctx.render(Some(&mut source_map), &PLAIN);
+    (code, source_map.build())
+}
+
+/// Format a `Program` in a fixed style: lines are broken to fit in
+/// `FormatOptions::print_width`, strings use the same quotes and statements
+/// end with semicolons or not.
+///
+/// Unlike `print`, the comments of the `Program` are kept, and blank lines
+/// between statements are preserved, so `ParserOptions::locations` should be
+/// enabled. An object literal stays expanded if its first property was on a
+/// new line in the original code.
+pub fn format(program: &Program, options: &FormatOptions) -> String {
+    let mut ctx = Codegen::new();
+    ctx.format = Some(Format::new(program, options.clone()));
+    statement::print_program(&mut ctx, program);
+    ctx.render(
+        None,
+        &Layout {
+            width: Some(options.print_width),
+            indent_width: options.indent_width,
+        },
+    )
+}
+
+const PLAIN: Layout = Layout {
+    width: None,
+    indent_width: 2,
+};
+
+// 二元表达式和逻辑表达式的换行方式
+#[derive(Clone, Copy)]
+pub(crate) struct BinaryLayout {
+    // 是否有自己的 group，否则和外层一起换行，比如 `a + b + c` 中的 `a + b`
+    group: bool,
+    // 换行之后的操作数是否缩进
+    indent: bool,
+}
+
+impl Default for BinaryLayout {
+    fn default() -> Self {
+        Self {
+            group: true,
+            indent: true,
+        }
+    }
+}
+
+pub(crate) struct Codegen<'s> {
+    // 打印的所有文本拼接在一起，Doc::Text 指向其中的一段，换行和缩进由 Doc::Line 表示，不在其中
+    code: String,
+    docs: Vec<Doc>,
+    // 还没有结束的 group 在 docs 中的位置
+    open_groups: Vec<usize>,
+    // 最后输出的字符，换行和空格也算在内
+    last_char: Option<char>,
+    // 下面几个位置用于判断一个节点是否位于某些语法位置的开头，比如以 `{` 开头的表达式语句会被当成代码块，
+    // 因此打印对象字面量时需要检查当前是否处于语句开头，是的话要加上括号
+    stmt_start: usize,
+    export_default_start: usize,
+    arrow_body_start: usize,
+    for_init_start: usize,
+    // 是否处于 directive prologue 中，此时字符串的表达式语句会被当成 directive
+    in_directive_prologue: bool,
+    // 只对下一个打印的表达式生效
+    binary_layout: BinaryLayout,
+    source_map: Option<SourceMapBuilder<'s>>,
+    format: Option<Format<'s>>,
+}
+
+impl<'s> Codegen<'s> {
+    fn new() -> Self {
+        Self {
+            code: String::new(),
+            docs: vec![],
+            open_groups: vec![],
+            last_char: None,
+            stmt_start: usize::MAX,
+            export_default_start: usize::MAX,
+            arrow_body_start: usize::MAX,
+            for_init_start: usize::MAX,
+            in_directive_prologue: false,
+            binary_layout: BinaryLayout::default(),
+            source_map: None,
+            format: None,
+        }
+    }
+
+    fn render(&self, source_map: Option<&mut SourceMapBuilder<'s>>, layout: &Layout) -> String {
+        doc::render(&self.docs, &self.code, layout, source_map)
+    }
+
+    fn push(&mut self, text: &str) {
+        let Some(first) = text.chars().next() else {
+            return;
+        };
+        // 避免 `a - -b`、`+ ++a` 这样的两个运算符被合并成 `--`、`++`
+        if let Some(last) = self.last_char {
+            if (last == '+' || last == '-') && last == first {
+                // 空格放在下一个节点的 Mapping 之前
+                let idx = self.docs.len()
+                    - self
+                        .docs
+                        .iter()
+                        .rev()
+                        .take_while(|doc| matches!(doc, Doc::Mapping(_)))
+                        .count();
+                let range = self.push_code(" ");
+                self.docs.insert(idx, Doc::Text(range));
+            }
+        }
+        let range = self.push_code(text);
+        self.docs.push(Doc::Text(range));
+        self.last_char = text.chars().last();
+    }
+
+    fn push_code(&mut self, text: &str) -> std::ops::Range<usize> {
+        let start = self.code.len();
+        self.code.push_str(text);
+        start..self.code.len()
+    }
+
+    fn is_formatting(&self) -> bool {
+        self.format.is_some()
+    }
+
+    // 总是换行
+    fn newline(&mut self) {
+        self.push_line(Line::Hard);
+        self.last_char = Some('\n');
+    }
+
+    // 所在的 group 不换行时为一个空格
+    fn line(&mut self) {
+        self.push_line(Line::Space);
+        self.last_char = Some(' ');
+    }
+
+    // 所在的 group 不换行时为空
+    fn soft_line(&mut self) {
+        self.push_line(Line::Soft);
+    }
+
+    fn push_line(&mut self, line: Line) {
+        self.docs.push(Doc::Line(line));
+    }
+
+    fn if_break(&mut self, text: &'static str) {
+        if self.is_formatting() {
+            self.docs.push(Doc::IfBreak(text));
+        }
+    }
+
+    fn group<F: FnOnce(&mut Self)>(&mut self, expand: bool, print: F) {
+        self.open_groups.push(self.docs.len());
+        self.docs.push(Doc::GroupStart(Group {
+            expand,
+            hug_from: None,
+        }));
+        print(self);
+        self.open_groups.pop();
+        self.docs.push(Doc::GroupEnd);
+    }
+
+    // 当前 group 放不下时，先尝试只让接下来打印的最后一个子节点换行
+    fn hug_last(&mut self) {
+        let hug_from = self.docs.len();
+        if let Some(&group) = self.open_groups.last() {
+            if let Doc::GroupStart(group) = &mut self.docs[group] {
+                group.hug_from = Some(hug_from);
+            }
+        }
+    }
+
+    fn indent<F: FnOnce(&mut Self)>(&mut self, print: F) {
+        self.docs.push(Doc::IndentStart);
+        print(self);
+        self.docs.push(Doc::IndentEnd);
+    }
+
+    // 是否在语句末尾打印分号
+    fn semicolons(&self) -> bool {
+        self.format
+            .as_ref()
+            .is_none_or(|format| format.options.semicolons)
+    }
+
+    fn semicolon(&mut self) {
+        if self.semicolons() {
+            self.push(";");
+        }
+    }
+
+    // 记录接下来输出的内容对应的原始位置
+    fn add_mapping(&mut self, loc: Option<&SourceLocation>) {
+        if let (Some(source_map), Some(loc)) = (&mut self.source_map, loc) {
+            if let Some(mapping) = source_map.mapping(loc) {
+                self.docs.push(Doc::Mapping(mapping));
+            }
+        }
+    }
+
+    fn add_name_mapping(&mut self, node: &Identifier) {
+        if let (Some(source_map), Some(loc)) = (&mut self.source_map, &node.loc) {
+            if let Some(mapping) = source_map.name_mapping(loc, node) {
+                self.docs.push(Doc::Mapping(mapping));
+            }
+        }
+    }
+
+    // 当前输出的位置
+    fn pos(&self) -> usize {
+        self.code.len()
+    }
+
+    fn wrap<F: FnOnce(&mut Self)>(&mut self, wrap: bool, print: F) {
+        if wrap {
+            self.push("(");
+        }
+        print(self);
+        if wrap {
+            self.push(")");
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::ast::{
+    expression::{Expression, Identifier},
+    import_export_declaration::ImportOrExportDeclaration,
+    pattern::Pattern,
+    statement::Statement,
+    Position, SourceLocation,
+};
+use crate::line_index::LineIndex;
+use crate::options::PositionEncoding;
+
+/// A [Source Map v3](https://tc39.es/ecma426/) which maps the code printed by
+/// `print_with_source_map` back to the original source.
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceMap {
+    pub version: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    pub sources: Vec<String>,
+    #[serde(
+        rename(serialize = "sourcesContent"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sources_content: Option<Vec<String>>,
+    pub names: Vec<String>,
+    pub mappings: String,
+}
+
+impl SourceMap {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+/// Options of `print_with_source_map`.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMapOptions {
+    /// The name of the printed file, recorded as `file`.
+    pub file: Option<String>,
+    /// The `ParserOptions::position_encoding` the `Program` was parsed with.
+    /// Original columns are converted to UTF-16 code units from it.
+    pub position_encoding: PositionEncoding,
+    /// Embed the original source as `sourcesContent`.
+    pub sources_content: bool,
+}
+
+// 节点开始处对应的原始位置，打印到这个位置时才成为 Mapping
+#[derive(Clone)]
+pub(crate) struct PendingMapping {
+    original_line: usize,
+    original_col: usize,
+    name: Option<usize>,
+}
+
+struct Mapping {
+    generated_line: usize,
+    generated_col: usize,
+    original_line: usize,
+    original_col: usize,
+    name: Option<usize>,
+}
+
+pub(crate) struct SourceMapBuilder<'s> {
+    source: &'s str,
+    options: SourceMapOptions,
+    lines: LineIndex,
+    // 源码中每个非 ASCII 字符结束处的位置，依次是解析时使用的单位、UTF-8 和 UTF-16 下的位置，
+    // 两个非 ASCII 字符之间的 ASCII 字符在三种单位下长度相同
+    wide_chars: Vec<(usize, usize, usize)>,
+    // 输出代码的当前行和 UTF-16 列
+    line: usize,
+    col: usize,
+    pending: Option<PendingMapping>,
+    mappings: Vec<Mapping>,
+    names: Vec<String>,
+    name_indexes: HashMap<String, usize>,
+    // 第一个带有 source 的节点的 loc.source
+    source_file: Option<String>,
+}
+
+impl<'s> SourceMapBuilder<'s> {
+    pub fn new(source: &'s str, options: SourceMapOptions) -> Self {
+        let mut wide_chars = vec![];
+        let (mut units, mut utf8, mut utf16) = (0, 0, 0);
+        for c in source.chars() {
+            units += match options.position_encoding {
+                PositionEncoding::Utf8 => c.len_utf8(),
+                PositionEncoding::Utf16 => c.len_utf16(),
+                PositionEncoding::Char => 1,
+            };
+            utf8 += c.len_utf8();
+            utf16 += c.len_utf16();
+            if !c.is_ascii() {
+                wide_chars.push((units, utf8, utf16));
+            }
+        }
+        Self {
+            source,
+            lines: LineIndex::new(source, options.position_encoding),
+            options,
+            wide_chars,
+            line: 0,
+            col: 0,
+            pending: None,
+            mappings: vec![],
+            names: vec![],
+            name_indexes: HashMap::new(),
+            source_file: None,
+        }
+    }
+
+    // 解析时单位下的位置对应的 UTF-8 和 UTF-16 位置
+    fn convert(&self, offset: usize) -> (usize, usize) {
+        let idx = self
+            .wide_chars
+            .partition_point(|&(end, _, _)| end <= offset);
+        let (units, utf8, utf16) = match idx.checked_sub(1) {
+            Some(idx) => self.wide_chars[idx],
+            None => (0, 0, 0),
+        };
+        (utf8 + offset - units, utf16 + offset - units)
+    }
+
+    // 原始位置在源码中的 UTF-8 位置和 UTF-16 列，合成的节点（行号为 0）或者超出源码范围的位置返回 None
+    fn locate(&self, position: &Position) -> Option<(usize, usize)> {
+        let offset = self.lines.offset(position)?;
+        let (utf8, utf16) = self.convert(offset);
+        let (_, line_start) = self.convert(offset - position.col);
+        Some((utf8, utf16 - line_start))
+    }
+
+    pub fn mapping(&mut self, loc: &SourceLocation) -> Option<PendingMapping> {
+        let (_, col) = self.locate(&loc.start)?;
+        Some(self.pending_mapping(loc, col, None))
+    }
+
+    // 标识符改名之后，在 names 中记录它原来的名字
+    pub fn name_mapping(
+        &mut self,
+        loc: &SourceLocation,
+        node: &Identifier,
+    ) -> Option<PendingMapping> {
+        let (start, col) = self.locate(&loc.start)?;
+        let name = self
+            .locate(&loc.end)
+            .and_then(|(end, _)| self.source.get(start..end))
+            // 原来的名字中包含转义时不认为被改过名
+            .filter(|original| *original != node.name && !original.contains('\\'))
+            .map(|original| match self.name_indexes.get(original) {
+                Some(&idx) => idx,
+                None => {
+                    self.names.push(original.to_string());
+                    self.name_indexes
+                        .insert(original.to_string(), self.names.len() - 1);
+                    self.names.len() - 1
+                }
+            });
+        Some(self.pending_mapping(loc, col, name))
+    }
+
+    fn pending_mapping(
+        &mut self,
+        loc: &SourceLocation,
+        col: usize,
+        name: Option<usize>,
+    ) -> PendingMapping {
+        if self.source_file.is_none() {
+            self.source_file = loc.source.map(str::to_string);
+        }
+        PendingMapping {
+            original_line: loc.start.line - 1,
+            original_col: col,
+            name,
+        }
+    }
+
+    // 映射等到下一次输出文本时才记录，这样可以跳过中间插入的空格和缩进，同一位置上后记录的（更内层的）节点优先
+    pub fn mark(&mut self, mapping: &PendingMapping) {
+        self.pending = Some(mapping.clone());
+    }
+
+    // 在即将输出的位置上记录等待中的映射
+    pub fn flush(&mut self) {
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        let mapping = Mapping {
+            generated_line: self.line,
+            generated_col: self.col,
+            original_line: pending.original_line,
+            original_col: pending.original_col,
+            name: pending.name,
+        };
+        match self.mappings.last_mut() {
+            Some(last) if last.generated_line == self.line && last.generated_col == self.col => {
+                *last = mapping
+            }
+            _ => self.mappings.push(mapping),
+        }
+    }
+
+    pub fn advance(&mut self, text: &str) {
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' | '\r' | '\u{2028}' | '\u{2029}' => {
+                    self.line += 1;
+                    self.col = 0;
+                }
+                c => self.col += c.len_utf16(),
+            }
+        }
+    }
+
+    pub fn build(self) -> SourceMap {
+        let mut mappings = String::new();
+        let (mut line, mut col) = (0, 0);
+        let (mut original_line, mut original_col, mut name) = (0, 0, 0);
+        for (idx, mapping) in self.mappings.iter().enumerate() {
+            if mapping.generated_line > line {
+                for _ in line..mapping.generated_line {
+                    mappings.push(';');
+                }
+                line = mapping.generated_line;
+                col = 0;
+            } else if idx > 0 {
+                mappings.push(',');
+            }
+            encode_vlq(&mut mappings, mapping.generated_col, col);
+            // 只有一个源文件，索引总是 0
+            encode_vlq(&mut mappings, 0, 0);
+            encode_vlq(&mut mappings, mapping.original_line, original_line);
+            encode_vlq(&mut mappings, mapping.original_col, original_col);
+            if let Some(mapping_name) = mapping.name {
+                encode_vlq(&mut mappings, mapping_name, name);
+                name = mapping_name;
+            }
+            col = mapping.generated_col;
+            original_line = mapping.original_line;
+            original_col = mapping.original_col;
+        }
+        SourceMap {
+            version: 3,
+            file: self.options.file,
+            sources: vec![self.source_file.unwrap_or_default()],
+            sources_content: self
+                .options
+                .sources_content
+                .then(|| vec![self.source.to_string()]),
+            names: self.names,
+            mappings,
+        }
+    }
+}
+
+const BASE64_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// 以 base64 VLQ 编码 value 相对 prev 的差值，最低位是符号位，每 5 位一组从低到高输出，第 6 位表示后面还有
+fn encode_vlq(out: &mut String, value: usize, prev: usize) {
+    let mut vlq = if value >= prev {
+        (value - prev) << 1
+    } else {
+        ((prev - value) << 1) | 1
+    };
+    loop {
+        let mut digit = vlq & 0b11111;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64_CHARS[digit] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+pub(crate) fn statement_loc<'b, 'a>(node: &'b Statement<'a>) -> Option<&'b SourceLocation<'a>> {
+    match node {
+        Statement::ExpressionStatement(node) => node.loc.as_ref(),
+        Statement::BlockStatement(node) => node.loc.as_ref(),
+        Statement::EmptyStatement(node) => node.loc.as_ref(),
+        Statement::DebuggerStatement(node) => node.loc.as_ref(),
+        Statement::WithStatement(node) => node.loc.as_ref(),
+        Statement::ReturnStatement(node) => node.loc.as_ref(),
+        Statement::LabeledStatement(node) => node.loc.as_ref(),
+        Statement::BreakStatement(node) => node.loc.as_ref(),
+        Statement::ContinueStatement(node) => node.loc.as_ref(),
+        Statement::IfStatement(node) => node.loc.as_ref(),
+        Statement::SwitchStatement(node) => node.loc.as_ref(),
+        Statement::ThrowStatement(node) => node.loc.as_ref(),
+        Statement::TryStatement(node) => node.loc.as_ref(),
+        Statement::WhileStatement(node) => node.loc.as_ref(),
+        Statement::DoWhileStatement(node) => node.loc.as_ref(),
+        Statement::ForStatement(node) => node.loc.as_ref(),
+        Statement::ForInStatement(node) => node.loc.as_ref(),
+        Statement::ForOfStatement(node) => node.loc.as_ref(),
+        Statement::FunctionDeclaration(node) => node.loc.as_ref(),
+        Statement::AnonymousDefaultExportedFunctionDeclaration(node) => node.loc.as_ref(),
+        Statement::AnonymousDefaultExportedClassDeclaration(node) => node.loc.as_ref(),
+        Statement::VariableDeclaration(node) => node.loc.as_ref(),
+        Statement::ClassDeclaration(node) => node.loc.as_ref(),
+        Statement::InvalidStatement(node) => node.loc.as_ref(),
+        Statement::ImportOrExportDeclaration(node) => module_declaration_loc(node),
+    }
+}
+
+pub(crate) fn module_declaration_loc<'b, 'a>(
+    node: &'b ImportOrExportDeclaration<'a>,
+) -> Option<&'b SourceLocation<'a>> {
+    match node {
+        ImportOrExportDeclaration::ImportDeclaration(node) => node.loc.as_ref(),
+        ImportOrExportDeclaration::ExportNamedDeclaration(node) => node.loc.as_ref(),
+        ImportOrExportDeclaration::ExportDefaultDeclaration(node) => node.loc.as_ref(),
+        ImportOrExportDeclaration::ExportAllDeclaration(node) => node.loc.as_ref(),
+    }
+}
+
+pub(crate) fn expression_loc<'b, 'a>(node: &'b Expression<'a>) -> Option<&'b SourceLocation<'a>> {
+    match node {
+        Expression::Identifier(node) => node.loc.as_ref(),
+        Expression::Literal(node) => node.loc.as_ref(),
+        Expression::ThisExpression(node) => node.loc.as_ref(),
+        Expression::ArrayExpression(node) => node.loc.as_ref(),
+        Expression::ObjectExpression(node) => node.loc.as_ref(),
+        Expression::FunctionExpression(node) => node.loc.as_ref(),
+        Expression::UnaryExpression(node) => node.loc.as_ref(),
+        Expression::UpdateExpression(node) => node.loc.as_ref(),
+        Expression::BinaryExpression(node) => node.loc.as_ref(),
+        Expression::AssignmentExpression(node) => node.loc.as_ref(),
+        Expression::LogicalExpression(node) => node.loc.as_ref(),
+        Expression::MemberExpression(node) => node.loc.as_ref(),
+        Expression::ConditionalExpression(node) => node.loc.as_ref(),
+        Expression::CallExpression(node) => node.loc.as_ref(),
+        Expression::NewExpression(node) => node.loc.as_ref(),
+        Expression::SequenceExpression(node) => node.loc.as_ref(),
+        Expression::ParenthesizedExpression(node) => node.loc.as_ref(),
+        Expression::ArrowFunctionExpression(node) => node.loc.as_ref(),
+        Expression::YieldExpression(node) => node.loc.as_ref(),
+        Expression::TemplateLiteral(node) => node.loc.as_ref(),
+        Expression::TaggedTemplateExpression(node) => node.loc.as_ref(),
+        Expression::ClassExpression(node) => node.loc.as_ref(),
+        Expression::MetaProperty(node) => node.loc.as_ref(),
+        Expression::AwaitExpression(node) => node.loc.as_ref(),
+        Expression::ChainExpression(node) => node.loc.as_ref(),
+        Expression::ImportExpression(node) => node.loc.as_ref(),
+        Expression::StaticBlock(node) => node.loc.as_ref(),
+    }
+}
+
+pub(crate) fn pattern_loc<'b, 'a>(node: &'b Pattern<'a>) -> Option<&'b SourceLocation<'a>> {
+    match node {
+        Pattern::RestElement(node) => node.loc.as_ref(),
+        Pattern::ArrayPattern(node) => node.loc.as_ref(),
+        Pattern::ObjectPattern(node) => node.loc.as_ref(),
+        Pattern::Identifier(node) => node.loc.as_ref(),
+        Pattern::AssignmentPattern(node) => node.loc.as_ref(),
+        Pattern::MemberExpression(node) => node.loc.as_ref(),
+    }
+}
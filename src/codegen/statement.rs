@@ -0,0 +1,1027 @@
+use std::mem;
+
+use super::{
+    expression::{
+        contains_in, is_binary_like, print_assignment_right, print_expression, print_list,
+        print_literal, print_name, print_pattern, print_property_key, ListLayout, Precedence,
+        TrailingComma,
+    },
+    format::{
+        end, has_comments_before, print_comments_before_else, print_dangling_comments,
+        print_leading_comments, print_list_item, print_remaining_comments, print_string,
+        protect_statement_start, start,
+    },
+    source_map::{expression_loc, module_declaration_loc, pattern_loc, statement_loc},
+    BinaryLayout, Codegen,
+};
+use crate::ast::{
+    directive::Directive,
+    expression::{Expression, Identifier, Literal, LiteralValue},
+    import_export_declaration::{
+        ExportAllExportedType, ExportDeclaration, ExportDefaultDeclarationType, ExportLocal,
+        ExportedType, ImportOrExportDeclaration, ImportSpecifiers, ImportedType,
+    },
+    pattern::Pattern,
+    statement::{
+        ClassBody, ClassBodyEl, ClassMethodKey, ClassPropertyKey, ForInOfStatementLeft,
+        ForStatementInit, FunctionBody, FunctionBodyContent, MethodKind, PropertyDefinition,
+        Statement, VariableDeclaration, VariableDeclarator, VariableKind,
+    },
+    Position, Program, ProgramNode, SourceLocation,
+};
+
+pub fn print_program(ctx: &mut Codegen, program: &Program) {
+    if let Some(hashbang) = program.hashbang {
+        ctx.push("#!");
+        ctx.push(hashbang);
+        ctx.newline();
+    }
+    let body: Vec<_> = program
+        .body
+        .iter()
+        .filter_map(|node| match node {
+            ProgramNode::Directive(directive) => Some((node, directive.loc.as_ref())),
+            ProgramNode::Statement(stmt) if is_omitted(ctx, stmt) => None,
+            ProgramNode::Statement(stmt) => Some((node, statement_loc(stmt))),
+            ProgramNode::ImportOrExportDeclaration(decl) => {
+                Some((node, module_declaration_loc(decl)))
+            }
+        })
+        .collect();
+    let mut in_prologue = true;
+    let mut prev_line = None;
+    for (idx, &(node, loc)) in body.iter().enumerate() {
+        let item_end = body.get(idx + 1).and_then(|(_, loc)| start(*loc));
+        print_list_item(ctx, loc, item_end, &mut prev_line, |ctx| match node {
+            ProgramNode::Directive(node) => print_directive(ctx, node),
+            ProgramNode::Statement(node) => {
+                ctx.in_directive_prologue = in_prologue;
+                print_list_statement(ctx, node);
+            }
+            ProgramNode::ImportOrExportDeclaration(node) => print_module_declaration(ctx, node),
+        });
+        if let ProgramNode::Statement(node) = node {
+            after_list_statement(&mut prev_line, node, item_end);
+        }
+        in_prologue &= matches!(node, ProgramNode::Directive(_));
+        ctx.newline();
+    }
+    print_remaining_comments(ctx, prev_line);
+}
+
+fn print_directive(ctx: &mut Codegen, node: &Directive) {
+    ctx.add_mapping(node.loc.as_ref());
+    match ctx.is_formatting() && !node.expression.raw.is_empty() {
+        true => print_string(ctx, node.expression.raw, true),
+        false => print_literal(ctx, &node.expression),
+    }
+    ctx.semicolon();
+}
+
+// 语句列表中不打印的语句，格式化时空语句也去掉，否则不打印分号时开头加上的分号会越来越多
+fn is_omitted(ctx: &Codegen, stmt: &Statement) -> bool {
+    match stmt {
+        Statement::InvalidStatement(_) => true,
+        Statement::EmptyStatement(_) => ctx.is_formatting(),
+        _ => false,
+    }
+}
+
+// 语句列表中的语句，不打印分号时可能需要在开头加上分号
+fn print_list_statement(ctx: &mut Codegen, node: &Statement) {
+    let start = (ctx.docs.len(), ctx.pos());
+    print_statement(ctx, node);
+    protect_statement_start(ctx, start);
+}
+
+// 不打印分号时，语句的分号可能在下一个语句的开头，比如 `a = 1\n\n;(b)`，这时按照分号之前的内容
+// 计算和下一个语句之间的空行，否则第二次格式化时空行会消失
+fn after_list_statement(prev_line: &mut Option<usize>, node: &Statement, next: Option<&Position>) {
+    if let (Some(loc), Some(line), Some(next)) = (statement_loc(node), *prev_line, next) {
+        if line == loc.end.line && next.line == loc.end.line {
+            *prev_line = statement_content_end(node)
+                .map(|end| end.line)
+                .or(*prev_line);
+        }
+    }
+}
+
+// 语句中最后一个分号之前的内容结束的位置
+fn statement_content_end<'b>(node: &'b Statement) -> Option<&'b Position> {
+    match node {
+        Statement::ExpressionStatement(node) => end(expression_loc(&node.expression)),
+        Statement::VariableDeclaration(node) => {
+            let last = node.declarations.last()?;
+            match &last.init {
+                Some(init) => end(expression_loc(init)),
+                None => end(pattern_loc(&last.id)),
+            }
+        }
+        Statement::ReturnStatement(node) => end(expression_loc(node.argument.as_ref()?)),
+        Statement::ThrowStatement(node) => end(expression_loc(&node.argument)),
+        Statement::IfStatement(node) => {
+            statement_content_end(node.alternate.as_ref().unwrap_or(&node.consequent))
+        }
+        Statement::LabeledStatement(node) => statement_content_end(&node.body),
+        Statement::WhileStatement(node) => statement_content_end(&node.body),
+        Statement::ForStatement(node) => statement_content_end(&node.body),
+        Statement::ForInStatement(node) => statement_content_end(&node.body),
+        Statement::ForOfStatement(node) => statement_content_end(&node.body),
+        Statement::WithStatement(node) => statement_content_end(&node.body),
+        _ => None,
+    }
+}
+
+// 列表结束之前剩下的注释，放在最后一项之后单独的行中
+fn print_list_end(ctx: &mut Codegen, loc: Option<&SourceLocation>, prev_line: Option<usize>) {
+    if has_comments_before(ctx, end(loc)) {
+        ctx.newline();
+        print_dangling_comments(ctx, end(loc), prev_line);
+    }
+}
+
+// loc 是代码块的位置，用于打印代码块中剩下的注释
+pub fn print_block(ctx: &mut Codegen, body: &[Statement], loc: Option<&SourceLocation>) {
+    let body: Vec<_> = body.iter().filter(|stmt| !is_omitted(ctx, stmt)).collect();
+    // `{` 之前的注释，比如 `function f(/* a */) {}` 中的注释
+    print_leading_comments(ctx, loc);
+    if body.is_empty() && !has_comments_before(ctx, end(loc)) {
+        ctx.push("{}");
+        return;
+    }
+    // 代码块总是换行，其中的缩进不受外层 group 的影响
+    ctx.group(true, |ctx| {
+        ctx.push("{");
+        ctx.indent(|ctx| {
+            let mut prev_line = None;
+            for (idx, stmt) in body.iter().enumerate() {
+                let item_end = match body.get(idx + 1) {
+                    Some(next) => start(statement_loc(next)),
+                    None => end(loc),
+                };
+                ctx.newline();
+                print_list_item(ctx, statement_loc(stmt), item_end, &mut prev_line, |ctx| {
+                    print_list_statement(ctx, stmt)
+                });
+                after_list_statement(&mut prev_line, stmt, item_end);
+            }
+            print_list_end(ctx, loc, prev_line);
+        });
+        ctx.newline();
+        ctx.push("}");
+    });
+}
+
+pub fn print_function_body(ctx: &mut Codegen, node: &FunctionBody) {
+    let loc = node.loc.as_ref();
+    print_leading_comments(ctx, loc);
+    if node.body.is_empty() && !has_comments_before(ctx, end(loc)) {
+        ctx.push("{}");
+        return;
+    }
+    ctx.group(true, |ctx| {
+        ctx.push("{");
+        ctx.indent(|ctx| {
+            let mut in_prologue = true;
+            let mut prev_line = None;
+            for (idx, content) in node.body.iter().enumerate() {
+                let item_end = match node.body.get(idx + 1) {
+                    Some(next) => start(function_body_content_loc(next)),
+                    None => end(loc),
+                };
+                match content {
+                    FunctionBodyContent::Directive(directive) => {
+                        ctx.newline();
+                        print_list_item(
+                            ctx,
+                            directive.loc.as_ref(),
+                            item_end,
+                            &mut prev_line,
+                            |ctx| print_directive(ctx, directive),
+                        );
+                    }
+                    FunctionBodyContent::Statement(stmt) => {
+                        if is_omitted(ctx, stmt) {
+                            continue;
+                        }
+                        ctx.newline();
+                        print_list_item(
+                            ctx,
+                            statement_loc(stmt),
+                            item_end,
+                            &mut prev_line,
+                            |ctx| {
+                                ctx.in_directive_prologue = in_prologue;
+                                print_list_statement(ctx, stmt);
+                            },
+                        );
+                        after_list_statement(&mut prev_line, stmt, item_end);
+                        in_prologue = false;
+                    }
+                }
+            }
+            print_list_end(ctx, loc, prev_line);
+        });
+        ctx.newline();
+        ctx.push("}");
+    });
+}
+
+fn function_body_content_loc<'b, 'a>(
+    content: &'b FunctionBodyContent<'a>,
+) -> Option<&'b SourceLocation<'a>> {
+    match content {
+        FunctionBodyContent::Directive(directive) => directive.loc.as_ref(),
+        FunctionBodyContent::Statement(stmt) => statement_loc(stmt),
+    }
+}
+
+// body_start 是函数体开始的位置，参数之后剩下的注释放在参数列表的末尾
+pub fn print_function_params(ctx: &mut Codegen, params: &[Pattern], body_start: Option<&Position>) {
+    // rest 参数之后不能有逗号
+    let trailing_comma = match params.last() {
+        Some(Pattern::RestElement(_)) => TrailingComma::Never,
+        _ => TrailingComma::IfBreak,
+    };
+    print_list(
+        ctx,
+        ["(", ")"],
+        params,
+        ListLayout {
+            hug_last: false,
+            trailing_comma,
+            end: body_start,
+        },
+        |param| pattern_loc(param),
+        print_pattern,
+    );
+}
+
+pub fn print_function(
+    ctx: &mut Codegen,
+    is_async: bool,
+    generator: bool,
+    id: Option<&Identifier>,
+    params: &[Pattern],
+    body: &FunctionBody,
+) {
+    if is_async {
+        ctx.push("async ");
+    }
+    ctx.push("function");
+    if generator {
+        ctx.push("*");
+    }
+    match id {
+        Some(id) => {
+            ctx.push(" ");
+            print_name(ctx, id);
+        }
+        // 格式化时和 Prettier 一样写作 `function () {}`
+        None if ctx.is_formatting() => ctx.push(" "),
+        None => {}
+    }
+    print_function_params(ctx, params, start(body.loc.as_ref()));
+    ctx.push(" ");
+    print_function_body(ctx, body);
+}
+
+pub fn print_class(
+    ctx: &mut Codegen,
+    id: Option<&Identifier>,
+    super_class: Option<&Expression>,
+    body: &ClassBody,
+) {
+    ctx.push("class");
+    if let Some(id) = id {
+        ctx.push(" ");
+        print_name(ctx, id);
+    }
+    if let Some(super_class) = super_class {
+        ctx.push(" extends ");
+        print_expression(ctx, super_class, Precedence::Call);
+    }
+    ctx.push(" ");
+    let loc = body.loc.as_ref();
+    if body.body.is_empty() && !has_comments_before(ctx, end(loc)) {
+        ctx.push("{}");
+        return;
+    }
+    ctx.group(true, |ctx| {
+        ctx.push("{");
+        ctx.indent(|ctx| {
+            let mut prev_line = None;
+            for (idx, el) in body.body.iter().enumerate() {
+                let item_end = match body.body.get(idx + 1) {
+                    Some(next) => start(class_element_loc(next)),
+                    None => end(loc),
+                };
+                ctx.newline();
+                print_list_item(
+                    ctx,
+                    class_element_loc(el),
+                    item_end,
+                    &mut prev_line,
+                    |ctx| print_class_element(ctx, el, body.body.get(idx + 1)),
+                );
+            }
+            print_list_end(ctx, loc, prev_line);
+        });
+        ctx.newline();
+        ctx.push("}");
+    });
+}
+
+fn class_element_loc<'b, 'a>(el: &'b ClassBodyEl<'a>) -> Option<&'b SourceLocation<'a>> {
+    match el {
+        ClassBodyEl::MethodDefinition(el) => el.loc.as_ref(),
+        ClassBodyEl::PropertyDefinition(el) => el.loc.as_ref(),
+        ClassBodyEl::StaticBlock(el) => el.loc.as_ref(),
+    }
+}
+
+fn print_class_element(ctx: &mut Codegen, el: &ClassBodyEl, next: Option<&ClassBodyEl>) {
+    match el {
+        ClassBodyEl::MethodDefinition(el) => {
+            if el.is_static {
+                ctx.push("static ");
+            }
+            match el.kind {
+                MethodKind::Get => ctx.push("get "),
+                MethodKind::Set => ctx.push("set "),
+                MethodKind::Constructor | MethodKind::Method => {}
+            }
+            if el.value.is_async {
+                ctx.push("async ");
+            }
+            if el.value.generator {
+                ctx.push("*");
+            }
+            match &el.key {
+                ClassMethodKey::Expression(key) => print_property_key(ctx, key, el.computed),
+                ClassMethodKey::PrivateIdentifier(key) => {
+                    ctx.push("#");
+                    ctx.push(&key.name);
+                }
+            }
+            print_function_params(ctx, &el.value.params, start(el.value.body.loc.as_ref()));
+            ctx.push(" ");
+            print_function_body(ctx, &el.value.body);
+        }
+        ClassBodyEl::PropertyDefinition(el) => {
+            if el.is_static {
+                ctx.push("static ");
+            }
+            match &el.key {
+                ClassPropertyKey::Expression(key) => print_property_key(ctx, key, el.computed),
+                ClassPropertyKey::PrivateIdentifier(key) => {
+                    ctx.push("#");
+                    ctx.push(&key.name);
+                }
+            }
+            if let Some(value) = &el.value {
+                ctx.push(" = ");
+                print_expression(ctx, value, Precedence::Assign);
+            }
+            // 总是加上分号，避免和下一个成员连在一起，比如 `a` 后面跟着 `[b]() {}`
+            if ctx.semicolons() || continues_class_property(el, next) {
+                ctx.push(";");
+            }
+        }
+        ClassBodyEl::StaticBlock(el) => {
+            ctx.push("static ");
+            print_block(ctx, &el.body, el.loc.as_ref());
+        }
+    }
+}
+
+// 不打印分号时，类属性和下一个成员之间是否需要分号，比如 `get`、`async` 后面跟着 `a() {}`
+// 会被当成 getter、async 方法，
+// `a = b` 后面跟着 `*c() {}` 会被当成乘法
+fn continues_class_property(el: &PropertyDefinition, next: Option<&ClassBodyEl>) -> bool {
+    let is_keyword = |key: &Expression, computed: bool, keywords: &[&str]| {
+        !computed
+            && matches!(key, Expression::Identifier(key) if keywords.contains(&key.name.as_ref()))
+    };
+    if let ClassPropertyKey::Expression(key) = &el.key {
+        if el.value.is_none()
+            && is_keyword(
+                key,
+                el.computed,
+                &["static", "get", "set", "async", "accessor"],
+            )
+        {
+            return true;
+        }
+    }
+    match next {
+        Some(ClassBodyEl::MethodDefinition(next)) => {
+            next.computed
+                || (!next.is_static && next.value.generator)
+                || matches!(&next.key, ClassMethodKey::Expression(key) if is_keyword(key, next.computed, &["in", "instanceof"]))
+        }
+        Some(ClassBodyEl::PropertyDefinition(next)) => {
+            next.computed
+                || matches!(&next.key, ClassPropertyKey::Expression(key) if is_keyword(key, next.computed, &["in", "instanceof"]))
+        }
+        Some(ClassBodyEl::StaticBlock(_)) | None => false,
+    }
+}
+
+fn print_variable_declaration(ctx: &mut Codegen, node: &VariableDeclaration, for_init: bool) {
+    ctx.push(match node.kind {
+        VariableKind::Var => "var ",
+        VariableKind::Let => "let ",
+        VariableKind::Const => "const ",
+    });
+    let Some((first, rest)) = node.declarations.split_first() else {
+        return;
+    };
+    let print_declarator = |ctx: &mut Codegen, declarator: &VariableDeclarator| {
+        print_pattern(ctx, &declarator.id);
+        if let Some(init) = &declarator.init {
+            if for_init {
+                ctx.push(" = ");
+                ctx.wrap(contains_in(init), |ctx| {
+                    print_expression(ctx, init, Precedence::Assign)
+                });
+            } else {
+                ctx.push(" =");
+                print_assignment_right(ctx, init);
+            }
+        }
+    };
+    print_declarator(ctx, first);
+    // 格式化时多个声明中有初始值的话每个声明一行
+    let multiline = ctx.is_formatting()
+        && !for_init
+        && !rest.is_empty()
+        && node
+            .declarations
+            .iter()
+            .any(|declarator| declarator.init.is_some());
+    if multiline {
+        ctx.indent(|ctx| {
+            for declarator in rest {
+                ctx.push(",");
+                ctx.newline();
+                print_declarator(ctx, declarator);
+            }
+        });
+    } else {
+        for declarator in rest {
+            ctx.push(", ");
+            print_declarator(ctx, declarator);
+        }
+    }
+}
+
+// if、while 等语句括号中的条件，格式化时放不下的二元表达式在括号中换行
+fn print_test(ctx: &mut Codegen, test: &Expression) {
+    if !(ctx.is_formatting() && is_binary_like(test)) {
+        print_expression(ctx, test, Precedence::Lowest);
+        return;
+    }
+    ctx.group(false, |ctx| {
+        ctx.indent(|ctx| {
+            ctx.soft_line();
+            ctx.binary_layout = BinaryLayout {
+                group: false,
+                indent: false,
+            };
+            print_expression(ctx, test, Precedence::Lowest);
+        });
+        ctx.soft_line();
+    });
+}
+
+// return、throw 和 yield 的参数，关键字之后不能直接换行，放不下的二元表达式以及
+// 带着单独成行的注释的参数需要用括号括起来再换行
+pub fn print_argument(ctx: &mut Codegen, argument: &Expression, precedence: Precedence) {
+    let binary_like = is_binary_like(argument);
+    if !(ctx.is_formatting()
+        && (binary_like || has_comments_before(ctx, end(expression_loc(argument)))))
+    {
+        print_expression(ctx, argument, precedence);
+        return;
+    }
+    ctx.group(false, |ctx| {
+        ctx.if_break("(");
+        ctx.indent(|ctx| {
+            ctx.soft_line();
+            if binary_like {
+                ctx.binary_layout = BinaryLayout {
+                    group: false,
+                    indent: false,
+                };
+            }
+            print_expression(ctx, argument, precedence);
+        });
+        ctx.soft_line();
+        ctx.if_break(")");
+    });
+}
+
+// 作为 if、while 等语句的子语句打印，代码块和 if 语句一样放在同一行
+fn print_body(ctx: &mut Codegen, body: &Statement) {
+    ctx.push(" ");
+    match body {
+        Statement::InvalidStatement(_) => ctx.push(";"),
+        body => print_statement(ctx, body),
+    }
+}
+
+// `if (a) if (b) c; else d;` 中的 else 属于内层的 if，外层的 consequent 需要用代码块包起来
+fn ends_with_if_without_else(node: &Statement) -> bool {
+    match node {
+        Statement::IfStatement(node) => match &node.alternate {
+            Some(alternate) => ends_with_if_without_else(alternate),
+            None => true,
+        },
+        Statement::LabeledStatement(node) => ends_with_if_without_else(&node.body),
+        Statement::WithStatement(node) => ends_with_if_without_else(&node.body),
+        Statement::WhileStatement(node) => ends_with_if_without_else(&node.body),
+        Statement::ForStatement(node) => ends_with_if_without_else(&node.body),
+        Statement::ForInStatement(node) => ends_with_if_without_else(&node.body),
+        Statement::ForOfStatement(node) => ends_with_if_without_else(&node.body),
+        _ => false,
+    }
+}
+
+fn print_for_in_of_left(ctx: &mut Codegen, left: &ForInOfStatementLeft) {
+    match left {
+        ForInOfStatementLeft::VariableDeclaration(left) => {
+            print_variable_declaration(ctx, left, false)
+        }
+        ForInOfStatementLeft::Pattern(left) => {
+            ctx.for_init_start = ctx.pos();
+            print_pattern(ctx, left);
+        }
+    }
+}
+
+pub fn print_statement(ctx: &mut Codegen, node: &Statement) {
+    // 只有 program 和函数体中直接出现的语句才可能处于 directive prologue 中
+    let in_prologue = mem::take(&mut ctx.in_directive_prologue);
+    ctx.add_mapping(statement_loc(node));
+    match node {
+        Statement::ExpressionStatement(node) => {
+            let is_string = matches!(
+                &node.expression,
+                Expression::Literal(Literal {
+                    value: LiteralValue::String(_),
+                    ..
+                })
+            );
+            ctx.wrap(in_prologue && is_string, |ctx| {
+                ctx.stmt_start = ctx.pos();
+                print_expression(ctx, &node.expression, Precedence::Lowest)
+            });
+            ctx.semicolon();
+        }
+        Statement::BlockStatement(node) => print_block(ctx, &node.body, node.loc.as_ref()),
+        Statement::EmptyStatement(_) => ctx.push(";"),
+        Statement::DebuggerStatement(_) => {
+            ctx.push("debugger");
+            ctx.semicolon();
+        }
+        Statement::WithStatement(node) => {
+            ctx.push("with (");
+            print_test(ctx, &node.object);
+            ctx.push(")");
+            print_body(ctx, &node.body);
+        }
+        Statement::ReturnStatement(node) => {
+            ctx.push("return");
+            if let Some(argument) = &node.argument {
+                ctx.push(" ");
+                print_argument(ctx, argument, Precedence::Lowest);
+            }
+            ctx.semicolon();
+        }
+        Statement::LabeledStatement(node) => {
+            print_name(ctx, &node.label);
+            ctx.push(":");
+            print_body(ctx, &node.body);
+        }
+        Statement::BreakStatement(node) => {
+            ctx.push("break");
+            if let Some(label) = &node.label {
+                ctx.push(" ");
+                print_name(ctx, label);
+            }
+            ctx.semicolon();
+        }
+        Statement::ContinueStatement(node) => {
+            ctx.push("continue");
+            if let Some(label) = &node.label {
+                ctx.push(" ");
+                print_name(ctx, label);
+            }
+            ctx.semicolon();
+        }
+        Statement::IfStatement(node) => {
+            ctx.push("if (");
+            print_test(ctx, &node.test);
+            ctx.push(")");
+            match &node.alternate {
+                Some(alternate) => {
+                    let in_block = ends_with_if_without_else(&node.consequent);
+                    if in_block {
+                        ctx.push(" ");
+                        print_block(
+                            ctx,
+                            std::slice::from_ref(&*node.consequent),
+                            statement_loc(&node.consequent),
+                        );
+                    } else {
+                        print_body(ctx, &node.consequent);
+                    }
+                    // 格式化时子语句不是代码块的话 else 放到下一行，不打印分号时 `if (a) b else c` 是错误的
+                    let is_block =
+                        in_block || matches!(&*node.consequent, Statement::BlockStatement(_));
+                    let break_line = print_comments_before_else(
+                        ctx,
+                        statement_loc(&node.consequent),
+                        start(statement_loc(alternate)),
+                    );
+                    if ctx.is_formatting() && (!is_block || break_line) {
+                        ctx.newline();
+                        ctx.push("else");
+                    } else {
+                        ctx.push(" else");
+                    }
+                    print_body(ctx, alternate);
+                }
+                None => print_body(ctx, &node.consequent),
+            }
+        }
+        Statement::SwitchStatement(node) => {
+            ctx.push("switch (");
+            print_expression(ctx, &node.discriminant, Precedence::Lowest);
+            ctx.push(") ");
+            ctx.group(true, |ctx| {
+                ctx.push("{");
+                ctx.indent(|ctx| {
+                    let mut prev_line = None;
+                    for (idx, case) in node.cases.iter().enumerate() {
+                        let case_end = match node.cases.get(idx + 1) {
+                            Some(next) => start(next.loc.as_ref()),
+                            None => end(node.loc.as_ref()),
+                        };
+                        ctx.newline();
+                        print_list_item(ctx, case.loc.as_ref(), case_end, &mut prev_line, |ctx| {
+                            match &case.test {
+                                Some(test) => {
+                                    ctx.push("case ");
+                                    print_expression(ctx, test, Precedence::Lowest);
+                                    ctx.push(":");
+                                }
+                                None => ctx.push("default:"),
+                            }
+                            ctx.indent(|ctx| {
+                                let mut prev_line = None;
+                                for (idx, stmt) in case.consequent.iter().enumerate() {
+                                    if is_omitted(ctx, stmt) {
+                                        continue;
+                                    }
+                                    let item_end = match case.consequent.get(idx + 1) {
+                                        Some(next) => start(statement_loc(next)),
+                                        None => case_end,
+                                    };
+                                    ctx.newline();
+                                    print_list_item(
+                                        ctx,
+                                        statement_loc(stmt),
+                                        item_end,
+                                        &mut prev_line,
+                                        |ctx| print_list_statement(ctx, stmt),
+                                    );
+                                    after_list_statement(&mut prev_line, stmt, item_end);
+                                }
+                            });
+                        });
+                    }
+                    print_list_end(ctx, node.loc.as_ref(), prev_line);
+                });
+                ctx.newline();
+                ctx.push("}");
+            });
+        }
+        Statement::ThrowStatement(node) => {
+            ctx.push("throw ");
+            print_argument(ctx, &node.argument, Precedence::Lowest);
+            ctx.semicolon();
+        }
+        Statement::TryStatement(node) => {
+            ctx.push("try ");
+            print_block(ctx, &node.block.body, node.block.loc.as_ref());
+            if let Some(handler) = &node.handler {
+                ctx.push(" catch ");
+                if let Some(param) = &handler.param {
+                    ctx.push("(");
+                    print_pattern(ctx, param);
+                    ctx.push(") ");
+                }
+                print_block(ctx, &handler.body.body, handler.body.loc.as_ref());
+            }
+            if let Some(finalizer) = &node.finalizer {
+                ctx.push(" finally ");
+                print_block(ctx, &finalizer.body, finalizer.loc.as_ref());
+            }
+        }
+        Statement::WhileStatement(node) => {
+            ctx.push("while (");
+            print_test(ctx, &node.test);
+            ctx.push(")");
+            print_body(ctx, &node.body);
+        }
+        Statement::DoWhileStatement(node) => {
+            ctx.push("do");
+            print_body(ctx, &node.body);
+            // 和 else 一样，不打印分号时 `do a while (b)` 是错误的
+            if ctx.is_formatting() && !matches!(&*node.body, Statement::BlockStatement(_)) {
+                ctx.newline();
+                ctx.push("while (");
+            } else {
+                ctx.push(" while (");
+            }
+            print_test(ctx, &node.test);
+            ctx.push(")");
+            ctx.semicolon();
+        }
+        Statement::ForStatement(node) => {
+            ctx.push("for (");
+            match &node.init {
+                ForStatementInit::VariableDeclaration(init) => {
+                    print_variable_declaration(ctx, init, true)
+                }
+                ForStatementInit::Expression(init) => {
+                    ctx.wrap(contains_in(init), |ctx| {
+                        ctx.for_init_start = ctx.pos();
+                        print_expression(ctx, init, Precedence::Lowest)
+                    });
+                }
+                ForStatementInit::Null => {}
+            }
+            ctx.push(";");
+            if let Some(test) = &node.test {
+                ctx.push(" ");
+                print_expression(ctx, test, Precedence::Lowest);
+            }
+            ctx.push(";");
+            if let Some(update) = &node.update {
+                ctx.push(" ");
+                print_expression(ctx, update, Precedence::Lowest);
+            }
+            ctx.push(")");
+            print_body(ctx, &node.body);
+        }
+        Statement::ForInStatement(node) => {
+            ctx.push("for (");
+            print_for_in_of_left(ctx, &node.left);
+            ctx.push(" in ");
+            print_expression(ctx, &node.right, Precedence::Lowest);
+            ctx.push(")");
+            print_body(ctx, &node.body);
+        }
+        Statement::ForOfStatement(node) => {
+            ctx.push(if node.is_await {
+                "for await ("
+            } else {
+                "for ("
+            });
+            print_for_in_of_left(ctx, &node.left);
+            ctx.push(" of ");
+            print_expression(ctx, &node.right, Precedence::Assign);
+            ctx.push(")");
+            print_body(ctx, &node.body);
+        }
+        Statement::FunctionDeclaration(node) => print_function(
+            ctx,
+            node.is_async,
+            node.generator,
+            Some(&node.id),
+            &node.params,
+            &node.body,
+        ),
+        Statement::AnonymousDefaultExportedFunctionDeclaration(node) => print_function(
+            ctx,
+            node.is_async,
+            node.generator,
+            node.id.as_ref(),
+            &node.params,
+            &node.body,
+        ),
+        Statement::AnonymousDefaultExportedClassDeclaration(node) => {
+            print_class(ctx, node.id.as_ref(), node.super_class.as_ref(), &node.body)
+        }
+        Statement::VariableDeclaration(node) => {
+            print_variable_declaration(ctx, node, false);
+            ctx.semicolon();
+        }
+        Statement::ClassDeclaration(node) => {
+            print_class(ctx, Some(&node.id), node.super_class.as_ref(), &node.body)
+        }
+        // 容错模式下跳过的源码没有对应的节点，不会被打印
+        Statement::InvalidStatement(_) => {}
+        Statement::ImportOrExportDeclaration(node) => print_module_declaration(ctx, node),
+    }
+}
+
+fn print_module_declaration(ctx: &mut Codegen, node: &ImportOrExportDeclaration) {
+    ctx.add_mapping(module_declaration_loc(node));
+    match node {
+        ImportOrExportDeclaration::ImportDeclaration(node) => {
+            ctx.push("import ");
+            let mut named = vec![];
+            let mut printed = false;
+            for specifier in node.specifiers.iter() {
+                match specifier {
+                    ImportSpecifiers::ImportSpecifier(specifier) => named.push(specifier),
+                    ImportSpecifiers::ImportDefaultSpecifier(specifier) => {
+                        if printed {
+                            ctx.push(", ");
+                        }
+                        print_name(ctx, &specifier.local);
+                        printed = true;
+                    }
+                    ImportSpecifiers::ImportNamespaceSpecifier(specifier) => {
+                        if printed {
+                            ctx.push(", ");
+                        }
+                        ctx.push("* as ");
+                        print_name(ctx, &specifier.local);
+                        printed = true;
+                    }
+                }
+            }
+            if !named.is_empty() {
+                if printed {
+                    ctx.push(", ");
+                }
+                print_specifiers(ctx, &named, |ctx, specifier| {
+                    match &specifier.imported {
+                        ImportedType::Identifier(imported)
+                            if imported.name == specifier.local.name => {}
+                        ImportedType::Identifier(imported) => {
+                            print_name(ctx, imported);
+                            ctx.push(" as ");
+                        }
+                        ImportedType::Literal(imported) => {
+                            print_literal(ctx, imported);
+                            ctx.push(" as ");
+                        }
+                    }
+                    print_name(ctx, &specifier.local);
+                });
+                printed = true;
+            }
+            if printed {
+                ctx.push(" from ");
+            }
+            print_literal(ctx, &node.source);
+            ctx.semicolon();
+        }
+        ImportOrExportDeclaration::ExportNamedDeclaration(node) => {
+            ctx.push("export ");
+            match &node.declaration {
+                Some(ExportDeclaration::FunctionDeclaration(declaration)) => print_function(
+                    ctx,
+                    declaration.is_async,
+                    declaration.generator,
+                    Some(&declaration.id),
+                    &declaration.params,
+                    &declaration.body,
+                ),
+                Some(ExportDeclaration::VariableDeclaration(declaration)) => {
+                    print_variable_declaration(ctx, declaration, false);
+                    ctx.semicolon();
+                }
+                Some(ExportDeclaration::ClassDeclaration(declaration)) => print_class(
+                    ctx,
+                    Some(&declaration.id),
+                    declaration.super_class.as_ref(),
+                    &declaration.body,
+                ),
+                None => {
+                    if node.specifiers.is_empty() {
+                        ctx.push("{}");
+                    } else {
+                        print_specifiers(ctx, &node.specifiers, |ctx, specifier| {
+                            let local_name = match &specifier.local {
+                                ExportLocal::Identifier(local) => {
+                                    print_name(ctx, local);
+                                    Some(&local.name)
+                                }
+                                ExportLocal::Literal(local) => {
+                                    print_literal(ctx, local);
+                                    None
+                                }
+                            };
+                            match &specifier.exported {
+                                ExportedType::Identifier(exported)
+                                    if Some(&exported.name) == local_name => {}
+                                ExportedType::Identifier(exported) => {
+                                    ctx.push(" as ");
+                                    print_name(ctx, exported);
+                                }
+                                ExportedType::Literal(exported) => {
+                                    ctx.push(" as ");
+                                    print_literal(ctx, exported);
+                                }
+                            }
+                        });
+                    }
+                    if let Some(source) = &node.source {
+                        ctx.push(" from ");
+                        print_literal(ctx, source);
+                    }
+                    ctx.semicolon();
+                }
+            }
+        }
+        ImportOrExportDeclaration::ExportDefaultDeclaration(node) => {
+            ctx.push("export default ");
+            match &node.declaration {
+                ExportDefaultDeclarationType::AnonymousDefaultExportedFunctionDeclaration(
+                    declaration,
+                ) => print_function(
+                    ctx,
+                    declaration.is_async,
+                    declaration.generator,
+                    declaration.id.as_ref(),
+                    &declaration.params,
+                    &declaration.body,
+                ),
+                ExportDefaultDeclarationType::FunctionDeclaration(declaration) => print_function(
+                    ctx,
+                    declaration.is_async,
+                    declaration.generator,
+                    Some(&declaration.id),
+                    &declaration.params,
+                    &declaration.body,
+                ),
+                ExportDefaultDeclarationType::AnonymousDefaultExportedClassDeclaration(
+                    declaration,
+                ) => print_class(
+                    ctx,
+                    declaration.id.as_ref(),
+                    declaration.super_class.as_ref(),
+                    &declaration.body,
+                ),
+                ExportDefaultDeclarationType::ClassDeclaration(declaration) => print_class(
+                    ctx,
+                    Some(&declaration.id),
+                    declaration.super_class.as_ref(),
+                    &declaration.body,
+                ),
+                ExportDefaultDeclarationType::Expression(declaration) => {
+                    ctx.export_default_start = ctx.pos();
+                    print_expression(ctx, declaration, Precedence::Assign);
+                    ctx.semicolon();
+                }
+            }
+        }
+        ImportOrExportDeclaration::ExportAllDeclaration(node) => {
+            ctx.push("export *");
+            match &node.exported {
+                ExportAllExportedType::Identifier(exported) => {
+                    ctx.push(" as ");
+                    print_name(ctx, exported);
+                }
+                ExportAllExportedType::Literal(exported) => {
+                    ctx.push(" as ");
+                    print_literal(ctx, exported);
+                }
+                ExportAllExportedType::Null => {}
+            }
+            ctx.push(" from ");
+            print_literal(ctx, &node.source);
+            ctx.semicolon();
+        }
+    }
+}
+
+// `{ a, b as c }`，格式化时放不下则每个一行
+fn print_specifiers<'s, T>(
+    ctx: &mut Codegen<'s>,
+    specifiers: &[T],
+    mut print: impl FnMut(&mut Codegen<'s>, &T),
+) {
+    ctx.group(false, |ctx| {
+        ctx.push("{");
+        ctx.indent(|ctx| {
+            for (idx, specifier) in specifiers.iter().enumerate() {
+                if idx > 0 {
+                    ctx.push(",");
+                }
+                ctx.line();
+                print(ctx, specifier);
+            }
+        });
+        ctx.if_break(",");
+        ctx.line();
+        ctx.push("}");
+    });
+}
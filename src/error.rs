@@ -0,0 +1,132 @@
+use crate::ast::Position;
+use std::{error::Error, fmt::Display};
+
+/// The category of a `ParseError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A token appeared where the grammar doesn't allow it.
+    UnexpectedToken,
+    /// A character which could not start any token.
+    UnexpectedCharacter,
+    /// A string, template, comment or regular expression which is not closed.
+    Unterminated,
+    /// A malformed numeric literal.
+    InvalidNumber,
+    /// A malformed escape sequence.
+    InvalidEscape,
+    /// An expression which can not be the target of an assignment or a binding.
+    InvalidAssignmentTarget,
+    /// A regular expression literal with an invalid pattern or flags.
+    InvalidRegExp,
+    /// Syntax which is not available in the configured `ecma_version`.
+    UnsupportedSyntax,
+    /// Other syntax errors.
+    SyntaxError,
+    /// A semantic error which is detected while parsing, such as a duplicate private name.
+    EarlyError(EarlyErrorCode),
+}
+
+/// The code of an early error, which identifies what semantic check failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EarlyErrorCode {
+    /// A private name is declared more than once in the same class.
+    DuplicatePrivateName,
+    /// A private name is used without being declared in an enclosing class.
+    UndeclaredPrivateName,
+    /// `yield`, `await` or `arguments` used where it is not allowed as an identifier.
+    InvalidIdentifier,
+    /// A keyword written with unicode escape sequences.
+    EscapedKeyword,
+    /// An invalid `new.target` or `import.meta`.
+    InvalidMetaProperty,
+    /// A getter or setter with wrong parameters.
+    InvalidAccessorParams,
+    /// `??` mixed with `&&` or `||` without parentheses.
+    MixedCoalesce,
+    /// A bad escape sequence in an untagged template literal.
+    InvalidTemplateEscape,
+    /// A trailing comma in `import()`.
+    ImportTrailingComma,
+    /// A switch statement with more than one default clause.
+    DuplicateDefaultClause,
+    /// A `break` or `continue` without a valid target.
+    InvalidJumpTarget,
+    /// A reserved word used as an identifier.
+    ReservedWord,
+    /// A name exported more than once from a module.
+    DuplicateExport,
+}
+
+impl EarlyErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DuplicatePrivateName => "duplicate-private-name",
+            Self::UndeclaredPrivateName => "undeclared-private-name",
+            Self::InvalidIdentifier => "invalid-identifier",
+            Self::EscapedKeyword => "escaped-keyword",
+            Self::InvalidMetaProperty => "invalid-meta-property",
+            Self::InvalidAccessorParams => "invalid-accessor-params",
+            Self::MixedCoalesce => "mixed-coalesce",
+            Self::InvalidTemplateEscape => "invalid-template-escape",
+            Self::ImportTrailingComma => "import-trailing-comma",
+            Self::DuplicateDefaultClause => "duplicate-default-clause",
+            Self::InvalidJumpTarget => "invalid-jump-target",
+            Self::ReservedWord => "reserved-word",
+            Self::DuplicateExport => "duplicate-export",
+        }
+    }
+}
+
+/// How an early error is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Severity {
+    /// Stop parsing and return the diagnostic as an error.
+    #[default]
+    Error,
+    /// Record the diagnostic and continue parsing.
+    Warning,
+}
+
+/// The error reported when the source code could not be parsed.
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub message: String,
+    /// The offset where the error occurred, in `ParserOptions::position_encoding`.
+    pub pos: usize,
+    pub loc: Position,
+    pub kind: ParseErrorKind,
+    pub severity: Severity,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, message: &str, pos: usize, loc: Position) -> Self {
+        Self {
+            message: message.to_string(),
+            pos,
+            loc,
+            kind,
+            severity: Severity::Error,
+        }
+    }
+
+    /// The code of the early error, `None` for syntax errors.
+    pub fn code(&self) -> Option<EarlyErrorCode> {
+        match self.kind {
+            ParseErrorKind::EarlyError(code) => Some(code),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(code) = self.code() {
+            write!(f, "[{}] ", code.as_str())?;
+        }
+        write!(f, "{} ({}:{})", self.message, self.loc.line, self.loc.col)
+    }
+}
+
+impl Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
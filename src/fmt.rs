@@ -0,0 +1,64 @@
+use snail::{
+    codegen::{self, FormatOptions},
+    Bump, ParserOptions, SourceType,
+};
+use std::{fs, process};
+
+use crate::file::{self, ReadFileError};
+
+const USAGE: &str = "usage: snail fmt <file> [--write] [--print-width <n>] [--indent-width <n>] [--single-quote] [--no-semi] [--module | --script]";
+
+// `snail fmt`：格式化文件，输出到标准输出，或者用 --write 写回文件
+pub fn run(args: Vec<String>) -> Result<(), ReadFileError> {
+    let mut options = FormatOptions::default();
+    let mut path = None;
+    let mut write = false;
+    // 默认按照脚本解析。不能先试模块再退回脚本，因为有些代码两种都能解析但是含义不同，
+    // 比如脚本中的 `await(1)` 是函数调用，在模块中却是 await 表达式
+    let mut source_type = SourceType::Script;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--write" => write = true,
+            "--single-quote" => options.single_quote = true,
+            "--no-semi" => options.semicolons = false,
+            "--module" => source_type = SourceType::Module,
+            "--script" => source_type = SourceType::Script,
+            "--print-width" => options.print_width = parse_number(args.next()),
+            "--indent-width" => options.indent_width = parse_number(args.next()),
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => usage(),
+        }
+    }
+    let Some(path) = path else { usage() };
+    let source = file::read_file_content(&path)?;
+    let arena = Bump::new();
+    let parser_options = ParserOptions {
+        source_type,
+        ..Default::default()
+    };
+    let program = snail::parse(&arena, &source, parser_options).unwrap_or_else(|err| {
+        eprintln!("{}: {}", path, err);
+        process::exit(1);
+    });
+    let code = codegen::format(&program, &options);
+    if write {
+        fs::write(&path, code).unwrap_or_else(|err| {
+            eprintln!("{}: {}", path, err);
+            process::exit(1);
+        });
+    } else {
+        print!("{}", code);
+    }
+    Ok(())
+}
+
+fn parse_number(arg: Option<String>) -> usize {
+    arg.and_then(|arg| arg.parse().ok())
+        .unwrap_or_else(|| usage())
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
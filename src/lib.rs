@@ -0,0 +1,85 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod ast;
+pub mod codegen;
+pub mod error;
+mod global;
+mod line_index;
+pub mod options;
+mod parser;
+mod regexp;
+mod statement;
+mod tokenizer;
+
+pub use ast::Program;
+pub use bumpalo::Bump;
+pub use error::ParseError;
+pub use line_index::LineIndex;
+pub use options::{OnComment, ParserOptions, PositionEncoding, SourceType};
+pub use tokenizer::js_token::{Token, TokenLabel};
+pub use tokenizer::Tokenizer;
+
+use parser::Parser;
+use statement::parse_top_level;
+
+/// The result of `parse_with_diagnostics`.
+pub struct ParseOutput<'a> {
+    pub program: Program<'a>,
+    /// Early errors which were reported as warnings, and the syntax errors
+    /// recovered from when `ParserOptions::error_recovery` is enabled.
+    pub diagnostics: Vec<ParseError>,
+}
+
+/// Split a piece of JavaScript source code into tokens without parsing it.
+/// Comments and white spaces are skipped, and the iteration stops after the
+/// first error.
+pub fn tokenize<'a>(arena: &'a Bump, source: &'a str, options: ParserOptions) -> Tokenizer<'a> {
+    Tokenizer::new(arena, source, options)
+}
+
+/// Parse a piece of JavaScript source code into a `Program` node.
+///
+/// Every node of the `Program` is allocated in `arena`, and is freed together
+/// with it.
+pub fn parse<'a>(
+    arena: &'a Bump,
+    source: &'a str,
+    options: ParserOptions,
+) -> Result<Program<'a>, ParseError> {
+    parse_with_diagnostics(arena, source, options).map(|output| output.program)
+}
+
+/// Parse a piece of JavaScript source code, and also return the early errors
+/// which are configured as warnings by `ParserOptions::early_errors`.
+///
+/// With `ParserOptions::error_recovery` enabled, a partial `Program` is returned
+/// together with every syntax error found.
+pub fn parse_with_diagnostics<'a>(
+    arena: &'a Bump,
+    source: &'a str,
+    options: ParserOptions,
+) -> Result<ParseOutput<'a>, ParseError> {
+    let mut parser = Parser::new(arena, source, options);
+
+    let result = parse_top_level(&mut parser);
+    let mut diagnostics = parser.diagnostics;
+    // 节点的位置在 parse_top_level 中已经处理过了
+    if let Some(config) = &parser.location_config {
+        diagnostics
+            .iter_mut()
+            .for_each(|err| config.finish_error(err));
+    }
+    match result {
+        Ok(program) => Ok(ParseOutput {
+            program,
+            diagnostics,
+        }),
+        Err(mut err) => {
+            if let Some(config) = &parser.location_config {
+                config.finish_error(&mut err);
+            }
+            Err(err)
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::ast::Position;
+use crate::options::PositionEncoding;
+
+/// The start offset of every line in a piece of source code, which maps any
+/// offset to a line and column and back, like acorn's `getLineInfo`.
+///
+/// `\n`, `\r`, `\r\n`, U+2028 and U+2029 all end a line, as in the parser.
+/// Offsets and columns are counted in the given `PositionEncoding`, which
+/// should be the one the offsets were produced with.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    /// 每一行开始的位置，第一行总是从 0 开始
+    line_starts: Vec<usize>,
+    /// 源码的总长度
+    len: usize,
+}
+
+impl LineIndex {
+    pub fn new(source: &str, encoding: PositionEncoding) -> Self {
+        let mut line_starts = vec![0];
+        let mut offset = 0;
+        let mut chars = source.chars().peekable();
+        while let Some(c) = chars.next() {
+            offset += match encoding {
+                PositionEncoding::Utf8 => c.len_utf8(),
+                PositionEncoding::Utf16 => c.len_utf16(),
+                PositionEncoding::Char => 1,
+            };
+            match c {
+                // \r\n 是一个换行，在 \n 之后才开始新的一行
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' | '\r' | '\u{2028}' | '\u{2029}' => line_starts.push(offset),
+                _ => {}
+            }
+        }
+        Self {
+            line_starts,
+            len: offset,
+        }
+    }
+
+    /// The number of lines, a source code without any line break has one line.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The line and column of `offset`. Offsets past the end of the source
+    /// are clamped to the end.
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        Position {
+            line,
+            col: offset - self.line_starts[line - 1],
+        }
+    }
+
+    /// The offset of `position`, `None` if the line doesn't exist or the
+    /// column is past the line terminator.
+    pub fn offset(&self, position: &Position) -> Option<usize> {
+        let start = *self.line_starts.get(position.line.checked_sub(1)?)?;
+        let offset = start + position.col;
+        let in_line = match self.line_starts.get(position.line) {
+            Some(&next) => offset < next,
+            None => offset <= self.len,
+        };
+        in_line.then_some(offset)
+    }
+}
@@ -1,19 +1,14 @@
-#[macro_use]
-extern crate lazy_static;
-
+use snail::{codegen, Bump, ParserOptions};
 use std::string::String;
 use std::{env, fs};
-mod ast;
 mod file;
-mod global;
-mod parser;
-mod statement;
-mod tokenizer;
-
-use statement::parse_top_level;
+mod fmt;
 
 fn main() -> Result<(), file::ReadFileError> {
     env::set_var("RUST_BACKTRACE", "1");
+    if env::args().nth(1).as_deref() == Some("fmt") {
+        return fmt::run(env::args().skip(2).collect());
+    }
     let file_path = file::get_file_path()?;
     let result: String = file::read_file_content(&file_path)?;
     println!("The content of file: ");
@@ -21,12 +16,41 @@ fn main() -> Result<(), file::ReadFileError> {
     println!();
     println!("----------------");
     println!();
-    let mut parser = parser::Parser::new(result);
-    parser.source_file = Some(file_path);
-    let root = parse_top_level(&mut parser);
+    let options = ParserOptions {
+        source_file: Some(file_path),
+        attach_comments: true,
+        ..Default::default()
+    };
+    let arena = Bump::new();
+    let root = match snail::parse_with_diagnostics(&arena, &result, options) {
+        Ok(output) => {
+            for diagnostic in output.diagnostics.iter() {
+                eprintln!("warning: {}", diagnostic);
+            }
+            output.program
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
     // Convert the Ast Data structure to a JSON string.
     let serialized = serde_json::to_string_pretty(&root).unwrap();
     fs::write("output/example.json", serialized).unwrap();
 
+    // 重新打印成 JavaScript，并在旁边写入对应的 source map
+    let source_map_options = codegen::SourceMapOptions {
+        file: Some("example.js".to_string()),
+        sources_content: true,
+        ..Default::default()
+    };
+    let (code, source_map) = codegen::print_with_source_map(&root, &result, source_map_options);
+    fs::write(
+        "output/example.js",
+        format!("{}//# sourceMappingURL=example.js.map\n", code),
+    )
+    .unwrap();
+    fs::write("output/example.js.map", source_map.to_json()).unwrap();
+
     Ok(())
 }
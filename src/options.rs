@@ -0,0 +1,155 @@
+use crate::ast::comment::Comment;
+use crate::error::Severity;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// The latest ECMAScript version supported by the parser.
+pub const LATEST_ECMA_VERSION: u32 = 2024;
+
+/// Whether the source code is parsed as a classic script or an ES module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all(serialize = "lowercase"))]
+pub enum SourceType {
+    #[default]
+    Script,
+    Module,
+}
+
+/// The unit of the `start` / `end` offsets and the `column`s of nodes, tokens,
+/// comments and errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PositionEncoding {
+    /// Byte offsets into the UTF-8 source, which can be used to slice the
+    /// source `&str` directly.
+    #[default]
+    Utf8,
+    /// UTF-16 code units, as used by JavaScript strings, V8, source maps and
+    /// the Language Server Protocol. Astral characters count as two units.
+    Utf16,
+    /// Unicode scalar values, i.e. `char`s.
+    Char,
+}
+
+/// Options used to control how the source code is parsed.
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    /// The source file name, which will be recorded in the `loc` of every node.
+    /// It's left out of the `loc` when `None`.
+    pub source_file: Option<String>,
+    /// How early errors, like a duplicate private name, are reported.
+    pub early_errors: Severity,
+    /// When enabled, statements that fail to parse are skipped and replaced by
+    /// `InvalidStatement` nodes, and the syntax errors are collected into the
+    /// diagnostics instead of aborting the whole parse.
+    pub error_recovery: bool,
+    /// The ECMAScript version to parse, either as an edition number (3, 5, 6, ...)
+    /// or as a year (2015, 2016, ...). Syntax introduced by later versions is
+    /// rejected. Defaults to `LATEST_ECMA_VERSION`.
+    pub ecma_version: u32,
+    /// Parse the code as a script or a module.
+    pub source_type: SourceType,
+    /// Allow `return` statements at the top level.
+    pub allow_return_outside_function: bool,
+    /// Allow `import` and `export` declarations to appear anywhere a statement
+    /// is allowed, instead of only at the top level.
+    pub allow_import_export_everywhere: bool,
+    /// Allow `await` expressions outside of async functions.
+    pub allow_await_outside_function: bool,
+    /// Allow a `#!` line at the start of the input. When `None`, it's allowed
+    /// from ECMAScript 2023 on.
+    pub allow_hash_bang: Option<bool>,
+    /// Allow reserved words, like `enum` or `implements`, to be used as
+    /// identifiers. When `None`, it's allowed for ECMAScript 3 only.
+    pub allow_reserved: Option<bool>,
+    /// Called with every comment as soon as the tokenizer skips it, like
+    /// acorn's `onComment`. The comments are collected into `Program::comments`
+    /// either way.
+    pub on_comment: Option<OnComment>,
+    /// Attach every comment to the nearest statement, expression or class
+    /// element as `leading_comments` or `trailing_comments`. Comments in an
+    /// empty block go to its `inner_comments`.
+    pub attach_comments: bool,
+    /// Parse the pattern of every regular expression literal into a
+    /// `regexpp`-style AST, which is stored in `Literal::reg.ast`.
+    pub parse_regex_ast: bool,
+    /// The unit of every offset and column in the result. Defaults to UTF-8
+    /// bytes.
+    pub position_encoding: PositionEncoding,
+    /// Record a `loc` object with the start and end line and column on every
+    /// node, token and comment, like acorn's `locations`. Enabled by default.
+    pub locations: bool,
+    /// Record a `range` array of `[start, end]` on every node, token and
+    /// comment, like acorn's `ranges`.
+    pub ranges: bool,
+}
+
+/// A callback which receives every comment found in the source code.
+#[derive(Clone)]
+pub struct OnComment(Rc<RefCell<CommentHandler>>);
+
+type CommentHandler = dyn FnMut(&Comment);
+
+impl OnComment {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnMut(&Comment) + 'static,
+    {
+        Self(Rc::new(RefCell::new(f)))
+    }
+
+    pub(crate) fn call(&self, comment: &Comment) {
+        (self.0.borrow_mut())(comment)
+    }
+}
+
+impl fmt::Debug for OnComment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OnComment")
+    }
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            source_file: None,
+            early_errors: Severity::default(),
+            error_recovery: false,
+            ecma_version: LATEST_ECMA_VERSION,
+            source_type: SourceType::default(),
+            allow_return_outside_function: false,
+            allow_import_export_everywhere: false,
+            allow_await_outside_function: false,
+            allow_hash_bang: None,
+            allow_reserved: None,
+            on_comment: None,
+            attach_comments: false,
+            parse_regex_ast: false,
+            position_encoding: PositionEncoding::default(),
+            locations: true,
+            ranges: false,
+        }
+    }
+}
+
+impl ParserOptions {
+    /// The ECMAScript edition number, e.g. 2015 is normalized to 6.
+    pub fn ecma_edition(&self) -> u32 {
+        if self.ecma_version >= 2015 {
+            self.ecma_version - 2009
+        } else {
+            self.ecma_version
+        }
+    }
+
+    pub fn allows_hash_bang(&self) -> bool {
+        self.allow_hash_bang
+            .unwrap_or_else(|| self.ecma_edition() >= 14)
+    }
+
+    pub fn allows_reserved(&self) -> bool {
+        self.allow_reserved
+            .unwrap_or_else(|| self.ecma_edition() < 5)
+    }
+}
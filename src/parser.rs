@@ -1,4 +1,9 @@
-use crate::ast::{Position, _LocationNode, get_location_at, AstNodePos};
+use crate::ast::comment::Comment;
+use crate::ast::location::{FinishLocations, LocationConfig};
+use crate::ast::{_LocationNode, get_location_at, AstNodePos, Position};
+use crate::error::{EarlyErrorCode, ParseError, ParseErrorKind, ParseResult, Severity};
+use crate::line_index::LineIndex;
+use crate::options::{ParserOptions, PositionEncoding, SourceType};
 use crate::statement::scope::{
     Scope, SCOPE_ARROW, SCOPE_ASYNC, SCOPE_CLASS_STATIC_BLOCK, SCOPE_DIRECT_SUPER, SCOPE_FUNCTION,
     SCOPE_GENERATOR, SCOPE_SUPER, SCOPE_TOP, SCOPE_VAR,
@@ -7,8 +12,12 @@ use crate::statement::util::{can_insert_semicolon, unexpected};
 use crate::tokenizer::context::{get_context_by_label, TokenContext, TokenContextLabel};
 use crate::tokenizer::js_token::Token;
 use crate::tokenizer::js_token::TokenLabel;
+use crate::tokenizer::space::skip_line_comment;
 use crate::tokenizer::{next_token, TokenResult};
-use std::collections::HashMap;
+use bumpalo::Bump;
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
 use std::string::String;
 use std::vec;
 
@@ -25,11 +34,14 @@ pub enum StatementContext {
 }
 
 #[derive(Debug)]
-pub struct Parser {
-    pub content: String,
-    pub chars: Vec<char>,
-    pub codes: Vec<u32>,
-    /// 当前光标所在的字符位置
+pub struct Parser<'a> {
+    /// 源码，token 与 AST 中的文本尽量直接引用其中的片段
+    pub content: &'a str,
+    /// AST 节点分配所在的 arena
+    pub arena: &'a Bump,
+    /// 源文件路径，只在 arena 中分配一次，所有节点的 SourceLocation 共享
+    pub source_file: Option<&'a str>,
+    /// 当前光标所在的字节位置，所有的位置信息都是字节偏移
     pub cursor: usize,
     /// 是否允许解析正则表达式
     pub expr_allowed: bool,
@@ -46,9 +58,9 @@ pub struct Parser {
     /// 上个 token 结束 loc
     pub last_token_end_loc: Option<Position>,
     /// 当前解析得到的 token
-    pub cur_token: Option<Token>,
+    pub cur_token: Option<Token<'a>>,
     /// 上个解析得到的 token
-    pub prev_token: Option<Token>,
+    pub prev_token: Option<Token<'a>>,
     /// 当前 token 的位置
     pub cur_token_start: usize,
     pub cur_token_end: usize,
@@ -73,8 +85,6 @@ pub struct Parser {
     pub await_ident_pos: usize,
     pub yield_pos: usize,
     pub await_pos: usize,
-    /// 当前解析的源文件
-    pub source_file: Option<String>,
     /// 用于 new 操作符后不允许解析 call 表达式中的情况
     pub disable_call_expr: bool,
     /// 用于禁止解析表达式在 forin 中可能出现的 in operator
@@ -84,6 +94,22 @@ pub struct Parser {
     pub private_name_stack: Vec<PrivateNameInfo>,
     /// statment labels
     pub labels: Vec<Label>,
+    /// 作为警告记录下来的 early error，以及容错模式下记录的语法错误
+    pub diagnostics: Vec<ParseError>,
+    /// 解析配置
+    pub options: ParserOptions,
+    /// 规范化后的 ecma 版本号，例如 2015 对应 6
+    pub ecma_version: u32,
+    /// 模块中已经导出的名称，用于检查重复导出
+    pub exports: HashSet<String>,
+    /// 已经跳过的注释
+    pub comments: Vec<Comment<'a>>,
+    /// 文件开头 #! 之后的内容
+    pub hashbang: Option<&'a str>,
+    /// 解析时始终使用字节位置并记录 loc，结果中的位置信息在最后统一处理，不需要处理时为 None
+    pub location_config: Option<LocationConfig>,
+    /// 源码的行索引，用于计算错误的位置
+    line_index: OnceCell<LineIndex>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -102,7 +128,8 @@ pub struct PrivateNameProp {
 #[derive(Debug)]
 pub struct PrivateNameInfo {
     pub declared: HashMap<String, PrivateNameProp>,
-    pub used: Vec<String>,
+    /// 使用到的 private name 以及其位置
+    pub used: Vec<(String, usize)>,
 }
 
 #[derive(Debug, Clone)]
@@ -119,26 +146,53 @@ pub enum LabelKind {
     Custom,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ForInitType {
     Normal,
     Await,
 }
 
-impl Parser {
-    pub fn new(content: String) -> Parser {
-        let mut chars = vec![];
-        let mut codes = vec![];
-        for char in content.chars() {
-            chars.push(char);
-            codes.push(char as u32);
-        }
-        Parser {
+/// 容错模式下，在解析一条语句之前保存的解析状态，用于出错后回退到语句的开始位置
+pub struct Checkpoint<'a> {
+    cursor: usize,
+    line: usize,
+    line_start: usize,
+    expr_allowed: bool,
+    contains_esc: bool,
+    cur_token: Option<Token<'a>>,
+    prev_token: Option<Token<'a>>,
+    cur_token_start: usize,
+    cur_token_end: usize,
+    cur_token_start_loc: Option<Position>,
+    cur_token_end_loc: Option<Position>,
+    last_token_start: usize,
+    last_token_end: usize,
+    last_token_start_loc: Option<Position>,
+    last_token_end_loc: Option<Position>,
+    token_context: Vec<TokenContext>,
+    strict_mode: bool,
+    scope_len: usize,
+    stmt_context_len: usize,
+    private_name_len: usize,
+    labels: Vec<Label>,
+    for_init: Option<ForInitType>,
+    disable_call_expr: bool,
+    disable_in_op: bool,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(arena: &'a Bump, content: &'a str, options: ParserOptions) -> Parser<'a> {
+        let allow_hash_bang = options.allows_hash_bang() && content.starts_with("#!");
+        let source_file = options
+            .source_file
+            .as_deref()
+            .map(|file| &*arena.alloc_str(file));
+        let mut parser = Parser {
             content,
-            chars,
-            codes,
+            arena,
+            source_file,
             cursor: 0,
-            expr_allowed: false,
+            expr_allowed: true,
             line: 1,
             line_start: 0,
             last_token_end: 0,
@@ -151,7 +205,7 @@ impl Parser {
             cur_token_end: 0,
             cur_token_start_loc: None,
             cur_token_end_loc: None,
-            token_context: vec![],
+            token_context: vec![get_context_by_label(TokenContextLabel::BraceStat)],
             strict_mode: false,
             contains_esc: false,
             stmt_context: vec![StatementContext::TopLevel],
@@ -161,16 +215,32 @@ impl Parser {
             await_ident_pos: 0,
             yield_pos: 0,
             await_pos: 0,
-            source_file: None,
             disable_call_expr: false,
             disable_in_op: false,
             for_init: None,
             private_name_stack: vec![],
             labels: vec![],
+            diagnostics: vec![],
+            ecma_version: options.ecma_edition(),
+            exports: HashSet::new(),
+            comments: vec![],
+            hashbang: None,
+            location_config: LocationConfig::new(content, &options),
+            line_index: OnceCell::new(),
+            options,
+        };
+        // 模块默认是严格模式
+        parser.strict_mode = parser.in_module();
+        // 将文件开头的 #! 当作单行注释跳过，和 acorn 一样它也会出现在注释列表中
+        if allow_hash_bang {
+            skip_line_comment(&mut parser, 2);
+            parser.hashbang = parser.comments.first().map(|comment| comment.value);
         }
+
+        parser
     }
 
-    pub fn next(&mut self) -> TokenResult {
+    pub fn next(&mut self) -> TokenResult<'a> {
         self.last_token_start = self.cur_token_start;
         self.last_token_end = self.cur_token_end;
         self.last_token_start_loc = self.cur_token_start_loc.clone();
@@ -179,18 +249,9 @@ impl Parser {
         next_token(self)
     }
 
-    pub fn next_unwrap(&mut self) -> Token {
-        let next_token_res = self.next();
-        if next_token_res.is_err() {
-            panic!("read token error: {:?}", next_token_res.unwrap_err());
-        }
-
-        next_token_res.unwrap()
-    }
-
     pub fn cur_token_test<F>(&self, f: F) -> bool
     where
-        F: FnOnce(&Token) -> bool,
+        F: FnOnce(&Token<'a>) -> bool,
     {
         self.cur_token.as_ref().map_or(false, f)
     }
@@ -199,17 +260,18 @@ impl Parser {
         self.cur_token.as_ref().map_or(false, |t| t.label == label)
     }
 
-    pub fn get_cur_token_value(&self) -> String {
-        let default_val = "".to_string();
+    /// 当前 token 的值，没有转义字符的标识符与字符串直接引用源码，不会复制
+    pub fn get_cur_token_value(&self) -> Cow<'a, str> {
         self.cur_token
             .as_ref()
-            .map_or(default_val, |t| t.value.clone().unwrap_or("".to_string()))
+            .and_then(|t| t.value.clone())
+            .unwrap_or_default()
     }
 
     pub fn cur_token_value_is(&self, val: &str) -> bool {
         self.cur_token
             .as_ref()
-            .map_or(false, |t| t.value.clone().map_or(false, |v| v == val))
+            .map_or(false, |t| t.value.as_deref() == Some(val))
     }
 
     pub fn get_cursor_position(&self) -> Position {
@@ -219,6 +281,153 @@ impl Parser {
         }
     }
 
+    /// 计算任意字节位置所在的行列，只在第一次用到时建立行索引
+    pub fn position_at(&self, pos: usize) -> Position {
+        self.line_index
+            .get_or_init(|| LineIndex::new(self.content, PositionEncoding::Utf8))
+            .position(pos)
+    }
+
+    /// 构造一个位于 pos 处的错误
+    pub fn raise(&self, pos: usize, kind: ParseErrorKind, message: &str) -> ParseError {
+        ParseError::new(kind, message, pos, self.position_at(pos))
+    }
+
+    /// 当前配置的 ecma 版本低于 version 时，返回 feature 不被支持的错误
+    pub fn expect_ecma_version(&self, version: u32, pos: usize, feature: &str) -> ParseResult<()> {
+        if self.ecma_version >= version {
+            return Ok(());
+        }
+        let name = if version >= 6 {
+            format!("ECMAScript {}", version + 2009)
+        } else {
+            format!("ECMAScript {}", version)
+        };
+        Err(self.raise(
+            pos,
+            ParseErrorKind::UnsupportedSyntax,
+            &format!("{} is not supported before {}", feature, name),
+        ))
+    }
+
+    /// 报告一个位于 pos 处的 early error，根据配置直接返回错误或者记录为警告后继续解析
+    pub fn raise_early(
+        &mut self,
+        pos: usize,
+        code: EarlyErrorCode,
+        message: &str,
+    ) -> ParseResult<()> {
+        let mut err = self.raise(pos, ParseErrorKind::EarlyError(code), message);
+        match self.options.early_errors {
+            Severity::Error => Err(err),
+            Severity::Warning => {
+                err.severity = Severity::Warning;
+                self.diagnostics.push(err);
+                Ok(())
+            }
+        }
+    }
+
+    /// 容错模式下记录一个语法错误，同一个位置的相同错误只记录一次
+    pub fn record_error(&mut self, err: ParseError) {
+        if !self
+            .diagnostics
+            .iter()
+            .any(|d| d.pos == err.pos && d.message == err.message)
+        {
+            self.diagnostics.push(err);
+        }
+    }
+
+    /// 记录一条注释，容错模式下回退后重复扫描到的注释会被忽略
+    pub fn push_comment(&mut self, comment: Comment<'a>) {
+        if self
+            .comments
+            .last()
+            .is_some_and(|last| last.start >= comment.start)
+        {
+            return;
+        }
+        if let Some(on_comment) = &self.options.on_comment {
+            match &self.location_config {
+                Some(config) => {
+                    let mut comment = comment.clone();
+                    comment.finish_locations(config);
+                    on_comment.call(&comment);
+                }
+                None => on_comment.call(&comment),
+            }
+        }
+        self.comments.push(comment);
+    }
+
+    /// 将光标移动到 pos 处，同时更新行信息
+    pub fn reset_cursor(&mut self, pos: usize) {
+        let pos = pos.min(self.content.len());
+        let position = self.position_at(pos);
+        self.cursor = pos;
+        self.line = position.line;
+        self.line_start = pos - position.col;
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint<'a> {
+        Checkpoint {
+            cursor: self.cursor,
+            line: self.line,
+            line_start: self.line_start,
+            expr_allowed: self.expr_allowed,
+            contains_esc: self.contains_esc,
+            cur_token: self.cur_token.clone(),
+            prev_token: self.prev_token.clone(),
+            cur_token_start: self.cur_token_start,
+            cur_token_end: self.cur_token_end,
+            cur_token_start_loc: self.cur_token_start_loc.clone(),
+            cur_token_end_loc: self.cur_token_end_loc.clone(),
+            last_token_start: self.last_token_start,
+            last_token_end: self.last_token_end,
+            last_token_start_loc: self.last_token_start_loc.clone(),
+            last_token_end_loc: self.last_token_end_loc.clone(),
+            token_context: self.token_context.clone(),
+            strict_mode: self.strict_mode,
+            scope_len: self.scope_stack.len(),
+            stmt_context_len: self.stmt_context.len(),
+            private_name_len: self.private_name_stack.len(),
+            labels: self.labels.clone(),
+            for_init: self.for_init.clone(),
+            disable_call_expr: self.disable_call_expr,
+            disable_in_op: self.disable_in_op,
+        }
+    }
+
+    /// 回退到 checkpoint 保存的状态
+    pub fn rewind(&mut self, checkpoint: Checkpoint<'a>) {
+        self.cursor = checkpoint.cursor;
+        self.line = checkpoint.line;
+        self.line_start = checkpoint.line_start;
+        self.expr_allowed = checkpoint.expr_allowed;
+        self.contains_esc = checkpoint.contains_esc;
+        self.cur_token = checkpoint.cur_token;
+        self.prev_token = checkpoint.prev_token;
+        self.cur_token_start = checkpoint.cur_token_start;
+        self.cur_token_end = checkpoint.cur_token_end;
+        self.cur_token_start_loc = checkpoint.cur_token_start_loc;
+        self.cur_token_end_loc = checkpoint.cur_token_end_loc;
+        self.last_token_start = checkpoint.last_token_start;
+        self.last_token_end = checkpoint.last_token_end;
+        self.last_token_start_loc = checkpoint.last_token_start_loc;
+        self.last_token_end_loc = checkpoint.last_token_end_loc;
+        self.token_context = checkpoint.token_context;
+        self.strict_mode = checkpoint.strict_mode;
+        self.scope_stack.truncate(checkpoint.scope_len);
+        self.stmt_context.truncate(checkpoint.stmt_context_len);
+        self.private_name_stack
+            .truncate(checkpoint.private_name_len);
+        self.labels = checkpoint.labels;
+        self.for_init = checkpoint.for_init;
+        self.disable_call_expr = checkpoint.disable_call_expr;
+        self.disable_in_op = checkpoint.disable_in_op;
+    }
+
     // token context
     pub fn cur_token_ctx(&self) -> Option<&TokenContext> {
         self.token_context.last()
@@ -247,19 +456,21 @@ impl Parser {
         }
     }
 
-    pub fn eat_contextual(&mut self, n: &str) -> bool {
+    pub fn eat_contextual(&mut self, n: &str) -> ParseResult<bool> {
         if self.is_contextual(n) {
-            self.next_unwrap();
-            return true;
+            self.next()?;
+            return Ok(true);
         }
 
-        false
+        Ok(false)
     }
 
-    pub fn expect_contexual(&mut self, n: &str) {
-        if !self.eat_contextual(n) {
-            unexpected(self.cur_token.clone().unwrap())
+    pub fn expect_contexual(&mut self, n: &str) -> ParseResult<()> {
+        if !self.eat_contextual(n)? {
+            return Err(unexpected(self));
         }
+
+        Ok(())
     }
 
     pub fn is_contextual(&self, n: &str) -> bool {
@@ -279,13 +490,20 @@ impl Parser {
 
     pub fn cur_this_scope(&self) -> Option<&Scope> {
         for sc in self.scope_stack.iter().rev() {
-            if sc.flags & SCOPE_VAR > 0 && !(sc.flags & SCOPE_ARROW) > 0 {
+            if sc.flags & SCOPE_VAR > 0 && sc.flags & SCOPE_ARROW == 0 {
                 return Some(sc);
             }
         }
         None
     }
 
+    pub fn cur_this_scope_mut(&mut self) -> Option<&mut Scope> {
+        self.scope_stack
+            .iter_mut()
+            .rev()
+            .find(|sc| sc.flags & SCOPE_VAR > 0 && sc.flags & SCOPE_ARROW == 0)
+    }
+
     pub fn cur_var_scope(&self) -> Option<&Scope> {
         for sc in self.scope_stack.iter().rev() {
             if sc.flags & SCOPE_VAR > 0 {
@@ -295,12 +513,8 @@ impl Parser {
         None
     }
 
-    pub fn cur_scope(&self) -> Option<&Scope> {
-        self.scope_stack.last()
-    }
-
     pub fn allow_super(&self) -> bool {
-        let cur_scope_op = self.cur_scope();
+        let cur_scope_op = self.cur_this_scope();
         if let Some(Scope {
             flags,
             in_class_field_init,
@@ -340,21 +554,21 @@ impl Parser {
     }
 
     pub fn in_function_scope(&self) -> bool {
-        if let Some(scp) = self.cur_scope() {
+        if let Some(scp) = self.cur_var_scope() {
             return scp.flags & SCOPE_FUNCTION > 0;
         }
         false
     }
 
     pub fn in_generator_scope(&self) -> bool {
-        if let Some(scp) = self.cur_scope() {
+        if let Some(scp) = self.cur_var_scope() {
             return scp.flags & SCOPE_GENERATOR > 0 && !scp.in_class_field_init;
         }
         false
     }
 
     pub fn in_async_scope(&self) -> bool {
-        if let Some(scp) = self.cur_scope() {
+        if let Some(scp) = self.cur_var_scope() {
             return scp.flags & SCOPE_ASYNC > 0 && !scp.in_class_field_init;
         }
         false
@@ -370,18 +584,23 @@ impl Parser {
             }
         }
 
-        return false;
+        // es2022 开始模块中允许使用顶层 await
+        (self.in_module() && self.ecma_version >= 13) || self.options.allow_await_outside_function
+    }
+
+    pub fn in_module(&self) -> bool {
+        self.options.source_type == SourceType::Module
     }
 
     pub fn in_class_field_init(&self) -> bool {
-        if let Some(scp) = self.cur_scope() {
+        if let Some(scp) = self.cur_this_scope() {
             return scp.in_class_field_init;
         }
         false
     }
 
     pub fn in_class_static_block(&self) -> bool {
-        if let Some(scp) = self.cur_scope() {
+        if let Some(scp) = self.cur_var_scope() {
             return scp.flags & SCOPE_CLASS_STATIC_BLOCK > 0;
         }
         false
@@ -402,38 +621,36 @@ impl Parser {
         )
     }
 
-    pub fn compose_loc_info(&self, start_loc: _LocationNode) -> AstNodePos {
-        (
-            start_loc,
-            self.end_location_node(),
-            self.source_file.clone(),
-        )
+    pub fn compose_loc_info(&self, start_loc: _LocationNode) -> AstNodePos<'a> {
+        (start_loc, self.end_location_node(), self.source_file)
     }
 
     /// if current token is label, consume it.
-    pub fn eat(&mut self, label: TokenLabel) -> bool {
+    pub fn eat(&mut self, label: TokenLabel) -> ParseResult<bool> {
         if self.cur_token_is(label) {
-            self.next_unwrap();
-            true
+            self.next()?;
+            Ok(true)
         } else {
-            false
+            Ok(false)
         }
     }
 
     /// assert the current token label
-    pub fn expect(&mut self, label: TokenLabel) -> bool {
-        if self.eat(label) {
-            return true;
+    pub fn expect(&mut self, label: TokenLabel) -> ParseResult<()> {
+        if self.eat(label)? {
+            return Ok(());
         }
 
-        panic!("Unexpected token");
+        Err(unexpected(self))
     }
 
     // consume a semicolon or check if a semicolon could appear in that position
-    pub fn semicolon(&mut self) {
-        if !self.eat(TokenLabel::Semi) && !can_insert_semicolon(self) {
-            unexpected(self.cur_token.clone().unwrap())
+    pub fn semicolon(&mut self) -> ParseResult<()> {
+        if !self.eat(TokenLabel::Semi)? && !can_insert_semicolon(self) {
+            return Err(unexpected(self));
         }
+
+        Ok(())
     }
 
     // statement context
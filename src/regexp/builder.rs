@@ -0,0 +1,576 @@
+// 在校验正则的同时构建 AST，校验器在每个语法结构确定之后调用对应的方法，
+// 传入的位置为 pattern 的 UTF-16 下标，这里负责转换为源码中的字符位置
+use crate::ast::regexp::{
+    Alternative, Assertion, AssertionKind, Backreference, BackreferenceRef, CapturingGroup,
+    Character, CharacterClass, CharacterClassRange, CharacterSet, CharacterSetKind, ClassElement,
+    ClassIntersection, ClassStringDisjunction, ClassSubtraction, ExpressionCharacterClass, Group,
+    Pattern, Quantifier, RegExpElement, RegExpNodeType, StringAlternative,
+};
+
+/// 尚未结束的节点
+enum Frame {
+    Pattern {
+        start: usize,
+        alternatives: Vec<Alternative>,
+    },
+    Alternative {
+        start: usize,
+        elements: Vec<RegExpElement>,
+    },
+    Group {
+        start: usize,
+        alternatives: Vec<Alternative>,
+    },
+    CapturingGroup {
+        start: usize,
+        name: Option<String>,
+        alternatives: Vec<Alternative>,
+    },
+    Lookaround {
+        start: usize,
+        kind: AssertionKind,
+        negate: bool,
+        alternatives: Vec<Alternative>,
+    },
+    CharacterClass {
+        start: usize,
+        negate: bool,
+        elements: Vec<ClassElement>,
+    },
+    ClassStringDisjunction {
+        start: usize,
+        alternatives: Vec<StringAlternative>,
+    },
+    StringAlternative {
+        start: usize,
+        elements: Vec<Character>,
+    },
+}
+
+pub struct AstBuilder {
+    pattern: String,
+    /// pattern 第一个字符在源码中的字节位置
+    offset: usize,
+    /// UTF-16 下标到 pattern 中字节下标的映射
+    unit_to_byte: Vec<usize>,
+    unicode_sets: bool,
+    stack: Vec<Frame>,
+    result: Option<Pattern>,
+}
+
+impl AstBuilder {
+    pub fn new(pattern: &str, offset: usize, unicode_sets: bool) -> Self {
+        let mut unit_to_byte = vec![];
+        for (i, c) in pattern.char_indices() {
+            unit_to_byte.push(i);
+            if c.len_utf16() == 2 {
+                // 非 unicode 模式下代理对会被拆开，后半部分对应字符的结尾
+                unit_to_byte.push(i + c.len_utf8());
+            }
+        }
+        unit_to_byte.push(pattern.len());
+        Self {
+            pattern: pattern.to_string(),
+            offset,
+            unit_to_byte,
+            unicode_sets,
+            stack: vec![],
+            result: None,
+        }
+    }
+
+    pub fn finish(self) -> Option<Pattern> {
+        self.result
+    }
+
+    /// 将 UTF-16 下标转换为源码中的字节位置
+    fn pos(&self, unit: usize) -> usize {
+        self.offset + self.unit_to_byte[unit]
+    }
+
+    fn raw(&self, start: usize, end: usize) -> String {
+        self.pattern[start - self.offset..end - self.offset].to_string()
+    }
+
+    pub fn reset(&mut self) {
+        self.stack.clear();
+        self.result = None;
+    }
+
+    pub fn pattern_enter(&mut self, start: usize) {
+        let start = self.pos(start);
+        self.stack.push(Frame::Pattern {
+            start,
+            alternatives: vec![],
+        });
+    }
+
+    pub fn pattern_leave(&mut self, end: usize) {
+        let end = self.pos(end);
+        if let Some(Frame::Pattern {
+            start,
+            alternatives,
+        }) = self.stack.pop()
+        {
+            self.result = Some(Pattern {
+                _type: RegExpNodeType::Pattern,
+                start,
+                end,
+                raw: self.raw(start, end),
+                alternatives,
+            });
+        }
+    }
+
+    pub fn alternative_enter(&mut self, start: usize) {
+        let start = self.pos(start);
+        self.stack.push(Frame::Alternative {
+            start,
+            elements: vec![],
+        });
+    }
+
+    pub fn alternative_leave(&mut self, end: usize) {
+        let end = self.pos(end);
+        if let Some(Frame::Alternative { start, elements }) = self.stack.pop() {
+            let node = Alternative {
+                _type: RegExpNodeType::Alternative,
+                start,
+                end,
+                raw: self.raw(start, end),
+                elements,
+            };
+            if let Some(
+                Frame::Pattern { alternatives, .. }
+                | Frame::Group { alternatives, .. }
+                | Frame::CapturingGroup { alternatives, .. }
+                | Frame::Lookaround { alternatives, .. },
+            ) = self.stack.last_mut()
+            {
+                alternatives.push(node);
+            }
+        }
+    }
+
+    pub fn group_enter(&mut self, start: usize) {
+        let start = self.pos(start);
+        self.stack.push(Frame::Group {
+            start,
+            alternatives: vec![],
+        });
+    }
+
+    pub fn group_leave(&mut self, end: usize) {
+        let end = self.pos(end);
+        if let Some(Frame::Group {
+            start,
+            alternatives,
+        }) = self.stack.pop()
+        {
+            let node = Group {
+                _type: RegExpNodeType::Group,
+                start,
+                end,
+                raw: self.raw(start, end),
+                alternatives,
+            };
+            self.push_element(RegExpElement::Group(node));
+        }
+    }
+
+    pub fn capturing_group_enter(&mut self, start: usize, name: Option<String>) {
+        let start = self.pos(start);
+        self.stack.push(Frame::CapturingGroup {
+            start,
+            name,
+            alternatives: vec![],
+        });
+    }
+
+    pub fn capturing_group_leave(&mut self, end: usize) {
+        let end = self.pos(end);
+        if let Some(Frame::CapturingGroup {
+            start,
+            name,
+            alternatives,
+        }) = self.stack.pop()
+        {
+            let node = CapturingGroup {
+                _type: RegExpNodeType::CapturingGroup,
+                start,
+                end,
+                raw: self.raw(start, end),
+                name,
+                alternatives,
+            };
+            self.push_element(RegExpElement::CapturingGroup(node));
+        }
+    }
+
+    pub fn lookaround_enter(&mut self, start: usize, kind: AssertionKind, negate: bool) {
+        let start = self.pos(start);
+        self.stack.push(Frame::Lookaround {
+            start,
+            kind,
+            negate,
+            alternatives: vec![],
+        });
+    }
+
+    pub fn lookaround_leave(&mut self, end: usize) {
+        let end = self.pos(end);
+        if let Some(Frame::Lookaround {
+            start,
+            kind,
+            negate,
+            alternatives,
+        }) = self.stack.pop()
+        {
+            let node = Assertion {
+                _type: RegExpNodeType::Assertion,
+                start,
+                end,
+                raw: self.raw(start, end),
+                kind,
+                negate: Some(negate),
+                alternatives: Some(alternatives),
+            };
+            self.push_element(RegExpElement::Assertion(node));
+        }
+    }
+
+    /// `^`、`$`、`\b`、`\B`
+    pub fn boundary_assertion(
+        &mut self,
+        start: usize,
+        end: usize,
+        kind: AssertionKind,
+        negate: Option<bool>,
+    ) {
+        let (start, end) = (self.pos(start), self.pos(end));
+        let node = Assertion {
+            _type: RegExpNodeType::Assertion,
+            start,
+            end,
+            raw: self.raw(start, end),
+            kind,
+            negate,
+            alternatives: None,
+        };
+        self.push_element(RegExpElement::Assertion(node));
+    }
+
+    /// 使用量词包裹当前 alternative 中的最后一个元素
+    pub fn quantifier(&mut self, end: usize, min: u32, max: Option<u32>, greedy: bool) {
+        let end = self.pos(end);
+        let Some(Frame::Alternative { elements, .. }) = self.stack.last_mut() else {
+            return;
+        };
+        let Some(element) = elements.pop() else {
+            return;
+        };
+        let start = element.start();
+        let node = Quantifier {
+            _type: RegExpNodeType::Quantifier,
+            start,
+            end,
+            raw: self.raw(start, end),
+            min,
+            max,
+            greedy,
+            element: Box::new(element),
+        };
+        self.push_element(RegExpElement::Quantifier(node));
+    }
+
+    /// `.`、`\d`、`\s`、`\w` 以及它们的大写形式
+    pub fn character_set(
+        &mut self,
+        start: usize,
+        end: usize,
+        kind: CharacterSetKind,
+        negate: Option<bool>,
+    ) {
+        self.push_character_set(start, end, kind, negate, (None, None, None));
+    }
+
+    /// `\p{key=value}`、`\p{key}`
+    pub fn property_character_set(
+        &mut self,
+        start: usize,
+        end: usize,
+        negate: bool,
+        key: String,
+        value: Option<String>,
+        strings: bool,
+    ) {
+        self.push_character_set(
+            start,
+            end,
+            CharacterSetKind::Property,
+            Some(negate),
+            (Some(key), value, Some(strings)),
+        );
+    }
+
+    fn push_character_set(
+        &mut self,
+        start: usize,
+        end: usize,
+        kind: CharacterSetKind,
+        negate: Option<bool>,
+        (key, value, strings): (Option<String>, Option<String>, Option<bool>),
+    ) {
+        let (start, end) = (self.pos(start), self.pos(end));
+        let node = CharacterSet {
+            _type: RegExpNodeType::CharacterSet,
+            start,
+            end,
+            raw: self.raw(start, end),
+            kind,
+            negate,
+            key,
+            value,
+            strings,
+        };
+        match self.stack.last_mut() {
+            Some(Frame::CharacterClass { elements, .. }) => {
+                elements.push(ClassElement::CharacterSet(node))
+            }
+            _ => self.push_element(RegExpElement::CharacterSet(node)),
+        }
+    }
+
+    pub fn character(&mut self, start: usize, end: usize, value: u32) {
+        let (start, end) = (self.pos(start), self.pos(end));
+        let node = Character {
+            _type: RegExpNodeType::Character,
+            start,
+            end,
+            raw: self.raw(start, end),
+            value,
+        };
+        match self.stack.last_mut() {
+            Some(Frame::CharacterClass { elements, .. }) => {
+                elements.push(ClassElement::Character(node))
+            }
+            Some(Frame::StringAlternative { elements, .. }) => elements.push(node),
+            _ => self.push_element(RegExpElement::Character(node)),
+        }
+    }
+
+    pub fn backreference(&mut self, start: usize, end: usize, reference: BackreferenceRef) {
+        let (start, end) = (self.pos(start), self.pos(end));
+        let node = Backreference {
+            _type: RegExpNodeType::Backreference,
+            start,
+            end,
+            raw: self.raw(start, end),
+            reference,
+        };
+        self.push_element(RegExpElement::Backreference(node));
+    }
+
+    pub fn character_class_enter(&mut self, start: usize, negate: bool) {
+        let start = self.pos(start);
+        self.stack.push(Frame::CharacterClass {
+            start,
+            negate,
+            elements: vec![],
+        });
+    }
+
+    pub fn character_class_leave(&mut self, end: usize) {
+        let end = self.pos(end);
+        let Some(Frame::CharacterClass {
+            start,
+            negate,
+            mut elements,
+        }) = self.stack.pop()
+        else {
+            return;
+        };
+        let raw = self.raw(start, end);
+        // 只包含交集或者差集的集合使用 ExpressionCharacterClass 表示
+        let is_expression = elements.len() == 1
+            && matches!(
+                elements[0],
+                ClassElement::ClassIntersection(_) | ClassElement::ClassSubtraction(_)
+            );
+        if is_expression {
+            let node = ExpressionCharacterClass {
+                _type: RegExpNodeType::ExpressionCharacterClass,
+                start,
+                end,
+                raw,
+                negate,
+                expression: Box::new(elements.pop().unwrap()),
+            };
+            match self.stack.last_mut() {
+                Some(Frame::CharacterClass { elements, .. }) => {
+                    elements.push(ClassElement::ExpressionCharacterClass(node))
+                }
+                _ => self.push_element(RegExpElement::ExpressionCharacterClass(node)),
+            }
+            return;
+        }
+        let node = CharacterClass {
+            _type: RegExpNodeType::CharacterClass,
+            start,
+            end,
+            raw,
+            unicode_sets: self.unicode_sets,
+            negate,
+            elements,
+        };
+        match self.stack.last_mut() {
+            Some(Frame::CharacterClass { elements, .. }) => {
+                elements.push(ClassElement::CharacterClass(node))
+            }
+            _ => self.push_element(RegExpElement::CharacterClass(node)),
+        }
+    }
+
+    /// 将最后的三个字符 `a`、`-`、`z` 合并为一个范围
+    pub fn character_class_range(&mut self) {
+        let Some(Frame::CharacterClass { elements, .. }) = self.stack.last_mut() else {
+            return;
+        };
+        if elements.len() < 3 {
+            return;
+        }
+        let mut tail = elements.split_off(elements.len() - 3);
+        let (Some(ClassElement::Character(max)), _, Some(ClassElement::Character(min))) =
+            (tail.pop(), tail.pop(), tail.pop())
+        else {
+            return;
+        };
+        let (start, end) = (min.start, max.end);
+        let node = CharacterClassRange {
+            _type: RegExpNodeType::CharacterClassRange,
+            start,
+            end,
+            raw: self.raw(start, end),
+            min,
+            max,
+        };
+        if let Some(Frame::CharacterClass { elements, .. }) = self.stack.last_mut() {
+            elements.push(ClassElement::CharacterClassRange(node));
+        }
+    }
+
+    /// 将最后的两个操作数合并为交集，`subtraction` 为 true 时合并为差集
+    pub fn class_set_operation(&mut self, end: usize, subtraction: bool) {
+        let end = self.pos(end);
+        let Some(Frame::CharacterClass { elements, .. }) = self.stack.last_mut() else {
+            return;
+        };
+        if elements.len() < 2 {
+            return;
+        }
+        let right = Box::new(elements.pop().unwrap());
+        let left = Box::new(elements.pop().unwrap());
+        let start = left.start();
+        let raw = self.raw(start, end);
+        let node = if subtraction {
+            ClassElement::ClassSubtraction(ClassSubtraction {
+                _type: RegExpNodeType::ClassSubtraction,
+                start,
+                end,
+                raw,
+                left,
+                right,
+            })
+        } else {
+            ClassElement::ClassIntersection(ClassIntersection {
+                _type: RegExpNodeType::ClassIntersection,
+                start,
+                end,
+                raw,
+                left,
+                right,
+            })
+        };
+        if let Some(Frame::CharacterClass { elements, .. }) = self.stack.last_mut() {
+            elements.push(node);
+        }
+    }
+
+    pub fn class_string_disjunction_enter(&mut self, start: usize) {
+        let start = self.pos(start);
+        self.stack.push(Frame::ClassStringDisjunction {
+            start,
+            alternatives: vec![],
+        });
+    }
+
+    pub fn class_string_disjunction_leave(&mut self, end: usize) {
+        let end = self.pos(end);
+        if let Some(Frame::ClassStringDisjunction {
+            start,
+            alternatives,
+        }) = self.stack.pop()
+        {
+            let node = ClassStringDisjunction {
+                _type: RegExpNodeType::ClassStringDisjunction,
+                start,
+                end,
+                raw: self.raw(start, end),
+                alternatives,
+            };
+            if let Some(Frame::CharacterClass { elements, .. }) = self.stack.last_mut() {
+                elements.push(ClassElement::ClassStringDisjunction(node));
+            }
+        }
+    }
+
+    pub fn string_alternative_enter(&mut self, start: usize) {
+        let start = self.pos(start);
+        self.stack.push(Frame::StringAlternative {
+            start,
+            elements: vec![],
+        });
+    }
+
+    pub fn string_alternative_leave(&mut self, end: usize) {
+        let end = self.pos(end);
+        if let Some(Frame::StringAlternative { start, elements }) = self.stack.pop() {
+            let node = StringAlternative {
+                _type: RegExpNodeType::StringAlternative,
+                start,
+                end,
+                raw: self.raw(start, end),
+                elements,
+            };
+            if let Some(Frame::ClassStringDisjunction { alternatives, .. }) = self.stack.last_mut()
+            {
+                alternatives.push(node);
+            }
+        }
+    }
+
+    /// 当前字符集合中已有的元素个数，用于回溯
+    pub fn class_elements_len(&self) -> usize {
+        match self.stack.last() {
+            Some(Frame::CharacterClass { elements, .. }) => elements.len(),
+            _ => 0,
+        }
+    }
+
+    pub fn truncate_class_elements(&mut self, len: usize) {
+        if let Some(Frame::CharacterClass { elements, .. }) = self.stack.last_mut() {
+            elements.truncate(len);
+        }
+    }
+
+    /// 回溯时丢弃尚未结束的节点
+    pub fn discard(&mut self) {
+        self.stack.pop();
+    }
+
+    fn push_element(&mut self, element: RegExpElement) {
+        if let Some(Frame::Alternative { elements, .. }) = self.stack.last_mut() {
+            elements.push(element);
+        }
+    }
+}
@@ -0,0 +1,1401 @@
+//! 正则表达式字面量的校验，参考 acorn 的 `RegExpValidationState`，
+//! 按照 ECMAScript 的 Pattern 语法逐个字符地检查，而不是交给 Rust 的 regex 库处理
+mod builder;
+mod unicode_property;
+
+use self::builder::AstBuilder;
+use crate::ast::regexp::{AssertionKind, BackreferenceRef, CharacterSetKind, RegExpAst};
+use crate::tokenizer::util::{is_identifier_char, is_identifier_start};
+
+/// 校验失败时返回错误信息，由调用方补充位置信息
+type RegExpResult<T> = Result<T, String>;
+
+/// 各个 ecma 版本支持的正则 flag
+fn valid_flags(ecma_version: u32) -> String {
+    let mut flags = String::from("gim");
+    if ecma_version >= 6 {
+        flags.push_str("uy");
+    }
+    if ecma_version >= 9 {
+        flags.push('s');
+    }
+    if ecma_version >= 13 {
+        flags.push('d');
+    }
+    if ecma_version >= 15 {
+        flags.push('v');
+    }
+    flags
+}
+
+/// 检查 flag 是否被当前版本支持、是否重复，以及 `u` 与 `v` 不能同时使用
+pub fn validate_regexp_flags(flags: &str, ecma_version: u32) -> Result<(), &'static str> {
+    let valid = valid_flags(ecma_version);
+    for (i, flag) in flags.char_indices() {
+        if !valid.contains(flag) {
+            return Err("Invalid regular expression flag");
+        }
+        if flags[i + 1..].contains(flag) {
+            return Err("Duplicate regular expression flag");
+        }
+    }
+    if ecma_version >= 15 && flags.contains('u') && flags.contains('v') {
+        return Err("Invalid regular expression flag");
+    }
+    Ok(())
+}
+
+/// 检查正则的 pattern 部分，错误信息的格式与 V8 保持一致
+pub fn validate_regexp_pattern(pattern: &str, flags: &str, ecma_version: u32) -> RegExpResult<()> {
+    let mut state = RegExpValidationState::new(pattern, flags, ecma_version);
+    state
+        .validate_pattern()
+        .map_err(|message| format!("Invalid regular expression: /{}/: {}", pattern, message))
+}
+
+/// 解析 pattern 为 AST，offset 为 pattern 第一个字符在源码中的字节位置。
+/// 只用于已经通过校验的正则，因此出错时返回 None
+pub fn parse_regexp_pattern(
+    pattern: &str,
+    flags: &str,
+    ecma_version: u32,
+    offset: usize,
+) -> Option<RegExpAst> {
+    let mut state = RegExpValidationState::new(pattern, flags, ecma_version);
+    state.builder = Some(AstBuilder::new(pattern, offset, state.switch_v));
+    state.validate_pattern().ok()?;
+    state.builder.and_then(|b| b.finish())
+}
+
+/// 字符集合相关的解析结果，用于禁止对可能匹配字符串的集合取反
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharSet {
+    /// 没有解析到内容
+    None,
+    /// 只能匹配单个字符
+    Ok,
+    /// 可能匹配字符串，比如 `\p{RGI_Emoji}`、`\q{abc}`
+    String,
+}
+
+struct RegExpValidationState {
+    ecma_version: u32,
+    /// pattern 的 UTF-16 编码，非 unicode 模式下代理对按两个字符处理
+    source: Vec<u16>,
+    switch_u: bool,
+    switch_v: bool,
+    switch_n: bool,
+    pos: usize,
+    last_int_value: i32,
+    last_string_value: String,
+    last_assertion_is_quantifiable: bool,
+    num_capturing_parens: i32,
+    max_back_reference: i32,
+    group_names: Vec<String>,
+    back_reference_names: Vec<String>,
+    /// 需要构建 AST 时才存在
+    builder: Option<AstBuilder>,
+}
+
+impl RegExpValidationState {
+    fn new(pattern: &str, flags: &str, ecma_version: u32) -> Self {
+        let unicode_sets = flags.contains('v') && ecma_version >= 15;
+        let unicode = flags.contains('u');
+        Self {
+            ecma_version,
+            source: pattern.encode_utf16().collect(),
+            switch_u: unicode_sets || (unicode && ecma_version >= 6),
+            switch_v: unicode_sets,
+            switch_n: unicode_sets || (unicode && ecma_version >= 9),
+            pos: 0,
+            last_int_value: 0,
+            last_string_value: String::new(),
+            last_assertion_is_quantifiable: false,
+            num_capturing_parens: 0,
+            max_back_reference: 0,
+            group_names: vec![],
+            back_reference_names: vec![],
+            builder: None,
+        }
+    }
+
+    /// 读取位置 i 处的字符，unicode 模式下会把代理对合并为一个码点，到达结尾时返回 -1
+    fn at(&self, i: usize, force_u: bool) -> i32 {
+        let Some(&c) = self.source.get(i) else {
+            return -1;
+        };
+        let c = c as i32;
+        if !(force_u || self.switch_u) || !(0xD800..=0xDBFF).contains(&c) {
+            return c;
+        }
+        match self.source.get(i + 1) {
+            Some(&next) if (0xDC00..=0xDFFF).contains(&next) => {
+                ((c - 0xD800) << 10) + (next as i32 - 0xDC00) + 0x10000
+            }
+            _ => c,
+        }
+    }
+
+    fn next_index(&self, i: usize, force_u: bool) -> usize {
+        let len = self.source.len();
+        if i >= len {
+            return len;
+        }
+        if self.at(i, force_u) > 0xFFFF {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn current(&self) -> i32 {
+        self.at(self.pos, false)
+    }
+
+    fn lookahead(&self) -> i32 {
+        self.at(self.next_index(self.pos, false), false)
+    }
+
+    fn advance(&mut self) {
+        self.pos = self.next_index(self.pos, false);
+    }
+
+    fn eat(&mut self, ch: u8) -> bool {
+        if self.current() == ch as i32 {
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    fn eat_chars(&mut self, chs: &[u8]) -> bool {
+        let mut pos = self.pos;
+        for ch in chs {
+            if self.at(pos, false) != *ch as i32 {
+                return false;
+            }
+            pos = self.next_index(pos, false);
+        }
+        self.pos = pos;
+        true
+    }
+
+    fn raise<T>(&self, message: &str) -> RegExpResult<T> {
+        Err(message.to_string())
+    }
+
+    /// 通知 builder 构建节点，只校验时什么也不做
+    fn on(&mut self, f: impl FnOnce(&mut AstBuilder)) {
+        if let Some(builder) = self.builder.as_mut() {
+            f(builder);
+        }
+    }
+
+    fn validate_pattern(&mut self) -> RegExpResult<()> {
+        self.pattern()?;
+        // 非 unicode 模式下如果存在命名捕获组，需要按照 `Pattern[~U, +N]` 重新解析一遍
+        if !self.switch_n && self.ecma_version >= 9 && !self.group_names.is_empty() {
+            self.switch_n = true;
+            self.pattern()?;
+        }
+        Ok(())
+    }
+
+    fn pattern(&mut self) -> RegExpResult<()> {
+        self.pos = 0;
+        self.last_int_value = 0;
+        self.last_string_value.clear();
+        self.last_assertion_is_quantifiable = false;
+        self.num_capturing_parens = 0;
+        self.max_back_reference = 0;
+        self.group_names.clear();
+        self.back_reference_names.clear();
+        self.on(|b| {
+            b.reset();
+            b.pattern_enter(0);
+        });
+
+        self.disjunction()?;
+
+        if self.pos != self.source.len() {
+            // ')'
+            if self.eat(0x29) {
+                return self.raise("Unmatched ')'");
+            }
+            // ']' or '}'
+            if self.eat(0x5D) || self.eat(0x7D) {
+                return self.raise("Lone quantifier brackets");
+            }
+        }
+        if self.max_back_reference > self.num_capturing_parens {
+            return self.raise("Invalid escape");
+        }
+        for name in &self.back_reference_names {
+            if !self.group_names.contains(name) {
+                return self.raise("Invalid named capture referenced");
+            }
+        }
+        let end = self.pos;
+        self.on(|b| b.pattern_leave(end));
+        Ok(())
+    }
+
+    fn disjunction(&mut self) -> RegExpResult<()> {
+        self.alternative()?;
+        // '|'
+        while self.eat(0x7C) {
+            self.alternative()?;
+        }
+        if self.eat_quantifier(true)? {
+            return self.raise("Nothing to repeat");
+        }
+        // '{'
+        if self.eat(0x7B) {
+            return self.raise("Lone quantifier brackets");
+        }
+        Ok(())
+    }
+
+    fn alternative(&mut self) -> RegExpResult<()> {
+        let start = self.pos;
+        self.on(|b| b.alternative_enter(start));
+        while self.pos < self.source.len() && self.eat_term()? {}
+        let end = self.pos;
+        self.on(|b| b.alternative_leave(end));
+        Ok(())
+    }
+
+    fn eat_term(&mut self) -> RegExpResult<bool> {
+        if self.eat_assertion()? {
+            // 非 unicode 模式下允许 `(?=a)*` 这样的写法
+            if self.last_assertion_is_quantifiable && self.eat_quantifier(false)? && self.switch_u {
+                return self.raise("Invalid quantifier");
+            }
+            return Ok(true);
+        }
+        let atom = if self.switch_u {
+            self.eat_atom()?
+        } else {
+            self.eat_extended_atom()?
+        };
+        if atom {
+            self.eat_quantifier(false)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn eat_assertion(&mut self) -> RegExpResult<bool> {
+        let start = self.pos;
+        self.last_assertion_is_quantifiable = false;
+
+        // '^' or '$'
+        let edge = match self.current() {
+            0x5E => Some(AssertionKind::Start),
+            0x24 => Some(AssertionKind::End),
+            _ => None,
+        };
+        if let Some(kind) = edge {
+            self.advance();
+            let end = self.pos;
+            self.on(|b| b.boundary_assertion(start, end, kind, None));
+            return Ok(true);
+        }
+        // '\b' or '\B'
+        if self.eat(0x5C) {
+            let negate = self.current() == 0x42;
+            if self.eat(0x42) || self.eat(0x62) {
+                let end = self.pos;
+                self.on(|b| b.boundary_assertion(start, end, AssertionKind::Word, Some(negate)));
+                return Ok(true);
+            }
+            self.pos = start;
+        }
+        // '(?=' '(?!' '(?<=' '(?<!'
+        if self.eat(0x28) && self.eat(0x3F) {
+            let lookbehind = self.ecma_version >= 9 && self.eat(0x3C);
+            let negate = self.current() == 0x21;
+            if self.eat(0x3D) || self.eat(0x21) {
+                let kind = if lookbehind {
+                    AssertionKind::Lookbehind
+                } else {
+                    AssertionKind::Lookahead
+                };
+                self.on(|b| b.lookaround_enter(start, kind, negate));
+                self.disjunction()?;
+                if !self.eat(0x29) {
+                    return self.raise("Unterminated group");
+                }
+                let end = self.pos;
+                self.on(|b| b.lookaround_leave(end));
+                self.last_assertion_is_quantifiable = !lookbehind;
+                return Ok(true);
+            }
+        }
+        self.pos = start;
+        Ok(false)
+    }
+
+    /// no_error 为 true 时只用于报告错误，此时不会构建 Quantifier 节点
+    fn eat_quantifier(&mut self, no_error: bool) -> RegExpResult<bool> {
+        // '*' '+' '?' or '{n,m}'
+        let (min, max) = if self.eat(0x2A) {
+            (0, None)
+        } else if self.eat(0x2B) {
+            (1, None)
+        } else if self.eat(0x3F) {
+            (0, Some(1))
+        } else if let Some(range) = self.eat_braced_quantifier(no_error)? {
+            range
+        } else {
+            return Ok(false);
+        };
+        // 非贪婪模式
+        let greedy = !self.eat(0x3F);
+        if !no_error {
+            let end = self.pos;
+            self.on(|b| b.quantifier(end, min, max, greedy));
+        }
+        Ok(true)
+    }
+
+    /// 返回 `{min,max}` 中的数字，`{min,}` 的 max 为 None
+    fn eat_braced_quantifier(
+        &mut self,
+        no_error: bool,
+    ) -> RegExpResult<Option<(u32, Option<u32>)>> {
+        let start = self.pos;
+        if self.eat(0x7B) {
+            if self.eat_decimal_digits() {
+                let min = self.last_int_value;
+                let mut max = Some(min);
+                if self.eat(0x2C) {
+                    max = None;
+                    if self.eat_decimal_digits() {
+                        max = Some(self.last_int_value);
+                    }
+                }
+                if self.eat(0x7D) {
+                    if max.is_some_and(|max| max < min) && !no_error {
+                        return self.raise("numbers out of order in {} quantifier");
+                    }
+                    return Ok(Some((min as u32, max.map(|max| max as u32))));
+                }
+            }
+            if self.switch_u && !no_error {
+                return self.raise("Incomplete quantifier");
+            }
+            self.pos = start;
+        }
+        Ok(None)
+    }
+
+    fn eat_atom(&mut self) -> RegExpResult<bool> {
+        Ok(self.eat_pattern_characters()
+            || self.eat_dot()
+            || self.eat_reverse_solidus_atom_escape()?
+            || self.eat_character_class()?
+            || self.eat_uncapturing_group()?
+            || self.eat_capturing_group()?)
+    }
+
+    fn eat_dot(&mut self) -> bool {
+        let start = self.pos;
+        if self.eat(0x2E) {
+            let end = self.pos;
+            self.on(|b| b.character_set(start, end, CharacterSetKind::Any, None));
+            return true;
+        }
+        false
+    }
+
+    fn eat_reverse_solidus_atom_escape(&mut self) -> RegExpResult<bool> {
+        let start = self.pos;
+        if self.eat(0x5C) {
+            if self.eat_atom_escape(start)? {
+                return Ok(true);
+            }
+            self.pos = start;
+        }
+        Ok(false)
+    }
+
+    fn eat_uncapturing_group(&mut self) -> RegExpResult<bool> {
+        let start = self.pos;
+        // '(?:'
+        if self.eat(0x28) {
+            if self.eat(0x3F) && self.eat(0x3A) {
+                self.on(|b| b.group_enter(start));
+                self.disjunction()?;
+                if self.eat(0x29) {
+                    let end = self.pos;
+                    self.on(|b| b.group_leave(end));
+                    return Ok(true);
+                }
+                return self.raise("Unterminated group");
+            }
+            self.pos = start;
+        }
+        Ok(false)
+    }
+
+    fn eat_capturing_group(&mut self) -> RegExpResult<bool> {
+        let start = self.pos;
+        if self.eat(0x28) {
+            let mut name = None;
+            if self.ecma_version >= 9 {
+                name = self.group_specifier()?;
+            } else if self.current() == 0x3F {
+                return self.raise("Invalid group");
+            }
+            self.on(|b| b.capturing_group_enter(start, name));
+            self.disjunction()?;
+            if self.eat(0x29) {
+                self.num_capturing_parens += 1;
+                let end = self.pos;
+                self.on(|b| b.capturing_group_leave(end));
+                return Ok(true);
+            }
+            return self.raise("Unterminated group");
+        }
+        Ok(false)
+    }
+
+    /// 非 unicode 模式下的 Annex B 语法，允许 `]`、`{` 等单独出现
+    fn eat_extended_atom(&mut self) -> RegExpResult<bool> {
+        Ok(self.eat_dot()
+            || self.eat_reverse_solidus_atom_escape()?
+            || self.eat_character_class()?
+            || self.eat_uncapturing_group()?
+            || self.eat_capturing_group()?
+            || self.eat_invalid_braced_quantifier()?
+            || self.eat_extended_pattern_character())
+    }
+
+    fn eat_invalid_braced_quantifier(&mut self) -> RegExpResult<bool> {
+        if self.eat_braced_quantifier(true)?.is_some() {
+            return self.raise("Nothing to repeat");
+        }
+        Ok(false)
+    }
+
+    fn eat_syntax_character(&mut self) -> bool {
+        let ch = self.current();
+        if is_syntax_character(ch) {
+            self.last_int_value = ch;
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    fn eat_pattern_characters(&mut self) -> bool {
+        let start = self.pos;
+        loop {
+            let ch = self.current();
+            if ch == -1 || is_syntax_character(ch) {
+                break;
+            }
+            self.eat_plain_character(ch);
+        }
+        self.pos != start
+    }
+
+    fn eat_extended_pattern_character(&mut self) -> bool {
+        let ch = self.current();
+        if ch != -1
+            && ch != 0x24
+            && !(0x28..=0x2B).contains(&ch)
+            && ch != 0x2E
+            && ch != 0x3F
+            && ch != 0x5B
+            && ch != 0x5E
+            && ch != 0x7C
+        {
+            self.eat_plain_character(ch);
+            return true;
+        }
+        false
+    }
+
+    /// 跳过当前的字符 ch，并构建对应的 Character 节点
+    fn eat_plain_character(&mut self, ch: i32) {
+        let start = self.pos;
+        self.advance();
+        let end = self.pos;
+        if end > start {
+            self.on(|b| b.character(start, end, ch as u32));
+        }
+    }
+
+    /// 返回命名捕获组的名字
+    fn group_specifier(&mut self) -> RegExpResult<Option<String>> {
+        if self.eat(0x3F) {
+            if self.eat_group_name()? {
+                if self.group_names.contains(&self.last_string_value) {
+                    return self.raise("Duplicate capture group name");
+                }
+                self.group_names.push(self.last_string_value.clone());
+                return Ok(Some(self.last_string_value.clone()));
+            }
+            return self.raise("Invalid group");
+        }
+        Ok(None)
+    }
+
+    fn eat_group_name(&mut self) -> RegExpResult<bool> {
+        self.last_string_value.clear();
+        // '<'
+        if self.eat(0x3C) {
+            if self.eat_regexp_identifier_name()? && self.eat(0x3E) {
+                return Ok(true);
+            }
+            return self.raise("Invalid capture group name");
+        }
+        Ok(false)
+    }
+
+    fn eat_regexp_identifier_name(&mut self) -> RegExpResult<bool> {
+        self.last_string_value.clear();
+        if self.eat_regexp_identifier_char(true)? {
+            self.push_last_int_value();
+            while self.eat_regexp_identifier_char(false)? {
+                self.push_last_int_value();
+            }
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn push_last_int_value(&mut self) {
+        let c = char::from_u32(self.last_int_value as u32).unwrap_or(char::REPLACEMENT_CHARACTER);
+        self.last_string_value.push(c);
+    }
+
+    /// 读取组名中的一个字符，支持 `\u` 转义，es2020 起非 unicode 模式下也按码点读取
+    fn eat_regexp_identifier_char(&mut self, is_start: bool) -> RegExpResult<bool> {
+        let start = self.pos;
+        let force_u = self.ecma_version >= 11;
+        let mut ch = self.at(self.pos, force_u);
+        self.pos = self.next_index(self.pos, force_u);
+        if ch == 0x5C && self.eat_regexp_unicode_escape_sequence(force_u)? {
+            ch = self.last_int_value;
+        }
+        let valid = if is_start {
+            is_regexp_identifier_start(ch)
+        } else {
+            is_regexp_identifier_part(ch)
+        };
+        if valid {
+            self.last_int_value = ch;
+            return Ok(true);
+        }
+        self.pos = start;
+        Ok(false)
+    }
+
+    /// start 为 '\\' 的位置
+    fn eat_atom_escape(&mut self, start: usize) -> RegExpResult<bool> {
+        if self.eat_back_reference() {
+            let (end, n) = (self.pos, self.last_int_value as u32);
+            self.on(|b| b.backreference(start, end, BackreferenceRef::Number(n)));
+            return Ok(true);
+        }
+        if self.eat_character_class_escape()? != CharSet::None {
+            return Ok(true);
+        }
+        if self.eat_character_escape()? {
+            let (end, value) = (self.pos, self.last_int_value as u32);
+            self.on(|b| b.character(start, end, value));
+            return Ok(true);
+        }
+        if self.switch_n && self.eat_k_group_name()? {
+            let (end, name) = (self.pos, self.last_string_value.clone());
+            self.on(|b| b.backreference(start, end, BackreferenceRef::Name(name)));
+            return Ok(true);
+        }
+        if self.switch_u {
+            // 'c'
+            if self.current() == 0x63 {
+                return self.raise("Invalid unicode escape");
+            }
+            return self.raise("Invalid escape");
+        }
+        Ok(false)
+    }
+
+    fn eat_back_reference(&mut self) -> bool {
+        let start = self.pos;
+        if self.eat_decimal_escape() {
+            let n = self.last_int_value;
+            if self.switch_u {
+                // 引用的分组需要在整个 pattern 解析完之后才能检查
+                self.max_back_reference = self.max_back_reference.max(n);
+                return true;
+            }
+            if n <= self.num_capturing_parens {
+                return true;
+            }
+            self.pos = start;
+        }
+        false
+    }
+
+    fn eat_k_group_name(&mut self) -> RegExpResult<bool> {
+        // 'k'
+        if self.eat(0x6B) {
+            if self.eat_group_name()? {
+                self.back_reference_names
+                    .push(self.last_string_value.clone());
+                return Ok(true);
+            }
+            return self.raise("Invalid named reference");
+        }
+        Ok(false)
+    }
+
+    fn eat_character_escape(&mut self) -> RegExpResult<bool> {
+        Ok(self.eat_control_escape()
+            || self.eat_c_control_letter()
+            || self.eat_zero()
+            || self.eat_hex_escape_sequence()?
+            || self.eat_regexp_unicode_escape_sequence(false)?
+            || (!self.switch_u && self.eat_legacy_octal_escape_sequence())
+            || self.eat_identity_escape())
+    }
+
+    fn eat_c_control_letter(&mut self) -> bool {
+        let start = self.pos;
+        // 'c'
+        if self.eat(0x63) {
+            if self.eat_control_letter() {
+                return true;
+            }
+            self.pos = start;
+        }
+        false
+    }
+
+    fn eat_zero(&mut self) -> bool {
+        if self.current() == 0x30 && !is_decimal_digit(self.lookahead()) {
+            self.last_int_value = 0;
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    fn eat_control_escape(&mut self) -> bool {
+        let value = match self.current() {
+            0x74 => 0x09, // 't'
+            0x6E => 0x0A, // 'n'
+            0x76 => 0x0B, // 'v'
+            0x66 => 0x0C, // 'f'
+            0x72 => 0x0D, // 'r'
+            _ => return false,
+        };
+        self.last_int_value = value;
+        self.advance();
+        true
+    }
+
+    fn eat_control_letter(&mut self) -> bool {
+        let ch = self.current();
+        if is_control_letter(ch) {
+            self.last_int_value = ch % 0x20;
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    fn eat_regexp_unicode_escape_sequence(&mut self, force_u: bool) -> RegExpResult<bool> {
+        let start = self.pos;
+        let switch_u = force_u || self.switch_u;
+
+        // 'u'
+        if self.eat(0x75) {
+            if self.eat_fixed_hex_digits(4) {
+                let lead = self.last_int_value;
+                if switch_u && (0xD800..=0xDBFF).contains(&lead) {
+                    let lead_surrogate_end = self.pos;
+                    if self.eat(0x5C) && self.eat(0x75) && self.eat_fixed_hex_digits(4) {
+                        let trail = self.last_int_value;
+                        if (0xDC00..=0xDFFF).contains(&trail) {
+                            self.last_int_value =
+                                (lead - 0xD800) * 0x400 + (trail - 0xDC00) + 0x10000;
+                            return Ok(true);
+                        }
+                    }
+                    self.pos = lead_surrogate_end;
+                    self.last_int_value = lead;
+                }
+                return Ok(true);
+            }
+            // '\u{...}'
+            if switch_u
+                && self.eat(0x7B)
+                && self.eat_hex_digits()
+                && self.eat(0x7D)
+                && (0..=0x10FFFF).contains(&self.last_int_value)
+            {
+                return Ok(true);
+            }
+            if switch_u {
+                return self.raise("Invalid unicode escape");
+            }
+            self.pos = start;
+        }
+        Ok(false)
+    }
+
+    fn eat_identity_escape(&mut self) -> bool {
+        if self.switch_u {
+            if self.eat_syntax_character() {
+                return true;
+            }
+            // '/'
+            if self.eat(0x2F) {
+                self.last_int_value = 0x2F;
+                return true;
+            }
+            return false;
+        }
+
+        let ch = self.current();
+        // 'c' 以及 N 模式下的 'k' 不能作为普通的转义字符
+        if ch != 0x63 && (!self.switch_n || ch != 0x6B) {
+            self.last_int_value = ch;
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    fn eat_decimal_escape(&mut self) -> bool {
+        self.last_int_value = 0;
+        let mut ch = self.current();
+        if (0x31..=0x39).contains(&ch) {
+            loop {
+                self.last_int_value = self
+                    .last_int_value
+                    .saturating_mul(10)
+                    .saturating_add(ch - 0x30);
+                self.advance();
+                ch = self.current();
+                if !is_decimal_digit(ch) {
+                    break;
+                }
+            }
+            return true;
+        }
+        false
+    }
+
+    /// 调用前已经读取了 '\\'
+    fn eat_character_class_escape(&mut self) -> RegExpResult<CharSet> {
+        let start = self.pos - 1;
+        let ch = self.current();
+        if is_character_class_escape(ch) {
+            self.last_int_value = -1;
+            self.advance();
+            let kind = match ch {
+                0x64 | 0x44 => CharacterSetKind::Digit,
+                0x73 | 0x53 => CharacterSetKind::Space,
+                _ => CharacterSetKind::Word,
+            };
+            // 大写的 \D \S \W 表示取反
+            let negate = (0x41..=0x5A).contains(&ch);
+            let end = self.pos;
+            self.on(|b| b.character_set(start, end, kind, Some(negate)));
+            return Ok(CharSet::Ok);
+        }
+
+        // 'P' or 'p'
+        let negate = ch == 0x50;
+        if self.switch_u && self.ecma_version >= 9 && (negate || ch == 0x70) {
+            self.last_int_value = -1;
+            self.advance();
+            if self.eat(0x7B) {
+                let (result, key, value) = self.eat_unicode_property_value_expression()?;
+                if result != CharSet::None && self.eat(0x7D) {
+                    if negate && result == CharSet::String {
+                        return self.raise("Invalid property name");
+                    }
+                    let end = self.pos;
+                    let strings = result == CharSet::String;
+                    self.on(|b| b.property_character_set(start, end, negate, key, value, strings));
+                    return Ok(result);
+                }
+            }
+            return self.raise("Invalid property name");
+        }
+        Ok(CharSet::None)
+    }
+
+    /// 返回解析结果以及属性的名字与值，`\p{Alpha}` 这样单独的属性没有值
+    fn eat_unicode_property_value_expression(
+        &mut self,
+    ) -> RegExpResult<(CharSet, String, Option<String>)> {
+        let start = self.pos;
+
+        // UnicodePropertyName `=` UnicodePropertyValue
+        if self.eat_unicode_property_name() && self.eat(0x3D) {
+            let name = self.last_string_value.clone();
+            if self.eat_unicode_property_value() {
+                if !unicode_property::is_non_binary_property(&name) {
+                    return self.raise("Invalid property name");
+                }
+                let value = &self.last_string_value;
+                if !unicode_property::is_property_value(self.ecma_version, &name, value) {
+                    return self.raise("Invalid property value");
+                }
+                return Ok((CharSet::Ok, name, Some(value.clone())));
+            }
+        }
+        self.pos = start;
+
+        // LoneUnicodePropertyNameOrValue
+        if self.eat_unicode_property_value() {
+            let name_or_value = self.last_string_value.clone();
+            // 与 regexpp 一致，`\p{Lu}` 记录为 `General_Category=Lu`
+            if unicode_property::is_general_category_value(&name_or_value) {
+                return Ok((
+                    CharSet::Ok,
+                    "General_Category".to_string(),
+                    Some(name_or_value),
+                ));
+            }
+            if unicode_property::is_binary_property(self.ecma_version, &name_or_value) {
+                return Ok((CharSet::Ok, name_or_value, None));
+            }
+            if self.switch_v
+                && unicode_property::is_binary_property_of_strings(
+                    self.ecma_version,
+                    &name_or_value,
+                )
+            {
+                return Ok((CharSet::String, name_or_value, None));
+            }
+            return self.raise("Invalid property name");
+        }
+        Ok((CharSet::None, String::new(), None))
+    }
+
+    fn eat_unicode_property_name(&mut self) -> bool {
+        self.last_string_value.clear();
+        loop {
+            let ch = self.current();
+            if !(is_control_letter(ch) || ch == 0x5F) {
+                break;
+            }
+            self.last_string_value.push(ch as u8 as char);
+            self.advance();
+        }
+        !self.last_string_value.is_empty()
+    }
+
+    fn eat_unicode_property_value(&mut self) -> bool {
+        self.last_string_value.clear();
+        loop {
+            let ch = self.current();
+            if !(is_control_letter(ch) || ch == 0x5F || is_decimal_digit(ch)) {
+                break;
+            }
+            self.last_string_value.push(ch as u8 as char);
+            self.advance();
+        }
+        !self.last_string_value.is_empty()
+    }
+
+    fn eat_character_class(&mut self) -> RegExpResult<bool> {
+        let start = self.pos;
+        // '['
+        if self.eat(0x5B) {
+            let negate = self.eat(0x5E);
+            self.on(|b| b.character_class_enter(start, negate));
+            let result = self.class_contents()?;
+            if !self.eat(0x5D) {
+                return self.raise("Unterminated character class");
+            }
+            if negate && result == CharSet::String {
+                return self.raise("Negated character class may contain strings");
+            }
+            let end = self.pos;
+            self.on(|b| b.character_class_leave(end));
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn class_contents(&mut self) -> RegExpResult<CharSet> {
+        if self.current() == 0x5D {
+            return Ok(CharSet::Ok);
+        }
+        if self.switch_v {
+            return self.class_set_expression();
+        }
+        self.non_empty_class_ranges()?;
+        Ok(CharSet::Ok)
+    }
+
+    fn non_empty_class_ranges(&mut self) -> RegExpResult<()> {
+        while self.eat_class_atom()? {
+            let left = self.last_int_value;
+            // '-'
+            if self.current() == 0x2D {
+                self.eat_plain_character(0x2D);
+                if self.eat_class_atom()? {
+                    let right = self.last_int_value;
+                    if self.switch_u && (left == -1 || right == -1) {
+                        return self.raise("Invalid character class");
+                    }
+                    if left != -1 && right != -1 && left > right {
+                        return self.raise("Range out of order in character class");
+                    }
+                    if left != -1 && right != -1 {
+                        self.on(|b| b.character_class_range());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn eat_class_atom(&mut self) -> RegExpResult<bool> {
+        let start = self.pos;
+
+        if self.eat(0x5C) {
+            if self.eat_class_escape()? {
+                // `\d` 这样的集合已经在 eat_character_class_escape 中处理
+                if self.last_int_value != -1 {
+                    let (end, value) = (self.pos, self.last_int_value as u32);
+                    self.on(|b| b.character(start, end, value));
+                }
+                return Ok(true);
+            }
+            if self.switch_u {
+                let ch = self.current();
+                if ch == 0x63 || is_octal_digit(ch) {
+                    return self.raise("Invalid class escape");
+                }
+                return self.raise("Invalid escape");
+            }
+            self.pos = start;
+        }
+
+        let ch = self.current();
+        if ch != 0x5D {
+            self.last_int_value = ch;
+            self.eat_plain_character(ch);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn eat_class_escape(&mut self) -> RegExpResult<bool> {
+        let start = self.pos;
+
+        // 'b'
+        if self.eat(0x62) {
+            self.last_int_value = 0x08;
+            return Ok(true);
+        }
+        // '-'
+        if self.switch_u && self.eat(0x2D) {
+            self.last_int_value = 0x2D;
+            return Ok(true);
+        }
+        // 'c'
+        if !self.switch_u && self.eat(0x63) {
+            if self.eat_class_control_letter() {
+                return Ok(true);
+            }
+            self.pos = start;
+        }
+
+        Ok(self.eat_character_class_escape()? != CharSet::None || self.eat_character_escape()?)
+    }
+
+    /// `v` 模式下的字符集合，支持交集 `&&`、差集 `--` 与嵌套的集合
+    fn class_set_expression(&mut self) -> RegExpResult<CharSet> {
+        let mut result = CharSet::Ok;
+        if self.eat_class_set_range()? {
+            // ClassUnion
+        } else {
+            let sub_result = self.eat_class_set_operand()?;
+            if sub_result == CharSet::None {
+                return self.raise("Invalid character in character class");
+            }
+            if sub_result == CharSet::String {
+                result = CharSet::String;
+            }
+
+            // ClassIntersection
+            let start = self.pos;
+            while self.eat_chars(b"&&") {
+                if self.current() != 0x26 {
+                    let sub_result = self.eat_class_set_operand()?;
+                    if sub_result != CharSet::None {
+                        if sub_result != CharSet::String {
+                            result = CharSet::Ok;
+                        }
+                        let end = self.pos;
+                        self.on(|b| b.class_set_operation(end, false));
+                        continue;
+                    }
+                }
+                return self.raise("Invalid character in character class");
+            }
+            if start != self.pos {
+                return Ok(result);
+            }
+
+            // ClassSubtraction
+            while self.eat_chars(b"--") {
+                if self.eat_class_set_operand()? != CharSet::None {
+                    let end = self.pos;
+                    self.on(|b| b.class_set_operation(end, true));
+                    continue;
+                }
+                return self.raise("Invalid character in character class");
+            }
+            if start != self.pos {
+                return Ok(result);
+            }
+        }
+
+        // ClassUnion
+        loop {
+            if self.eat_class_set_range()? {
+                continue;
+            }
+            match self.eat_class_set_operand()? {
+                CharSet::None => return Ok(result),
+                CharSet::String => result = CharSet::String,
+                CharSet::Ok => {}
+            }
+        }
+    }
+
+    fn eat_class_set_range(&mut self) -> RegExpResult<bool> {
+        let start = self.pos;
+        let mark = self.builder.as_ref().map_or(0, |b| b.class_elements_len());
+        if self.eat_class_set_character()? {
+            let left = self.last_int_value;
+            if self.current() == 0x2D {
+                self.eat_plain_character(0x2D);
+                if self.eat_class_set_character()? {
+                    let right = self.last_int_value;
+                    if left != -1 && right != -1 && left > right {
+                        return self.raise("Range out of order in character class");
+                    }
+                    self.on(|b| b.character_class_range());
+                    return Ok(true);
+                }
+            }
+            self.pos = start;
+            self.on(|b| b.truncate_class_elements(mark));
+        }
+        Ok(false)
+    }
+
+    fn eat_class_set_operand(&mut self) -> RegExpResult<CharSet> {
+        if self.eat_class_set_character()? {
+            return Ok(CharSet::Ok);
+        }
+        match self.eat_class_string_disjunction()? {
+            CharSet::None => self.eat_nested_class(),
+            result => Ok(result),
+        }
+    }
+
+    fn eat_nested_class(&mut self) -> RegExpResult<CharSet> {
+        let start = self.pos;
+        if self.eat(0x5B) {
+            let negate = self.eat(0x5E);
+            self.on(|b| b.character_class_enter(start, negate));
+            let result = self.class_contents()?;
+            if self.eat(0x5D) {
+                if negate && result == CharSet::String {
+                    return self.raise("Negated character class may contain strings");
+                }
+                let end = self.pos;
+                self.on(|b| b.character_class_leave(end));
+                return Ok(result);
+            }
+            self.pos = start;
+            self.on(|b| b.discard());
+        }
+        if self.eat(0x5C) {
+            let result = self.eat_character_class_escape()?;
+            if result != CharSet::None {
+                return Ok(result);
+            }
+            self.pos = start;
+        }
+        Ok(CharSet::None)
+    }
+
+    /// `\q{abc|def}`
+    fn eat_class_string_disjunction(&mut self) -> RegExpResult<CharSet> {
+        let start = self.pos;
+        if self.eat_chars(b"\\q") {
+            if self.eat(0x7B) {
+                self.on(|b| b.class_string_disjunction_enter(start));
+                let result = self.class_string_disjunction_contents()?;
+                if self.eat(0x7D) {
+                    let end = self.pos;
+                    self.on(|b| b.class_string_disjunction_leave(end));
+                    return Ok(result);
+                }
+                self.on(|b| b.discard());
+            } else {
+                return self.raise("Invalid escape");
+            }
+            self.pos = start;
+        }
+        Ok(CharSet::None)
+    }
+
+    fn class_string_disjunction_contents(&mut self) -> RegExpResult<CharSet> {
+        let mut result = self.class_string()?;
+        while self.eat(0x7C) {
+            if self.class_string()? == CharSet::String {
+                result = CharSet::String;
+            }
+        }
+        Ok(result)
+    }
+
+    fn class_string(&mut self) -> RegExpResult<CharSet> {
+        let start = self.pos;
+        self.on(|b| b.string_alternative_enter(start));
+        let mut count = 0;
+        while self.eat_class_set_character()? {
+            count += 1;
+        }
+        let end = self.pos;
+        self.on(|b| b.string_alternative_leave(end));
+        Ok(if count == 1 {
+            CharSet::Ok
+        } else {
+            CharSet::String
+        })
+    }
+
+    fn eat_class_set_character(&mut self) -> RegExpResult<bool> {
+        let start = self.pos;
+        if self.eat(0x5C) {
+            if self.eat_character_escape()? || self.eat_class_set_reserved_punctuator() {
+                let (end, value) = (self.pos, self.last_int_value as u32);
+                self.on(|b| b.character(start, end, value));
+                return Ok(true);
+            }
+            if self.eat(0x62) {
+                self.last_int_value = 0x08;
+                let end = self.pos;
+                self.on(|b| b.character(start, end, 0x08));
+                return Ok(true);
+            }
+            self.pos = start;
+            return Ok(false);
+        }
+        let ch = self.current();
+        if ch < 0
+            || (ch == self.lookahead() && is_class_set_reserved_double_punctuator_character(ch))
+            || is_class_set_syntax_character(ch)
+        {
+            return Ok(false);
+        }
+        self.eat_plain_character(ch);
+        self.last_int_value = ch;
+        Ok(true)
+    }
+
+    fn eat_class_set_reserved_punctuator(&mut self) -> bool {
+        let ch = self.current();
+        if is_class_set_reserved_punctuator(ch) {
+            self.last_int_value = ch;
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    fn eat_class_control_letter(&mut self) -> bool {
+        let ch = self.current();
+        if is_decimal_digit(ch) || ch == 0x5F {
+            self.last_int_value = ch % 0x20;
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    fn eat_hex_escape_sequence(&mut self) -> RegExpResult<bool> {
+        let start = self.pos;
+        // 'x'
+        if self.eat(0x78) {
+            if self.eat_fixed_hex_digits(2) {
+                return Ok(true);
+            }
+            if self.switch_u {
+                return self.raise("Invalid escape");
+            }
+            self.pos = start;
+        }
+        Ok(false)
+    }
+
+    fn eat_decimal_digits(&mut self) -> bool {
+        let start = self.pos;
+        self.last_int_value = 0;
+        loop {
+            let ch = self.current();
+            if !is_decimal_digit(ch) {
+                break;
+            }
+            self.last_int_value = self
+                .last_int_value
+                .saturating_mul(10)
+                .saturating_add(ch - 0x30);
+            self.advance();
+        }
+        self.pos != start
+    }
+
+    fn eat_hex_digits(&mut self) -> bool {
+        let start = self.pos;
+        self.last_int_value = 0;
+        while let Some(v) = hex_to_int(self.current()) {
+            self.last_int_value = self.last_int_value.saturating_mul(16).saturating_add(v);
+            self.advance();
+        }
+        self.pos != start
+    }
+
+    fn eat_legacy_octal_escape_sequence(&mut self) -> bool {
+        if !self.eat_octal_digit() {
+            return false;
+        }
+        let n1 = self.last_int_value;
+        if self.eat_octal_digit() {
+            let n2 = self.last_int_value;
+            if n1 <= 3 && self.eat_octal_digit() {
+                self.last_int_value += n1 * 64 + n2 * 8;
+            } else {
+                self.last_int_value = n1 * 8 + n2;
+            }
+        } else {
+            self.last_int_value = n1;
+        }
+        true
+    }
+
+    fn eat_octal_digit(&mut self) -> bool {
+        let ch = self.current();
+        if is_octal_digit(ch) {
+            self.last_int_value = ch - 0x30;
+            self.advance();
+            return true;
+        }
+        self.last_int_value = 0;
+        false
+    }
+
+    fn eat_fixed_hex_digits(&mut self, len: usize) -> bool {
+        let start = self.pos;
+        self.last_int_value = 0;
+        for _ in 0..len {
+            match hex_to_int(self.current()) {
+                Some(v) => {
+                    self.last_int_value = self.last_int_value * 16 + v;
+                    self.advance();
+                }
+                None => {
+                    self.pos = start;
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+fn is_syntax_character(ch: i32) -> bool {
+    // $ ( ) * + . ? [ \ ] ^ { | }
+    ch == 0x24
+        || (0x28..=0x2B).contains(&ch)
+        || ch == 0x2E
+        || ch == 0x3F
+        || (0x5B..=0x5E).contains(&ch)
+        || (0x7B..=0x7D).contains(&ch)
+}
+
+fn is_regexp_identifier_start(ch: i32) -> bool {
+    ch >= 0 && is_identifier_start(ch as usize)
+}
+
+fn is_regexp_identifier_part(ch: i32) -> bool {
+    ch >= 0 && is_identifier_char(ch as usize)
+}
+
+/// d D s S w W
+fn is_character_class_escape(ch: i32) -> bool {
+    matches!(ch, 0x64 | 0x44 | 0x73 | 0x53 | 0x77 | 0x57)
+}
+
+fn is_control_letter(ch: i32) -> bool {
+    (0x41..=0x5A).contains(&ch) || (0x61..=0x7A).contains(&ch)
+}
+
+fn is_decimal_digit(ch: i32) -> bool {
+    (0x30..=0x39).contains(&ch)
+}
+
+fn is_octal_digit(ch: i32) -> bool {
+    (0x30..=0x37).contains(&ch)
+}
+
+fn hex_to_int(ch: i32) -> Option<i32> {
+    char::from_u32(ch as u32)
+        .and_then(|c| c.to_digit(16))
+        .map(|v| v as i32)
+}
+
+/// `&& !! ## $$ %% ** ++ ,, .. :: ;; << == >> ?? @@ ^^ `` ~~` 在 `v` 模式下被保留
+fn is_class_set_reserved_double_punctuator_character(ch: i32) -> bool {
+    ch == 0x21
+        || (0x23..=0x26).contains(&ch)
+        || (0x2A..=0x2C).contains(&ch)
+        || ch == 0x2E
+        || (0x3A..=0x40).contains(&ch)
+        || ch == 0x5E
+        || ch == 0x60
+        || ch == 0x7E
+}
+
+/// ( ) [ ] { } / - \ |
+fn is_class_set_syntax_character(ch: i32) -> bool {
+    ch == 0x28
+        || ch == 0x29
+        || ch == 0x2D
+        || ch == 0x2F
+        || (0x5B..=0x5D).contains(&ch)
+        || (0x7B..=0x7D).contains(&ch)
+}
+
+/// & - ! # % , : ; < = > @ ` ~
+fn is_class_set_reserved_punctuator(ch: i32) -> bool {
+    ch == 0x21
+        || ch == 0x23
+        || ch == 0x25
+        || ch == 0x26
+        || ch == 0x2C
+        || ch == 0x2D
+        || (0x3A..=0x3E).contains(&ch)
+        || ch == 0x40
+        || ch == 0x60
+        || ch == 0x7E
+}
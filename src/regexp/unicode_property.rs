@@ -0,0 +1,80 @@
+// `\p{...}` 中可以使用的 Unicode 属性名与属性值，与 acorn 的 unicode-property-data 保持一致
+
+const ECMA9_BINARY_PROPERTIES: &str = "ASCII ASCII_Hex_Digit AHex Alphabetic Alpha Any Assigned Bidi_Control Bidi_C Bidi_Mirrored Bidi_M Case_Ignorable CI Cased Changes_When_Casefolded CWCF Changes_When_Casemapped CWCM Changes_When_Lowercased CWL Changes_When_NFKC_Casefolded CWKCF Changes_When_Titlecased CWT Changes_When_Uppercased CWU Dash Default_Ignorable_Code_Point DI Deprecated Dep Diacritic Dia Emoji Emoji_Component Emoji_Modifier Emoji_Modifier_Base Emoji_Presentation Extender Ext Grapheme_Base Gr_Base Grapheme_Extend Gr_Ext Hex_Digit Hex IDS_Binary_Operator IDSB IDS_Trinary_Operator IDST ID_Continue IDC ID_Start IDS Ideographic Ideo Join_Control Join_C Logical_Order_Exception LOE Lowercase Lower Math Noncharacter_Code_Point NChar Pattern_Syntax Pat_Syn Pattern_White_Space Pat_WS Quotation_Mark QMark Radical Regional_Indicator RI Sentence_Terminal STerm Soft_Dotted SD Terminal_Punctuation Term Unified_Ideograph UIdeo Uppercase Upper Variation_Selector VS White_Space space XID_Continue XIDC XID_Start XIDS";
+const ECMA10_BINARY_PROPERTIES: &str = "Extended_Pictographic";
+const ECMA12_BINARY_PROPERTIES: &str = "EBase EComp EMod EPres ExtPict";
+
+/// 只能在 `v` 模式下使用，可以匹配多个字符的属性
+const ECMA14_BINARY_PROPERTIES_OF_STRINGS: &str = "Basic_Emoji Emoji_Keycap_Sequence RGI_Emoji_Modifier_Sequence RGI_Emoji_Flag_Sequence RGI_Emoji_Tag_Sequence RGI_Emoji_ZWJ_Sequence RGI_Emoji";
+
+const GENERAL_CATEGORY_VALUES: &str = "Cased_Letter LC Close_Punctuation Pe Connector_Punctuation Pc Control Cc cntrl Currency_Symbol Sc Dash_Punctuation Pd Decimal_Number Nd digit Enclosing_Mark Me Final_Punctuation Pf Format Cf Initial_Punctuation Pi Letter L Letter_Number Nl Line_Separator Zl Lowercase_Letter Ll Mark M Combining_Mark Math_Symbol Sm Modifier_Letter Lm Modifier_Symbol Sk Nonspacing_Mark Mn Number N Open_Punctuation Ps Other C Other_Letter Lo Other_Number No Other_Punctuation Po Other_Symbol So Paragraph_Separator Zp Private_Use Co Punctuation P punct Separator Z Space_Separator Zs Spacing_Mark Mc Surrogate Cs Symbol S Titlecase_Letter Lt Unassigned Cn Uppercase_Letter Lu";
+
+const ECMA9_SCRIPT_VALUES: &str = "Adlam Adlm Ahom Anatolian_Hieroglyphs Hluw Arabic Arab Armenian Armn Avestan Avst Balinese Bali Bamum Bamu Bassa_Vah Bass Batak Batk Bengali Beng Bhaiksuki Bhks Bopomofo Bopo Brahmi Brah Braille Brai Buginese Bugi Buhid Buhd Canadian_Aboriginal Cans Carian Cari Caucasian_Albanian Aghb Chakma Cakm Cham Cherokee Cher Common Zyyy Coptic Copt Qaac Cuneiform Xsux Cypriot Cprt Cyrillic Cyrl Deseret Dsrt Devanagari Deva Duployan Dupl Egyptian_Hieroglyphs Egyp Elbasan Elba Ethiopic Ethi Georgian Geor Glagolitic Glag Gothic Goth Grantha Gran Greek Grek Gujarati Gujr Gurmukhi Guru Han Hani Hangul Hang Hanunoo Hano Hatran Hatr Hebrew Hebr Hiragana Hira Imperial_Aramaic Armi Inherited Zinh Qaai Inscriptional_Pahlavi Phli Inscriptional_Parthian Prti Javanese Java Kaithi Kthi Kannada Knda Katakana Kana Kayah_Li Kali Kharoshthi Khar Khmer Khmr Khojki Khoj Khudawadi Sind Lao Laoo Latin Latn Lepcha Lepc Limbu Limb Linear_A Lina Linear_B Linb Lisu Lycian Lyci Lydian Lydi Mahajani Mahj Malayalam Mlym Mandaic Mand Manichaean Mani Marchen Marc Masaram_Gondi Gonm Meetei_Mayek Mtei Mende_Kikakui Mend Meroitic_Cursive Merc Meroitic_Hieroglyphs Mero Miao Plrd Modi Mongolian Mong Mro Mroo Multani Mult Myanmar Mymr Nabataean Nbat New_Tai_Lue Talu Newa Nko Nkoo Nushu Nshu Ogham Ogam Ol_Chiki Olck Old_Hungarian Hung Old_Italic Ital Old_North_Arabian Narb Old_Permic Perm Old_Persian Xpeo Old_South_Arabian Sarb Old_Turkic Orkh Oriya Orya Osage Osge Osmanya Osma Pahawh_Hmong Hmng Palmyrene Palm Pau_Cin_Hau Pauc Phags_Pa Phag Phoenician Phnx Psalter_Pahlavi Phlp Rejang Rjng Runic Runr Samaritan Samr Saurashtra Saur Sharada Shrd Shavian Shaw Siddham Sidd SignWriting Sgnw Sinhala Sinh Sora_Sompeng Sora Soyombo Soyo Sundanese Sund Syloti_Nagri Sylo Syriac Syrc Tagalog Tglg Tagbanwa Tagb Tai_Le Tale Tai_Tham Lana Tai_Viet Tavt Takri Takr Tamil Taml Tangut Tang Telugu Telu Thaana Thaa Thai Tibetan Tibt Tifinagh Tfng Tirhuta Tirh Ugaritic Ugar Vai Vaii Warang_Citi Wara Yi Yiii Zanabazar_Square Zanb";
+const ECMA10_SCRIPT_VALUES: &str = "Dogra Dogr Gunjala_Gondi Gong Hanifi_Rohingya Rohg Makasar Maka Medefaidrin Medf Old_Sogdian Sogo Sogdian Sogd";
+const ECMA11_SCRIPT_VALUES: &str =
+    "Elymaic Elym Nandinagari Nand Nyiakeng_Puachue_Hmong Hmnp Wancho Wcho";
+const ECMA12_SCRIPT_VALUES: &str =
+    "Chorasmian Chrs Diak Dives_Akuru Khitan_Small_Script Kits Yezi Yezidi";
+const ECMA13_SCRIPT_VALUES: &str =
+    "Cypro_Minoan Cpmn Old_Uyghur Ougr Tangsa Tnsa Toto Vithkuqi Vith";
+const ECMA14_SCRIPT_VALUES: &str = "Hrkt Katakana_Or_Hiragana Kawi Nag_Mundari Nagm Unknown Zzzz";
+
+fn contains_word(words: &str, word: &str) -> bool {
+    words.split(' ').any(|w| w == word)
+}
+
+/// 根据 ecma 版本，依次检查各版本新增的词表
+fn contains_since(tables: &[(u32, &str)], ecma_version: u32, word: &str) -> bool {
+    tables
+        .iter()
+        .any(|(since, words)| ecma_version >= *since && contains_word(words, word))
+}
+
+/// `\p{Lu}` 是 `\p{General_Category=Lu}` 的简写
+pub fn is_general_category_value(name: &str) -> bool {
+    contains_word(GENERAL_CATEGORY_VALUES, name)
+}
+
+/// 可以单独使用的属性，比如 `\p{Alpha}`、`\p{Lu}`
+pub fn is_binary_property(ecma_version: u32, name: &str) -> bool {
+    is_general_category_value(name)
+        || contains_since(
+            &[
+                (9, ECMA9_BINARY_PROPERTIES),
+                (10, ECMA10_BINARY_PROPERTIES),
+                (12, ECMA12_BINARY_PROPERTIES),
+            ],
+            ecma_version,
+            name,
+        )
+}
+
+pub fn is_binary_property_of_strings(ecma_version: u32, name: &str) -> bool {
+    ecma_version >= 14 && contains_word(ECMA14_BINARY_PROPERTIES_OF_STRINGS, name)
+}
+
+pub fn is_non_binary_property(name: &str) -> bool {
+    matches!(
+        name,
+        "General_Category" | "gc" | "Script" | "sc" | "Script_Extensions" | "scx"
+    )
+}
+
+/// `\p{name=value}` 中的 value 是否合法，name 需要先通过 `is_non_binary_property` 的检查
+pub fn is_property_value(ecma_version: u32, name: &str, value: &str) -> bool {
+    if name == "General_Category" || name == "gc" {
+        return contains_word(GENERAL_CATEGORY_VALUES, value);
+    }
+    contains_since(
+        &[
+            (9, ECMA9_SCRIPT_VALUES),
+            (10, ECMA10_SCRIPT_VALUES),
+            (11, ECMA11_SCRIPT_VALUES),
+            (12, ECMA12_SCRIPT_VALUES),
+            (13, ECMA13_SCRIPT_VALUES),
+            (14, ECMA14_SCRIPT_VALUES),
+        ],
+        ecma_version,
+        value,
+    )
+}
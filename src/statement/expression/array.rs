@@ -1,7 +1,7 @@
 use super::{
     assignment::parse_maybe_assign,
     object::{build_obj_ir_as_expr, build_obj_ir_as_pattern, parse_ir_value, Object_IR},
-    util::{get_assign_left_ident, get_paren_expr_val},
+    util::{get_assign_left_ident, get_paren_expr_val, invalid_destructuring, raise_at},
 };
 use crate::ast::_LocationNode;
 use crate::ast::expression::{
@@ -9,17 +9,20 @@ use crate::ast::expression::{
     AssignmentOperator, Expression, SpreadElement,
 };
 use crate::ast::pattern::{ArrayPattern, AssignmentPattern, Pattern, RestElement};
+use crate::ast::{Box, Vec};
+use crate::error::{ParseErrorKind, ParseResult};
 use crate::{
     parser::Parser, statement::util::after_trailing_comma, tokenizer::js_token::TokenLabel,
 };
+use bumpalo::Bump;
 
 // represents an array expression or array pattern
 #[allow(non_camel_case_types)]
-pub struct Array_IR {
+pub struct Array_IR<'a> {
     pub start_loc: _LocationNode,
     pub end_loc: _LocationNode,
-    pub source: Option<String>,
-    pub elements: Vec<Array_Element_IR>,
+    pub source: Option<&'a str>,
+    pub elements: std::vec::Vec<Array_Element_IR<'a>>,
     /// indicate it must be a array pattern.
     pub assert_destructuring: bool,
     /// indicate it must be a array expression.
@@ -27,29 +30,37 @@ pub struct Array_IR {
 }
 
 #[allow(non_camel_case_types)]
-pub struct Array_Element_IR {
+pub struct Array_Element_IR<'a> {
     pub start_loc: _LocationNode,
     pub end_loc: _LocationNode,
     // if starts with '...'
     pub is_dots: bool,
-    pub value: Element_IR_Value,
-    pub rhs_expr: Option<Expression>,
+    pub value: Element_IR_Value<'a>,
+    pub rhs_expr: Option<Expression<'a>>,
 }
 
 #[allow(non_camel_case_types)]
-pub enum Element_IR_Value {
-    Elision,                // represents case like, [,,]
-    Expression(Expression), // expression except Object_IR or Array_IR
-    Object_IR(Object_IR),   // for case like: [{ a: { b, c.. }, }]
-    Array_IR(Array_IR),     // for case like: [[a], ]
+pub enum Element_IR_Value<'a> {
+    Elision,                    // represents case like, [,,]
+    Expression(Expression<'a>), // expression except Object_IR or Array_IR
+    Object_IR(Object_IR<'a>),   // for case like: [{ a: { b, c.. }, }]
+    Array_IR(Array_IR<'a>),     // for case like: [[a], ]
 }
 
-pub fn build_array_ir_as_expr(arr_ir: Array_IR, strict_mode: bool) -> ArrayExpression {
+pub fn build_array_ir_as_expr<'a>(
+    arr_ir: Array_IR<'a>,
+    arena: &'a Bump,
+    strict_mode: bool,
+) -> ParseResult<ArrayExpression<'a>> {
     if arr_ir.assert_destructuring {
-        panic!("Invalid array initializer")
+        return Err(raise_at(
+            &arr_ir.start_loc,
+            ParseErrorKind::SyntaxError,
+            "Invalid array initializer",
+        ));
     }
 
-    let mut els = vec![];
+    let mut els = Vec::new_in(arena);
     for element in arr_ir.elements {
         match element.value {
             Element_IR_Value::Array_IR(arr_ir_val) => {
@@ -58,24 +69,24 @@ pub fn build_array_ir_as_expr(arr_ir: Array_IR, strict_mode: bool) -> ArrayExpre
                 let el_val = if has_rhs_expr {
                     Expression::AssignmentExpression(AssignmentExpression::new(
                         AssignmentExpressionLeft::Pattern(Pattern::ArrayPattern(
-                            build_array_ir_as_pattern(arr_ir_val, strict_mode),
+                            build_array_ir_as_pattern(arr_ir_val, arena, strict_mode)?,
                         )),
                         AssignmentOperator::Assignment,
-                        Box::new(element.rhs_expr.unwrap()),
-                        (
-                            arr_ir_val_start_loc,
-                            element.end_loc.clone(),
-                            arr_ir.source.clone(),
-                        ),
+                        Box::new_in(element.rhs_expr.unwrap(), arena),
+                        (arr_ir_val_start_loc, element.end_loc.clone(), arr_ir.source),
                     ))
                 } else {
-                    Expression::ArrayExpression(build_array_ir_as_expr(arr_ir_val, strict_mode))
+                    Expression::ArrayExpression(build_array_ir_as_expr(
+                        arr_ir_val,
+                        arena,
+                        strict_mode,
+                    )?)
                 };
                 // for case: [...[w] = [1, 2, 3]]
                 els.push(if element.is_dots {
                     ArrayExprEle::SpreadElement(SpreadElement::new(
                         el_val,
-                        (element.start_loc, element.end_loc, arr_ir.source.clone()),
+                        (element.start_loc, element.end_loc, arr_ir.source),
                     ))
                 } else {
                     ArrayExprEle::Expression(el_val)
@@ -87,24 +98,24 @@ pub fn build_array_ir_as_expr(arr_ir: Array_IR, strict_mode: bool) -> ArrayExpre
                 let el_val = if has_rhs_expr {
                     Expression::AssignmentExpression(AssignmentExpression::new(
                         AssignmentExpressionLeft::Pattern(Pattern::ObjectPattern(
-                            build_obj_ir_as_pattern(obj_ir_val, strict_mode),
+                            build_obj_ir_as_pattern(obj_ir_val, arena, strict_mode)?,
                         )),
                         AssignmentOperator::Assignment,
-                        Box::new(element.rhs_expr.unwrap()),
-                        (
-                            object_ir_start_loc,
-                            element.end_loc.clone(),
-                            arr_ir.source.clone(),
-                        ),
+                        Box::new_in(element.rhs_expr.unwrap(), arena),
+                        (object_ir_start_loc, element.end_loc.clone(), arr_ir.source),
                     ))
                 } else {
-                    Expression::ObjectExpression(build_obj_ir_as_expr(obj_ir_val, strict_mode))
+                    Expression::ObjectExpression(build_obj_ir_as_expr(
+                        obj_ir_val,
+                        arena,
+                        strict_mode,
+                    )?)
                 };
                 // [...{} = {} ]
                 els.push(if element.is_dots {
                     ArrayExprEle::SpreadElement(SpreadElement::new(
                         el_val,
-                        (element.start_loc, element.end_loc, arr_ir.source.clone()),
+                        (element.start_loc, element.end_loc, arr_ir.source),
                     ))
                 } else {
                     ArrayExprEle::Expression(el_val)
@@ -114,7 +125,7 @@ pub fn build_array_ir_as_expr(arr_ir: Array_IR, strict_mode: bool) -> ArrayExpre
                 els.push(if element.is_dots {
                     ArrayExprEle::SpreadElement(SpreadElement::new(
                         expr_val,
-                        (element.start_loc, element.end_loc, arr_ir.source.clone()),
+                        (element.start_loc, element.end_loc, arr_ir.source),
                     ))
                 } else {
                     ArrayExprEle::Expression(expr_val)
@@ -126,16 +137,23 @@ pub fn build_array_ir_as_expr(arr_ir: Array_IR, strict_mode: bool) -> ArrayExpre
         }
     }
 
-    ArrayExpression::new(els, (arr_ir.start_loc, arr_ir.end_loc, arr_ir.source))
+    Ok(ArrayExpression::new(
+        els,
+        (arr_ir.start_loc, arr_ir.end_loc, arr_ir.source),
+    ))
 }
 
-pub fn build_array_ir_as_pattern(arr_ir: Array_IR, strict_mode: bool) -> ArrayPattern {
+pub fn build_array_ir_as_pattern<'a>(
+    arr_ir: Array_IR<'a>,
+    arena: &'a Bump,
+    strict_mode: bool,
+) -> ParseResult<ArrayPattern<'a>> {
     // perfer pattern if array_ir got rhs_expr.
     if arr_ir.assert_expr {
-        panic!("Invalid destructuring assignment target");
+        return Err(invalid_destructuring(&arr_ir.start_loc));
     }
 
-    let mut els: Vec<Option<Pattern>> = vec![];
+    let mut els = Vec::new_in(arena);
     for element in arr_ir.elements {
         match element.value {
             Element_IR_Value::Elision => {
@@ -144,16 +162,12 @@ pub fn build_array_ir_as_pattern(arr_ir: Array_IR, strict_mode: bool) -> ArrayPa
             Element_IR_Value::Array_IR(arr_ir_val) => {
                 let arr_ir_val_start_loc = arr_ir_val.start_loc.clone();
                 let has_rhs_expr = element.rhs_expr.is_some();
-                let arr_pat = build_array_ir_as_pattern(arr_ir_val, strict_mode);
+                let arr_pat = build_array_ir_as_pattern(arr_ir_val, arena, strict_mode)?;
                 let mut ele_val = if has_rhs_expr {
                     Pattern::AssignmentPattern(AssignmentPattern::new(
-                        Box::new(Pattern::ArrayPattern(arr_pat)),
-                        Box::new(element.rhs_expr.unwrap()),
-                        (
-                            arr_ir_val_start_loc,
-                            element.end_loc.clone(),
-                            arr_ir.source.clone(),
-                        ),
+                        Box::new_in(Pattern::ArrayPattern(arr_pat), arena),
+                        Box::new_in(element.rhs_expr.unwrap(), arena),
+                        (arr_ir_val_start_loc, element.end_loc.clone(), arr_ir.source),
                     ))
                 } else {
                     Pattern::ArrayPattern(arr_pat)
@@ -161,8 +175,8 @@ pub fn build_array_ir_as_pattern(arr_ir: Array_IR, strict_mode: bool) -> ArrayPa
 
                 if element.is_dots {
                     ele_val = Pattern::RestElement(RestElement::new(
-                        Box::new(ele_val),
-                        (element.start_loc, element.end_loc, arr_ir.source.clone()),
+                        Box::new_in(ele_val, arena),
+                        (element.start_loc, element.end_loc, arr_ir.source),
                     ));
                 }
                 els.push(Some(ele_val));
@@ -179,8 +193,8 @@ pub fn build_array_ir_as_pattern(arr_ir: Array_IR, strict_mode: bool) -> ArrayPa
                         if element.is_dots {
                             els.push(Some(
                                 RestElement::new(
-                                    Box::new(Pattern::Identifier(ident_val)),
-                                    (element.start_loc, element.end_loc, arr_ir.source.clone()),
+                                    Box::new_in(Pattern::Identifier(ident_val), arena),
+                                    (element.start_loc, element.end_loc, arr_ir.source),
                                 )
                                 .into(),
                             ));
@@ -188,49 +202,48 @@ pub fn build_array_ir_as_pattern(arr_ir: Array_IR, strict_mode: bool) -> ArrayPa
                             els.push(Some(Pattern::Identifier(ident_val)));
                         }
                     }
-                    Expression::CallExpression(call_expr) => {}
                     // DestructuringAssignmentTarget[?Yield, ?Await] Initializer[+In, ?Yield, ?Await]opt
                     Expression::AssignmentExpression(AssignmentExpression {
                         left, right, ..
                     }) => {
                         if element.is_dots || outer_is_paren {
-                            panic!("Invalid destructuring assignment target");
+                            return Err(invalid_destructuring(&element.start_loc));
                         }
                         let ident_val_op = get_assign_left_ident(left);
                         if ident_val_op.is_some() {
                             let ident_val = ident_val_op.unwrap();
                             let ident_val_start_loc = _LocationNode {
                                 pos: ident_val.start,
-                                loc: ident_val.loc.start.clone(),
+                                loc: ident_val
+                                    .loc
+                                    .as_ref()
+                                    .map(|loc| loc.start.clone())
+                                    .unwrap_or_default(),
                             };
                             let assi_val = AssignmentPattern::new(
-                                Box::new(Pattern::Identifier(ident_val)),
+                                Box::new_in(Pattern::Identifier(ident_val), arena),
                                 right,
-                                (ident_val_start_loc, element.end_loc, arr_ir.source.clone()),
+                                (ident_val_start_loc, element.end_loc, arr_ir.source),
                             );
                             els.push(Some(Pattern::AssignmentPattern(assi_val)));
                         } else {
-                            panic!("Invalid destructuring assignment target");
+                            return Err(invalid_destructuring(&element.start_loc));
                         }
                     }
                     _ => {
-                        panic!("Invalid destructuring assignment target");
+                        return Err(invalid_destructuring(&element.start_loc));
                     }
                 }
             }
             Element_IR_Value::Object_IR(obj_ir_val) => {
                 let obj_ir_val_start_loc = obj_ir_val.start_loc.clone();
                 let has_rhs_expr = element.rhs_expr.is_some();
-                let obj_pat = build_obj_ir_as_pattern(obj_ir_val, strict_mode);
+                let obj_pat = build_obj_ir_as_pattern(obj_ir_val, arena, strict_mode)?;
                 let mut ele_val = if has_rhs_expr {
                     Pattern::AssignmentPattern(AssignmentPattern::new(
-                        Box::new(Pattern::ObjectPattern(obj_pat)),
-                        Box::new(element.rhs_expr.unwrap()),
-                        (
-                            obj_ir_val_start_loc,
-                            element.end_loc.clone(),
-                            arr_ir.source.clone(),
-                        ),
+                        Box::new_in(Pattern::ObjectPattern(obj_pat), arena),
+                        Box::new_in(element.rhs_expr.unwrap(), arena),
+                        (obj_ir_val_start_loc, element.end_loc.clone(), arr_ir.source),
                     ))
                 } else {
                     Pattern::ObjectPattern(obj_pat)
@@ -238,8 +251,8 @@ pub fn build_array_ir_as_pattern(arr_ir: Array_IR, strict_mode: bool) -> ArrayPa
 
                 if element.is_dots {
                     ele_val = Pattern::RestElement(RestElement::new(
-                        Box::new(ele_val),
-                        (element.start_loc, element.end_loc, arr_ir.source.clone()),
+                        Box::new_in(ele_val, arena),
+                        (element.start_loc, element.end_loc, arr_ir.source),
                     ));
                 }
                 els.push(Some(ele_val));
@@ -247,38 +260,47 @@ pub fn build_array_ir_as_pattern(arr_ir: Array_IR, strict_mode: bool) -> ArrayPa
         }
     }
 
-    ArrayPattern::new(els, (arr_ir.start_loc, arr_ir.end_loc, arr_ir.source))
+    Ok(ArrayPattern::new(
+        els,
+        (arr_ir.start_loc, arr_ir.end_loc, arr_ir.source),
+    ))
 }
 
-pub fn parse_arr_expr_or_pattern(ctx: &mut Parser) -> Expression {
+pub fn parse_arr_expr_or_pattern<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     let start_loc = ctx.start_location_node();
-    let arr_ir = parse_arr_ir(ctx);
+    let arr_ir = parse_arr_ir(ctx)?;
 
-    if ctx.eat(TokenLabel::Eq) {
-        let arr_pat = build_array_ir_as_pattern(arr_ir, ctx.strict_mode);
-        return Expression::AssignmentExpression(AssignmentExpression::new(
+    if ctx.cur_token_is(TokenLabel::Eq) {
+        ctx.expect_ecma_version(6, start_loc.pos, "Destructuring assignment")?;
+        ctx.next()?;
+        let arr_pat = build_array_ir_as_pattern(arr_ir, ctx.arena, ctx.strict_mode)?;
+        return Ok(Expression::AssignmentExpression(AssignmentExpression::new(
             AssignmentExpressionLeft::Pattern(arr_pat.into()),
             AssignmentOperator::Assignment,
-            Box::new(parse_maybe_assign(ctx)),
+            Box::new_in(parse_maybe_assign(ctx)?, ctx.arena),
             ctx.compose_loc_info(start_loc),
-        ));
+        )));
     }
 
-    Expression::ArrayExpression(build_array_ir_as_expr(arr_ir, ctx.strict_mode))
+    Ok(Expression::ArrayExpression(build_array_ir_as_expr(
+        arr_ir,
+        ctx.arena,
+        ctx.strict_mode,
+    )?))
 }
 
-pub fn parse_arr_ir(ctx: &mut Parser) -> Array_IR {
+pub fn parse_arr_ir<'a>(ctx: &mut Parser<'a>) -> ParseResult<Array_IR<'a>> {
     let start_loc = ctx.start_location_node();
     let mut first = true;
-    let mut arr_ir_els: Vec<Array_Element_IR> = vec![];
+    let mut arr_ir_els = vec![];
     let mut assert_destructuring = false;
     let mut assert_expr = false;
     let mut saw_dots = false;
-    ctx.expect(TokenLabel::BracketL);
-    while !ctx.eat(TokenLabel::BracketR) {
+    ctx.expect(TokenLabel::BracketL)?;
+    while !ctx.eat(TokenLabel::BracketR)? {
         if !first {
-            ctx.expect(TokenLabel::Comma);
-            if after_trailing_comma(ctx, TokenLabel::BracketR, true) {
+            ctx.expect(TokenLabel::Comma)?;
+            if after_trailing_comma(ctx, TokenLabel::BracketR, true)? {
                 break;
             }
         } else {
@@ -287,7 +309,7 @@ pub fn parse_arr_ir(ctx: &mut Parser) -> Array_IR {
 
         if ctx.cur_token_is(TokenLabel::Comma) {
             let start_loc = ctx.start_location_node();
-            ctx.next_unwrap();
+            ctx.next()?;
             arr_ir_els.push(Array_Element_IR {
                 start_loc,
                 end_loc: ctx.end_location_node(),
@@ -298,7 +320,7 @@ pub fn parse_arr_ir(ctx: &mut Parser) -> Array_IR {
             continue;
         }
 
-        let element_ir = parse_arr_ir_element(ctx);
+        let element_ir = parse_arr_ir_element(ctx)?;
         if element_ir.is_dots {
             if saw_dots {
                 assert_expr = true;
@@ -310,26 +332,26 @@ pub fn parse_arr_ir(ctx: &mut Parser) -> Array_IR {
         arr_ir_els.push(element_ir);
     }
 
-    Array_IR {
+    Ok(Array_IR {
         start_loc,
-        end_loc: ctx.start_location_node(),
-        source: ctx.source_file.clone(),
+        end_loc: ctx.end_location_node(),
+        source: ctx.source_file,
         elements: arr_ir_els,
         assert_destructuring,
         assert_expr,
-    }
+    })
 }
 
-pub fn parse_arr_ir_element(ctx: &mut Parser) -> Array_Element_IR {
+pub fn parse_arr_ir_element<'a>(ctx: &mut Parser<'a>) -> ParseResult<Array_Element_IR<'a>> {
     let start_loc = ctx.start_location_node();
-    let is_dots = ctx.eat(TokenLabel::Ellipsis);
-    let (ele_ir_value, rhs_expr) = parse_ir_value(ctx, TokenLabel::BracketR);
+    let is_dots = ctx.eat(TokenLabel::Ellipsis)?;
+    let (ele_ir_value, rhs_expr) = parse_ir_value(ctx, TokenLabel::BracketR)?;
 
-    Array_Element_IR {
+    Ok(Array_Element_IR {
         start_loc,
         end_loc: ctx.end_location_node(),
         is_dots,
         value: ele_ir_value.into(),
         rhs_expr,
-    }
+    })
 }
@@ -2,15 +2,17 @@ use super::{conditional::parse_maybe_conditional, parse_yield};
 use crate::ast::expression::{
     AssignmentExpression, AssignmentExpressionLeft, AssignmentOperator, Expression,
 };
+use crate::ast::Box;
+use crate::error::ParseResult;
 use crate::{
     parser::{ForInitType, Parser},
     tokenizer::js_token::TokenLabel,
 };
 
-pub fn parse_maybe_assign(ctx: &mut Parser) -> Expression {
+pub fn parse_maybe_assign<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     if ctx.is_contextual("yield") {
         if ctx.in_generator_scope() {
-            parse_yield(ctx);
+            return Ok(parse_yield(ctx)?.into());
         } else {
             ctx.expr_allowed = true;
         }
@@ -25,22 +27,22 @@ pub fn parse_maybe_assign(ctx: &mut Parser) -> Expression {
             .map_or(false, |t| *t == ForInitType::Await);
     }
 
-    let left = parse_maybe_conditional(ctx);
+    let left = parse_maybe_conditional(ctx)?;
 
     if ctx.cur_token_test(|t| t.is_assign) {
-        let operator = AssignmentOperator::from(ctx.get_cur_token_value());
+        let operator = AssignmentOperator::from(ctx.get_cur_token_value().as_ref());
 
         // TODO: skip assignable check for now.
-        ctx.next_unwrap();
-        let right = parse_maybe_assign(ctx);
+        ctx.next()?;
+        let right = parse_maybe_assign(ctx)?;
 
-        return Expression::AssignmentExpression(AssignmentExpression::new(
-            AssignmentExpressionLeft::Expression(Box::new(left)),
+        return Ok(Expression::AssignmentExpression(AssignmentExpression::new(
+            AssignmentExpressionLeft::Expression(Box::new_in(left, ctx.arena)),
             operator,
-            Box::new(right),
+            Box::new_in(right, ctx.arena),
             ctx.compose_loc_info(start_loc),
-        ));
+        )));
     }
 
-    left
+    Ok(left)
 }
@@ -4,7 +4,9 @@ use crate::ast::{
         BinaryExpression, BinaryOpeartorLeft, BinaryOperator, Expression, LogicalExpression,
         LogicalOperator, PrivateIdentifier,
     },
+    Box,
 };
+use crate::error::{EarlyErrorCode, ParseErrorKind, ParseResult};
 use crate::{
     parser::Parser,
     statement::util::unexpected,
@@ -14,16 +16,16 @@ use crate::{
 use super::{parse_private_ident, unary::parse_maybe_unary};
 
 // parse expression by operator
-pub fn parse_expr_ops(ctx: &mut Parser) -> Expression {
+pub fn parse_expr_ops<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     let start_loc = ctx.start_location_node();
     if ctx.cur_token_is(TokenLabel::PrivateId) {
         if ctx.private_name_stack.len() == 0 {
-            unexpected(ctx.cur_token.clone().unwrap());
+            return Err(unexpected(ctx));
         }
-        let private_ident = parse_private_ident(ctx);
+        let private_ident = parse_private_ident(ctx)?;
         // Only 'IN' operator can be used after private identifier before parsing subscript expression.
         if !ctx.cur_token_is(TokenLabel::_In) {
-            unexpected(ctx.cur_token.clone().unwrap());
+            return Err(unexpected(ctx));
         }
         return parse_expr_op(
             ctx,
@@ -33,24 +35,24 @@ pub fn parse_expr_ops(ctx: &mut Parser) -> Expression {
         );
     }
 
-    let expr = parse_maybe_unary(ctx, false, false);
+    let expr = parse_maybe_unary(ctx, false, false)?;
     // TODO: check expression errors
     // TODO: check if expr is ArrowFunctionExpression
     // if expr.start == start_loc.pos &&
     parse_expr_op(ctx, ExpressionOperatorLeft::Expression(expr), start_loc, -1)
 }
 
-pub enum ExpressionOperatorLeft {
-    Expression(Expression),
-    PrivateIdentifier(PrivateIdentifier),
+pub enum ExpressionOperatorLeft<'a> {
+    Expression(Expression<'a>),
+    PrivateIdentifier(PrivateIdentifier<'a>),
 }
 
-pub fn parse_expr_op(
-    ctx: &mut Parser,
-    left: ExpressionOperatorLeft,
+pub fn parse_expr_op<'a>(
+    ctx: &mut Parser<'a>,
+    left: ExpressionOperatorLeft<'a>,
     left_loc_node: _LocationNode,
     min_prec: i8,
-) -> Expression {
+) -> ParseResult<Expression<'a>> {
     let cur_token = ctx.cur_token.as_ref().unwrap();
     if cur_token.binop.is_some() && !(ctx.for_init.is_some() && ctx.cur_token_is(TokenLabel::_In)) {
         let mut prec = cur_token.binop.unwrap() as i8;
@@ -70,30 +72,34 @@ pub fn parse_expr_op(
             }
             let op_val = ctx.get_cur_token_value();
 
-            ctx.next_unwrap();
+            ctx.next()?;
 
             let cur_left_loc_node = ctx.start_location_node();
             let next_left = if ctx.cur_token_is(TokenLabel::PrivateId) {
-                ExpressionOperatorLeft::PrivateIdentifier(parse_private_ident(ctx))
+                ExpressionOperatorLeft::PrivateIdentifier(parse_private_ident(ctx)?)
             } else {
-                ExpressionOperatorLeft::Expression(parse_maybe_unary(ctx, false, false))
+                ExpressionOperatorLeft::Expression(parse_maybe_unary(ctx, false, false)?)
             };
-            let right = parse_expr_op(ctx, next_left, cur_left_loc_node, prec);
+            let right = parse_expr_op(ctx, next_left, cur_left_loc_node, prec)?;
             let node = build_binary(
                 ctx,
                 left_loc_node.clone(),
                 left,
                 right,
-                op_val,
+                &op_val,
                 logical || coalesce,
-            );
+            )?;
             if logical && ctx.cur_token_is(TokenLabel::Coalesce)
                 || coalesce
                     && ctx.cur_token_test(|t| {
                         t.label == TokenLabel::LogicalAnd || t.label == TokenLabel::LogicalOr
                     })
             {
-                println!("Logical expressions and coalesce expressions cannot be mixed. Wrap either by parentheses");
+                ctx.raise_early(
+                    ctx.cur_token_start,
+                    EarlyErrorCode::MixedCoalesce,
+                    "Logical expressions and coalesce expressions cannot be mixed. Wrap either by parentheses",
+                )?;
             }
 
             return parse_expr_op(
@@ -107,52 +113,54 @@ pub fn parse_expr_op(
 
     // PrivateIdenfier could only be at the left of relational expression
     match left {
-        ExpressionOperatorLeft::PrivateIdentifier(_) => {
-            panic!("Private identifier can only be left side of binary expression");
-        }
-        ExpressionOperatorLeft::Expression(expr) => expr,
+        ExpressionOperatorLeft::PrivateIdentifier(pri_ident) => Err(ctx.raise(
+            pri_ident.start,
+            ParseErrorKind::SyntaxError,
+            "Private identifier can only be left side of binary expression",
+        )),
+        ExpressionOperatorLeft::Expression(expr) => Ok(expr),
     }
 }
 
-pub fn build_binary(
-    ctx: &Parser,
+pub fn build_binary<'a>(
+    ctx: &Parser<'a>,
     start_loc_node: _LocationNode,
-    left: ExpressionOperatorLeft,
-    right: Expression,
-    op: String,
+    left: ExpressionOperatorLeft<'a>,
+    right: Expression<'a>,
+    op: &str,
     logical: bool,
-) -> Expression {
+) -> ParseResult<Expression<'a>> {
     // TODO: check if private identifier was placed in the right side of expression
     match left {
         ExpressionOperatorLeft::PrivateIdentifier(pri_ident) => {
             if op != "in" {
-                unexpected(ctx.cur_token.clone().unwrap());
+                return Err(unexpected(ctx));
             }
-            BinaryExpression::new(
+            Ok(BinaryExpression::new(
                 BinaryOpeartorLeft::PrivateIdentifier(pri_ident),
                 BinaryOperator::from(op),
-                Box::new(right),
+                Box::new_in(right, ctx.arena),
                 ctx.compose_loc_info(start_loc_node),
             )
-            .into()
+            .into())
         }
         ExpressionOperatorLeft::Expression(expr) => {
             if logical {
-                LogicalExpression::new(
-                    Box::new(expr),
+                Ok(LogicalExpression::new(
+                    Box::new_in(expr, ctx.arena),
                     LogicalOperator::from(op),
-                    Box::new(right),
+                    Box::new_in(right, ctx.arena),
                     ctx.compose_loc_info(start_loc_node),
                 )
-                .into()
+                .into())
             } else {
-                BinaryExpression::new(
-                    BinaryOpeartorLeft::Expression(Box::new(expr)),
+                Ok(BinaryExpression::new(
+                    BinaryOpeartorLeft::Expression(Box::new_in(expr, ctx.arena)),
                     BinaryOperator::from(op),
-                    Box::new(right),
+                    Box::new_in(right, ctx.arena),
                     ctx.compose_loc_info(start_loc_node),
                 )
-                .into()
+                .into())
             }
         }
     }
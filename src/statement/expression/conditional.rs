@@ -1,26 +1,28 @@
 use crate::ast::expression::{ConditionalExpression, Expression};
+use crate::error::ParseResult;
 use crate::{parser::Parser, tokenizer::js_token::TokenLabel};
 
 use super::{assignment::parse_maybe_assign, binary::parse_expr_ops};
+use crate::ast::Box;
 
 // parse a ternary operator
-pub fn parse_maybe_conditional(ctx: &mut Parser) -> Expression {
+pub fn parse_maybe_conditional<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     let start_loc = ctx.start_location_node();
-    let expr = parse_expr_ops(ctx);
+    let expr = parse_expr_ops(ctx)?;
     // TODO: check expression errors
 
-    if ctx.cur_token_is(TokenLabel::Question) {
-        let consequent = parse_maybe_assign(ctx);
-        ctx.expect(TokenLabel::Colon);
-        let alternate = parse_maybe_assign(ctx);
-        return ConditionalExpression::new(
-            Box::new(expr),
-            Box::new(consequent),
-            Box::new(alternate),
+    if ctx.eat(TokenLabel::Question)? {
+        let consequent = parse_maybe_assign(ctx)?;
+        ctx.expect(TokenLabel::Colon)?;
+        let alternate = parse_maybe_assign(ctx)?;
+        return Ok(ConditionalExpression::new(
+            Box::new_in(expr, ctx.arena),
+            Box::new_in(consequent, ctx.arena),
+            Box::new_in(alternate, ctx.arena),
             ctx.compose_loc_info(start_loc),
         )
-        .into();
+        .into());
     }
 
-    expr
+    Ok(expr)
 }
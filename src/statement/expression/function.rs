@@ -4,97 +4,114 @@ use crate::ast::{
     expression::{ArrowFunctionBody, ArrowFunctionExpression, Expression, FunctionExpression},
     pattern::Pattern,
     statement::{ExpressionStatement, FunctionBody, FunctionBodyContent, Statement},
+    Box, Vec,
 };
+use crate::error::ParseResult;
 use crate::{
-    parser::Parser,
+    parser::{Parser, StatementContext},
     statement::{
         lval::parse_binding_list,
-        parse_statement,
+        parse_recoverable, parse_statement,
         scope::{get_func_flags, SCOPE_ARROW},
-        util::is_directive_candidate,
+        util::{eat_generator_star, is_directive_candidate, is_use_strict, strict_directive},
     },
     tokenizer::js_token::TokenLabel,
 };
 
-pub fn parse_arrow_expr(
-    ctx: &mut Parser,
+pub fn parse_arrow_expr<'a>(
+    ctx: &mut Parser<'a>,
     start_loc: _LocationNode,
-    params: Vec<Pattern>,
+    params: Vec<'a, Pattern<'a>>,
     is_async: bool,
-) -> ArrowFunctionExpression {
+) -> ParseResult<ArrowFunctionExpression<'a>> {
     ctx.enter_scope(get_func_flags(is_async, false) | SCOPE_ARROW);
     let is_blk = ctx.cur_token_is(TokenLabel::BraceL);
     let body = if is_blk {
-        ArrowFunctionBody::FunctionBoby(parse_function_body(ctx))
+        ArrowFunctionBody::FunctionBoby(parse_function_body(ctx)?)
     } else {
-        ArrowFunctionBody::Expression(Box::new(parse_maybe_assign(ctx)))
+        ArrowFunctionBody::Expression(Box::new_in(parse_maybe_assign(ctx)?, ctx.arena))
     };
     ctx.exit_scope();
 
-    ArrowFunctionExpression::new(
+    Ok(ArrowFunctionExpression::new(
         params,
         body,
         !is_blk,
         is_async,
         ctx.compose_loc_info(start_loc),
-    )
+    ))
 }
 
-pub fn parse_func_expr(ctx: &mut Parser, is_async: bool) -> FunctionExpression {
+pub fn parse_func_expr<'a>(
+    ctx: &mut Parser<'a>,
+    is_async: bool,
+) -> ParseResult<FunctionExpression<'a>> {
     let start_loc = ctx.start_location_node();
     if is_async {
-        ctx.expect_contexual("async");
+        ctx.expect_contexual("async")?;
     }
-    ctx.expect(TokenLabel::_Function);
-    let is_generator = ctx.eat(TokenLabel::Star);
+    ctx.expect(TokenLabel::_Function)?;
+    let is_generator = eat_generator_star(ctx, is_async)?;
+    // 函数表达式的名称属于函数自身的作用域，比如 `(function* yield() {})` 是不合法的
+    ctx.enter_scope(get_func_flags(is_async, is_generator));
     let id = if ctx.cur_token_is(TokenLabel::Name) {
-        Some(parse_ident(ctx, true))
+        Some(parse_ident(ctx, false)?)
     } else {
         None
     };
-    ctx.enter_scope(get_func_flags(is_async, is_generator));
-    ctx.expect(TokenLabel::ParenL);
-    let params = parse_binding_list(ctx, TokenLabel::ParenR, false, true);
-    let body = parse_function_body(ctx);
+    ctx.expect(TokenLabel::ParenL)?;
+    let params = parse_binding_list(ctx, TokenLabel::ParenR, false, ctx.ecma_version >= 8)?;
+    let body = parse_function_body(ctx)?;
     ctx.exit_scope();
 
-    FunctionExpression::new(
+    Ok(FunctionExpression::new(
         id,
-        params.into_iter().map(|p| p.unwrap()).collect(),
+        Vec::from_iter_in(params.into_iter().map(|p| p.unwrap()), ctx.arena),
         body,
         is_generator,
         is_async,
         ctx.compose_loc_info(start_loc),
-    )
+    ))
 }
 
-pub fn parse_function_body(ctx: &mut Parser) -> FunctionBody {
+pub fn parse_function_body<'a>(ctx: &mut Parser<'a>) -> ParseResult<FunctionBody<'a>> {
     let start_loc = ctx.start_location_node();
     let old_labels = ctx.labels.clone();
-    let mut body: Vec<FunctionBodyContent> = vec![];
+    let mut body = Vec::new_in(ctx.arena);
     let mut allow_dirctive = true;
+    let old_strict = ctx.strict_mode;
     ctx.labels = vec![];
     // TODO: check simple params in strict mode
-    ctx.expect(TokenLabel::BraceL);
-    while !ctx.eat(TokenLabel::BraceR) {
-        let stmt = parse_statement(ctx);
+    if !old_strict && strict_directive(ctx, ctx.cur_token_end) {
+        ctx.strict_mode = true;
+    }
+    ctx.expect(TokenLabel::BraceL)?;
+    ctx.enter_stmt_ctx(StatementContext::TopLevel);
+    while !ctx.cur_token_is(TokenLabel::BraceR) {
+        let stmt = parse_recoverable(ctx, true, parse_statement)?;
         if !allow_dirctive {
-            body.push(FunctionBodyContent::Statement(stmt.into()));
+            body.push(FunctionBodyContent::Statement(Box::new_in(stmt, ctx.arena)));
         } else if is_directive_candidate(&stmt) {
             if let Statement::ExpressionStatement(ExpressionStatement {
                 expression: Expression::Literal(literal),
                 ..
             }) = stmt
             {
+                if is_use_strict(ctx, &literal) {
+                    ctx.strict_mode = true;
+                }
                 body.push(FunctionBodyContent::Directive(literal.into()));
             }
         } else {
             allow_dirctive = false;
-            body.push(FunctionBodyContent::Statement(stmt.into()));
+            body.push(FunctionBodyContent::Statement(Box::new_in(stmt, ctx.arena)));
         }
     }
-
+    ctx.exit_stmt_ctx();
+    // `}` 之后的 token 不在函数体中，比如 `function f() { 'use strict' } 017`
+    ctx.strict_mode = old_strict;
+    ctx.next()?;
     ctx.labels = old_labels;
 
-    FunctionBody::new(body, ctx.compose_loc_info(start_loc))
+    Ok(FunctionBody::new(body, ctx.compose_loc_info(start_loc)))
 }
@@ -1,3 +1,5 @@
+use crate::error::{EarlyErrorCode, ParseResult};
+use crate::options::SourceType;
 use crate::{
     ast::expression::{Expression, ImportExpression, MetaProperty},
     parser::Parser,
@@ -6,47 +8,74 @@ use crate::{
 };
 
 use super::{assignment::parse_maybe_assign, parse_ident};
+use crate::ast::Box;
 
 // parse import.meta or a dynamic import expression
-pub fn parse_import_expr(ctx: &mut Parser) -> Expression {
+pub fn parse_import_expr<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     let start_loc = ctx.start_location_node();
+    ctx.expect_ecma_version(11, start_loc.pos, "Import expression")?;
     if ctx.contains_esc {
-        println!("Escape sequence in keyword import");
+        ctx.raise_early(
+            start_loc.pos,
+            EarlyErrorCode::EscapedKeyword,
+            "Escape sequence in keyword import",
+        )?;
     }
-    let meta = parse_ident(ctx, true);
+    let meta = parse_ident(ctx, true)?;
 
     // import("")
-    if ctx.cur_token_is(TokenLabel::ParenR) && !ctx.disable_call_expr {
-        ctx.next_unwrap();
+    if ctx.cur_token_is(TokenLabel::ParenL) && !ctx.disable_call_expr {
+        ctx.next()?;
         //    TODO: complete parse assign
-        let source = parse_maybe_assign(ctx);
-        if !ctx.eat(TokenLabel::ParenR) {
-            if ctx.eat(TokenLabel::Comma) && ctx.eat(TokenLabel::ParenR) {
-                println!("Trailing comma is not allowed in import()");
+        let source = parse_maybe_assign(ctx)?;
+        if !ctx.eat(TokenLabel::ParenR)? {
+            let comma_start = ctx.cur_token_start;
+            if ctx.eat(TokenLabel::Comma)? && ctx.eat(TokenLabel::ParenR)? {
+                ctx.raise_early(
+                    comma_start,
+                    EarlyErrorCode::ImportTrailingComma,
+                    "Trailing comma is not allowed in import()",
+                )?;
             } else {
-                unexpected(ctx.cur_token.clone().unwrap());
+                return Err(unexpected(ctx));
             }
         }
 
-        return ImportExpression::new(Box::new(source), ctx.compose_loc_info(start_loc)).into();
+        return Ok(ImportExpression::new(
+            Box::new_in(source, ctx.arena),
+            ctx.compose_loc_info(start_loc),
+        )
+        .into());
     }
 
     if ctx.cur_token_is(TokenLabel::Dot) {
-        ctx.next_unwrap();
+        ctx.next()?;
         let contains_esc = ctx.contains_esc;
-        let property = parse_ident(ctx, true);
+        let property = parse_ident(ctx, true)?;
         if property.name != "meta" {
-            println!("The only valid meta property for import is 'import.meta'");
+            ctx.raise_early(
+                property.start,
+                EarlyErrorCode::InvalidMetaProperty,
+                "The only valid meta property for import is 'import.meta'",
+            )?;
         }
         if contains_esc {
-            println!("'import.meta' must not contain escaped characters");
+            ctx.raise_early(
+                property.start,
+                EarlyErrorCode::InvalidMetaProperty,
+                "'import.meta' must not contain escaped characters",
+            )?;
         }
-        if ctx.source_file.as_ref().map_or(true, |s| s != "module") {
-            println!("Cannot use 'import.meta' outside a module");
+        if ctx.options.source_type != SourceType::Module {
+            ctx.raise_early(
+                start_loc.pos,
+                EarlyErrorCode::InvalidMetaProperty,
+                "Cannot use 'import.meta' outside a module",
+            )?;
         }
 
-        return MetaProperty::new(meta, property, ctx.compose_loc_info(start_loc)).into();
+        return Ok(MetaProperty::new(meta, property, ctx.compose_loc_info(start_loc)).into());
     }
 
-    unexpected(ctx.cur_token.clone().unwrap());
+    Err(unexpected(ctx))
 }
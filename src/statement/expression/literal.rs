@@ -1,35 +1,70 @@
+use crate::error::{ParseErrorKind, ParseResult};
+use crate::statement::util::unexpected;
 use crate::{
-    ast::expression::{Literal, LiteralValue},
+    ast::expression::{Literal, LiteralValue, Reg},
     parser::Parser,
+    regexp::parse_regexp_pattern,
     tokenizer::js_token::TokenLabel,
 };
-use regex::Regex;
 
-pub fn parse_literal(ctx: &mut Parser) -> Literal {
+pub fn parse_literal<'a>(ctx: &mut Parser<'a>) -> ParseResult<Literal<'a>> {
     let start_loc = ctx.start_location_node();
     let cur_token = ctx.cur_token.as_ref().unwrap();
-    // TODO: ignore raw property for now
+    let raw = cur_token.raw;
+
+    let mut bigint = None;
+    let mut reg = None;
 
     let literal_val = match cur_token.label {
         TokenLabel::String => LiteralValue::String(cur_token.value.clone().unwrap()),
+        // BigInt 的 value 是去掉 `n` 与数字分隔符后的文本
+        TokenLabel::Number if cur_token.raw.ends_with('n') => {
+            bigint = cur_token.value.clone();
+            LiteralValue::BigInt
+        }
         TokenLabel::Number => {
-            LiteralValue::Number(cur_token.value.clone().unwrap().parse::<i64>().unwrap())
+            let value = cur_token.value.clone().unwrap();
+            match value.parse::<f64>() {
+                Ok(v) => LiteralValue::Number(v),
+                Err(_) => {
+                    return Err(ctx.raise(
+                        ctx.cur_token_start,
+                        ParseErrorKind::InvalidNumber,
+                        &format!("Invalid number '{}'", cur_token.raw),
+                    ))
+                }
+            }
         }
         TokenLabel::_True | TokenLabel::_False => {
             LiteralValue::Boolean(cur_token.value.clone().unwrap().parse::<bool>().unwrap())
         }
         TokenLabel::Regexp => {
-            let reg = Regex::new(&cur_token.value.clone().unwrap());
-            LiteralValue::Regx(reg.ok())
+            // token 的原始文本为 `/pattern/flags`，tokenizer 中已经校验过
+            let end = raw.rfind('/').unwrap();
+            let (pattern, flags) = (&raw[1..end], &raw[end + 1..]);
+            let ast = if ctx.options.parse_regex_ast {
+                parse_regexp_pattern(pattern, flags, ctx.ecma_version, ctx.cur_token_start + 1)
+            } else {
+                None
+            };
+            reg = Some(Reg {
+                pattern,
+                flags,
+                ast,
+            });
+            LiteralValue::Regx
         }
         TokenLabel::_Null => LiteralValue::Null,
-        _ => {
-            panic!("Unexpected token");
-        }
+        _ => return Err(unexpected(ctx)),
     };
 
-    ctx.next_unwrap();
+    ctx.next()?;
 
-    // TODO: 补充 Regex 的详细字段 { flag, pattern }
-    Literal::new(literal_val, None, None, ctx.compose_loc_info(start_loc))
+    Ok(Literal::new(
+        literal_val,
+        raw,
+        reg,
+        bigint,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
@@ -23,7 +23,9 @@ use self::import::parse_import_expr;
 use self::literal::parse_literal;
 use self::new::parse_new;
 use self::object::parse_obj_expr_or_pattern;
-use self::paren::parse_parenl;
+use self::paren::{
+    build_paren_ir_as_args, build_paren_ir_as_pattern, parse_paren_ir_list, parse_parenl,
+};
 use self::super_property::parse_super;
 use self::template::parse_template;
 use self::unary::parse_maybe_unary;
@@ -36,28 +38,32 @@ use crate::ast::{
         PrivateIdentifier, SequenceExpression, SpreadElement, ThisExpression, YieldExpression,
     },
     pattern::Pattern,
+    Box, Vec,
 };
+use crate::error::{EarlyErrorCode, ParseResult};
 use crate::parser::Parser;
 use crate::tokenizer::{context::TokenContextLabel, js_token::TokenLabel, util::get_code_from_idx};
 use core::panic;
+use std::borrow::Cow;
 use std::vec;
 
-pub fn parse_expression(ctx: &mut Parser) -> Expression {
+pub fn parse_expression<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     let start_loc = ctx.start_location_node();
-    let expr = parse_maybe_assign(ctx);
+    let expr = parse_maybe_assign(ctx)?;
     if ctx.cur_token_is(TokenLabel::Comma) {
-        let mut expressions = vec![expr];
-        while ctx.eat(TokenLabel::Comma) {
-            expressions.push(parse_maybe_assign(ctx));
+        let mut expressions = Vec::new_in(ctx.arena);
+        expressions.push(expr);
+        while ctx.eat(TokenLabel::Comma)? {
+            expressions.push(parse_maybe_assign(ctx)?);
         }
-        return SequenceExpression::new(expressions, ctx.compose_loc_info(start_loc)).into();
+        return Ok(SequenceExpression::new(expressions, ctx.compose_loc_info(start_loc)).into());
     }
 
-    expr
+    Ok(expr)
 }
 
 // TODO: Figure out a good way to predicate expression and pattern.
-pub fn parse_expr_atom(ctx: &mut Parser) -> Expression {
+pub fn parse_expr_atom<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     // TODO:
     // In accorn, here need to judge if this is a slash token, and if it's, then to read regex token.
     // I think we should keep tokinize implemention decoupling with parse process,
@@ -68,8 +74,8 @@ pub fn parse_expr_atom(ctx: &mut Parser) -> Expression {
         TokenLabel::_Super => parse_super(ctx),
         TokenLabel::_This => {
             let start_loc = ctx.start_location_node();
-            ctx.next_unwrap();
-            ThisExpression::new(ctx.compose_loc_info(start_loc)).into()
+            ctx.next()?;
+            Ok(ThisExpression::new(ctx.compose_loc_info(start_loc)).into())
         }
         TokenLabel::Name => parse_atom_name(ctx),
         TokenLabel::Regexp
@@ -77,19 +83,19 @@ pub fn parse_expr_atom(ctx: &mut Parser) -> Expression {
         | TokenLabel::String
         | TokenLabel::_True
         | TokenLabel::_False
-        | TokenLabel::_Null => parse_literal(ctx).into(),
+        | TokenLabel::_Null => Ok(parse_literal(ctx)?.into()),
         TokenLabel::ParenL => parse_parenl(ctx),
         TokenLabel::BracketL => parse_arr_expr_or_pattern(ctx),
         TokenLabel::BraceL => {
             ctx.overrid_token_context(TokenContextLabel::BraceExpr);
             parse_obj_expr_or_pattern(ctx)
         }
-        TokenLabel::_Function => parse_func_expr(ctx, false).into(),
-        TokenLabel::_Class => parse_class_expr(ctx).into(),
+        TokenLabel::_Function => Ok(parse_func_expr(ctx, false)?.into()),
+        TokenLabel::_Class => Ok(parse_class_expr(ctx)?.into()),
         TokenLabel::_New => parse_new(ctx),
-        TokenLabel::BackQuote => parse_template(ctx, false).into(),
+        TokenLabel::BackQuote => Ok(parse_template(ctx, false)?.into()),
         TokenLabel::_Import => parse_import_expr(ctx),
-        _ => unexpected(ctx.cur_token.clone().unwrap()),
+        _ => Err(unexpected(ctx)),
     }
 }
 
@@ -98,23 +104,29 @@ pub fn parse_expr_atom(ctx: &mut Parser) -> Expression {
 // - async arrow function expression, eg. let fn = async () => {}; and, let f = async ident => {}
 // - an arrow function expression, eg. let fn = param => {}
 // - an ordinary identifier
-pub fn parse_atom_name(ctx: &mut Parser) -> Expression {
+pub fn parse_atom_name<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     let start_loc = ctx.start_location_node();
     let may_be_arrow_func = ctx.potential_arrow_pos == ctx.cur_token_start;
 
     // maybe async function expression.
     if is_async_func(ctx) {
         ctx.overrid_token_context(TokenContextLabel::FnExpr);
-        return parse_func_expr(ctx, true).into();
+        return Ok(parse_func_expr(ctx, true)?.into());
     }
 
-    let mut maybe_async_arrow_func = may_be_arrow_func && ctx.is_contextual("async");
-    let mut ident_node = parse_ident(ctx, false);
+    let maybe_async_arrow_func =
+        may_be_arrow_func && ctx.ecma_version >= 8 && ctx.is_contextual("async");
+    let mut ident_node = parse_ident(ctx, false)?;
 
     // for case: ident => {}
-    if may_be_arrow_func && !can_insert_semicolon(ctx) && ctx.eat(TokenLabel::Arrow) {
-        return parse_arrow_expr(ctx, start_loc, vec![Pattern::Identifier(ident_node)], false)
-            .into();
+    if may_be_arrow_func && !can_insert_semicolon(ctx) && ctx.eat(TokenLabel::Arrow)? {
+        return Ok(parse_arrow_expr(
+            ctx,
+            start_loc,
+            bumpalo::vec![in ctx.arena; Pattern::Identifier(ident_node)],
+            false,
+        )?
+        .into());
     }
 
     // for case: async [no LineTerminator here] ident [no LineTerminator here] => {}
@@ -123,46 +135,52 @@ pub fn parse_atom_name(ctx: &mut Parser) -> Expression {
         && ctx.cur_token_is(TokenLabel::Name)
         && (!ctx.potential_arrow_in_for_await || !ctx.cur_token_value_is("of") || ctx.contains_esc)
     {
-        ident_node = parse_ident(ctx, false);
-        if can_insert_semicolon(ctx) && !ctx.eat(TokenLabel::Arrow) {
-            unexpected(ctx.cur_token.clone().unwrap());
+        ident_node = parse_ident(ctx, false)?;
+        if can_insert_semicolon(ctx) || !ctx.eat(TokenLabel::Arrow)? {
+            return Err(unexpected(ctx));
         }
-        return parse_arrow_expr(ctx, start_loc, vec![Pattern::Identifier(ident_node)], true)
-            .into();
+        return Ok(parse_arrow_expr(
+            ctx,
+            start_loc,
+            bumpalo::vec![in ctx.arena; Pattern::Identifier(ident_node)],
+            true,
+        )?
+        .into());
     }
 
-    if !ctx.disable_call_expr && ctx.cur_token_is(TokenLabel::ParenL) {
-        maybe_async_arrow_func = maybe_async_arrow_func && !can_insert_semicolon(ctx);
-        ctx.eat(TokenLabel::ParenL);
-        let expr_list = parse_epxr_list(ctx, TokenLabel::ParenR, true, false);
-        // TODO: check ecam >= 8
-        // for case: async (..) => {}
-        if maybe_async_arrow_func && !can_insert_semicolon(ctx) && ctx.eat(TokenLabel::Arrow) {
+    // for case: async (..) => {}, or a call of function named async
+    if maybe_async_arrow_func
+        && !ctx.disable_call_expr
+        && !can_insert_semicolon(ctx)
+        && ctx.cur_token_is(TokenLabel::ParenL)
+    {
+        let paren_ir_list = parse_paren_ir_list(ctx)?;
+        if !can_insert_semicolon(ctx) && ctx.eat(TokenLabel::Arrow)? {
             // TODO: check pattern errors for arrow functions
-            return parse_arrow_expr(ctx, start_loc, vec![Pattern::Identifier(ident_node)], true)
-                .into();
+            let params = build_paren_ir_as_pattern(paren_ir_list, ctx.arena, ctx.strict_mode)?;
+            return Ok(parse_arrow_expr(ctx, start_loc, params, true)?.into());
         }
         // TODO: check expression errors.
-        return CallExpression::new(
-            Expression::from(ident_node).into(),
-            expr_list.into_iter().map(|e| e.into()).collect(),
+        return Ok(CallExpression::new(
+            Box::new_in(Expression::from(ident_node), ctx.arena).into(),
+            build_paren_ir_as_args(paren_ir_list, ctx.arena, ctx.strict_mode)?,
             false,
             ctx.compose_loc_info(start_loc),
         )
-        .into();
+        .into());
     }
 
-    ident_node.into()
+    Ok(ident_node.into())
 }
 
-pub enum ExprListElement {
-    Expression(Expression),
-    SpreadElement(SpreadElement),
+pub enum ExprListElement<'a> {
+    Expression(Expression<'a>),
+    SpreadElement(SpreadElement<'a>),
     Null,
 }
 
-impl From<ExprListElement> for ArrayExprEle {
-    fn from(value: ExprListElement) -> Self {
+impl<'a> From<ExprListElement<'a>> for ArrayExprEle<'a> {
+    fn from(value: ExprListElement<'a>) -> Self {
         match value {
             ExprListElement::Expression(e) => Self::Expression(e),
             ExprListElement::SpreadElement(s) => Self::SpreadElement(s),
@@ -171,8 +189,8 @@ impl From<ExprListElement> for ArrayExprEle {
     }
 }
 
-impl From<ExprListElement> for CallExprArgs {
-    fn from(value: ExprListElement) -> Self {
+impl<'a> From<ExprListElement<'a>> for CallExprArgs<'a> {
+    fn from(value: ExprListElement<'a>) -> Self {
         match value {
             ExprListElement::Expression(e) => Self::Expression(e),
             ExprListElement::SpreadElement(s) => Self::SpreadElement(s),
@@ -185,55 +203,61 @@ impl From<ExprListElement> for CallExprArgs {
 
 // parse a list of expression, for case:
 // - call expression params list
-pub fn parse_epxr_list(
-    ctx: &mut Parser,
+pub fn parse_epxr_list<'a>(
+    ctx: &mut Parser<'a>,
     close: TokenLabel,
     allow_trailing_comma: bool,
     allow_empty: bool,
-) -> Vec<ExprListElement> {
-    let mut eles: Vec<ExprListElement> = vec![];
+) -> ParseResult<std::vec::Vec<ExprListElement<'a>>> {
+    let mut eles = vec![];
     let mut first = true;
-    while !ctx.eat(close) {
+    while !ctx.eat(close)? {
         if first {
             first = false;
         } else {
-            ctx.expect(TokenLabel::Comma);
+            ctx.expect(TokenLabel::Comma)?;
         }
 
-        if allow_trailing_comma && after_trailing_comma(ctx, close, true) {
+        if allow_trailing_comma && after_trailing_comma(ctx, close, true)? {
             break;
         }
 
         if allow_empty && ctx.cur_token_is(TokenLabel::Comma) {
             eles.push(ExprListElement::Null);
         } else if ctx.cur_token_is(TokenLabel::Ellipsis) {
-            eles.push(ExprListElement::SpreadElement(parse_spread_el(ctx)));
+            eles.push(ExprListElement::SpreadElement(parse_spread_el(ctx)?));
         } else {
-            eles.push(ExprListElement::Expression(parse_maybe_assign(ctx)));
+            eles.push(ExprListElement::Expression(parse_maybe_assign(ctx)?));
         }
     }
 
-    eles
+    Ok(eles)
 }
 
-pub fn parse_spread_el(ctx: &mut Parser) -> SpreadElement {
+pub fn parse_spread_el<'a>(ctx: &mut Parser<'a>) -> ParseResult<SpreadElement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
+    ctx.next()?;
 
-    let argument = parse_maybe_assign(ctx);
+    let argument = parse_maybe_assign(ctx)?;
 
-    SpreadElement::new(argument, ctx.compose_loc_info(start_loc))
+    Ok(SpreadElement::new(
+        argument,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
 /// 解析一个标识符，如果 liberal 为 true 表示是一个对象属性，忽略关键字检查
-pub fn parse_ident(ctx: &mut Parser, liberal: bool) -> Identifier {
-    let mut name = String::new();
+pub fn parse_ident<'a>(ctx: &mut Parser<'a>, liberal: bool) -> ParseResult<Identifier<'a>> {
     let start_loc = ctx.start_location_node();
-    let cur_token = ctx.cur_token.as_ref().expect("Unexpected Token");
+    let cur_token = match ctx.cur_token.as_ref() {
+        Some(t) => t,
+        None => return Err(unexpected(ctx)),
+    };
+    let name: Cow<'a, str>;
     if ctx.cur_token_is(TokenLabel::Name) {
-        name.push_str(cur_token.value.as_ref().unwrap());
+        name = cur_token.value.clone().unwrap();
     } else if cur_token.keyword {
-        name.push_str(cur_token.label.as_str());
+        name = cur_token.label.as_str().into();
         // To fix: https://github.com/acornjs/acorn/issues/575
         if (name == TokenLabel::_Class.as_str() || name == TokenLabel::_Function.as_str())
             && (ctx.last_token_end != ctx.last_token_start + 1
@@ -242,35 +266,36 @@ pub fn parse_ident(ctx: &mut Parser, liberal: bool) -> Identifier {
             ctx.token_context.pop();
         }
     } else {
-        unexpected(ctx.cur_token.clone().unwrap());
+        return Err(unexpected(ctx));
     }
 
     if liberal && cur_token.keyword && ctx.contains_esc {
-        println!("Escape sequence in keyword {}", cur_token.label.as_str());
+        let message = format!("Escape sequence in keyword {}", cur_token.label.as_str());
+        ctx.raise_early(start_loc.pos, EarlyErrorCode::EscapedKeyword, &message)?;
     }
 
-    ctx.next_unwrap();
+    ctx.next()?;
 
     let ident_node = Identifier::new(name, ctx.compose_loc_info(start_loc));
 
     if !liberal {
-        check_unreserved(ctx, &ident_node.name, ident_node.start, ident_node.end);
+        check_unreserved(ctx, &ident_node.name, ident_node.start)?;
         if &ident_node.name == "await" && ctx.await_ident_pos == 0 {
             ctx.await_ident_pos = ident_node.start;
         }
     }
 
-    ident_node
+    Ok(ident_node)
 }
 
 // 解析一个 yield 表达式
-pub fn parse_yield(ctx: &mut Parser) -> YieldExpression {
+pub fn parse_yield<'a>(ctx: &mut Parser<'a>) -> ParseResult<YieldExpression<'a>> {
     let start_loc = ctx.start_location_node();
     if ctx.yield_pos == 0 {
         ctx.yield_pos = ctx.cur_token_start;
     }
 
-    ctx.next_unwrap();
+    ctx.next()?;
 
     let mut delegate = false;
     let mut argument = None;
@@ -278,56 +303,69 @@ pub fn parse_yield(ctx: &mut Parser) -> YieldExpression {
         && !can_insert_semicolon(ctx)
         && !ctx.cur_token_test(|t| t.label != TokenLabel::Star && !t.starts_expr)
     {
-        if ctx.eat(TokenLabel::Star) {
+        if ctx.eat(TokenLabel::Star)? {
             delegate = true;
         }
-        argument = Some(Box::new(parse_maybe_assign(ctx)));
+        argument = Some(Box::new_in(parse_maybe_assign(ctx)?, ctx.arena));
     }
 
-    YieldExpression::new(delegate, argument, ctx.compose_loc_info(start_loc))
+    Ok(YieldExpression::new(
+        delegate,
+        argument,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
 // parse await expression
-pub fn parse_await(ctx: &mut Parser) -> AwaitExpression {
+pub fn parse_await<'a>(ctx: &mut Parser<'a>) -> ParseResult<AwaitExpression<'a>> {
     let start_loc = ctx.start_location_node();
     if ctx.await_pos == 0 {
         ctx.await_pos = ctx.cur_token_start;
     }
 
-    ctx.next_unwrap();
+    ctx.next()?;
 
-    let argument = parse_maybe_unary(ctx, false, false);
+    let argument = parse_maybe_unary(ctx, false, false)?;
 
-    AwaitExpression::new(Box::new(argument), ctx.compose_loc_info(start_loc))
+    Ok(AwaitExpression::new(
+        Box::new_in(argument, ctx.arena),
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_private_ident(ctx: &mut Parser) -> PrivateIdentifier {
+pub fn parse_private_ident<'a>(ctx: &mut Parser<'a>) -> ParseResult<PrivateIdentifier<'a>> {
     let start_loc = ctx.start_location_node();
 
     if !ctx.cur_token_is(TokenLabel::PrivateId) {
-        unexpected(ctx.cur_token.clone().unwrap());
+        return Err(unexpected(ctx));
     }
     let name = ctx.get_cur_token_value();
-    ctx.next_unwrap();
-
-    let last_private_name_op = ctx.private_name_stack.last_mut();
-    if last_private_name_op.is_none() {
-        println!(
-            "Private field '#${}' must be declared in an enclosing class",
-            name
-        );
-    } else {
-        let last_private_name = last_private_name_op.unwrap();
-        last_private_name.used.push(name.clone());
+    ctx.next()?;
+
+    match ctx.private_name_stack.last_mut() {
+        Some(last_private_name) => last_private_name
+            .used
+            .push((name.to_string(), start_loc.pos)),
+        None => ctx.raise_early(
+            start_loc.pos,
+            EarlyErrorCode::UndeclaredPrivateName,
+            &format!(
+                "Private field '#{}' must be declared in an enclosing class",
+                name
+            ),
+        )?,
     }
 
-    PrivateIdentifier::new(name, ctx.compose_loc_info(start_loc))
+    Ok(PrivateIdentifier::new(
+        name,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_paren_expression(ctx: &mut Parser) -> Expression {
-    ctx.expect(TokenLabel::ParenL);
-    let expr = parse_expression(ctx);
-    ctx.expect(TokenLabel::ParenR);
+pub fn parse_paren_expression<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
+    ctx.expect(TokenLabel::ParenL)?;
+    let expr = parse_expression(ctx)?;
+    ctx.expect(TokenLabel::ParenR)?;
 
-    expr
+    Ok(expr)
 }
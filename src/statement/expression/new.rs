@@ -1,42 +1,59 @@
 use super::{parse_epxr_list, parse_ident, subscript::parse_expr_subscripts, ExprListElement};
+use crate::ast::Box;
+use crate::ast::Vec;
+use crate::error::{EarlyErrorCode, ParseResult};
 use crate::{
     ast::expression::{Expression, MetaProperty, NewExpression},
     parser::Parser,
     tokenizer::js_token::TokenLabel,
 };
 
-pub fn parse_new(ctx: &mut Parser) -> Expression {
+pub fn parse_new<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
+    let start_loc = ctx.start_location_node();
     if ctx.contains_esc {
-        println!("Escape sequence in keyword new");
+        ctx.raise_early(
+            start_loc.pos,
+            EarlyErrorCode::EscapedKeyword,
+            "Escape sequence in keyword new",
+        )?;
     }
-    let start_loc = ctx.start_location_node();
-    let meta = parse_ident(ctx, true);
-    // TODO: check ecam version
-    if ctx.eat(TokenLabel::Dot) {
+    let meta = parse_ident(ctx, true)?;
+    if ctx.ecma_version >= 6 && ctx.eat(TokenLabel::Dot)? {
         let meta_contains_esc = ctx.contains_esc;
-        let property = parse_ident(ctx, false);
+        let property = parse_ident(ctx, true)?;
         if property.name != "target" {
-            println!("The only valid meta property for new is 'new.target'");
+            ctx.raise_early(
+                property.start,
+                EarlyErrorCode::InvalidMetaProperty,
+                "The only valid meta property for new is 'new.target'",
+            )?;
         }
         if meta_contains_esc {
-            println!("'new.target' must not contain escaped characters");
+            ctx.raise_early(
+                property.start,
+                EarlyErrorCode::InvalidMetaProperty,
+                "'new.target' must not contain escaped characters",
+            )?;
         }
-        if ctx.allow_new_dot_target() {
-            println!("'new.target' can only be used in functions and class static block");
+        if !ctx.allow_new_dot_target() {
+            ctx.raise_early(
+                start_loc.pos,
+                EarlyErrorCode::InvalidMetaProperty,
+                "'new.target' can only be used in functions and class static block",
+            )?;
         }
 
-        return MetaProperty::new(meta, property, ctx.compose_loc_info(start_loc)).into();
+        return Ok(MetaProperty::new(meta, property, ctx.compose_loc_info(start_loc)).into());
     }
 
-    let mut new_args = vec![];
+    let mut new_args = Vec::new_in(ctx.arena);
     let old_disable_call_expr = ctx.disable_call_expr;
     ctx.disable_call_expr = true;
-    let callee = parse_expr_subscripts(ctx);
+    let callee = parse_expr_subscripts(ctx)?;
     ctx.disable_call_expr = old_disable_call_expr;
 
-    if ctx.eat(TokenLabel::ParenL) {
-        // TODO: allow trailing comma when ecam version >= 8
-        let expr_list = parse_epxr_list(ctx, TokenLabel::ParenR, true, false);
+    if ctx.eat(TokenLabel::ParenL)? {
+        let expr_list = parse_epxr_list(ctx, TokenLabel::ParenR, ctx.ecma_version >= 8, false)?;
         for e in expr_list {
             match e {
                 ExprListElement::Expression(expr) => {
@@ -50,5 +67,10 @@ pub fn parse_new(ctx: &mut Parser) -> Expression {
         }
     }
 
-    NewExpression::new(Box::new(callee), new_args, ctx.compose_loc_info(start_loc)).into()
+    Ok(NewExpression::new(
+        Box::new_in(callee, ctx.arena),
+        new_args,
+        ctx.compose_loc_info(start_loc),
+    )
+    .into())
 }
@@ -8,8 +8,10 @@ use super::{
     literal::parse_literal,
     parse_ident,
     subscript::parse_subscripts,
-    util::{get_assign_left_ident, get_paren_expr_val},
+    util::{get_assign_left_ident, get_paren_expr_val, invalid_destructuring, raise_at},
 };
+use crate::error::{ParseErrorKind, ParseResult};
+use crate::statement::util::check_unreserved;
 use crate::{
     ast::{
         _LocationNode,
@@ -22,24 +24,26 @@ use crate::{
             AssignmentPattern, AssignmentProperty, ObjectPattern, ObjectPatternProperty, Pattern,
             RestElement,
         },
+        Box, Vec,
     },
     parser::Parser,
     statement::{
         lval::parse_binding_list,
-        scope::{get_func_flags, SCOPE_DIRECT_SUPER},
-        util::{after_trailing_comma, unexpected},
+        scope::{get_func_flags, SCOPE_DIRECT_SUPER, SCOPE_SUPER},
+        util::{after_trailing_comma, check_accessor_params, eat_generator_star, unexpected},
     },
     tokenizer::{js_token::TokenLabel, util::has_break_in_range},
 };
+use bumpalo::Bump;
 use std::vec;
 
 // represents object expression or object pattern.
 #[allow(non_camel_case_types)]
-pub struct Object_IR {
+pub struct Object_IR<'a> {
     pub start_loc: _LocationNode,
     pub end_loc: _LocationNode,
-    pub source: Option<String>,
-    pub properties: Vec<Object_Property_IR>,
+    pub source: Option<&'a str>,
+    pub properties: std::vec::Vec<Object_Property_IR<'a>>,
     /// indicate it must be a object pattern.
     pub assert_destructuring: bool,
     /// indicate it must be a object expression.
@@ -47,19 +51,19 @@ pub struct Object_IR {
 }
 
 #[allow(non_camel_case_types)]
-pub struct Object_Property_IR {
+pub struct Object_Property_IR<'a> {
     start_loc: _LocationNode,
     end_loc: _LocationNode,
     // when is_dots is true, the value of key is None.
-    key: Option<Expression>,
-    value: Property_IR_Value,
+    key: Option<Expression<'a>>,
+    value: Property_IR_Value<'a>,
     /// The type of rhs_expr is Some when the operator between value and rhs_expr is equal,
     /// and the value starts with another object_ir or array_ir,
     /// such as:
     /// - { a: { b } = [rhs_expr] }
     /// - { a: [c] = [rhs_expr] }
     /// When rhs_expr is some, the type of object_ir_value could be assignment expression or assignment pattern.
-    rhs_expr: Option<Expression>,
+    rhs_expr: Option<Expression<'a>>,
     kind: PropertyKind,
     is_method: bool,
     is_async: bool,
@@ -71,19 +75,27 @@ pub struct Object_Property_IR {
 }
 
 #[allow(non_camel_case_types)]
-pub enum Property_IR_Value {
-    Object_IR(Object_IR), // for case like: { a: { b, c.. }, }
-    Array_IR(Array_IR),
-    Expression(Expression), // for case like method、expression except object_ir or array_ir
+pub enum Property_IR_Value<'a> {
+    Object_IR(Object_IR<'a>), // for case like: { a: { b, c.. }, }
+    Array_IR(Array_IR<'a>),
+    Expression(Expression<'a>), // for case like method、expression except object_ir or array_ir
 }
 
-pub fn build_obj_ir_as_expr(obj_ir: Object_IR, strict_mode: bool) -> ObjectExpression {
+pub fn build_obj_ir_as_expr<'a>(
+    obj_ir: Object_IR<'a>,
+    arena: &'a Bump,
+    strict_mode: bool,
+) -> ParseResult<ObjectExpression<'a>> {
     if obj_ir.assert_destructuring {
-        panic!("Invalid shorthand property initializer");
+        return Err(raise_at(
+            &obj_ir.start_loc,
+            ParseErrorKind::SyntaxError,
+            "Invalid shorthand property initializer",
+        ));
     }
 
     // build a object expression
-    let mut obj_expr_props: Vec<ObjectProperty> = vec![];
+    let mut obj_expr_props = Vec::new_in(arena);
     for prop in obj_ir.properties {
         match prop.value {
             Property_IR_Value::Array_IR(arr_ir_val) => {
@@ -92,23 +104,23 @@ pub fn build_obj_ir_as_expr(obj_ir: Object_IR, strict_mode: bool) -> ObjectExpre
                 let prop_val = if has_rhs_expr {
                     Expression::AssignmentExpression(AssignmentExpression::new(
                         AssignmentExpressionLeft::Pattern(Pattern::ArrayPattern(
-                            build_array_ir_as_pattern(arr_ir_val, strict_mode),
+                            build_array_ir_as_pattern(arr_ir_val, arena, strict_mode)?,
                         )),
                         AssignmentOperator::Assignment,
-                        prop.rhs_expr.unwrap().into(),
-                        (
-                            arr_val_start_loc,
-                            prop.end_loc.clone(),
-                            obj_ir.source.clone(),
-                        ),
+                        Box::new_in(prop.rhs_expr.unwrap(), arena),
+                        (arr_val_start_loc, prop.end_loc.clone(), obj_ir.source),
                     ))
                 } else {
-                    Expression::ArrayExpression(build_array_ir_as_expr(arr_ir_val, strict_mode))
+                    Expression::ArrayExpression(build_array_ir_as_expr(
+                        arr_ir_val,
+                        arena,
+                        strict_mode,
+                    )?)
                 };
                 obj_expr_props.push(if prop.is_dots {
                     ObjectProperty::SpreadElement(SpreadElement::new(
                         prop_val,
-                        (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                        (prop.start_loc, prop.end_loc, obj_ir.source),
                     ))
                 } else {
                     ObjectProperty::Property(Property::new(
@@ -118,7 +130,7 @@ pub fn build_obj_ir_as_expr(obj_ir: Object_IR, strict_mode: bool) -> ObjectExpre
                         prop.is_method,
                         prop.is_shorthand,
                         prop.computed,
-                        (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                        (prop.start_loc, prop.end_loc, obj_ir.source),
                     ))
                 });
             }
@@ -128,23 +140,23 @@ pub fn build_obj_ir_as_expr(obj_ir: Object_IR, strict_mode: bool) -> ObjectExpre
                 let prop_val = if has_rhs_expr {
                     Expression::AssignmentExpression(AssignmentExpression::new(
                         AssignmentExpressionLeft::Pattern(Pattern::ObjectPattern(
-                            build_obj_ir_as_pattern(obj_ir_val, strict_mode),
+                            build_obj_ir_as_pattern(obj_ir_val, arena, strict_mode)?,
                         )),
                         AssignmentOperator::Assignment,
-                        prop.rhs_expr.unwrap().into(),
-                        (
-                            obj_ir_val_start_loc,
-                            prop.end_loc.clone(),
-                            obj_ir.source.clone(),
-                        ),
+                        Box::new_in(prop.rhs_expr.unwrap(), arena),
+                        (obj_ir_val_start_loc, prop.end_loc.clone(), obj_ir.source),
                     ))
                 } else {
-                    Expression::ObjectExpression(build_obj_ir_as_expr(obj_ir_val, strict_mode))
+                    Expression::ObjectExpression(build_obj_ir_as_expr(
+                        obj_ir_val,
+                        arena,
+                        strict_mode,
+                    )?)
                 };
                 obj_expr_props.push(if prop.is_dots {
                     ObjectProperty::SpreadElement(SpreadElement::new(
                         prop_val,
-                        (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                        (prop.start_loc, prop.end_loc, obj_ir.source),
                     ))
                 } else {
                     ObjectProperty::Property(Property::new(
@@ -154,7 +166,7 @@ pub fn build_obj_ir_as_expr(obj_ir: Object_IR, strict_mode: bool) -> ObjectExpre
                         prop.is_method,
                         prop.is_shorthand,
                         prop.computed,
-                        (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                        (prop.start_loc, prop.end_loc, obj_ir.source),
                     ))
                 });
             }
@@ -162,7 +174,7 @@ pub fn build_obj_ir_as_expr(obj_ir: Object_IR, strict_mode: bool) -> ObjectExpre
                 obj_expr_props.push(if prop.is_dots {
                     ObjectProperty::SpreadElement(SpreadElement::new(
                         expr_val,
-                        (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                        (prop.start_loc, prop.end_loc, obj_ir.source),
                     ))
                 } else {
                     ObjectProperty::Property(Property::new(
@@ -172,25 +184,29 @@ pub fn build_obj_ir_as_expr(obj_ir: Object_IR, strict_mode: bool) -> ObjectExpre
                         prop.is_method,
                         prop.is_shorthand,
                         prop.computed,
-                        (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                        (prop.start_loc, prop.end_loc, obj_ir.source),
                     ))
                 });
             }
         }
     }
 
-    ObjectExpression::new(
+    Ok(ObjectExpression::new(
         obj_expr_props,
-        (obj_ir.start_loc, obj_ir.end_loc, obj_ir.source.clone()),
-    )
+        (obj_ir.start_loc, obj_ir.end_loc, obj_ir.source),
+    ))
 }
 
-pub fn build_obj_ir_as_pattern(obj_ir: Object_IR, strict_mode: bool) -> ObjectPattern {
+pub fn build_obj_ir_as_pattern<'a>(
+    obj_ir: Object_IR<'a>,
+    arena: &'a Bump,
+    strict_mode: bool,
+) -> ParseResult<ObjectPattern<'a>> {
     if obj_ir.assert_expr {
-        panic!("Invalid destructuring assignment target")
+        return Err(invalid_destructuring(&obj_ir.start_loc));
     }
 
-    let mut obj_pattern_props: Vec<ObjectPatternProperty> = vec![];
+    let mut obj_pattern_props = Vec::new_in(arena);
 
     for prop in obj_ir.properties {
         if prop.is_dots {
@@ -204,25 +220,29 @@ pub fn build_obj_ir_as_pattern(obj_ir: Object_IR, strict_mode: bool) -> ObjectPa
                             if strict_mode
                                 && (ident_val.name == "eval" || ident_val.name == "arguments")
                             {
-                                panic!("Invalid destructuring assignment target");
+                                return Err(invalid_destructuring(&prop.start_loc));
                             }
-                            Box::new(Pattern::Identifier(ident_val))
+                            Box::new_in(Pattern::Identifier(ident_val), arena)
                         }
                         Expression::MemberExpression(mem_expr) => {
-                            Box::new(Pattern::MemberExpression(mem_expr))
+                            Box::new_in(Pattern::MemberExpression(mem_expr), arena)
                         }
                         _ => {
-                            panic!("Invalid destructuring assignment target");
+                            return Err(invalid_destructuring(&prop.start_loc));
                         }
                     };
                     obj_pattern_props.push(ObjectPatternProperty::RestElement(RestElement::new(
                         prop_val,
-                        (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                        (prop.start_loc, prop.end_loc, obj_ir.source),
                     )));
                 }
-                _ => panic!(
-                    "`...` must be followed by an assignable reference in assignment contexts"
-                ),
+                _ => {
+                    return Err(raise_at(
+                        &prop.start_loc,
+                        ParseErrorKind::InvalidAssignmentTarget,
+                        "`...` must be followed by an assignable reference in assignment contexts",
+                    ))
+                }
             }
             continue;
         }
@@ -232,12 +252,12 @@ pub fn build_obj_ir_as_pattern(obj_ir: Object_IR, strict_mode: bool) -> ObjectPa
             Property_IR_Value::Object_IR(obj_ir_val) => {
                 let has_rhs_expr = prop.rhs_expr.is_some();
                 let pat_start_loc = obj_ir_val.start_loc.clone();
-                let obj_pat = build_obj_ir_as_pattern(obj_ir_val, strict_mode);
+                let obj_pat = build_obj_ir_as_pattern(obj_ir_val, arena, strict_mode)?;
                 let assign_prop_val = if has_rhs_expr {
                     Pattern::AssignmentPattern(AssignmentPattern::new(
-                        Box::new(Pattern::ObjectPattern(obj_pat)),
-                        prop.rhs_expr.unwrap().into(),
-                        (pat_start_loc, prop.end_loc.clone(), obj_ir.source.clone()),
+                        Box::new_in(Pattern::ObjectPattern(obj_pat), arena),
+                        Box::new_in(prop.rhs_expr.unwrap(), arena),
+                        (pat_start_loc, prop.end_loc.clone(), obj_ir.source),
                     ))
                 } else {
                     Pattern::ObjectPattern(obj_pat)
@@ -247,19 +267,19 @@ pub fn build_obj_ir_as_pattern(obj_ir: Object_IR, strict_mode: bool) -> ObjectPa
                     assign_prop_val,
                     false,
                     prop.computed,
-                    (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                    (prop.start_loc, prop.end_loc, obj_ir.source),
                 );
                 obj_pattern_props.push(ObjectPatternProperty::AssignmentProperty(assign_prop));
             }
             Property_IR_Value::Array_IR(arr_ir) => {
                 let has_rhs_expr = prop.rhs_expr.is_some();
                 let pat_start_loc = arr_ir.start_loc.clone();
-                let pat_value = build_array_ir_as_pattern(arr_ir, strict_mode);
+                let pat_value = build_array_ir_as_pattern(arr_ir, arena, strict_mode)?;
                 let assign_prop_val = if has_rhs_expr {
                     Pattern::AssignmentPattern(AssignmentPattern::new(
-                        Box::new(Pattern::ArrayPattern(pat_value)),
-                        prop.rhs_expr.unwrap().into(),
-                        (pat_start_loc, prop.end_loc.clone(), obj_ir.source.clone()),
+                        Box::new_in(Pattern::ArrayPattern(pat_value), arena),
+                        Box::new_in(prop.rhs_expr.unwrap(), arena),
+                        (pat_start_loc, prop.end_loc.clone(), obj_ir.source),
                     ))
                 } else {
                     Pattern::ArrayPattern(pat_value)
@@ -269,7 +289,7 @@ pub fn build_obj_ir_as_pattern(obj_ir: Object_IR, strict_mode: bool) -> ObjectPa
                     assign_prop_val,
                     false,
                     prop.computed,
-                    (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                    (prop.start_loc, prop.end_loc, obj_ir.source),
                 );
                 obj_pattern_props.push(ObjectPatternProperty::AssignmentProperty(assign_prop));
             }
@@ -283,7 +303,7 @@ pub fn build_obj_ir_as_pattern(obj_ir: Object_IR, strict_mode: bool) -> ObjectPa
                             Pattern::Identifier(ident_val),
                             prop.is_shorthand,
                             prop.computed,
-                            (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                            (prop.start_loc, prop.end_loc, obj_ir.source),
                         );
                         obj_pattern_props
                             .push(ObjectPatternProperty::AssignmentProperty(assign_prop));
@@ -294,7 +314,7 @@ pub fn build_obj_ir_as_pattern(obj_ir: Object_IR, strict_mode: bool) -> ObjectPa
                             Pattern::MemberExpression(member_expr),
                             false,
                             prop.computed,
-                            (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                            (prop.start_loc, prop.end_loc, obj_ir.source),
                         );
                         obj_pattern_props
                             .push(ObjectPatternProperty::AssignmentProperty(assign_prop));
@@ -303,83 +323,89 @@ pub fn build_obj_ir_as_pattern(obj_ir: Object_IR, strict_mode: bool) -> ObjectPa
                         left, right, ..
                     }) => {
                         if outer_is_paren {
-                            panic!("Invalid destructuring assignment target");
+                            return Err(invalid_destructuring(&prop.start_loc));
                         }
                         let left_ident_op = get_assign_left_ident(left);
                         if left_ident_op.is_some() {
                             let left_ident = left_ident_op.unwrap();
                             let left_ident_start_loc = _LocationNode {
                                 pos: left_ident.start,
-                                loc: left_ident.loc.start.clone(),
+                                loc: left_ident
+                                    .loc
+                                    .as_ref()
+                                    .map(|loc| loc.start.clone())
+                                    .unwrap_or_default(),
                             };
                             let assign_val = AssignmentPattern::new(
-                                Box::new(left_ident.into()),
+                                Box::new_in(left_ident.into(), arena),
                                 right,
-                                (
-                                    left_ident_start_loc,
-                                    prop.end_loc.clone(),
-                                    obj_ir.source.clone(),
-                                ),
+                                (left_ident_start_loc, prop.end_loc.clone(), obj_ir.source),
                             );
                             let assign_prop = AssignmentProperty::new(
                                 prop_key,
                                 Pattern::AssignmentPattern(assign_val),
                                 prop.is_shorthand,
                                 prop.computed,
-                                (prop.start_loc, prop.end_loc, obj_ir.source.clone()),
+                                (prop.start_loc, prop.end_loc, obj_ir.source),
                             );
                             obj_pattern_props
                                 .push(ObjectPatternProperty::AssignmentProperty(assign_prop));
                         } else {
-                            panic!("Invalid destructuring assignment target");
+                            return Err(invalid_destructuring(&prop.start_loc));
                         }
                     }
                     _ => {
-                        panic!("Invalid destructuring assignment target");
+                        return Err(invalid_destructuring(&prop.start_loc));
                     }
                 }
             }
         }
     }
 
-    ObjectPattern::new(
+    Ok(ObjectPattern::new(
         obj_pattern_props,
         (obj_ir.start_loc, obj_ir.end_loc, obj_ir.source),
-    )
+    ))
 }
 
 // ({} = ..)
 // let a = {};
-pub fn parse_obj_expr_or_pattern(ctx: &mut Parser) -> Expression {
+pub fn parse_obj_expr_or_pattern<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     let start_loc = ctx.start_location_node();
-    let object_ir = parse_obj_ir(ctx);
+    let object_ir = parse_obj_ir(ctx)?;
 
-    if ctx.eat(TokenLabel::Eq) {
-        let left = build_obj_ir_as_pattern(object_ir, ctx.strict_mode);
-        return Expression::AssignmentExpression(AssignmentExpression::new(
+    if ctx.cur_token_is(TokenLabel::Eq) {
+        ctx.expect_ecma_version(6, start_loc.pos, "Destructuring assignment")?;
+        ctx.next()?;
+        let left = build_obj_ir_as_pattern(object_ir, ctx.arena, ctx.strict_mode)?;
+        return Ok(Expression::AssignmentExpression(AssignmentExpression::new(
             AssignmentExpressionLeft::Pattern(Pattern::ObjectPattern(left)),
             AssignmentOperator::Assignment,
-            Box::new(parse_maybe_assign(ctx)),
+            Box::new_in(parse_maybe_assign(ctx)?, ctx.arena),
             ctx.compose_loc_info(start_loc),
-        ));
+        )));
     }
 
-    Expression::ObjectExpression(build_obj_ir_as_expr(object_ir, ctx.strict_mode))
+    Ok(Expression::ObjectExpression(build_obj_ir_as_expr(
+        object_ir,
+        ctx.arena,
+        ctx.strict_mode,
+    )?))
 }
 
-pub fn parse_obj_ir(ctx: &mut Parser) -> Object_IR {
+pub fn parse_obj_ir<'a>(ctx: &mut Parser<'a>) -> ParseResult<Object_IR<'a>> {
     let start_loc = ctx.start_location_node();
     let mut first = true;
     let mut properties = vec![];
 
-    ctx.expect(TokenLabel::BraceL);
+    ctx.expect(TokenLabel::BraceL)?;
     let mut assert_destructuring = false;
     let mut assert_expr = false;
     let mut saw_dots = false;
-    while !ctx.eat(TokenLabel::BraceR) {
+    while !ctx.eat(TokenLabel::BraceR)? {
         if !first {
-            ctx.expect(TokenLabel::Comma);
-            if after_trailing_comma(ctx, TokenLabel::BraceR, true) {
+            ctx.expect(TokenLabel::Comma)?;
+            if after_trailing_comma(ctx, TokenLabel::BraceR, true)? {
                 break;
             }
         } else {
@@ -387,7 +413,7 @@ pub fn parse_obj_ir(ctx: &mut Parser) -> Object_IR {
         }
 
         // TODO: check properties clash error
-        let property_ir = parse_property_ir(ctx);
+        let property_ir = parse_property_ir(ctx)?;
         if property_ir.is_dots {
             if !saw_dots {
                 saw_dots = true;
@@ -397,7 +423,7 @@ pub fn parse_obj_ir(ctx: &mut Parser) -> Object_IR {
                 assert_expr = true;
             }
         }
-        if property_ir.is_method {
+        if property_ir.is_method || property_ir.kind != PropertyKind::Init {
             assert_expr = true;
         }
         // check format like: { a = 1, }
@@ -412,39 +438,39 @@ pub fn parse_obj_ir(ctx: &mut Parser) -> Object_IR {
         properties.push(property_ir);
     }
     if assert_expr && assert_destructuring {
-        panic!("Invalid destructuring assignment target");
+        return Err(invalid_destructuring(&start_loc));
     }
 
-    Object_IR {
+    Ok(Object_IR {
         start_loc,
-        end_loc: ctx.start_location_node(),
-        source: ctx.source_file.clone(),
+        end_loc: ctx.end_location_node(),
+        source: ctx.source_file,
         properties,
         assert_destructuring,
         assert_expr,
-    }
+    })
 }
 
-pub fn parse_expr_starts_with_atom(
-    ctx: &mut Parser,
-    expr: Expression,
+pub fn parse_expr_starts_with_atom<'a>(
+    ctx: &mut Parser<'a>,
+    expr: Expression<'a>,
     start_loc: _LocationNode,
-) -> Expression {
-    let expr_with_scripts = parse_subscripts(ctx, expr, start_loc.clone());
+) -> ParseResult<Expression<'a>> {
+    let expr_with_scripts = parse_subscripts(ctx, expr, start_loc.clone())?;
     let expr_with_postfix = parse_expr_op(
         ctx,
         ExpressionOperatorLeft::Expression(expr_with_scripts),
         start_loc.clone(),
         -1,
-    );
-    let expr_with_conditional = if ctx.cur_token_is(TokenLabel::Question) {
-        let consequent = parse_maybe_assign(ctx);
-        ctx.expect(TokenLabel::Colon);
-        let alternate = parse_maybe_assign(ctx);
+    )?;
+    let expr_with_conditional = if ctx.eat(TokenLabel::Question)? {
+        let consequent = parse_maybe_assign(ctx)?;
+        ctx.expect(TokenLabel::Colon)?;
+        let alternate = parse_maybe_assign(ctx)?;
         ConditionalExpression::new(
-            Box::new(expr_with_postfix),
-            Box::new(consequent),
-            Box::new(alternate),
+            Box::new_in(expr_with_postfix, ctx.arena),
+            Box::new_in(consequent, ctx.arena),
+            Box::new_in(alternate, ctx.arena),
             ctx.compose_loc_info(start_loc.clone()),
         )
         .into()
@@ -453,27 +479,29 @@ pub fn parse_expr_starts_with_atom(
     };
 
     let mut maybe_assignment_expr = expr_with_conditional;
-    while ctx.cur_token_test(|t| t.is_assign) {
+    if ctx.cur_token_test(|t| t.is_assign) {
+        let operator = AssignmentOperator::from(ctx.get_cur_token_value().as_ref());
+        ctx.next()?;
         maybe_assignment_expr = Expression::AssignmentExpression(AssignmentExpression::new(
-            AssignmentExpressionLeft::Expression(Box::new(maybe_assignment_expr)),
-            AssignmentOperator::Assignment,
-            Box::new(parse_maybe_assign(ctx)),
+            AssignmentExpressionLeft::Expression(Box::new_in(maybe_assignment_expr, ctx.arena)),
+            operator,
+            Box::new_in(parse_maybe_assign(ctx)?, ctx.arena),
             ctx.compose_loc_info(start_loc.clone()),
         ));
     }
 
-    maybe_assignment_expr
+    Ok(maybe_assignment_expr)
 }
 
 #[allow(non_camel_case_types)]
-pub enum IR_Value {
-    Array_IR(Array_IR),
-    Object_IR(Object_IR),
-    Expression(Expression),
+pub enum IR_Value<'a> {
+    Array_IR(Array_IR<'a>),
+    Object_IR(Object_IR<'a>),
+    Expression(Expression<'a>),
 }
 
-impl From<IR_Value> for Property_IR_Value {
-    fn from(value: IR_Value) -> Self {
+impl<'a> From<IR_Value<'a>> for Property_IR_Value<'a> {
+    fn from(value: IR_Value<'a>) -> Self {
         match value {
             IR_Value::Array_IR(arr_ir) => Property_IR_Value::Array_IR(arr_ir),
             IR_Value::Object_IR(obj_ir) => Property_IR_Value::Object_IR(obj_ir),
@@ -482,8 +510,8 @@ impl From<IR_Value> for Property_IR_Value {
     }
 }
 
-impl From<IR_Value> for Element_IR_Value {
-    fn from(value: IR_Value) -> Self {
+impl<'a> From<IR_Value<'a>> for Element_IR_Value<'a> {
+    fn from(value: IR_Value<'a>) -> Self {
         match value {
             IR_Value::Array_IR(arr_ir) => Self::Array_IR(arr_ir),
             IR_Value::Object_IR(obj_ir) => Self::Object_IR(obj_ir),
@@ -492,15 +520,17 @@ impl From<IR_Value> for Element_IR_Value {
     }
 }
 
-pub fn parse_property_ir(ctx: &mut Parser) -> Object_Property_IR {
+pub fn parse_property_ir<'a>(ctx: &mut Parser<'a>) -> ParseResult<Object_Property_IR<'a>> {
     let start_loc = ctx.start_location_node();
 
-    if ctx.eat(TokenLabel::Ellipsis) {
-        let (ir_val, rhs_expr) = parse_ir_value(ctx, TokenLabel::BraceR);
+    if ctx.cur_token_is(TokenLabel::Ellipsis) {
+        ctx.expect_ecma_version(9, start_loc.pos, "Object rest/spread property")?;
+        ctx.next()?;
+        let (ir_val, rhs_expr) = parse_ir_value(ctx, TokenLabel::BraceR)?;
 
-        return Object_Property_IR {
+        return Ok(Object_Property_IR {
             start_loc,
-            end_loc: ctx.start_location_node(),
+            end_loc: ctx.end_location_node(),
             key: None,
             value: ir_val.into(),
             rhs_expr,
@@ -511,7 +541,7 @@ pub fn parse_property_ir(ctx: &mut Parser) -> Object_Property_IR {
             is_shorthand: false,
             computed: false,
             is_dots: true,
-        };
+        });
     }
 
     let mut kind = PropertyKind::Init;
@@ -519,8 +549,8 @@ pub fn parse_property_ir(ctx: &mut Parser) -> Object_Property_IR {
     let mut property_key: Option<Expression> = None;
 
     // maybe async function, such as: let obj = { async *[fn]() {} };
-    if ctx.is_contextual("async") {
-        property_key = Some(parse_ident(ctx, true).into());
+    if ctx.ecma_version >= 8 && ctx.is_contextual("async") {
+        property_key = Some(parse_ident(ctx, true)?.into());
         if !has_break_in_range(ctx, (ctx.last_token_end, ctx.cur_token_start))
             && ctx.cur_token_test(|t| {
                 let label = t.label;
@@ -538,15 +568,15 @@ pub fn parse_property_ir(ctx: &mut Parser) -> Object_Property_IR {
     }
 
     // maybe generator function
-    let is_generator = if kind == PropertyKind::Init {
-        ctx.eat(TokenLabel::Star)
-    } else {
-        false
-    };
+    let is_generator = kind == PropertyKind::Init && eat_generator_star(ctx, is_async)?;
 
     // maybe a getter or setter function, such as: let obj = { get method() {}, set setVal() }
-    if !is_generator && !is_async && (ctx.is_contextual("get") || ctx.is_contextual("set")) {
-        property_key = Some(parse_ident(ctx, true).into());
+    if !is_generator
+        && !is_async
+        && ctx.ecma_version >= 5
+        && (ctx.is_contextual("get") || ctx.is_contextual("set"))
+    {
+        property_key = Some(parse_ident(ctx, true)?.into());
         if ctx.cur_token_test(|t| {
             let label = t.label;
             return label == TokenLabel::Name
@@ -555,7 +585,8 @@ pub fn parse_property_ir(ctx: &mut Parser) -> Object_Property_IR {
                 || label == TokenLabel::String
                 || t.keyword;
         }) {
-            kind = if ctx.cur_token_value_is("get") {
+            kind = if matches!(&property_key, Some(Expression::Identifier(ident)) if ident.name == "get")
+            {
                 PropertyKind::Get
             } else {
                 PropertyKind::Set
@@ -569,16 +600,18 @@ pub fn parse_property_ir(ctx: &mut Parser) -> Object_Property_IR {
     // To parse property key here if need.
     if property_key.is_none() {
         let key;
-        computed = ctx.eat(TokenLabel::BracketL);
+        computed = ctx.cur_token_is(TokenLabel::BracketL);
         if computed {
-            key = parse_maybe_assign(ctx);
-            ctx.expect(TokenLabel::BracketR);
+            ctx.expect_ecma_version(6, ctx.cur_token_start, "Computed property name")?;
+            ctx.next()?;
+            key = parse_maybe_assign(ctx)?;
+            ctx.expect(TokenLabel::BracketR)?;
         } else if ctx
             .cur_token_test(|t| t.label == TokenLabel::String || t.label == TokenLabel::Number)
         {
-            key = parse_literal(ctx).into();
+            key = parse_literal(ctx)?.into();
         } else {
-            key = parse_ident(ctx, true).into();
+            key = parse_ident(ctx, true)?.into();
         }
         property_key = Some(key);
     }
@@ -591,55 +624,47 @@ pub fn parse_property_ir(ctx: &mut Parser) -> Object_Property_IR {
 
     // parse method first
     if ctx.cur_token_is(TokenLabel::ParenL) {
-        let func_expr = parse_method(ctx, is_generator, is_async, false);
-        match kind {
-            PropertyKind::Get => {
-                if func_expr.params.len() != 0 {
-                    println!("getter should have no params");
-                }
-            }
-            PropertyKind::Set => {
-                if func_expr.params.len() != 1 {
-                    println!("setter should have exactly one param");
-                }
-                if func_expr
-                    .params
-                    .first()
-                    .map_or(false, |p| matches!(p, Pattern::RestElement(..)))
-                {
-                    println!("Setter cannot use rest params")
-                }
-            }
-            _ => {}
+        if kind == PropertyKind::Init {
+            ctx.expect_ecma_version(6, start_loc.pos, "Method definition")?;
+        }
+        let func_expr = parse_method(ctx, is_generator, is_async, false)?;
+        if kind != PropertyKind::Init {
+            check_accessor_params(
+                ctx,
+                kind == PropertyKind::Get,
+                &func_expr.params,
+                func_expr.start,
+            )?;
         }
 
-        return Object_Property_IR {
+        return Ok(Object_Property_IR {
             start_loc,
-            end_loc: ctx.start_location_node(),
+            end_loc: ctx.end_location_node(),
             key: Some(key),
             value: Property_IR_Value::Expression(Expression::FunctionExpression(func_expr)),
             rhs_expr: None,
             kind: kind.clone(),
-            is_method: true,
+            // getter 和 setter 不是 method
+            is_method: kind == PropertyKind::Init,
             is_async,
             is_generator,
             is_shorthand,
             computed,
             is_dots: false,
-        };
+        });
     }
 
     if is_async || is_generator || kind != PropertyKind::Init {
-        unexpected(ctx.cur_token.clone().unwrap());
+        return Err(unexpected(ctx));
     }
 
-    if ctx.eat(TokenLabel::Colon) {
-        let (ir_val, rhs_expr) = parse_ir_value(ctx, TokenLabel::BraceR);
+    if ctx.eat(TokenLabel::Colon)? {
+        let (ir_val, rhs_expr) = parse_ir_value(ctx, TokenLabel::BraceR)?;
 
-        return Object_Property_IR {
+        return Ok(Object_Property_IR {
             start_loc,
-            end_loc: ctx.start_location_node(),
-            key: None,
+            end_loc: ctx.end_location_node(),
+            key: Some(key),
             value: ir_val.into(),
             rhs_expr,
             kind: PropertyKind::Init,
@@ -647,28 +672,36 @@ pub fn parse_property_ir(ctx: &mut Parser) -> Object_Property_IR {
             is_async: false,
             is_generator: false,
             is_shorthand: false,
-            computed: false,
+            computed,
             is_dots: false,
-        };
+        });
     }
 
     if is_shorthand {
         if let Expression::Identifier(ident_key) = &key {
-            let ir_value = if ctx.eat(TokenLabel::Eq) {
-                let right_val = parse_maybe_assign(ctx);
+            ctx.expect_ecma_version(6, start_loc.pos, "Shorthand property")?;
+            check_unreserved(ctx, &ident_key.name, ident_key.start)?;
+            if ident_key.name == "await" && ctx.await_ident_pos == 0 {
+                ctx.await_ident_pos = ident_key.start;
+            }
+            let ir_value = if ctx.eat(TokenLabel::Eq)? {
+                let right_val = parse_maybe_assign(ctx)?;
                 Expression::AssignmentExpression(AssignmentExpression::new(
-                    AssignmentExpressionLeft::Expression(Box::new(ident_key.clone().into())),
+                    AssignmentExpressionLeft::Expression(Box::new_in(
+                        ident_key.clone().into(),
+                        ctx.arena,
+                    )),
                     AssignmentOperator::Assignment,
-                    right_val.into(),
+                    Box::new_in(right_val, ctx.arena),
                     ctx.compose_loc_info(start_loc.clone()),
                 ))
             } else {
                 ident_key.clone().into()
             };
 
-            return Object_Property_IR {
+            return Ok(Object_Property_IR {
                 start_loc,
-                end_loc: ctx.start_location_node(),
+                end_loc: ctx.end_location_node(),
                 key: Some(key),
                 value: Property_IR_Value::Expression(ir_value),
                 rhs_expr: None,
@@ -679,25 +712,28 @@ pub fn parse_property_ir(ctx: &mut Parser) -> Object_Property_IR {
                 is_shorthand: true,
                 computed: false,
                 is_dots: false,
-            };
+            });
         }
     }
 
-    unexpected(ctx.cur_token.clone().unwrap());
+    Err(unexpected(ctx))
 }
 
-pub fn parse_ir_value(ctx: &mut Parser, close_label: TokenLabel) -> (IR_Value, Option<Expression>) {
+pub fn parse_ir_value<'a>(
+    ctx: &mut Parser<'a>,
+    close_label: TokenLabel,
+) -> ParseResult<(IR_Value<'a>, Option<Expression<'a>>)> {
     if ctx.cur_token_is(TokenLabel::BraceL) {
-        let obj_ir_value = parse_obj_ir(ctx);
-        if ctx.eat(TokenLabel::Eq) {
-            return (
+        let obj_ir_value = parse_obj_ir(ctx)?;
+        if ctx.eat(TokenLabel::Eq)? {
+            return Ok((
                 IR_Value::Object_IR(obj_ir_value),
-                Some(parse_maybe_assign(ctx)),
-            );
+                Some(parse_maybe_assign(ctx)?),
+            ));
         }
 
         if ctx.cur_token_test(|t| t.label == TokenLabel::Comma || t.label == close_label) {
-            return (IR_Value::Object_IR(obj_ir_value), None);
+            return Ok((IR_Value::Object_IR(obj_ir_value), None));
         }
 
         // try to parse ir as expression:
@@ -705,63 +741,70 @@ pub fn parse_ir_value(ctx: &mut Parser, close_label: TokenLabel) -> (IR_Value, O
         let obj_ir_start_loc = obj_ir_value.start_loc.clone();
         let expr_val = parse_expr_starts_with_atom(
             ctx,
-            Expression::ObjectExpression(build_obj_ir_as_expr(obj_ir_value, ctx.strict_mode)),
+            Expression::ObjectExpression(build_obj_ir_as_expr(
+                obj_ir_value,
+                ctx.arena,
+                ctx.strict_mode,
+            )?),
             obj_ir_start_loc,
-        );
+        )?;
 
-        return (IR_Value::Expression(expr_val), None);
+        return Ok((IR_Value::Expression(expr_val), None));
     }
 
     if ctx.cur_token_is(TokenLabel::BracketL) {
-        let arr_ir_val = parse_arr_ir(ctx);
-        if ctx.eat(TokenLabel::Eq) {
-            return (
+        let arr_ir_val = parse_arr_ir(ctx)?;
+        if ctx.eat(TokenLabel::Eq)? {
+            return Ok((
                 IR_Value::Array_IR(arr_ir_val),
-                Some(parse_maybe_assign(ctx)),
-            );
+                Some(parse_maybe_assign(ctx)?),
+            ));
         }
 
         if ctx.cur_token_test(|t| t.label == TokenLabel::Comma || t.label == close_label) {
-            return (IR_Value::Array_IR(arr_ir_val), None);
+            return Ok((IR_Value::Array_IR(arr_ir_val), None));
         }
 
         let arr_ir_start_loc = arr_ir_val.start_loc.clone();
         let expr_val = parse_expr_starts_with_atom(
             ctx,
-            Expression::ArrayExpression(build_array_ir_as_expr(arr_ir_val, ctx.strict_mode)),
+            Expression::ArrayExpression(build_array_ir_as_expr(
+                arr_ir_val,
+                ctx.arena,
+                ctx.strict_mode,
+            )?),
             arr_ir_start_loc,
-        );
+        )?;
 
-        return (IR_Value::Expression(expr_val), None);
+        return Ok((IR_Value::Expression(expr_val), None));
     }
 
-    return (IR_Value::Expression(parse_maybe_assign(ctx)), None);
+    Ok((IR_Value::Expression(parse_maybe_assign(ctx)?), None))
 }
 
-pub fn parse_method(
-    ctx: &mut Parser,
+pub fn parse_method<'a>(
+    ctx: &mut Parser<'a>,
     is_generator: bool,
     is_async: bool,
     allow_super: bool,
-) -> FunctionExpression {
+) -> ParseResult<FunctionExpression<'a>> {
     let start_loc = ctx.start_location_node();
     let flags = get_func_flags(is_async, is_generator);
-    // TODO: check the usage of SCOPE_SUPER as default here
-    ctx.enter_scope(flags | if allow_super { SCOPE_DIRECT_SUPER } else { 0 });
+    ctx.enter_scope(flags | SCOPE_SUPER | if allow_super { SCOPE_DIRECT_SUPER } else { 0 });
 
-    ctx.expect(TokenLabel::ParenL);
-    let params = parse_binding_list(ctx, TokenLabel::ParenR, false, true);
+    ctx.expect(TokenLabel::ParenL)?;
+    let params = parse_binding_list(ctx, TokenLabel::ParenR, false, ctx.ecma_version >= 8)?;
 
     // TODO: check param error.
-    let function_body = parse_function_body(ctx);
+    let function_body = parse_function_body(ctx)?;
     ctx.exit_scope();
 
-    FunctionExpression::new(
+    Ok(FunctionExpression::new(
         None,
-        params.into_iter().map(|p| p.unwrap()).collect(),
+        Vec::from_iter_in(params.into_iter().map(|p| p.unwrap()), ctx.arena),
         function_body,
         is_generator,
         is_async,
         ctx.compose_loc_info(start_loc),
-    )
+    ))
 }
@@ -2,72 +2,75 @@ use super::{
     array::{build_array_ir_as_expr, build_array_ir_as_pattern},
     function::parse_arrow_expr,
     object::{build_obj_ir_as_expr, build_obj_ir_as_pattern, parse_ir_value, IR_Value},
-    util::get_assign_left_ident,
+    util::{get_assign_left_ident, invalid_destructuring, raise_at},
 };
+use crate::error::{ParseErrorKind, ParseResult};
 use crate::{
     ast::{
         _LocationNode,
         expression::{
-            AssignmentExpression, AssignmentExpressionLeft, AssignmentOperator, Expression,
-            ParenthesizedExpression, SequenceExpression,
+            AssignmentExpression, AssignmentExpressionLeft, AssignmentOperator, CallExprArgs,
+            Expression, ParenthesizedExpression, SequenceExpression, SpreadElement,
         },
         pattern::{AssignmentPattern, Pattern, RestElement},
+        Box, Vec,
     },
     parser::Parser,
     statement::util::{after_trailing_comma, can_insert_semicolon, unexpected},
     tokenizer::js_token::TokenLabel,
 };
+use bumpalo::Bump;
 
 #[allow(non_camel_case_types)]
-pub struct Paren_IR_List {
+pub struct Paren_IR_List<'a> {
     start_loc: _LocationNode,
     end_loc: _LocationNode,
     inner_start_loc: _LocationNode,
     inner_end_loc: _LocationNode,
-    source: Option<String>,
-    elements: Vec<Paren_IR_Element>,
+    source: Option<&'a str>,
+    elements: std::vec::Vec<Paren_IR_Element<'a>>,
     assert_expr: bool,
     assert_binding: bool,
     last_is_comma: bool,
 }
 
 #[allow(non_camel_case_types)]
-pub struct Paren_IR_Element {
+pub struct Paren_IR_Element<'a> {
     start_loc: _LocationNode,
     end_loc: _LocationNode,
     is_dots: bool,
-    value: IR_Value,
-    rhs_expr: Option<Expression>,
+    value: IR_Value<'a>,
+    rhs_expr: Option<Expression<'a>>,
 }
 
-pub fn build_paren_ir_as_pattern(ir_list: Paren_IR_List, strict_mode: bool) -> Vec<Pattern> {
-    if !ir_list.assert_expr {
-        panic!("Invalid destructuring assignment target");
+pub fn build_paren_ir_as_pattern<'a>(
+    ir_list: Paren_IR_List<'a>,
+    arena: &'a Bump,
+    strict_mode: bool,
+) -> ParseResult<Vec<'a, Pattern<'a>>> {
+    if ir_list.assert_expr {
+        return Err(invalid_destructuring(&ir_list.start_loc));
     }
 
-    let mut results = vec![];
+    let mut results = Vec::new_in(arena);
     for element in ir_list.elements {
         match element.value {
             IR_Value::Array_IR(arr_ir_val) => {
                 let arr_ir_start_loc = arr_ir_val.start_loc.clone();
-                let arr_pat = build_array_ir_as_pattern(arr_ir_val, strict_mode);
+                let arr_pat = build_array_ir_as_pattern(arr_ir_val, arena, strict_mode)?;
                 let mut el_val = if element.rhs_expr.is_some() {
                     Pattern::AssignmentPattern(AssignmentPattern::new(
-                        Box::new(Pattern::ArrayPattern(arr_pat)),
-                        Box::new(element.rhs_expr.unwrap()),
-                        (
-                            arr_ir_start_loc,
-                            element.end_loc.clone(),
-                            ir_list.source.clone(),
-                        ),
+                        Box::new_in(Pattern::ArrayPattern(arr_pat), arena),
+                        Box::new_in(element.rhs_expr.unwrap(), arena),
+                        (arr_ir_start_loc, element.end_loc.clone(), ir_list.source),
                     ))
                 } else {
                     Pattern::ArrayPattern(arr_pat)
                 };
                 if element.is_dots {
                     el_val = Pattern::RestElement(RestElement::new(
-                        Box::new(el_val),
-                        (element.start_loc, element.end_loc, ir_list.source.clone()),
+                        Box::new_in(el_val, arena),
+                        (element.start_loc, element.end_loc, ir_list.source),
                     ))
                 }
                 results.push(el_val);
@@ -75,107 +78,83 @@ pub fn build_paren_ir_as_pattern(ir_list: Paren_IR_List, strict_mode: bool) -> V
             IR_Value::Object_IR(obj_ir_val) => {
                 let obj_ir_start_loc = obj_ir_val.start_loc.clone();
                 let has_rhs_expr = element.rhs_expr.is_some();
-                let obj_pat = build_obj_ir_as_pattern(obj_ir_val, strict_mode);
+                let obj_pat = build_obj_ir_as_pattern(obj_ir_val, arena, strict_mode)?;
                 let mut el_val = if has_rhs_expr {
                     Pattern::AssignmentPattern(AssignmentPattern::new(
-                        Box::new(Pattern::ObjectPattern(obj_pat)),
-                        Box::new(element.rhs_expr.unwrap()),
-                        (
-                            obj_ir_start_loc,
-                            element.end_loc.clone(),
-                            ir_list.source.clone(),
-                        ),
+                        Box::new_in(Pattern::ObjectPattern(obj_pat), arena),
+                        Box::new_in(element.rhs_expr.unwrap(), arena),
+                        (obj_ir_start_loc, element.end_loc.clone(), ir_list.source),
                     ))
                 } else {
                     Pattern::ObjectPattern(obj_pat)
                 };
                 if element.is_dots {
                     el_val = Pattern::RestElement(RestElement::new(
-                        Box::new(el_val),
-                        (element.start_loc, element.end_loc, ir_list.source.clone()),
+                        Box::new_in(el_val, arena),
+                        (element.start_loc, element.end_loc, ir_list.source),
                     ));
                 }
                 results.push(el_val);
             }
             IR_Value::Expression(expr_val) => match expr_val {
                 Expression::Identifier(ident_val) => {
-                    results.push(Pattern::Identifier(ident_val));
+                    results.push(if element.is_dots {
+                        Pattern::RestElement(RestElement::new(
+                            Box::new_in(Pattern::Identifier(ident_val), arena),
+                            (element.start_loc, element.end_loc, ir_list.source),
+                        ))
+                    } else {
+                        Pattern::Identifier(ident_val)
+                    });
                 }
                 Expression::AssignmentExpression(AssignmentExpression { left, right, .. }) => {
                     let left_val = get_assign_left_ident(left);
                     if left_val.is_some() {
                         results.push(Pattern::AssignmentPattern(AssignmentPattern::new(
-                            Box::new(Pattern::Identifier(left_val.unwrap())),
+                            Box::new_in(Pattern::Identifier(left_val.unwrap()), arena),
                             right,
                             (
                                 element.start_loc.clone(),
                                 element.end_loc.clone(),
-                                ir_list.source.clone(),
+                                ir_list.source,
                             ),
                         )));
                     } else {
-                        panic!("Invalid destructuring assignment target")
+                        return Err(invalid_destructuring(&element.start_loc));
                     }
                 }
                 _ => {
-                    panic!("Invalid destructuring assignment target")
+                    return Err(invalid_destructuring(&element.start_loc));
                 }
             },
         }
     }
 
-    results
+    Ok(results)
 }
 
-pub fn build_paren_ir_as_expr(
-    ir_list: Paren_IR_List,
+pub fn build_paren_ir_as_expr<'a>(
+    ir_list: Paren_IR_List<'a>,
+    arena: &'a Bump,
     strict_mode: bool,
     keep_paren: bool,
-) -> Expression {
+) -> ParseResult<Expression<'a>> {
     if ir_list.assert_binding {
-        panic!("");
+        return Err(raise_at(
+            &ir_list.start_loc,
+            ParseErrorKind::UnexpectedToken,
+            "Unexpected token '...'",
+        ));
     }
 
-    let mut results = vec![];
+    let mut results = Vec::new_in(arena);
     for element in ir_list.elements {
-        match element.value {
-            IR_Value::Array_IR(arr_ir_val) => {
-                let arr_ir_start_loc = arr_ir_val.start_loc.clone();
-                let arr_val = build_array_ir_as_expr(arr_ir_val, strict_mode);
-                let el_val = if element.rhs_expr.is_some() {
-                    Expression::AssignmentExpression(AssignmentExpression::new(
-                        AssignmentExpressionLeft::Expression(Box::new(
-                            Expression::ArrayExpression(arr_val),
-                        )),
-                        AssignmentOperator::Assignment,
-                        Box::new(element.rhs_expr.unwrap()),
-                        (arr_ir_start_loc, element.end_loc, ir_list.source.clone()),
-                    ))
-                } else {
-                    Expression::ArrayExpression(arr_val)
-                };
-                results.push(el_val);
-            }
-            IR_Value::Object_IR(obj_ir_val) => {
-                let obj_ir_start_loc = obj_ir_val.start_loc.clone();
-                let obj_val = build_obj_ir_as_expr(obj_ir_val, strict_mode);
-                results.push(if element.rhs_expr.is_some() {
-                    Expression::AssignmentExpression(AssignmentExpression::new(
-                        AssignmentExpressionLeft::Expression(Box::new(
-                            Expression::ObjectExpression(obj_val),
-                        )),
-                        AssignmentOperator::Assignment,
-                        Box::new(element.rhs_expr.unwrap()),
-                        (obj_ir_start_loc, element.end_loc, ir_list.source.clone()),
-                    ))
-                } else {
-                    Expression::ObjectExpression(obj_val)
-                });
-            }
-            IR_Value::Expression(expr_val) => {
-                results.push(expr_val);
-            }
-        }
+        results.push(build_paren_ir_element_as_expr(
+            element,
+            ir_list.source,
+            arena,
+            strict_mode,
+        )?);
     }
 
     let expr = if results.len() > 1 {
@@ -184,7 +163,7 @@ pub fn build_paren_ir_as_expr(
             (
                 ir_list.inner_start_loc,
                 ir_list.inner_end_loc,
-                ir_list.source.clone(),
+                ir_list.source,
             ),
         )
         .into()
@@ -193,53 +172,137 @@ pub fn build_paren_ir_as_expr(
     };
 
     if keep_paren {
-        Expression::ParenthesizedExpression(ParenthesizedExpression::new(
-            Box::new(expr),
-            (ir_list.start_loc, ir_list.end_loc, ir_list.source.clone()),
+        Ok(Expression::ParenthesizedExpression(
+            ParenthesizedExpression::new(
+                Box::new_in(expr, arena),
+                (ir_list.start_loc, ir_list.end_loc, ir_list.source),
+            ),
         ))
     } else {
-        expr
+        Ok(expr)
     }
 }
 
+fn build_paren_ir_element_as_expr<'a>(
+    element: Paren_IR_Element<'a>,
+    source: Option<&'a str>,
+    arena: &'a Bump,
+    strict_mode: bool,
+) -> ParseResult<Expression<'a>> {
+    match element.value {
+        IR_Value::Array_IR(arr_ir_val) => {
+            let arr_ir_start_loc = arr_ir_val.start_loc.clone();
+            let arr_val = build_array_ir_as_expr(arr_ir_val, arena, strict_mode)?;
+            Ok(if element.rhs_expr.is_some() {
+                Expression::AssignmentExpression(AssignmentExpression::new(
+                    AssignmentExpressionLeft::Expression(Box::new_in(
+                        Expression::ArrayExpression(arr_val),
+                        arena,
+                    )),
+                    AssignmentOperator::Assignment,
+                    Box::new_in(element.rhs_expr.unwrap(), arena),
+                    (arr_ir_start_loc, element.end_loc, source),
+                ))
+            } else {
+                Expression::ArrayExpression(arr_val)
+            })
+        }
+        IR_Value::Object_IR(obj_ir_val) => {
+            let obj_ir_start_loc = obj_ir_val.start_loc.clone();
+            let obj_val = build_obj_ir_as_expr(obj_ir_val, arena, strict_mode)?;
+            Ok(if element.rhs_expr.is_some() {
+                Expression::AssignmentExpression(AssignmentExpression::new(
+                    AssignmentExpressionLeft::Expression(Box::new_in(
+                        Expression::ObjectExpression(obj_val),
+                        arena,
+                    )),
+                    AssignmentOperator::Assignment,
+                    Box::new_in(element.rhs_expr.unwrap(), arena),
+                    (obj_ir_start_loc, element.end_loc, source),
+                ))
+            } else {
+                Expression::ObjectExpression(obj_val)
+            })
+        }
+        IR_Value::Expression(expr_val) => Ok(expr_val),
+    }
+}
+
+// 将括号中的 ir 作为函数调用的参数，用于 async(..) 不是箭头函数的情况
+pub fn build_paren_ir_as_args<'a>(
+    ir_list: Paren_IR_List<'a>,
+    arena: &'a Bump,
+    strict_mode: bool,
+) -> ParseResult<Vec<'a, CallExprArgs<'a>>> {
+    let mut args = Vec::new_in(arena);
+    for element in ir_list.elements {
+        let start_loc = element.start_loc.clone();
+        let end_loc = element.end_loc.clone();
+        let is_dots = element.is_dots;
+        let expr = build_paren_ir_element_as_expr(element, ir_list.source, arena, strict_mode)?;
+        args.push(if is_dots {
+            CallExprArgs::SpreadElement(SpreadElement::new(
+                expr,
+                (start_loc, end_loc, ir_list.source),
+            ))
+        } else {
+            CallExprArgs::Expression(expr)
+        });
+    }
+
+    Ok(args)
+}
+
 // parse irs surrounded by pair parens
-fn parse_paren_ir_list(ctx: &mut Parser) -> Paren_IR_List {
+pub fn parse_paren_ir_list<'a>(ctx: &mut Parser<'a>) -> ParseResult<Paren_IR_List<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.expect(TokenLabel::ParenL);
+    ctx.expect(TokenLabel::ParenL)?;
     let inner_start_loc = ctx.start_location_node();
 
     let mut assert_binding = false;
     let assert_expr = false;
     let mut first = true;
     let mut last_is_comma = false;
-    let mut elements: Vec<Paren_IR_Element> = vec![];
+    let mut elements = vec![];
 
     while !ctx.cur_token_is(TokenLabel::ParenR) {
         if first {
             first = false;
         } else {
-            ctx.expect(TokenLabel::Comma);
+            ctx.expect(TokenLabel::Comma)?;
         }
 
-        if after_trailing_comma(ctx, TokenLabel::ParenR, false) {
+        if ctx.ecma_version >= 8 && after_trailing_comma(ctx, TokenLabel::ParenR, false)? {
             last_is_comma = true;
             break;
         }
 
         if ctx.cur_token_is(TokenLabel::Ellipsis) {
             let rest_start_loc = ctx.start_location_node();
-            ctx.next_unwrap();
-            let (ir_val, rhs_expr) = parse_ir_value(ctx, TokenLabel::ParenR);
+            ctx.next()?;
+            let (ir_val, rhs_expr) = parse_ir_value(ctx, TokenLabel::ParenR)?;
             if ctx.cur_token_is(TokenLabel::Comma) {
-                panic!("Comma is not permitted after the rest element");
+                return Err(ctx.raise(
+                    ctx.cur_token_start,
+                    ParseErrorKind::SyntaxError,
+                    "Comma is not permitted after the rest element",
+                ));
             }
             if assert_binding {
-                panic!("Rest parameter must be last formal parameter");
+                return Err(ctx.raise(
+                    rest_start_loc.pos,
+                    ParseErrorKind::SyntaxError,
+                    "Rest parameter must be last formal parameter",
+                ));
             } else {
                 assert_binding = true;
             }
             if rhs_expr.is_some() {
-                panic!("Rest parameter may not have a default initializer");
+                return Err(ctx.raise(
+                    rest_start_loc.pos,
+                    ParseErrorKind::SyntaxError,
+                    "Rest parameter may not have a default initializer",
+                ));
             }
             elements.push(Paren_IR_Element {
                 start_loc: rest_start_loc,
@@ -252,7 +315,7 @@ fn parse_paren_ir_list(ctx: &mut Parser) -> Paren_IR_List {
         }
 
         let ir_start_loc = ctx.start_location_node();
-        let (ir_val, rhs_expr) = parse_ir_value(ctx, TokenLabel::ParenR);
+        let (ir_val, rhs_expr) = parse_ir_value(ctx, TokenLabel::ParenR)?;
         elements.push(Paren_IR_Element {
             start_loc: ir_start_loc,
             end_loc: ctx.end_location_node(),
@@ -263,13 +326,13 @@ fn parse_paren_ir_list(ctx: &mut Parser) -> Paren_IR_List {
     }
 
     if assert_expr && assert_binding {
-        panic!("Invalid destructuring assignment target");
+        return Err(invalid_destructuring(&start_loc));
     }
 
     let inner_end_loc = ctx.end_location_node();
-    ctx.expect(TokenLabel::ParenR);
+    ctx.expect(TokenLabel::ParenR)?;
 
-    Paren_IR_List {
+    Ok(Paren_IR_List {
         start_loc,
         end_loc: ctx.end_location_node(),
         inner_start_loc,
@@ -277,42 +340,42 @@ fn parse_paren_ir_list(ctx: &mut Parser) -> Paren_IR_List {
         assert_expr,
         assert_binding,
         elements,
-        source: ctx.source_file.clone(),
+        source: ctx.source_file,
         last_is_comma,
-    }
+    })
 }
 
 // When pasring a single parenl in expression, the case is a little complex,
 // because it could be a paren expression or the start of an arrow function, or an assignment pattern ect.
-pub fn parse_parenl(ctx: &mut Parser) -> Expression {
+pub fn parse_parenl<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     let may_be_arrow = ctx.potential_arrow_pos == ctx.cur_token_start;
     let start_loc = ctx.start_location_node();
     let old_yield_pos = ctx.yield_pos;
     let old_await_pos = ctx.await_pos;
 
-    let paren_ir_list = parse_paren_ir_list(ctx);
+    let paren_ir_list = parse_paren_ir_list(ctx)?;
 
-    if may_be_arrow && !can_insert_semicolon(ctx) && ctx.eat(TokenLabel::Arrow) {
+    if may_be_arrow && !can_insert_semicolon(ctx) && ctx.eat(TokenLabel::Arrow)? {
         // TODO: ignore error check for now
         ctx.yield_pos = old_yield_pos;
         ctx.await_pos = old_await_pos;
-        return parse_arrow_expr(
+        return Ok(parse_arrow_expr(
             ctx,
             start_loc,
-            build_paren_ir_as_pattern(paren_ir_list, ctx.strict_mode),
+            build_paren_ir_as_pattern(paren_ir_list, ctx.arena, ctx.strict_mode)?,
             false,
-        )
-        .into();
+        )?
+        .into());
     }
 
     if paren_ir_list.assert_binding {
-        unexpected(ctx.cur_token.clone().unwrap());
+        return Err(unexpected(ctx));
     }
 
     // parenthsized expression must contain at lease one element,
     //  and the last one can not be comma.
     if paren_ir_list.elements.len() == 0 || paren_ir_list.last_is_comma {
-        unexpected(ctx.cur_token.clone().unwrap());
+        return Err(unexpected(ctx));
     }
 
     // TODO: check expression error
@@ -324,5 +387,5 @@ pub fn parse_parenl(ctx: &mut Parser) -> Expression {
     }
 
     // TODO: add option for keep_paren
-    build_paren_ir_as_expr(paren_ir_list, ctx.strict_mode, false)
+    build_paren_ir_as_expr(paren_ir_list, ctx.arena, ctx.strict_mode, false)
 }
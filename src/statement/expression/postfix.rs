@@ -1,4 +1,6 @@
 use super::{subscript::parse_expr_subscripts, util::is_lhs_expr_simple};
+use crate::ast::Box;
+use crate::error::{ParseErrorKind, ParseResult};
 use crate::{
     ast::expression::{Expression, UpdateExpression, UpdateOperator},
     parser::Parser,
@@ -8,23 +10,27 @@ use crate::{
 // UpdateExpression[Yield, Await] :
 // LeftHandSideExpression[?Yield, ?Await] [no LineTerminator here] ++
 // LeftHandSideExpression[?Yield, ?Await] [no LineTerminator here] --
-pub fn parse_maybe_postfix(ctx: &mut Parser) -> Expression {
+pub fn parse_maybe_postfix<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     let start_loc = ctx.start_location_node();
-    let expr = parse_expr_subscripts(ctx);
+    let expr = parse_expr_subscripts(ctx)?;
     if ctx.cur_token_test(|t| t.postfix) && !can_insert_semicolon(ctx) {
         if !is_lhs_expr_simple(&expr, ctx.strict_mode) {
-            panic!("Invalid left-hand side expression in postfix operation");
+            return Err(ctx.raise(
+                start_loc.pos,
+                ParseErrorKind::InvalidAssignmentTarget,
+                "Invalid left-hand side expression in postfix operation",
+            ));
         }
-        let operator = UpdateOperator::from(ctx.get_cur_token_value());
-        ctx.next_unwrap();
-        return UpdateExpression::new(
+        let operator = UpdateOperator::from(ctx.get_cur_token_value().as_ref());
+        ctx.next()?;
+        return Ok(UpdateExpression::new(
             operator,
-            Box::new(expr),
+            Box::new_in(expr, ctx.arena),
             false,
             ctx.compose_loc_info(start_loc.clone()),
         )
-        .into();
+        .into());
     }
 
-    expr
+    Ok(expr)
 }
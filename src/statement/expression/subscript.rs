@@ -2,6 +2,7 @@ use super::{
     parse_epxr_list, parse_expr_atom, parse_expression, parse_ident, parse_private_ident,
     template::parse_template,
 };
+use crate::error::{ParseErrorKind, ParseResult};
 use crate::{
     ast::{
         _LocationNode,
@@ -9,6 +10,7 @@ use crate::{
             CallExpression, ChainExpression, ChainExpressionElement, Expression,
             MemberExprProperty, MemberExpression, TaggedTemplateExpression,
         },
+        Box, Vec,
     },
     parser::Parser,
     statement::util::unexpected,
@@ -16,76 +18,84 @@ use crate::{
 };
 
 // parse []、.、?.、() subscript
-pub fn parse_expr_subscripts(ctx: &mut Parser) -> Expression {
+pub fn parse_expr_subscripts<'a>(ctx: &mut Parser<'a>) -> ParseResult<Expression<'a>> {
     let start_loc = ctx.start_location_node();
-    let expr = parse_expr_atom(ctx);
+    let expr = parse_expr_atom(ctx)?;
     // TODO: check if expr is Arrow Expression
 
     parse_subscripts(ctx, expr, start_loc.clone())
 }
 
 // parse subscript based exp
-pub fn parse_subscripts(
-    ctx: &mut Parser,
-    base: Expression,
+pub fn parse_subscripts<'a>(
+    ctx: &mut Parser<'a>,
+    base: Expression<'a>,
     start_loc_node: _LocationNode,
-) -> Expression {
+) -> ParseResult<Expression<'a>> {
     let mut base_node = base;
     let mut optional_chained = false;
 
     loop {
-        let optional = ctx.eat(TokenLabel::QuestionDot);
-        let computed = ctx.eat(TokenLabel::BracketL);
+        let optional = ctx.eat(TokenLabel::QuestionDot)?;
+        let computed = ctx.eat(TokenLabel::BracketL)?;
         if optional {
             optional_chained = true;
         }
         if ctx.disable_call_expr && optional {
-            panic!("Optional chaining cannot appear in the callee of new expressions");
+            return Err(ctx.raise(
+                ctx.last_token_start,
+                ParseErrorKind::SyntaxError,
+                "Optional chaining cannot appear in the callee of new expressions",
+            ));
         }
         if computed
             || (optional
-                && !ctx.cur_token_test(|t| {
+                && ctx.cur_token_test(|t| {
                     t.label != TokenLabel::ParenL && t.label != TokenLabel::BackQuote
                 }))
-            || ctx.eat(TokenLabel::Dot)
+            || ctx.eat(TokenLabel::Dot)?
         {
             let property: MemberExprProperty;
             if computed {
-                property = parse_expression(ctx).into();
-                ctx.expect(TokenLabel::BracketR);
+                property = Box::new_in(parse_expression(ctx)?, ctx.arena).into();
+                ctx.expect(TokenLabel::BracketR)?;
             }
             // TODO: alse to check to token's type
             else if ctx.cur_token_is(TokenLabel::PrivateId) {
-                property = parse_private_ident(ctx).into();
+                property = parse_private_ident(ctx)?.into();
             } else {
-                property = Expression::from(parse_ident(ctx, true)).into();
+                property = Box::new_in(Expression::from(parse_ident(ctx, true)?), ctx.arena).into();
             }
 
             base_node = MemberExpression::new(
-                base_node.into(),
+                Box::new_in(base_node, ctx.arena).into(),
                 property,
                 computed,
                 optional,
                 ctx.compose_loc_info(start_loc_node.clone()),
             )
             .into();
-        } else if !ctx.disable_call_expr && ctx.eat(TokenLabel::ParenL) {
-            let expr_list = parse_epxr_list(ctx, TokenLabel::ParenR, true, false);
+        } else if !ctx.disable_call_expr && ctx.eat(TokenLabel::ParenL)? {
+            let expr_list = parse_epxr_list(ctx, TokenLabel::ParenR, ctx.ecma_version >= 8, false)?;
             // TODO: check expression errors
             base_node = CallExpression::new(
-                base_node.into(),
-                expr_list.into_iter().map(|e| e.into()).collect(),
+                Box::new_in(base_node, ctx.arena).into(),
+                Vec::from_iter_in(expr_list.into_iter().map(|e| e.into()), ctx.arena),
                 optional,
                 ctx.compose_loc_info(start_loc_node.clone()),
             )
             .into();
         } else if ctx.cur_token_is(TokenLabel::BackQuote) {
             if optional || optional_chained {
-                panic!("Optional chaining cannot appear in the tag of tagged template expressions");
+                return Err(ctx.raise(
+                    ctx.cur_token_start,
+                    ParseErrorKind::SyntaxError,
+                    "Optional chaining cannot appear in the tag of tagged template expressions",
+                ));
             }
-            let quasis = parse_template(ctx, true);
+            let quasis = parse_template(ctx, true)?;
             base_node = TaggedTemplateExpression::new(
-                Box::new(base_node),
+                Box::new_in(base_node, ctx.arena),
                 quasis,
                 ctx.compose_loc_info(start_loc_node.clone()),
             )
@@ -100,11 +110,11 @@ pub fn parse_subscripts(
             Expression::CallExpression(expr) => ChainExpressionElement::CallExpression(expr),
             Expression::MemberExpression(expr) => ChainExpressionElement::MemberExpression(expr),
             _ => {
-                unexpected(ctx.cur_token.clone().unwrap());
+                return Err(unexpected(ctx));
             }
         };
         base_node = ChainExpression::new(chain_expr, ctx.compose_loc_info(start_loc_node)).into();
     }
 
-    base_node
+    Ok(base_node)
 }
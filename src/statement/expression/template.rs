@@ -1,62 +1,81 @@
+use std::borrow::Cow;
+
 use super::parse_expression;
+use crate::ast::Vec;
+use crate::error::{EarlyErrorCode, ParseErrorKind, ParseResult};
 use crate::{
-    ast::expression::{Expression, TemplateElement, TemplateLiteral, TemplateValue},
+    ast::expression::{TemplateElement, TemplateLiteral, TemplateValue},
     parser::Parser,
     statement::util::unexpected,
     tokenizer::js_token::TokenLabel,
 };
 
-pub fn parse_template(ctx: &mut Parser, tagged: bool) -> TemplateLiteral {
+pub fn parse_template<'a>(ctx: &mut Parser<'a>, tagged: bool) -> ParseResult<TemplateLiteral<'a>> {
     let start_loc = ctx.start_location_node();
-    let mut expressions: Vec<Expression> = vec![];
-    let mut quasis: Vec<TemplateElement> = vec![];
+    let mut expressions = Vec::new_in(ctx.arena);
+    let mut quasis = Vec::new_in(ctx.arena);
 
-    ctx.next_unwrap();
+    ctx.next()?;
 
     loop {
         if ctx.cur_token_is(TokenLabel::Eof) {
-            panic!("Unterminated template literal");
+            return Err(ctx.raise(
+                start_loc.pos,
+                ParseErrorKind::Unterminated,
+                "Unterminated template literal",
+            ));
         }
-        let tmp_el = parse_template_ele(ctx, tagged);
+        let tmp_el = parse_template_ele(ctx, tagged)?;
         if tmp_el.tail {
             quasis.push(tmp_el);
-            ctx.next_unwrap();
+            ctx.next()?;
             break;
         }
         quasis.push(tmp_el);
-        ctx.expect(TokenLabel::DollarBraceL);
-        expressions.push(parse_expression(ctx));
-        ctx.expect(TokenLabel::BraceR);
+        ctx.expect(TokenLabel::DollarBraceL)?;
+        expressions.push(parse_expression(ctx)?);
+        ctx.expect(TokenLabel::BraceR)?;
     }
 
-    TemplateLiteral::new(quasis, expressions, ctx.compose_loc_info(start_loc))
+    Ok(TemplateLiteral::new(
+        quasis,
+        expressions,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_template_ele(ctx: &mut Parser, tagged: bool) -> TemplateElement {
+pub fn parse_template_ele<'a>(
+    ctx: &mut Parser<'a>,
+    tagged: bool,
+) -> ParseResult<TemplateElement<'a>> {
     let start_loc = ctx.start_location_node();
+    let raw: Cow<'a, str> = match ctx.cur_token.as_ref().map_or("", |t| t.raw) {
+        raw if raw.contains('\r') => raw.replace("\r\n", "\n").replace('\r', "\n").into(),
+        raw => raw.into(),
+    };
     let value = if ctx.cur_token_is(TokenLabel::Invalidtemplate) {
         if !tagged {
-            println!("Bad escape sequence in untagged template literal");
-        }
-        TemplateValue {
-            raw: ctx.get_cur_token_value(),
-            cooked: None,
+            ctx.raise_early(
+                start_loc.pos,
+                EarlyErrorCode::InvalidTemplateEscape,
+                "Bad escape sequence in untagged template literal",
+            )?;
         }
+        TemplateValue { raw, cooked: None }
     } else if ctx.cur_token_is(TokenLabel::Template) {
-        let v = ctx.get_cur_token_value();
         TemplateValue {
-            raw: v.clone(),
-            cooked: Some(v),
+            raw,
+            cooked: Some(ctx.get_cur_token_value()),
         }
     } else {
-        unexpected(ctx.cur_token.clone().unwrap())
+        return Err(unexpected(ctx));
     };
 
-    ctx.next_unwrap();
+    ctx.next()?;
 
-    TemplateElement::new(
+    Ok(TemplateElement::new(
         value,
         ctx.cur_token_is(TokenLabel::BackQuote),
         ctx.compose_loc_info(start_loc),
-    )
+    ))
 }
@@ -1,4 +1,10 @@
-use super::{parse_await, postfix::parse_maybe_postfix};
+use super::{
+    binary::{build_binary, ExpressionOperatorLeft},
+    parse_await,
+    postfix::parse_maybe_postfix,
+};
+use crate::ast::Box;
+use crate::error::ParseResult;
 use crate::{
     ast::expression::{
         Expression, UnaryExpression, UnaryOperator, UpdateExpression, UpdateOperator,
@@ -9,18 +15,22 @@ use crate::{
 };
 
 // parse unary operator
-pub fn parse_maybe_unary(ctx: &mut Parser, mut saw_unary: bool, is_inc_dec: bool) -> Expression {
+pub fn parse_maybe_unary<'a>(
+    ctx: &mut Parser<'a>,
+    mut saw_unary: bool,
+    is_inc_dec: bool,
+) -> ParseResult<Expression<'a>> {
     let start_loc = ctx.start_location_node();
     let expr;
 
     if ctx.is_contextual("await") && ctx.can_await() {
-        expr = parse_await(ctx).into();
+        expr = parse_await(ctx)?.into();
         saw_unary = true;
     } else if ctx.cur_token_test(|t| t.prefix) {
         let is_update = ctx.cur_token_test(|t| t.label == TokenLabel::IncDec);
         let operator_val = ctx.get_cur_token_value();
-        ctx.next_unwrap();
-        let argument = parse_maybe_unary(ctx, saw_unary, is_update);
+        ctx.next()?;
+        let argument = parse_maybe_unary(ctx, true, is_update)?;
         // TODO: check expression errors
 
         if is_update {
@@ -31,30 +41,41 @@ pub fn parse_maybe_unary(ctx: &mut Parser, mut saw_unary: bool, is_inc_dec: bool
 
         expr = if is_update {
             UpdateExpression::new(
-                UpdateOperator::from(operator_val),
-                Box::new(argument),
+                UpdateOperator::from(operator_val.as_ref()),
+                Box::new_in(argument, ctx.arena),
                 true,
-                ctx.compose_loc_info(start_loc),
+                ctx.compose_loc_info(start_loc.clone()),
             )
             .into()
         } else {
             UnaryExpression::new(
-                UnaryOperator::from(operator_val),
-                Box::new(argument),
+                UnaryOperator::from(operator_val.as_ref()),
+                Box::new_in(argument, ctx.arena),
                 true,
-                ctx.compose_loc_info(start_loc),
+                ctx.compose_loc_info(start_loc.clone()),
             )
             .into()
         };
     } else {
-        expr = parse_maybe_postfix(ctx);
+        expr = parse_maybe_postfix(ctx)?;
     }
 
-    if !is_inc_dec && ctx.eat(TokenLabel::StarStar) {
+    // the exponentiation operator is right-associative, and it can't follow an unary expression directly
+    if !is_inc_dec && ctx.cur_token_is(TokenLabel::StarStar) {
         if saw_unary {
-            unexpected(ctx.cur_token.clone().unwrap());
+            return Err(unexpected(ctx));
         }
+        ctx.next()?;
+        let right = parse_maybe_unary(ctx, false, false)?;
+        return build_binary(
+            ctx,
+            start_loc,
+            ExpressionOperatorLeft::Expression(expr),
+            right,
+            "**",
+            false,
+        );
     }
 
-    expr
+    Ok(expr)
 }
@@ -1,9 +1,12 @@
 use crate::ast::{
+    _LocationNode,
     expression::{AssignmentExpressionLeft, Expression, Identifier, ParenthesizedExpression},
     pattern::Pattern,
+    Box,
 };
+use crate::error::{ParseError, ParseErrorKind};
 
-pub fn is_lhs_expr_simple(mut expr: &Expression, strict: bool) -> bool {
+pub fn is_lhs_expr_simple<'a>(mut expr: &Expression<'a>, strict: bool) -> bool {
     while matches!(expr, Expression::ParenthesizedExpression(..)) {
         if let Expression::ParenthesizedExpression(ParenthesizedExpression {
             expression: val,
@@ -29,12 +32,12 @@ pub fn is_lhs_expr_simple(mut expr: &Expression, strict: bool) -> bool {
     }
 }
 
-pub fn get_paren_expr_val(expr: Expression) -> Expression {
+pub fn get_paren_expr_val<'a>(expr: Expression<'a>) -> Expression<'a> {
     let mut val = expr;
     loop {
         if let Expression::ParenthesizedExpression(ParenthesizedExpression { expression, .. }) = val
         {
-            val = *expression;
+            val = Box::into_inner(expression);
         } else {
             break;
         }
@@ -43,10 +46,10 @@ pub fn get_paren_expr_val(expr: Expression) -> Expression {
     val
 }
 
-pub fn get_assign_left_ident(assign: AssignmentExpressionLeft) -> Option<Identifier> {
+pub fn get_assign_left_ident<'a>(assign: AssignmentExpressionLeft<'a>) -> Option<Identifier<'a>> {
     match assign {
         AssignmentExpressionLeft::Expression(expr) => {
-            if let Expression::Identifier(ident) = *expr {
+            if let Expression::Identifier(ident) = Box::into_inner(expr) {
                 return Some(ident);
             }
         }
@@ -58,3 +61,16 @@ pub fn get_assign_left_ident(assign: AssignmentExpressionLeft) -> Option<Identif
 
     None
 }
+
+/// 构造一个位于 loc 处的错误，用于没有 Parser 上下文的场景
+pub fn raise_at(loc: &_LocationNode, kind: ParseErrorKind, message: &str) -> ParseError {
+    ParseError::new(kind, message, loc.pos, loc.loc.clone())
+}
+
+pub fn invalid_destructuring(loc: &_LocationNode) -> ParseError {
+    raise_at(
+        loc,
+        ParseErrorKind::InvalidAssignmentTarget,
+        "Invalid destructuring assignment target",
+    )
+}
@@ -1,15 +1,16 @@
 use super::expression::assignment::parse_maybe_assign;
 use super::expression::literal::parse_literal;
 use super::expression::parse_ident;
-use super::util::{after_trailing_comma, unexpected};
+use super::util::{after_trailing_comma, check_unreserved, unexpected};
 use crate::ast::_LocationNode;
 use crate::ast::expression::{Expression, Identifier};
 use crate::ast::pattern::{
     ArrayPattern, AssignmentPattern, AssignmentProperty, ObjectPattern, ObjectPatternProperty,
     Pattern, RestElement,
 };
+use crate::ast::{Box, Vec};
+use crate::error::{ParseErrorKind, ParseResult};
 use crate::{parser::Parser, tokenizer::js_token::TokenLabel};
-use std::vec;
 
 // For object and array destructuring, there are two kinds of destructruing patterns, binding pattern and assignment pattern.
 // There is a little difference between them,
@@ -25,32 +26,32 @@ use std::vec;
 // const obj = { a: 1, b: 2 };
 // ({ a: numbers[0], b: numbers[1] } = obj);
 
-pub enum PatternAtom {
-    ArrayPattern(ArrayPattern),
-    ObjectPattern(ObjectPattern),
-    Identifier(Identifier),
+pub enum PatternAtom<'a> {
+    ArrayPattern(ArrayPattern<'a>),
+    ObjectPattern(ObjectPattern<'a>),
+    Identifier(Identifier<'a>),
 }
 
-impl From<ArrayPattern> for PatternAtom {
-    fn from(value: ArrayPattern) -> Self {
+impl<'a> From<ArrayPattern<'a>> for PatternAtom<'a> {
+    fn from(value: ArrayPattern<'a>) -> Self {
         Self::ArrayPattern(value)
     }
 }
 
-impl From<ObjectPattern> for PatternAtom {
-    fn from(value: ObjectPattern) -> Self {
+impl<'a> From<ObjectPattern<'a>> for PatternAtom<'a> {
+    fn from(value: ObjectPattern<'a>) -> Self {
         Self::ObjectPattern(value)
     }
 }
 
-impl From<Identifier> for PatternAtom {
-    fn from(value: Identifier) -> Self {
+impl<'a> From<Identifier<'a>> for PatternAtom<'a> {
+    fn from(value: Identifier<'a>) -> Self {
         Self::Identifier(value)
     }
 }
 
-impl From<PatternAtom> for Pattern {
-    fn from(value: PatternAtom) -> Self {
+impl<'a> From<PatternAtom<'a>> for Pattern<'a> {
+    fn from(value: PatternAtom<'a>) -> Self {
         match value {
             PatternAtom::ArrayPattern(v) => Self::ArrayPattern(v),
             PatternAtom::ObjectPattern(v) => Self::ObjectPattern(v),
@@ -65,74 +66,85 @@ impl From<PatternAtom> for Pattern {
 // - Identifier
 // The RestElement and AssingmentPattern is not binding atom, because they only can appear in
 // the body of pattern like ArrayPattern、ObjectPattern、function params list ect...
-pub fn parse_binding_atom(ctx: &mut Parser) -> PatternAtom {
+pub fn parse_binding_atom<'a>(ctx: &mut Parser<'a>) -> ParseResult<PatternAtom<'a>> {
+    if ctx.cur_token_is(TokenLabel::BracketL) || ctx.cur_token_is(TokenLabel::BraceL) {
+        ctx.expect_ecma_version(6, ctx.cur_token_start, "Destructuring pattern")?;
+    }
     if ctx.cur_token_is(TokenLabel::BracketL) {
         let start_loc = ctx.start_location_node();
-        ctx.next_unwrap();
-        let elements = parse_binding_list(ctx, TokenLabel::BracketR, true, true);
-        return ArrayPattern::new(elements, ctx.compose_loc_info(start_loc)).into();
+        ctx.next()?;
+        let elements = parse_binding_list(ctx, TokenLabel::BracketR, true, true)?;
+        return Ok(ArrayPattern::new(elements, ctx.compose_loc_info(start_loc)).into());
     }
 
     if ctx.cur_token_is(TokenLabel::BraceL) {
-        return parse_object_binding(ctx).into();
+        return Ok(parse_object_binding(ctx)?.into());
     }
 
-    parse_ident(ctx, false).into()
+    Ok(parse_ident(ctx, false)?.into())
 }
 
-pub fn parse_object_binding(ctx: &mut Parser) -> ObjectPattern {
+pub fn parse_object_binding<'a>(ctx: &mut Parser<'a>) -> ParseResult<ObjectPattern<'a>> {
     let start_loc = ctx.start_location_node();
-    let mut properties = vec![];
-    ctx.expect(TokenLabel::BraceL);
+    let mut properties = Vec::new_in(ctx.arena);
+    ctx.expect(TokenLabel::BraceL)?;
 
     let mut first = true;
-    while !ctx.eat(TokenLabel::BraceR) {
+    while !ctx.eat(TokenLabel::BraceR)? {
         if !first {
-            ctx.expect(TokenLabel::Comma);
-            if after_trailing_comma(ctx, TokenLabel::BraceR, true) {
+            ctx.expect(TokenLabel::Comma)?;
+            if after_trailing_comma(ctx, TokenLabel::BraceR, true)? {
                 break;
             }
         } else {
-            first = true;
+            first = false;
         }
 
         if ctx.cur_token_is(TokenLabel::Ellipsis) {
             let property_start_loc = ctx.start_location_node();
-            ctx.next_unwrap();
-            let value = parse_ident(ctx, false);
+            ctx.expect_ecma_version(9, property_start_loc.pos, "Object rest/spread property")?;
+            ctx.next()?;
+            let value = parse_ident(ctx, false)?;
             if ctx.cur_token_is(TokenLabel::Comma) {
-                panic!("Comma is not permitted after the rest element");
+                return Err(ctx.raise(
+                    ctx.cur_token_start,
+                    ParseErrorKind::SyntaxError,
+                    "Comma is not permitted after the rest element",
+                ));
             }
 
             properties.push(ObjectPatternProperty::RestElement(RestElement::new(
-                Box::new(Pattern::Identifier(value)),
+                Box::new_in(Pattern::Identifier(value), ctx.arena),
                 ctx.compose_loc_info(property_start_loc),
             )));
             continue;
         }
 
         properties.push(ObjectPatternProperty::AssignmentProperty(
-            parse_binding_property(ctx),
+            parse_binding_property(ctx)?,
         ));
     }
 
-    ObjectPattern::new(properties, ctx.compose_loc_info(start_loc))
+    Ok(ObjectPattern::new(
+        properties,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_binding_property(ctx: &mut Parser) -> AssignmentProperty {
+pub fn parse_binding_property<'a>(ctx: &mut Parser<'a>) -> ParseResult<AssignmentProperty<'a>> {
     let property_start_loc = ctx.start_location_node();
-    let computed = ctx.eat(TokenLabel::BracketL);
+    let computed = ctx.eat(TokenLabel::BracketL)?;
     let key;
     if computed {
-        key = parse_maybe_assign(ctx);
-        ctx.expect(TokenLabel::BracketR);
+        key = parse_maybe_assign(ctx)?;
+        ctx.expect(TokenLabel::BracketR)?;
     } else {
         key = if ctx
             .cur_token_test(|t| t.label == TokenLabel::String || t.label == TokenLabel::Number)
         {
-            parse_literal(ctx).into()
+            parse_literal(ctx)?.into()
         } else {
-            parse_ident(ctx, true).into()
+            parse_ident(ctx, true)?.into()
         };
     }
 
@@ -141,105 +153,121 @@ pub fn parse_binding_property(ctx: &mut Parser) -> AssignmentProperty {
             || ctx.cur_token_is(TokenLabel::Eq)
             || ctx.cur_token_is(TokenLabel::BraceR));
 
-    if ctx.eat(TokenLabel::Colon) {
-        return AssignmentProperty::new(
+    if ctx.eat(TokenLabel::Colon)? {
+        return Ok(AssignmentProperty::new(
             key,
-            parse_may_assignment_pattern(ctx),
+            parse_may_assignment_pattern(ctx)?,
             false,
             computed,
             ctx.compose_loc_info(property_start_loc),
-        );
+        ));
     }
 
     if is_shorthand {
         // only identifier key could be shorthand
         if let Expression::Identifier(ident) = &key {
-            let value = if ctx.eat(TokenLabel::Eq) {
+            // 简写的属性名同时也是绑定的名称
+            check_unreserved(ctx, &ident.name, ident.start)?;
+            let value = if ctx.eat(TokenLabel::Eq)? {
                 let assi_start_loc = _LocationNode {
                     pos: ident.start,
-                    loc: ident.loc.start.clone(),
+                    loc: ident
+                        .loc
+                        .as_ref()
+                        .map(|loc| loc.start.clone())
+                        .unwrap_or_default(),
                 };
                 Pattern::AssignmentPattern(AssignmentPattern::new(
-                    Box::new(ident.clone().into()),
-                    Box::new(parse_maybe_assign(ctx)),
+                    Box::new_in(ident.clone().into(), ctx.arena),
+                    Box::new_in(parse_maybe_assign(ctx)?, ctx.arena),
                     ctx.compose_loc_info(assi_start_loc),
                 ))
             } else {
                 ident.clone().into()
             };
-            return AssignmentProperty::new(
+            return Ok(AssignmentProperty::new(
                 key,
                 value,
                 true,
                 false,
                 ctx.compose_loc_info(property_start_loc),
-            );
+            ));
         }
     }
 
-    unexpected(ctx.cur_token.clone().unwrap())
+    Err(unexpected(ctx))
 }
 
-pub fn parse_rest_binding(ctx: &mut Parser) -> RestElement {
+pub fn parse_rest_binding<'a>(ctx: &mut Parser<'a>) -> ParseResult<RestElement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
+    ctx.next()?;
 
-    // TODO: in es6, this position must be an identifier
-    let argument = parse_binding_atom(ctx);
+    // in es6, the argument of rest element must be an identifier
+    if ctx.ecma_version == 6 && !ctx.cur_token_is(TokenLabel::Name) {
+        return Err(unexpected(ctx));
+    }
+    let argument = parse_binding_atom(ctx)?;
 
-    RestElement::new(Box::new(argument.into()), ctx.compose_loc_info(start_loc))
+    Ok(RestElement::new(
+        Box::new_in(argument.into(), ctx.arena),
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_binding_list(
-    ctx: &mut Parser,
+pub fn parse_binding_list<'a>(
+    ctx: &mut Parser<'a>,
     close_label: TokenLabel,
     allow_empty: bool,
     allow_trailing_comma: bool,
-) -> Vec<Option<Pattern>> {
-    let mut elems: Vec<Option<Pattern>> = vec![];
+) -> ParseResult<Vec<'a, Option<Pattern<'a>>>> {
+    let mut elems = Vec::new_in(ctx.arena);
     let mut first = true;
-    while !ctx.eat(close_label) {
+    while !ctx.eat(close_label)? {
         if first {
             first = false;
         } else {
-            ctx.expect(TokenLabel::Comma);
+            ctx.expect(TokenLabel::Comma)?;
         }
 
         if allow_empty && ctx.cur_token_is(TokenLabel::Comma) {
             elems.push(None);
             continue;
         }
-        if allow_trailing_comma && after_trailing_comma(ctx, close_label, true) {
+        if allow_trailing_comma && after_trailing_comma(ctx, close_label, true)? {
             break;
         }
         // RestElement must be last element; And for object pattern, the argumemt of rest element must be identifier.
         if ctx.cur_token_is(TokenLabel::Ellipsis) {
-            elems.push(Some(parse_rest_binding(ctx).into()));
+            elems.push(Some(parse_rest_binding(ctx)?.into()));
             if ctx.cur_token_is(TokenLabel::Comma) {
-                panic!("Comma is not permitted after the rest element");
+                return Err(ctx.raise(
+                    ctx.cur_token_start,
+                    ParseErrorKind::SyntaxError,
+                    "Comma is not permitted after the rest element",
+                ));
             }
-            ctx.expect(close_label);
+            ctx.expect(close_label)?;
             break;
         }
 
-        elems.push(Some(parse_may_assignment_pattern(ctx)));
+        elems.push(Some(parse_may_assignment_pattern(ctx)?));
     }
 
-    elems
+    Ok(elems)
 }
 
-pub fn parse_may_assignment_pattern(ctx: &mut Parser) -> Pattern {
+pub fn parse_may_assignment_pattern<'a>(ctx: &mut Parser<'a>) -> ParseResult<Pattern<'a>> {
     let start_loc = ctx.start_location_node();
-    let left: Pattern = parse_binding_atom(ctx).into();
-    if !ctx.eat(TokenLabel::Eq) {
-        return left;
+    let left: Pattern = parse_binding_atom(ctx)?.into();
+    if ctx.ecma_version < 6 || !ctx.eat(TokenLabel::Eq)? {
+        return Ok(left);
     }
 
-    let right = parse_maybe_assign(ctx);
+    let right = parse_maybe_assign(ctx)?;
 
-    Pattern::AssignmentPattern(AssignmentPattern::new(
-        Box::new(left),
-        Box::new(right),
+    Ok(Pattern::AssignmentPattern(AssignmentPattern::new(
+        Box::new_in(left, ctx.arena),
+        Box::new_in(right, ctx.arena),
         ctx.compose_loc_info(start_loc),
-    ))
+    )))
 }
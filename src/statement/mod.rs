@@ -13,10 +13,12 @@ use self::expression::{
 use self::lval::{parse_binding_atom, parse_binding_list};
 use self::scope::{get_func_flags, SCOPE_CLASS_STATIC_BLOCK, SCOPE_SIMPLE_CATCH, SCOPE_SUPER};
 use self::util::{
-    after_trailing_comma, can_insert_semicolon, check_label_destination,
-    check_private_name_conflicts, check_used_private_name, is_async_func, is_directive_candidate,
-    is_import_expr, is_let, unexpected,
+    advance_with_recovery, after_trailing_comma, can_insert_semicolon, check_accessor_params,
+    check_export, check_label_destination, check_pattern_export, check_private_name_conflicts,
+    check_used_private_name, eat_generator_star, is_async_func, is_directive_candidate, is_for_of,
+    is_import_expr, is_let, is_use_strict, skip_to_sync_point, strict_directive, unexpected,
 };
+use crate::ast::comment::attach_comments;
 use crate::ast::directive::Directive;
 use crate::ast::expression::{
     Expression, Identifier, Literal, LiteralValue, PrivateIdentifier, StaticBlock,
@@ -28,51 +30,44 @@ use crate::ast::import_export_declaration::{
     ExportedType, ImportDeclaration, ImportDefaultSpecifier, ImportNamespaceSpecifier,
     ImportOrExportDeclaration, ImportSpecifier, ImportSpecifiers, ImportedType,
 };
+use crate::ast::location::FinishLocations;
 use crate::ast::pattern::Pattern;
 use crate::ast::statement::{
     BlockStatement, BreakStatement, CatchClause, ClassBody, ClassBodyEl, ClassDeclaration,
     ClassDeclarationType, ClassMethodKey, ClassPropertyKey, ContinueStatement, DebuggerStatement,
     DoWhileStatement, EmptyStatement, ExpressionStatement, ForInOfStatementLeft, ForInStatement,
     ForOfStatement, ForStatement, ForStatementInit, FunctionDeclaration, FunctionDeclarationType,
-    IfStatement, LabeledStatement, MethodDefinition, MethodKind, PropertyDefinition,
-    ReturnStatement, Statement, SwitchCase, SwitchStatement, ThrowStatement, TryStatement,
-    VariableDeclaration, VariableDeclarator, VariableKind, WhileStatement, WithStatement,
+    IfStatement, InvalidStatement, LabeledStatement, MethodDefinition, MethodKind,
+    PropertyDefinition, ReturnStatement, Statement, SwitchCase, SwitchStatement, ThrowStatement,
+    TryStatement, VariableDeclaration, VariableDeclarator, VariableKind, WhileStatement,
+    WithStatement,
 };
-use crate::ast::{Program, _LocationNode, create_program_node};
+use crate::ast::{_LocationNode, create_program_node, Box, Program, ProgramNode, Vec};
+use crate::error::{EarlyErrorCode, ParseErrorKind, ParseResult};
 use crate::parser::{AccessorKind, ForInitType, Label, LabelKind, Parser, StatementContext};
 use crate::tokenizer::js_token::TokenLabel;
 use crate::tokenizer::util::has_break_in_range;
 use std::vec;
 
-pub fn parse_top_level(ctx: &mut Parser) -> Program {
-    let mut root_node = create_program_node();
+pub fn parse_top_level<'a>(ctx: &mut Parser<'a>) -> ParseResult<Program<'a>> {
+    let mut root_node = create_program_node(ctx.arena, ctx.options.source_type);
     let mut maybe_directive = true;
 
-    ctx.next_unwrap();
+    if strict_directive(ctx, ctx.cursor) {
+        ctx.strict_mode = true;
+    }
+    if ctx.options.error_recovery {
+        advance_with_recovery(ctx);
+    } else {
+        ctx.next()?;
+    }
 
     loop {
         if ctx.cur_token.as_ref().map_or(false, |t| t.is_eof()) {
             break;
         }
-        let start_token = ctx.cur_token.as_ref().unwrap();
-        match start_token.label {
-            TokenLabel::_Import => {
-                // TODO: check ecma version >= 10
-                if !is_import_expr(ctx) {
-                    root_node.body.push(parse_statement(ctx).into());
-                    continue;
-                }
-                // TODO: check if it's in a module
-                root_node
-                    .body
-                    .push(ImportOrExportDeclaration::from(parse_import(ctx)).into());
-            }
-            TokenLabel::_Export => {
-                // TODO: check if it's in a module
-                root_node.body.push(parse_export(ctx).into());
-            }
-            _ => {
-                let stmt = parse_statement(ctx);
+        match parse_recoverable(ctx, false, parse_program_item)? {
+            ProgramNode::Statement(stmt) => {
                 if !maybe_directive {
                     root_node.body.push(stmt.into());
                 } else if is_directive_candidate(&stmt) {
@@ -81,6 +76,9 @@ pub fn parse_top_level(ctx: &mut Parser) -> Program {
                         ..
                     }) = stmt
                     {
+                        if is_use_strict(ctx, &literal) {
+                            ctx.strict_mode = true;
+                        }
                         root_node.body.push(Directive::from(literal).into());
                     }
                 } else {
@@ -88,85 +86,169 @@ pub fn parse_top_level(ctx: &mut Parser) -> Program {
                     root_node.body.push(stmt.into());
                 }
             }
+            node => {
+                maybe_directive = false;
+                root_node.body.push(node);
+            }
         }
     }
 
     root_node.end = ctx.cursor;
-    root_node.loc.end = ctx.get_cursor_position();
+    if let Some(loc) = &mut root_node.loc {
+        loc.end = ctx.get_cursor_position();
+    }
+    root_node.hashbang = ctx.hashbang;
+    root_node.comments = std::mem::take(&mut ctx.comments);
+    if ctx.options.attach_comments {
+        attach_comments(&mut root_node);
+    }
+    if let Some(config) = &ctx.location_config {
+        root_node.finish_locations(config);
+    }
+
+    Ok(root_node)
+}
+
+fn parse_program_item<'a>(ctx: &mut Parser<'a>) -> ParseResult<ProgramNode<'a>> {
+    let label = ctx.cur_token.as_ref().unwrap().label;
+    let is_import_export =
+        label == TokenLabel::_Export || label == TokenLabel::_Import && !is_import_expr(ctx);
+    if !is_import_export {
+        return Ok(parse_statement(ctx)?.into());
+    }
+    if !ctx.in_module() && !ctx.options.allow_import_export_everywhere {
+        return Err(ctx.raise(
+            ctx.cur_token_start,
+            ParseErrorKind::SyntaxError,
+            "'import' and 'export' may appear only with 'sourceType: module'",
+        ));
+    }
 
-    root_node
+    if label == TokenLabel::_Import {
+        Ok(ImportOrExportDeclaration::from(parse_import(ctx)?).into())
+    } else {
+        Ok(parse_export(ctx)?.into())
+    }
 }
 
-pub fn parse_statement(ctx: &mut Parser) -> Statement {
+/// 解析一条语句，容错模式下解析失败时记录错误，跳过到下一个同步点并使用 InvalidStatement 代替该语句
+pub fn parse_recoverable<'a, T, F>(ctx: &mut Parser<'a>, nested: bool, parse: F) -> ParseResult<T>
+where
+    F: FnOnce(&mut Parser<'a>) -> ParseResult<T>,
+    T: From<Statement<'a>>,
+{
+    if !ctx.options.error_recovery {
+        return parse(ctx);
+    }
+
+    let start_loc = ctx.start_location_node();
+    let checkpoint = ctx.checkpoint();
+    match parse(ctx) {
+        Ok(node) => Ok(node),
+        Err(err) => {
+            ctx.rewind(checkpoint);
+            skip_to_sync_point(ctx, nested);
+            // 没有跳过任何 token 时（例如语句块未闭合就到达了文件末尾）交给外层处理，避免死循环
+            if ctx.last_token_end <= start_loc.pos {
+                return Err(err);
+            }
+            ctx.record_error(err);
+            Ok(Statement::from(InvalidStatement::new(ctx.compose_loc_info(start_loc))).into())
+        }
+    }
+}
+
+pub fn parse_statement<'a>(ctx: &mut Parser<'a>) -> ParseResult<Statement<'a>> {
     if is_let(ctx) {
-        return parse_var_stmt(ctx, VariableKind::Let).into();
+        return Ok(parse_var_stmt(ctx, VariableKind::Let)?.into());
     }
 
     let start_type = ctx.cur_token.as_ref().unwrap();
     match start_type.label {
         TokenLabel::_Break | TokenLabel::_Continue => parse_break_continue(ctx),
-        TokenLabel::_Debugger => parse_debugger(ctx).into(),
-        TokenLabel::_Do => parse_do_loop(ctx).into(),
+        TokenLabel::_Debugger => Ok(parse_debugger(ctx)?.into()),
+        TokenLabel::_Do => Ok(parse_do_loop(ctx)?.into()),
         TokenLabel::_For => parse_for_loop(ctx),
-        TokenLabel::_Function => parse_function_stmt(ctx, false, false).into(),
+        TokenLabel::_Function => Ok(parse_function_stmt(ctx, false, false)?.into()),
         TokenLabel::_Class => {
             if !ctx.cur_stmt_ctx_is(StatementContext::TopLevel) {
-                unexpected(ctx.cur_token.clone().unwrap());
+                return Err(unexpected(ctx));
             }
-            parse_class(ctx, false).into()
+            Ok(parse_class(ctx, false)?.into())
         }
-        TokenLabel::_If => parse_if(ctx).into(),
-        TokenLabel::_Return => parse_return(ctx).into(),
-        TokenLabel::_Switch => parse_switch(ctx).into(),
-        TokenLabel::_Throw => parse_throw(ctx).into(),
-        TokenLabel::_Try => parse_try(ctx).into(),
-        TokenLabel::_Const | TokenLabel::_Var => parse_var_stmt(
+        TokenLabel::_If => Ok(parse_if(ctx)?.into()),
+        TokenLabel::_Return => Ok(parse_return(ctx)?.into()),
+        TokenLabel::_Switch => Ok(parse_switch(ctx)?.into()),
+        TokenLabel::_Throw => Ok(parse_throw(ctx)?.into()),
+        TokenLabel::_Try => Ok(parse_try(ctx)?.into()),
+        TokenLabel::_Const | TokenLabel::_Var => Ok(parse_var_stmt(
             ctx,
             if ctx.cur_token_is(TokenLabel::_Const) {
                 VariableKind::Const
             } else {
                 VariableKind::Var
             },
-        )
-        .into(),
-        TokenLabel::_While => parse_while(ctx).into(),
-        TokenLabel::_With => parse_with(ctx).into(),
-        TokenLabel::BraceL => parse_block_stmt(ctx, true).into(),
-        TokenLabel::Semi => parse_empty(ctx).into(),
+        )?
+        .into()),
+        TokenLabel::_While => Ok(parse_while(ctx)?.into()),
+        TokenLabel::_With => Ok(parse_with(ctx)?.into()),
+        TokenLabel::BraceL => Ok(parse_block_stmt(ctx, true)?.into()),
+        TokenLabel::Semi => Ok(parse_empty(ctx)?.into()),
+        TokenLabel::_Import | TokenLabel::_Export
+            if !(ctx.cur_token_is(TokenLabel::_Import) && is_import_expr(ctx)) =>
+        {
+            if !ctx.options.allow_import_export_everywhere {
+                return Err(ctx.raise(
+                    ctx.cur_token_start,
+                    ParseErrorKind::SyntaxError,
+                    "'import' and 'export' may only appear at the top level",
+                ));
+            }
+            if ctx.cur_token_is(TokenLabel::_Import) {
+                Ok(ImportOrExportDeclaration::from(parse_import(ctx)?).into())
+            } else {
+                Ok(parse_export(ctx)?.into())
+            }
+        }
         // import alse could be expression
         TokenLabel::_Import => {
             let start_loc = ctx.start_location_node();
             // TODO: add parse import expression function, didn't need to use the general parse_expression function
-            ctx.semicolon();
-            ExpressionStatement::new(parse_expression(ctx), ctx.compose_loc_info(start_loc)).into()
+            let expr = parse_expression(ctx)?;
+            ctx.semicolon()?;
+            Ok(ExpressionStatement::new(expr, ctx.compose_loc_info(start_loc)).into())
         }
         _ => {
             let start_loc = ctx.start_location_node();
             if is_async_func(ctx) {
-                return parse_function_stmt(ctx, false, true).into();
+                return Ok(parse_function_stmt(ctx, false, true)?.into());
             }
-            let expr = parse_expression(ctx);
+            let expr = parse_expression(ctx)?;
             if ctx.cur_token_is(TokenLabel::Colon) {
                 if let Expression::Identifier(ident) = expr {
-                    ctx.next_unwrap();
-                    return parse_labeled_stmt(ctx, start_loc, ident).into();
+                    ctx.next()?;
+                    return Ok(parse_labeled_stmt(ctx, start_loc, ident)?.into());
                 }
             }
 
-            ctx.semicolon();
-            ExpressionStatement::new(expr, ctx.compose_loc_info(start_loc)).into()
+            ctx.semicolon()?;
+            Ok(ExpressionStatement::new(expr, ctx.compose_loc_info(start_loc)).into())
         }
     }
 }
 
-pub fn parse_labeled_stmt(
-    ctx: &mut Parser,
+pub fn parse_labeled_stmt<'a>(
+    ctx: &mut Parser<'a>,
     start_loc: _LocationNode,
-    label: Identifier,
-) -> LabeledStatement {
+    label: Identifier<'a>,
+) -> ParseResult<LabeledStatement<'a>> {
     for l in ctx.labels.iter() {
         if l.name.as_ref().map_or(false, |n| n == &label.name) {
-            panic!("Label '{}' is already declared", label.name);
+            return Err(ctx.raise(
+                label.start,
+                ParseErrorKind::SyntaxError,
+                &format!("Label '{}' is already declared", label.name),
+            ));
         }
     }
     let label_kind = if ctx.cur_token_test(|t| t.is_loop) {
@@ -179,43 +261,54 @@ pub fn parse_labeled_stmt(
 
     ctx.labels.push(Label {
         kind: label_kind,
-        name: Some(label.name.clone()),
+        name: Some(label.name.to_string()),
     });
 
     ctx.enter_stmt_ctx(StatementContext::LabelStmt);
-    let body = parse_statement(ctx);
+    let body = parse_statement(ctx)?;
     ctx.exit_stmt_ctx();
 
-    LabeledStatement::new(label, Box::new(body), ctx.compose_loc_info(start_loc))
+    Ok(LabeledStatement::new(
+        label,
+        Box::new_in(body, ctx.arena),
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
 // TODO: check if exported thing exists.
-pub fn parse_export(ctx: &mut Parser) -> ImportOrExportDeclaration {
+pub fn parse_export<'a>(ctx: &mut Parser<'a>) -> ParseResult<ImportOrExportDeclaration<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
+    ctx.next()?;
     // export * [as ident] from ''
-    if ctx.cur_token_is(TokenLabel::Star) {
-        // TODO: check ecma >= 11
-        let exported = if ctx.eat_contextual("as") {
-            match parse_import_export_name(ctx) {
+    if ctx.eat(TokenLabel::Star)? {
+        let exported = if ctx.is_contextual("as") {
+            ctx.expect_ecma_version(11, ctx.cur_token_start, "'export * as ns'")?;
+            ctx.next()?;
+            let name = parse_import_export_name(ctx)?;
+            let (exported_name, pos) = name.name();
+            check_export(ctx, &exported_name, pos)?;
+            match name {
                 ImportExportName::Identifier(ident) => ExportAllExportedType::Identifier(ident),
                 ImportExportName::Literal(literal) => ExportAllExportedType::Literal(literal),
             }
         } else {
             ExportAllExportedType::Null
         };
-        ctx.expect_contexual("from");
+        ctx.expect_contexual("from")?;
         if !ctx.cur_token_is(TokenLabel::String) {
-            unexpected(ctx.cur_token.clone().unwrap());
+            return Err(unexpected(ctx));
         }
-        let source = parse_literal(ctx);
-        ctx.semicolon();
-        return ExportAllDeclaration::new(exported, source, ctx.compose_loc_info(start_loc)).into();
+        let source = parse_literal(ctx)?;
+        ctx.semicolon()?;
+        return Ok(
+            ExportAllDeclaration::new(exported, source, ctx.compose_loc_info(start_loc)).into(),
+        );
     }
 
     // export default [function declaration|class declaration|expression]
-    if ctx.eat(TokenLabel::_Default) {
-        return parse_export_default(ctx, start_loc).into();
+    if ctx.eat(TokenLabel::_Default)? {
+        check_export(ctx, "default", ctx.last_token_start)?;
+        return Ok(parse_export_default(ctx, start_loc)?.into());
     }
 
     // parse may variable declaration, export var|let|const|[async] function|class ..
@@ -228,38 +321,43 @@ pub fn parse_export(ctx: &mut Parser) -> ImportOrExportDeclaration {
     }) || is_let(ctx)
         || is_async_func(ctx)
     {
-        let stmt_declaration = parse_statement(ctx);
+        let stmt_declaration = parse_statement(ctx)?;
         let declaration = match stmt_declaration {
             Statement::FunctionDeclaration(func_decl) => {
+                check_export(ctx, &func_decl.id.name, func_decl.id.start)?;
                 ExportDeclaration::FunctionDeclaration(func_decl)
             }
             Statement::ClassDeclaration(class_decl) => {
+                check_export(ctx, &class_decl.id.name, class_decl.id.start)?;
                 ExportDeclaration::ClassDeclaration(class_decl)
             }
             Statement::VariableDeclaration(var_decl) => {
+                for declarator in var_decl.declarations.iter() {
+                    check_pattern_export(ctx, &declarator.id)?;
+                }
                 ExportDeclaration::VariableDeclaration(var_decl)
             }
             _ => {
-                unexpected(ctx.cur_token.clone().unwrap());
+                return Err(unexpected(ctx));
             }
         };
-        return ExportNamedDeclaration::new(
+        return Ok(ExportNamedDeclaration::new(
             Some(declaration),
-            None,
+            Vec::new_in(ctx.arena),
             None,
             ctx.compose_loc_info(start_loc),
         )
-        .into();
+        .into());
     }
 
     // parse named exported, { x, y as z } [from '...']
-    ctx.expect(TokenLabel::BraceL);
-    let mut specifiers = vec![];
+    ctx.expect(TokenLabel::BraceL)?;
+    let mut specifiers = Vec::new_in(ctx.arena);
     let mut first = true;
-    while !ctx.eat(TokenLabel::BraceR) {
+    while !ctx.eat(TokenLabel::BraceR)? {
         if !first {
-            ctx.expect(TokenLabel::Comma);
-            if after_trailing_comma(ctx, TokenLabel::BraceR, true) {
+            ctx.expect(TokenLabel::Comma)?;
+            if after_trailing_comma(ctx, TokenLabel::BraceR, true)? {
                 break;
             }
         } else {
@@ -267,12 +365,14 @@ pub fn parse_export(ctx: &mut Parser) -> ImportOrExportDeclaration {
         }
 
         let specifier_start_loc = ctx.start_location_node();
-        let local = parse_import_export_name(ctx);
-        let exported = if ctx.eat_contextual("as") {
-            parse_import_export_name(ctx)
+        let local = parse_import_export_name(ctx)?;
+        let exported = if ctx.eat_contextual("as")? {
+            parse_import_export_name(ctx)?
         } else {
             local.clone()
         };
+        let (exported_name, pos) = exported.name();
+        check_export(ctx, &exported_name, pos)?;
         specifiers.push(ExportSpecifier::new(
             local.into(),
             exported.into(),
@@ -280,32 +380,30 @@ pub fn parse_export(ctx: &mut Parser) -> ImportOrExportDeclaration {
         ));
     }
 
-    let source = if ctx.eat_contextual("from") {
+    let source = if ctx.eat_contextual("from")? {
         if !ctx.cur_token_is(TokenLabel::String) {
-            unexpected(ctx.cur_token.clone().unwrap());
+            return Err(unexpected(ctx));
         }
-        Some(parse_literal(ctx))
+        Some(parse_literal(ctx)?)
     } else {
         // check if there is local literal in export specifiers
         None
     };
+    ctx.semicolon()?;
 
-    ExportNamedDeclaration::new(
-        None,
-        Some(specifiers),
-        source,
-        ctx.compose_loc_info(start_loc),
+    Ok(
+        ExportNamedDeclaration::new(None, specifiers, source, ctx.compose_loc_info(start_loc))
+            .into(),
     )
-    .into()
 }
 
-pub fn parse_export_default(
-    ctx: &mut Parser,
+pub fn parse_export_default<'a>(
+    ctx: &mut Parser<'a>,
     start_loc: _LocationNode,
-) -> ExportDefaultDeclaration {
+) -> ParseResult<ExportDefaultDeclaration<'a>> {
     let is_async = is_async_func(ctx);
     if is_async || ctx.cur_token_is(TokenLabel::_Function) {
-        let func_declaration = parse_function_stmt(ctx, true, is_async);
+        let func_declaration = parse_function_stmt(ctx, true, is_async)?;
         let declaration = match func_declaration {
             FunctionDeclarationType::AnonymousDefaultExportedFunctionDeclaration(anony_func) => {
                 ExportDefaultDeclarationType::AnonymousDefaultExportedFunctionDeclaration(
@@ -316,11 +414,13 @@ pub fn parse_export_default(
                 ExportDefaultDeclarationType::FunctionDeclaration(func)
             }
         };
-        return ExportDefaultDeclaration::new(declaration, ctx.compose_loc_info(start_loc)).into();
+        return Ok(
+            ExportDefaultDeclaration::new(declaration, ctx.compose_loc_info(start_loc)).into(),
+        );
     }
 
     if ctx.cur_token_is(TokenLabel::_Class) {
-        let declaration = match parse_class(ctx, true) {
+        let declaration = match parse_class(ctx, true)? {
             ClassDeclarationType::AnonymousDefaultExportedClassDeclaration(anony_class) => {
                 ExportDefaultDeclarationType::AnonymousDefaultExportedClassDeclaration(anony_class)
             }
@@ -328,32 +428,47 @@ pub fn parse_export_default(
                 ExportDefaultDeclarationType::ClassDeclaration(decl_class)
             }
         };
-        return ExportDefaultDeclaration::new(declaration, ctx.compose_loc_info(start_loc)).into();
+        return Ok(
+            ExportDefaultDeclaration::new(declaration, ctx.compose_loc_info(start_loc)).into(),
+        );
     }
 
-    let declaration = ExportDefaultDeclarationType::Expression(parse_maybe_assign(ctx));
-    ctx.semicolon();
+    let declaration = ExportDefaultDeclarationType::Expression(parse_maybe_assign(ctx)?);
+    ctx.semicolon()?;
 
-    ExportDefaultDeclaration::new(declaration, ctx.compose_loc_info(start_loc))
+    Ok(ExportDefaultDeclaration::new(
+        declaration,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_import(ctx: &mut Parser) -> ImportDeclaration {
+pub fn parse_import<'a>(ctx: &mut Parser<'a>) -> ParseResult<ImportDeclaration<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
+    ctx.next()?;
 
     if ctx.cur_token_is(TokenLabel::String) {
-        let source = parse_literal(ctx);
-        return ImportDeclaration::new(source, vec![], ctx.compose_loc_info(start_loc));
+        let source = parse_literal(ctx)?;
+        ctx.semicolon()?;
+        return Ok(ImportDeclaration::new(
+            source,
+            Vec::new_in(ctx.arena),
+            ctx.compose_loc_info(start_loc),
+        ));
     }
 
-    let specifiers = parse_import_specifers(ctx);
-    ctx.expect_contexual("from");
+    let specifiers = parse_import_specifers(ctx)?;
+    ctx.expect_contexual("from")?;
     if !ctx.cur_token_is(TokenLabel::String) {
-        unexpected(ctx.cur_token.clone().unwrap());
+        return Err(unexpected(ctx));
     }
-    let source = parse_literal(ctx);
+    let source = parse_literal(ctx)?;
+    ctx.semicolon()?;
 
-    ImportDeclaration::new(source, specifiers, ctx.compose_loc_info(start_loc))
+    Ok(ImportDeclaration::new(
+        source,
+        specifiers,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
 // #16.2.2 Imports
@@ -363,38 +478,40 @@ pub fn parse_import(ctx: &mut Parser) -> ImportDeclaration {
 // NamedImports     such as: { a as b, c, }
 // ImportedDefaultBinding , NameSpaceImport
 // ImportedDefaultBinding , NamedImports
-pub fn parse_import_specifers(ctx: &mut Parser) -> Vec<ImportSpecifiers> {
-    let mut specifiers = vec![];
+pub fn parse_import_specifers<'a>(
+    ctx: &mut Parser<'a>,
+) -> ParseResult<Vec<'a, ImportSpecifiers<'a>>> {
+    let mut specifiers = Vec::new_in(ctx.arena);
     if ctx.cur_token_is(TokenLabel::Name) {
         let default_start_loc = ctx.start_location_node();
-        let local = parse_ident(ctx, true);
+        let local = parse_ident(ctx, true)?;
         // TODO: check if the value is valid
         specifiers.push(ImportSpecifiers::ImportDefaultSpecifier(
             ImportDefaultSpecifier::new(local, ctx.compose_loc_info(default_start_loc)),
         ));
-        if !ctx.eat(TokenLabel::Comma) {
-            return specifiers;
+        if !ctx.eat(TokenLabel::Comma)? {
+            return Ok(specifiers);
         }
     }
     // check if there is NameSpaceImport
     if ctx.cur_token_is(TokenLabel::Star) {
         let space_start_loc = ctx.start_location_node();
-        ctx.next_unwrap();
-        ctx.expect_contexual("as");
-        let local = parse_ident(ctx, true);
+        ctx.next()?;
+        ctx.expect_contexual("as")?;
+        let local = parse_ident(ctx, true)?;
         // TODO: check if the value is valid
         specifiers.push(ImportSpecifiers::ImportNamespaceSpecifier(
             ImportNamespaceSpecifier::new(local, ctx.compose_loc_info(space_start_loc)),
         ));
-        return specifiers;
+        return Ok(specifiers);
     }
 
-    ctx.expect(TokenLabel::BraceL);
+    ctx.expect(TokenLabel::BraceL)?;
     let mut first = true;
-    while !ctx.eat(TokenLabel::BraceR) {
+    while !ctx.eat(TokenLabel::BraceR)? {
         if !first {
-            ctx.expect(TokenLabel::Comma);
-            if after_trailing_comma(ctx, TokenLabel::BraceR, true) {
+            ctx.expect(TokenLabel::Comma)?;
+            if after_trailing_comma(ctx, TokenLabel::BraceR, true)? {
                 break;
             }
         } else {
@@ -402,13 +519,13 @@ pub fn parse_import_specifers(ctx: &mut Parser) -> Vec<ImportSpecifiers> {
         }
 
         let named_specifier_start = ctx.start_location_node();
-        let imported = parse_import_export_name(ctx);
-        let local = if ctx.eat_contextual("as") {
-            parse_ident(ctx, true)
+        let imported = parse_import_export_name(ctx)?;
+        let local = if ctx.eat_contextual("as")? {
+            parse_ident(ctx, true)?
         } else {
             match &imported {
                 ImportExportName::Identifier(ident) => ident.clone(),
-                ImportExportName::Literal(_) => unexpected(ctx.cur_token.clone().unwrap()),
+                ImportExportName::Literal(_) => return Err(unexpected(ctx)),
             }
         };
         // TODO: check left value
@@ -419,17 +536,30 @@ pub fn parse_import_specifers(ctx: &mut Parser) -> Vec<ImportSpecifiers> {
         )));
     }
 
-    specifiers
+    Ok(specifiers)
 }
 
 #[derive(Clone)]
-pub enum ImportExportName {
-    Identifier(Identifier),
-    Literal(Literal),
+pub enum ImportExportName<'a> {
+    Identifier(Identifier<'a>),
+    Literal(Literal<'a>),
 }
 
-impl From<ImportExportName> for ExportLocal {
-    fn from(value: ImportExportName) -> Self {
+impl<'a> ImportExportName<'a> {
+    /// 名称以及其开始位置
+    fn name(&self) -> (String, usize) {
+        match self {
+            Self::Identifier(ident) => (ident.name.to_string(), ident.start),
+            Self::Literal(literal) => match &literal.value {
+                LiteralValue::String(s) => (s.to_string(), literal.start),
+                _ => (String::new(), literal.start),
+            },
+        }
+    }
+}
+
+impl<'a> From<ImportExportName<'a>> for ExportLocal<'a> {
+    fn from(value: ImportExportName<'a>) -> Self {
         match value {
             ImportExportName::Identifier(ident) => Self::Identifier(ident),
             ImportExportName::Literal(literal) => Self::Literal(literal),
@@ -437,8 +567,8 @@ impl From<ImportExportName> for ExportLocal {
     }
 }
 
-impl From<ImportExportName> for ExportedType {
-    fn from(value: ImportExportName) -> Self {
+impl<'a> From<ImportExportName<'a>> for ExportedType<'a> {
+    fn from(value: ImportExportName<'a>) -> Self {
         match value {
             ImportExportName::Identifier(ident) => Self::Identifier(ident),
             ImportExportName::Literal(literal) => Self::Literal(literal),
@@ -446,8 +576,8 @@ impl From<ImportExportName> for ExportedType {
     }
 }
 
-impl From<ImportExportName> for ImportedType {
-    fn from(value: ImportExportName) -> Self {
+impl<'a> From<ImportExportName<'a>> for ImportedType<'a> {
+    fn from(value: ImportExportName<'a>) -> Self {
         match value {
             ImportExportName::Identifier(ident) => Self::Identifier(ident),
             ImportExportName::Literal(literal) => Self::Literal(literal),
@@ -455,86 +585,108 @@ impl From<ImportExportName> for ImportedType {
     }
 }
 
-pub fn parse_import_export_name(ctx: &mut Parser) -> ImportExportName {
-    // TODO: check if ecam >= 13
+pub fn parse_import_export_name<'a>(ctx: &mut Parser<'a>) -> ParseResult<ImportExportName<'a>> {
     if ctx.cur_token_is(TokenLabel::String) {
-        let name = parse_literal(ctx);
+        ctx.expect_ecma_version(13, ctx.cur_token_start, "String literal module export name")?;
+        let name = parse_literal(ctx)?;
         // TODO: check if contains lone surrogate.
-        return ImportExportName::Literal(name);
+        return Ok(ImportExportName::Literal(name));
     }
 
-    return ImportExportName::Identifier(parse_ident(ctx, true));
+    Ok(ImportExportName::Identifier(parse_ident(ctx, true)?))
 }
 
-pub fn parse_empty(ctx: &mut Parser) -> EmptyStatement {
+pub fn parse_empty<'a>(ctx: &mut Parser<'a>) -> ParseResult<EmptyStatement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
+    ctx.next()?;
 
-    EmptyStatement::new(ctx.compose_loc_info(start_loc))
+    Ok(EmptyStatement::new(ctx.compose_loc_info(start_loc)))
 }
 
-pub fn parse_with(ctx: &mut Parser) -> WithStatement {
+pub fn parse_with<'a>(ctx: &mut Parser<'a>) -> ParseResult<WithStatement<'a>> {
     let start_loc = ctx.start_location_node();
     if ctx.strict_mode {
-        panic!("'with' in strict mode");
+        return Err(ctx.raise(
+            ctx.cur_token_start,
+            ParseErrorKind::SyntaxError,
+            "'with' in strict mode",
+        ));
     }
-    ctx.next_unwrap();
-    let object = parse_paren_expression(ctx);
+    ctx.next()?;
+    let object = parse_paren_expression(ctx)?;
     ctx.stmt_context.push(StatementContext::With);
-    let body = parse_statement(ctx);
+    let body = parse_statement(ctx)?;
     ctx.stmt_context.pop();
 
-    WithStatement::new(object, Box::new(body), ctx.compose_loc_info(start_loc))
+    Ok(WithStatement::new(
+        object,
+        Box::new_in(body, ctx.arena),
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_while(ctx: &mut Parser) -> WhileStatement {
+pub fn parse_while<'a>(ctx: &mut Parser<'a>) -> ParseResult<WhileStatement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
-    let test = parse_paren_expression(ctx);
+    ctx.next()?;
+    let test = parse_paren_expression(ctx)?;
     ctx.labels.push(Label {
         kind: LabelKind::Loop,
         name: None,
     });
     ctx.stmt_context.push(StatementContext::While);
-    let body = parse_statement(ctx);
+    let body = parse_statement(ctx)?;
     ctx.stmt_context.pop();
     ctx.labels.pop();
 
-    WhileStatement::new(test, Box::new(body), ctx.compose_loc_info(start_loc))
+    Ok(WhileStatement::new(
+        test,
+        Box::new_in(body, ctx.arena),
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_var_stmt(ctx: &mut Parser, kind: VariableKind) -> VariableDeclaration {
+pub fn parse_var_stmt<'a>(
+    ctx: &mut Parser<'a>,
+    kind: VariableKind,
+) -> ParseResult<VariableDeclaration<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
-    let declarators = parse_var_declarator(ctx, kind.clone());
-    ctx.semicolon();
+    ctx.next()?;
+    let declarators = parse_var_declarator(ctx, kind.clone())?;
+    ctx.semicolon()?;
 
-    VariableDeclaration::new(declarators, kind, ctx.compose_loc_info(start_loc))
+    Ok(VariableDeclaration::new(
+        declarators,
+        kind,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_try(ctx: &mut Parser) -> TryStatement {
+pub fn parse_try<'a>(ctx: &mut Parser<'a>) -> ParseResult<TryStatement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
-    let block = parse_block_stmt(ctx, true);
+    ctx.next()?;
+    let block = parse_block_stmt(ctx, true)?;
     let mut is_simple_catch = false;
     let handler = if ctx.cur_token_is(TokenLabel::_Catch) {
         let handler_start = ctx.start_location_node();
-        ctx.next_unwrap();
+        ctx.next()?;
         let mut param = None;
-        if ctx.eat(TokenLabel::ParenL) {
-            param = Some(parse_binding_atom(ctx).into());
+        if !ctx.cur_token_is(TokenLabel::ParenL) {
+            ctx.expect_ecma_version(10, ctx.cur_token_start, "Optional catch binding")?;
+        }
+        if ctx.eat(TokenLabel::ParenL)? {
+            param = Some(parse_binding_atom(ctx)?.into());
             if matches!(param, Some(Pattern::Identifier(..))) {
                 is_simple_catch = true;
             }
             // TODO: check left hand pattern
-            ctx.expect(TokenLabel::ParenR);
+            ctx.expect(TokenLabel::ParenR)?;
         }
         ctx.enter_scope(if is_simple_catch {
             SCOPE_SIMPLE_CATCH
         } else {
             0
         });
-        let handler_body = parse_block_stmt(ctx, false);
+        let handler_body = parse_block_stmt(ctx, false)?;
         ctx.exit_scope();
 
         Some(CatchClause::new(
@@ -546,71 +698,92 @@ pub fn parse_try(ctx: &mut Parser) -> TryStatement {
         None
     };
 
-    let finalizer = if ctx.eat(TokenLabel::_Finally) {
-        Some(parse_block_stmt(ctx, true))
+    let finalizer = if ctx.eat(TokenLabel::_Finally)? {
+        Some(parse_block_stmt(ctx, true)?)
     } else {
         None
     };
 
     if handler.is_none() && finalizer.is_none() {
-        panic!("Missing catch or finally clause");
+        return Err(ctx.raise(
+            start_loc.pos,
+            ParseErrorKind::SyntaxError,
+            "Missing catch or finally clause",
+        ));
     }
 
-    TryStatement::new(block, handler, finalizer, ctx.compose_loc_info(start_loc))
+    Ok(TryStatement::new(
+        block,
+        handler,
+        finalizer,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_throw(ctx: &mut Parser) -> ThrowStatement {
+pub fn parse_throw<'a>(ctx: &mut Parser<'a>) -> ParseResult<ThrowStatement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
+    ctx.next()?;
     if has_break_in_range(ctx, (ctx.last_token_end, ctx.cur_token_start)) {
-        panic!("Illegal newline after throw");
+        return Err(ctx.raise(
+            ctx.last_token_end,
+            ParseErrorKind::SyntaxError,
+            "Illegal newline after throw",
+        ));
     }
-    let argument = parse_expression(ctx);
-    ctx.semicolon();
+    let argument = parse_expression(ctx)?;
+    ctx.semicolon()?;
 
-    ThrowStatement::new(argument, ctx.compose_loc_info(start_loc))
+    Ok(ThrowStatement::new(
+        argument,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_switch(ctx: &mut Parser) -> SwitchStatement {
+pub fn parse_switch<'a>(ctx: &mut Parser<'a>) -> ParseResult<SwitchStatement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
-    let discriminant = parse_paren_expression(ctx);
-    let mut cases: Vec<SwitchCase> = vec![];
+    ctx.next()?;
+    let discriminant = parse_paren_expression(ctx)?;
+    let mut cases = Vec::new_in(ctx.arena);
     ctx.labels.push(Label {
         kind: LabelKind::Switch,
         name: None,
     });
-    ctx.expect(TokenLabel::BraceL);
+    ctx.expect(TokenLabel::BraceL)?;
     ctx.enter_scope(0);
 
     let mut saw_default = false;
-    while !ctx.eat(TokenLabel::BraceR) {
+    while !ctx.eat(TokenLabel::BraceR)? {
         if !ctx.cur_token_test(|t| t.label == TokenLabel::_Case || t.label == TokenLabel::_Default)
         {
-            unexpected(ctx.cur_token.clone().unwrap());
+            return Err(unexpected(ctx));
         }
         let case_start = ctx.start_location_node();
         let is_case_clause = ctx.cur_token_is(TokenLabel::_Case);
-        let mut consequent = vec![];
-        ctx.next_unwrap();
+        let mut consequent = Vec::new_in(ctx.arena);
+        ctx.next()?;
         let test = if is_case_clause {
-            Some(parse_expression(ctx))
+            Some(parse_expression(ctx)?)
         } else {
             if !saw_default {
                 saw_default = true;
             } else {
-                println!("Multiple default clauses");
+                ctx.raise_early(
+                    case_start.pos,
+                    EarlyErrorCode::DuplicateDefaultClause,
+                    "Multiple default clauses",
+                )?;
             }
             None
         };
-        ctx.expect(TokenLabel::Colon);
+        ctx.expect(TokenLabel::Colon)?;
         while !ctx.cur_token_test(|t| {
             t.label == TokenLabel::_Case
                 || t.label == TokenLabel::_Default
                 || t.label == TokenLabel::BraceR
         }) {
-            // TODO: may need to reset statment context
-            consequent.push(parse_statement(ctx));
+            ctx.enter_stmt_ctx(StatementContext::TopLevel);
+            consequent.push(parse_recoverable(ctx, true, parse_statement)?);
+            ctx.exit_stmt_ctx();
         }
         cases.push(SwitchCase::new(
             test,
@@ -622,106 +795,128 @@ pub fn parse_switch(ctx: &mut Parser) -> SwitchStatement {
     ctx.exit_scope();
     ctx.labels.pop();
 
-    SwitchStatement::new(discriminant, cases, ctx.compose_loc_info(start_loc))
+    Ok(SwitchStatement::new(
+        discriminant,
+        cases,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_return(ctx: &mut Parser) -> ReturnStatement {
-    if !ctx.in_function_scope() {
-        panic!("'return' outside of function");
+pub fn parse_return<'a>(ctx: &mut Parser<'a>) -> ParseResult<ReturnStatement<'a>> {
+    if !ctx.in_function_scope() && !ctx.options.allow_return_outside_function {
+        return Err(ctx.raise(
+            ctx.cur_token_start,
+            ParseErrorKind::SyntaxError,
+            "'return' outside of function",
+        ));
     }
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
+    ctx.next()?;
 
-    let argument = if ctx.eat(TokenLabel::Semi) || can_insert_semicolon(ctx) {
+    let argument = if ctx.eat(TokenLabel::Semi)? || can_insert_semicolon(ctx) {
         None
     } else {
-        let v = Some(parse_expression(ctx));
-        ctx.semicolon();
+        let v = Some(parse_expression(ctx)?);
+        ctx.semicolon()?;
         v
     };
 
-    ReturnStatement::new(argument, ctx.compose_loc_info(start_loc))
+    Ok(ReturnStatement::new(
+        argument,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_if(ctx: &mut Parser) -> IfStatement {
+pub fn parse_if<'a>(ctx: &mut Parser<'a>) -> ParseResult<IfStatement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
-    let test = parse_paren_expression(ctx);
+    ctx.next()?;
+    let test = parse_paren_expression(ctx)?;
     ctx.stmt_context.push(StatementContext::If);
-    let consequent = parse_statement(ctx);
-    let alternate = if ctx.eat(TokenLabel::_Else) {
-        Some(parse_statement(ctx))
+    let consequent = parse_statement(ctx)?;
+    let alternate = if ctx.eat(TokenLabel::_Else)? {
+        Some(parse_statement(ctx)?)
     } else {
         None
     };
     ctx.stmt_context.pop();
 
-    IfStatement::new(
+    Ok(IfStatement::new(
         test,
-        Box::new(consequent),
-        alternate.map(|a| Box::new(a)),
+        Box::new_in(consequent, ctx.arena),
+        alternate.map(|a| Box::new_in(a, ctx.arena)),
         ctx.compose_loc_info(start_loc),
-    )
+    ))
 }
 
-pub fn parse_class(ctx: &mut Parser, nullable_id: bool) -> ClassDeclarationType {
+pub fn parse_class<'a>(
+    ctx: &mut Parser<'a>,
+    nullable_id: bool,
+) -> ParseResult<ClassDeclarationType<'a>> {
     let start_loc = ctx.start_location_node();
     let old_strict = ctx.strict_mode;
     // A class definition is always strict mode code.
     ctx.strict_mode = true;
-    ctx.next_unwrap();
+    ctx.next()?;
 
     let id = if !nullable_id || ctx.cur_token_is(TokenLabel::Name) {
-        Some(parse_ident(ctx, true))
+        Some(parse_ident(ctx, false)?)
     } else {
         None
     };
-    let super_class = if ctx.eat(TokenLabel::_Extends) {
-        Some(parse_expr_subscripts(ctx))
+    let super_class = if ctx.eat(TokenLabel::_Extends)? {
+        Some(parse_expr_subscripts(ctx)?)
     } else {
         None
     };
 
-    let class_body = parse_class_body(ctx, super_class.is_some());
-    ctx.strict_mode = old_strict;
+    let class_body = parse_class_body(ctx, super_class.is_some(), old_strict)?;
 
     if id.is_some() {
-        ClassDeclaration::new(
+        Ok(ClassDeclaration::new(
             id.unwrap(),
             super_class,
             class_body,
             ctx.compose_loc_info(start_loc),
         )
-        .into()
+        .into())
     } else {
-        AnonymousDefaultExportedClassDeclaration::new(
+        Ok(AnonymousDefaultExportedClassDeclaration::new(
             super_class,
             class_body,
             ctx.compose_loc_info(start_loc),
         )
-        .into()
+        .into())
     }
 }
 
-pub fn parse_class_body(ctx: &mut Parser, has_super: bool) -> ClassBody {
+/// `outer_strict` 是类之外的严格模式，在读取 `}` 之后的 token 之前恢复
+pub fn parse_class_body<'a>(
+    ctx: &mut Parser<'a>,
+    has_super: bool,
+    outer_strict: bool,
+) -> ParseResult<ClassBody<'a>> {
     let start_loc = ctx.start_location_node();
     let mut has_constructor = false;
-    let mut body_eles: Vec<ClassBodyEl> = vec![];
+    let mut body_eles = Vec::new_in(ctx.arena);
 
-    ctx.expect(TokenLabel::BraceL);
+    ctx.expect(TokenLabel::BraceL)?;
     ctx.enter_private_name_stack();
 
-    while !ctx.eat(TokenLabel::BraceR) {
-        if ctx.cur_token_is(TokenLabel::Semi) {
+    while !ctx.cur_token_is(TokenLabel::BraceR) {
+        if ctx.eat(TokenLabel::Semi)? {
             continue;
         }
-        let element = parse_class_element(ctx, has_super);
+        let element = parse_class_element(ctx, has_super)?;
         // check if class element definition conflicts
         match &element {
             ClassBodyEl::MethodDefinition(mtd) => {
                 if matches!(mtd.kind, MethodKind::Constructor) {
                     if has_constructor {
-                        panic!("Duplicate constructor in the same class");
+                        return Err(ctx.raise(
+                            mtd.start,
+                            ParseErrorKind::SyntaxError,
+                            "Duplicate constructor in the same class",
+                        ));
                     } else {
                         has_constructor = true;
                     }
@@ -734,44 +929,48 @@ pub fn parse_class_body(ctx: &mut Parser, has_super: bool) -> ClassBody {
                             _ => None,
                         };
                         check_private_name_conflicts(
-                            ctx.private_name_stack.last_mut().unwrap(),
+                            ctx,
                             &pri_ident.name,
+                            pri_ident.start,
                             mtd.is_static,
                             accessor_kind,
-                        )
+                        )?;
                     }
                     _ => {}
                 }
             }
             ClassBodyEl::PropertyDefinition(prop) => match &prop.key {
                 ClassPropertyKey::PrivateIdentifier(pri_ident) => check_private_name_conflicts(
-                    ctx.private_name_stack.last_mut().unwrap(),
+                    ctx,
                     &pri_ident.name,
+                    pri_ident.start,
                     prop.is_static,
                     None,
-                ),
+                )?,
                 _ => {}
             },
             _ => {}
         }
         body_eles.push(element);
     }
+    ctx.strict_mode = outer_strict;
+    ctx.next()?;
 
     let private_info = ctx.exit_private_name_stack().unwrap();
-    check_used_private_name(ctx, private_info);
+    check_used_private_name(ctx, private_info)?;
 
-    ClassBody::new(body_eles, ctx.compose_loc_info(start_loc))
+    Ok(ClassBody::new(body_eles, ctx.compose_loc_info(start_loc)))
 }
 
-pub enum ClassElementKey {
-    Identifier(Identifier),
-    Expression(Expression),
-    PrivateIdentifier(PrivateIdentifier),
-    Literal(Literal),
+pub enum ClassElementKey<'a> {
+    Identifier(Identifier<'a>),
+    Expression(Expression<'a>),
+    PrivateIdentifier(PrivateIdentifier<'a>),
+    Literal(Literal<'a>),
 }
 
-impl From<ClassElementKey> for ClassMethodKey {
-    fn from(value: ClassElementKey) -> Self {
+impl<'a> From<ClassElementKey<'a>> for ClassMethodKey<'a> {
+    fn from(value: ClassElementKey<'a>) -> Self {
         match value {
             ClassElementKey::Identifier(ident) => Self::Expression(ident.into()),
             ClassElementKey::Expression(expr) => Self::Expression(expr),
@@ -781,8 +980,8 @@ impl From<ClassElementKey> for ClassMethodKey {
     }
 }
 
-impl From<ClassElementKey> for ClassPropertyKey {
-    fn from(value: ClassElementKey) -> Self {
+impl<'a> From<ClassElementKey<'a>> for ClassPropertyKey<'a> {
+    fn from(value: ClassElementKey<'a>) -> Self {
         match value {
             ClassElementKey::Identifier(ident) => Self::Expression(ident.into()),
             ClassElementKey::Expression(expr) => Self::Expression(expr),
@@ -792,7 +991,10 @@ impl From<ClassElementKey> for ClassPropertyKey {
     }
 }
 
-pub fn parse_class_element(ctx: &mut Parser, has_super: bool) -> ClassBodyEl {
+pub fn parse_class_element<'a>(
+    ctx: &mut Parser<'a>,
+    has_super: bool,
+) -> ParseResult<ClassBodyEl<'a>> {
     let start_loc = ctx.start_location_node();
     let mut element_key: Option<ClassElementKey> = None;
     let mut is_static = false;
@@ -801,25 +1003,29 @@ pub fn parse_class_element(ctx: &mut Parser, has_super: bool) -> ClassBodyEl {
     let mut method_kind = MethodKind::Method;
 
     if ctx.is_contextual("static") {
-        element_key = Some(ClassElementKey::Identifier(parse_ident(ctx, true)));
-        // TODO: check the ecam version >= 13
+        element_key = Some(ClassElementKey::Identifier(parse_ident(ctx, true)?));
         if ctx.cur_token_is(TokenLabel::BraceL) {
+            ctx.expect_ecma_version(13, start_loc.pos, "Class static block")?;
             let old_labels = ctx.labels.clone();
-            let mut body = vec![];
+            let mut body = Vec::new_in(ctx.arena);
             ctx.labels = vec![];
             ctx.enter_scope(SCOPE_CLASS_STATIC_BLOCK | SCOPE_SUPER);
+            ctx.enter_stmt_ctx(StatementContext::TopLevel);
 
-            while ctx.eat(TokenLabel::BraceR) {
-                body.push(parse_statement(ctx));
+            ctx.next()?;
+            while !ctx.eat(TokenLabel::BraceR)? {
+                body.push(parse_recoverable(ctx, true, parse_statement)?);
             }
 
+            ctx.exit_stmt_ctx();
+
             ctx.exit_scope();
             ctx.labels = old_labels;
 
-            return ClassBodyEl::StaticBlock(StaticBlock::new(
+            return Ok(ClassBodyEl::StaticBlock(StaticBlock::new(
                 body,
                 ctx.compose_loc_info(start_loc),
-            ));
+            )));
         }
         if ctx.cur_token_test(|t| {
             let label = t.label;
@@ -837,8 +1043,8 @@ pub fn parse_class_element(ctx: &mut Parser, has_super: bool) -> ClassBodyEl {
     }
 
     // check if this is async func
-    if ctx.is_contextual("async") {
-        element_key = Some(ClassElementKey::Identifier(parse_ident(ctx, true)));
+    if ctx.ecma_version >= 8 && ctx.is_contextual("async") {
+        element_key = Some(ClassElementKey::Identifier(parse_ident(ctx, true)?));
         if ctx.cur_token_test(|t| {
             let label = t.label;
             return label == TokenLabel::Name
@@ -854,13 +1060,13 @@ pub fn parse_class_element(ctx: &mut Parser, has_super: bool) -> ClassBodyEl {
         }
     }
 
-    if ctx.eat(TokenLabel::Star) {
+    if eat_generator_star(ctx, is_async)? {
         is_generator = true;
     }
 
     // maybe getter/setter method
     if !is_async && !is_generator && (ctx.is_contextual("get") || ctx.is_contextual("set")) {
-        element_key = Some(ClassElementKey::Identifier(parse_ident(ctx, true)));
+        element_key = Some(ClassElementKey::Identifier(parse_ident(ctx, true)?));
         if ctx.cur_token_test(|t| {
             let label = t.label;
             return label == TokenLabel::Name
@@ -870,7 +1076,8 @@ pub fn parse_class_element(ctx: &mut Parser, has_super: bool) -> ClassBodyEl {
                 || label == TokenLabel::BracketL
                 || t.keyword;
         }) {
-            method_kind = if ctx.cur_token_value_is("get") {
+            method_kind = if matches!(&element_key, Some(ClassElementKey::Identifier(ident)) if ident.name == "get")
+            {
                 MethodKind::Get
             } else {
                 MethodKind::Set
@@ -884,17 +1091,20 @@ pub fn parse_class_element(ctx: &mut Parser, has_super: bool) -> ClassBodyEl {
     // Parse element_key if need.
     if element_key.is_none() {
         if ctx.cur_token_is(TokenLabel::PrivateId) {
-            element_key = Some(ClassElementKey::PrivateIdentifier(parse_private_ident(ctx)));
+            element_key = Some(ClassElementKey::PrivateIdentifier(parse_private_ident(
+                ctx,
+            )?));
         } else {
-            computed = ctx.eat(TokenLabel::ParenL);
+            computed = ctx.eat(TokenLabel::BracketL)?;
             if computed {
-                element_key = Some(ClassElementKey::Expression(parse_expression(ctx)));
+                element_key = Some(ClassElementKey::Expression(parse_expression(ctx)?));
+                ctx.expect(TokenLabel::BracketR)?;
             } else if ctx
                 .cur_token_test(|t| t.label == TokenLabel::String || t.label == TokenLabel::Number)
             {
-                element_key = Some(ClassElementKey::Literal(parse_literal(ctx)));
+                element_key = Some(ClassElementKey::Literal(parse_literal(ctx)?));
             } else {
-                element_key = Some(ClassElementKey::Identifier(parse_ident(ctx, true)));
+                element_key = Some(ClassElementKey::Identifier(parse_ident(ctx, true)?));
             }
         }
     }
@@ -908,76 +1118,93 @@ pub fn parse_class_element(ctx: &mut Parser, has_super: bool) -> ClassBodyEl {
             !is_static && check_class_ele_key(element_key.as_ref().unwrap(), "constructor");
         if is_constructor {
             if method_kind != MethodKind::Method {
-                panic!("Constructor can't have get/set modifier");
+                return Err(ctx.raise(
+                    start_loc.pos,
+                    ParseErrorKind::SyntaxError,
+                    "Constructor can't have get/set modifier",
+                ));
             }
             if is_generator {
-                panic!("Constructor can't be a generator");
+                return Err(ctx.raise(
+                    start_loc.pos,
+                    ParseErrorKind::SyntaxError,
+                    "Constructor can't be a generator",
+                ));
             }
             if is_async {
-                panic!("Constructor can't be an async method");
+                return Err(ctx.raise(
+                    start_loc.pos,
+                    ParseErrorKind::SyntaxError,
+                    "Constructor can't be an async method",
+                ));
             }
             method_kind = MethodKind::Constructor;
         }
         if is_static && check_class_ele_key(element_key.as_ref().unwrap(), "prototype") {
-            panic!("Classes may not have a static property named prototype");
+            return Err(ctx.raise(
+                start_loc.pos,
+                ParseErrorKind::SyntaxError,
+                "Classes may not have a static property named prototype",
+            ));
         }
-        let ele_value = parse_method(ctx, is_generator, is_async, has_super && is_constructor);
-        match method_kind {
-            MethodKind::Get => {
-                if ele_value.params.len() != 0 {
-                    println!("getter should have no params");
-                }
-            }
-            MethodKind::Set => {
-                if ele_value.params.len() != 1 {
-                    println!("setter should have exactly one param");
-                }
-                if let Pattern::RestElement(res) = ele_value.params.last().unwrap() {
-                    println!("Setter cannot use rest params");
-                }
-            }
-            _ => {}
+        let ele_value = parse_method(ctx, is_generator, is_async, has_super && is_constructor)?;
+        if method_kind == MethodKind::Get || method_kind == MethodKind::Set {
+            check_accessor_params(
+                ctx,
+                method_kind == MethodKind::Get,
+                &ele_value.params,
+                ele_value.start,
+            )?;
         }
-        return ClassBodyEl::MethodDefinition(MethodDefinition::new(
+        return Ok(ClassBodyEl::MethodDefinition(MethodDefinition::new(
             element_key.unwrap().into(),
             ele_value,
             method_kind,
             computed,
             is_static,
             ctx.compose_loc_info(start_loc),
-        ));
+        )));
     }
 
-    // TODO: check ecam version > 13
+    ctx.expect_ecma_version(13, start_loc.pos, "Class field")?;
     if check_class_ele_key(element_key.as_ref().unwrap(), "constructor") {
-        panic!("Classes can't have a field named 'constructor'")
+        return Err(ctx.raise(
+            start_loc.pos,
+            ParseErrorKind::SyntaxError,
+            "Classes can't have a field named 'constructor'",
+        ));
     }
 
     if is_static && check_class_ele_key(element_key.as_ref().unwrap(), "prototype") {
-        panic!("Classes can't have a static field named 'prototype'")
+        return Err(ctx.raise(
+            start_loc.pos,
+            ParseErrorKind::SyntaxError,
+            "Classes can't have a static field named 'prototype'",
+        ));
     }
 
     let mut field_value = None;
-    if ctx.eat(TokenLabel::Eq) {
-        let cur_scope = ctx.scope_stack.last_mut().unwrap();
-        let old_in_class_field_init = cur_scope.in_class_field_init;
-        cur_scope.in_class_field_init = true;
-        field_value = Some(parse_maybe_assign(ctx));
-        ctx.scope_stack.last_mut().unwrap().in_class_field_init = old_in_class_field_init;
+    if ctx.eat(TokenLabel::Eq)? {
+        // 标记在 this 所在的作用域上，类可能位于语句块中
+        let this_scope = ctx.cur_this_scope_mut().unwrap();
+        let old_in_class_field_init = this_scope.in_class_field_init;
+        this_scope.in_class_field_init = true;
+        field_value = Some(parse_maybe_assign(ctx)?);
+        ctx.cur_this_scope_mut().unwrap().in_class_field_init = old_in_class_field_init;
     }
 
-    ctx.semicolon();
+    ctx.semicolon()?;
 
-    ClassBodyEl::PropertyDefinition(PropertyDefinition::new(
+    Ok(ClassBodyEl::PropertyDefinition(PropertyDefinition::new(
         element_key.unwrap().into(),
         field_value,
         computed,
         is_static,
         ctx.compose_loc_info(start_loc),
-    ))
+    )))
 }
 
-pub fn check_class_ele_key(el: &ClassElementKey, name: &str) -> bool {
+pub fn check_class_ele_key<'a>(el: &ClassElementKey<'a>, name: &str) -> bool {
     match el {
         ClassElementKey::Identifier(iden) => iden.name == name,
         ClassElementKey::Literal(Literal { value, .. }) => match value {
@@ -988,25 +1215,25 @@ pub fn check_class_ele_key(el: &ClassElementKey, name: &str) -> bool {
     }
 }
 
-pub fn parse_function_stmt(
-    ctx: &mut Parser,
+pub fn parse_function_stmt<'a>(
+    ctx: &mut Parser<'a>,
     nullable_id: bool,
     is_async: bool,
-) -> FunctionDeclarationType {
+) -> ParseResult<FunctionDeclarationType<'a>> {
     let start_loc = ctx.start_location_node();
     // let top_level_ctx = ctx.cur_stmt_ctx_is(StatementContext::TopLevel);
     if is_async {
-        ctx.expect_contexual("async");
+        ctx.expect_contexual("async")?;
     }
-    ctx.expect(TokenLabel::_Function);
-    let is_generator = ctx.eat(TokenLabel::Star);
+    ctx.expect(TokenLabel::_Function)?;
+    let is_generator = eat_generator_star(ctx, is_async)?;
     // TODO: figure out why this is invalid
     // if is_generator && func_stmt_flags & FUNC_HANGING_STATEMENT > 0 {
-    //     unexpected(ctx.cur_token.clone().unwrap());
+    //     return Err(unexpected(ctx));
     // }
 
     let id = if !nullable_id || ctx.cur_token_is(TokenLabel::Name) {
-        Some(parse_ident(ctx, true))
+        Some(parse_ident(ctx, false)?)
     } else {
         None
     };
@@ -1016,31 +1243,31 @@ pub fn parse_function_stmt(
     // }
 
     ctx.enter_scope(get_func_flags(is_async, is_generator));
-    ctx.expect(TokenLabel::ParenL);
+    ctx.expect(TokenLabel::ParenL)?;
 
-    let params = parse_binding_list(ctx, TokenLabel::ParenR, false, true);
-    let body = parse_function_body(ctx);
+    let params = parse_binding_list(ctx, TokenLabel::ParenR, false, ctx.ecma_version >= 8)?;
+    let body = parse_function_body(ctx)?;
     ctx.exit_scope();
 
     if id.is_some() {
-        FunctionDeclaration::new(
+        Ok(FunctionDeclaration::new(
             id.unwrap(),
-            params.into_iter().map(|p| p.unwrap()).collect(),
+            Vec::from_iter_in(params.into_iter().map(|p| p.unwrap()), ctx.arena),
             body,
             is_generator,
             is_async,
             ctx.compose_loc_info(start_loc),
         )
-        .into()
+        .into())
     } else {
-        AnonymousDefaultExportedFunctionDeclaration::new(
-            params.into_iter().map(|p| p.unwrap()).collect(),
+        Ok(AnonymousDefaultExportedFunctionDeclaration::new(
+            Vec::from_iter_in(params.into_iter().map(|p| p.unwrap()), ctx.arena),
             body,
             is_generator,
             is_async,
             ctx.compose_loc_info(start_loc),
         )
-        .into()
+        .into())
     }
 }
 
@@ -1051,23 +1278,23 @@ pub fn parse_function_stmt(
 // - for (;;) {}
 // - for await (let/const/var .. of ..) {}
 // - for (lhs in/of ..) {}
-pub fn parse_for_loop(ctx: &mut Parser) -> Statement {
+pub fn parse_for_loop<'a>(ctx: &mut Parser<'a>) -> ParseResult<Statement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
+    ctx.next()?;
     ctx.labels.push(Label {
         kind: LabelKind::Loop,
         name: None,
     });
     ctx.enter_scope(0);
 
-    let is_for_await = ctx.can_await() && ctx.eat_contextual("await");
-    ctx.expect(TokenLabel::ParenL);
+    let is_for_await = ctx.ecma_version >= 9 && ctx.can_await() && ctx.eat_contextual("await")?;
+    ctx.expect(TokenLabel::ParenL)?;
     // handle case: for (;;) {}
     if ctx.cur_token_is(TokenLabel::Semi) {
         if is_for_await {
-            unexpected(ctx.cur_token.clone().unwrap())
+            return Err(unexpected(ctx));
         }
-        return parse_regular_for(ctx, start_loc, ForStatementInit::Null).into();
+        return Ok(parse_regular_for(ctx, start_loc, ForStatementInit::Null)?.into());
     }
 
     let is_let = is_let(ctx);
@@ -1082,34 +1309,38 @@ pub fn parse_for_loop(ctx: &mut Parser) -> Statement {
         } else {
             VariableKind::Const
         };
+        ctx.next()?;
 
         ctx.for_init = Some(if is_for_await {
             ForInitType::Await
         } else {
             ForInitType::Normal
         });
-        let declarators = parse_var_declarator(ctx, kind.clone());
+        let declarators = parse_var_declarator(ctx, kind.clone())?;
         ctx.for_init = None;
 
         // handle case: for (var/let/const .. in/of ..) {}
-        if (ctx.cur_token_is(TokenLabel::_In) || ctx.is_contextual("of")) && declarators.len() == 1
-        {
+        if (ctx.cur_token_is(TokenLabel::_In) || is_for_of(ctx)) && declarators.len() == 1 {
             if is_for_await && ctx.cur_token_is(TokenLabel::_In) {
-                unexpected(ctx.cur_token.clone().unwrap());
+                return Err(unexpected(ctx));
             }
             // check if there is init value in declarator
             if declarators
                 .first()
                 .map_or(false, |declarator| declarator.init.is_some())
             {
-                panic!(
-                    "{} loop variable declaration may not have an initializer",
-                    if ctx.cur_token_is(TokenLabel::_In) {
-                        "for-in"
-                    } else {
-                        "for-of"
-                    }
-                );
+                return Err(ctx.raise(
+                    init_start_loc.pos,
+                    ParseErrorKind::SyntaxError,
+                    &format!(
+                        "{} loop variable declaration may not have an initializer",
+                        if ctx.cur_token_is(TokenLabel::_In) {
+                            "for-in"
+                        } else {
+                            "for-of"
+                        }
+                    ),
+                ));
             }
             return parse_for_in_of(
                 ctx,
@@ -1121,16 +1352,16 @@ pub fn parse_for_loop(ctx: &mut Parser) -> Statement {
         }
         // for await can only be used with for_of syntax
         if is_for_await {
-            unexpected(ctx.cur_token.clone().unwrap())
+            return Err(unexpected(ctx));
         }
         // hanld case: for (var/let/cont ..;;) {}
-        return parse_regular_for(
+        return Ok(parse_regular_for(
             ctx,
             start_loc,
             VariableDeclaration::new(declarators, kind, ctx.compose_loc_info(init_start_loc))
                 .into(),
-        )
-        .into();
+        )?
+        .into());
     }
 
     // handle case: for (lhs in/of ..) {}
@@ -1140,115 +1371,153 @@ pub fn parse_for_loop(ctx: &mut Parser) -> Statement {
         ForInitType::Normal
     });
     let starts_with_let = ctx.is_contextual("let");
-    let init = parse_expression(ctx);
+    let init_start = ctx.cur_token_start;
+    let init = parse_expression(ctx)?;
     ctx.for_init = None;
-    if ctx.cur_token_is(TokenLabel::_In) || ctx.is_contextual("of") {
+    if ctx.cur_token_is(TokenLabel::_In) || is_for_of(ctx) {
         if is_for_await && ctx.cur_token_is(TokenLabel::_In) {
-            unexpected(ctx.cur_token.clone().unwrap());
+            return Err(unexpected(ctx));
         }
         if starts_with_let && ctx.is_contextual("of") {
-            panic!("The left-hand side of a for-of loop may not start with 'let'.")
+            return Err(ctx.raise(
+                init_start,
+                ParseErrorKind::SyntaxError,
+                "The left-hand side of a for-of loop may not start with 'let'.",
+            ));
         }
-        // TODO: convert expression to lhs
-        // return parse_for_in_of(ctx, ForInOfStatementLeft::Pattern(), start_loc, is_await);
+        // TODO: convert array/object expression to pattern
+        let left = match init {
+            Expression::Identifier(ident) => Pattern::Identifier(ident),
+            Expression::MemberExpression(member) => Pattern::MemberExpression(member),
+            _ => {
+                return Err(ctx.raise(
+                    init_start,
+                    ParseErrorKind::InvalidAssignmentTarget,
+                    "Invalid left-hand side in for-loop",
+                ))
+            }
+        };
+        return parse_for_in_of(
+            ctx,
+            ForInOfStatementLeft::Pattern(left),
+            start_loc,
+            is_for_await,
+        );
     }
 
     // hanlde case: for (;;) {}
-    parse_regular_for(ctx, start_loc, init.into()).into()
+    Ok(parse_regular_for(ctx, start_loc, init.into())?.into())
 }
 
 // parse a for-in or for-of loop
-pub fn parse_for_in_of(
-    ctx: &mut Parser,
-    left: ForInOfStatementLeft,
+pub fn parse_for_in_of<'a>(
+    ctx: &mut Parser<'a>,
+    left: ForInOfStatementLeft<'a>,
     start_loc: _LocationNode,
     is_await: bool,
-) -> Statement {
+) -> ParseResult<Statement<'a>> {
     let is_for_in = ctx.cur_token_is(TokenLabel::_In);
-    ctx.next_unwrap();
+    ctx.next()?;
     let right = if is_for_in {
-        parse_expression(ctx)
+        parse_expression(ctx)?
     } else {
-        parse_maybe_assign(ctx)
+        parse_maybe_assign(ctx)?
     };
-    ctx.expect(TokenLabel::ParenR);
+    ctx.expect(TokenLabel::ParenR)?;
     ctx.enter_stmt_ctx(StatementContext::For);
-    let body = parse_statement(ctx);
+    let body = parse_statement(ctx)?;
     ctx.exit_stmt_ctx();
     ctx.exit_scope();
     ctx.labels.pop();
 
     if is_for_in {
-        ForInStatement::new(left, right, Box::new(body), ctx.compose_loc_info(start_loc)).into()
+        Ok(ForInStatement::new(
+            left,
+            right,
+            Box::new_in(body, ctx.arena),
+            ctx.compose_loc_info(start_loc),
+        )
+        .into())
     } else {
-        ForOfStatement::new(
+        Ok(ForOfStatement::new(
             left,
             right,
-            Box::new(body),
+            Box::new_in(body, ctx.arena),
             is_await,
             ctx.compose_loc_info(start_loc),
         )
-        .into()
+        .into())
     }
 }
 
 // parse the regular for loop: for(;;) {}
-pub fn parse_regular_for(
-    ctx: &mut Parser,
+pub fn parse_regular_for<'a>(
+    ctx: &mut Parser<'a>,
     start_loc: _LocationNode,
-    init: ForStatementInit,
-) -> ForStatement {
-    ctx.expect(TokenLabel::Semi);
+    init: ForStatementInit<'a>,
+) -> ParseResult<ForStatement<'a>> {
+    ctx.expect(TokenLabel::Semi)?;
     let test = if ctx.cur_token_is(TokenLabel::Semi) {
         None
     } else {
-        Some(parse_expression(ctx))
+        Some(parse_expression(ctx)?)
     };
-    ctx.expect(TokenLabel::Semi);
-    let update = if ctx.cur_token_is(TokenLabel::Semi) {
+    ctx.expect(TokenLabel::Semi)?;
+    let update = if ctx.cur_token_is(TokenLabel::ParenR) {
         None
     } else {
-        Some(parse_expression(ctx))
+        Some(parse_expression(ctx)?)
     };
-    ctx.expect(TokenLabel::Semi);
+    ctx.expect(TokenLabel::ParenR)?;
 
     ctx.enter_stmt_ctx(StatementContext::For);
-    let body = parse_statement(ctx);
+    let body = parse_statement(ctx)?;
     ctx.exit_stmt_ctx();
     ctx.exit_scope();
     ctx.labels.pop();
 
-    ForStatement::new(
+    Ok(ForStatement::new(
         init,
         test,
         update,
-        Box::new(body),
+        Box::new_in(body, ctx.arena),
         ctx.compose_loc_info(start_loc),
-    )
+    ))
 }
 
-pub fn parse_var_declarator(ctx: &mut Parser, kind: VariableKind) -> Vec<VariableDeclarator> {
-    let mut declarations: Vec<VariableDeclarator> = vec![];
+pub fn parse_var_declarator<'a>(
+    ctx: &mut Parser<'a>,
+    kind: VariableKind,
+) -> ParseResult<Vec<'a, VariableDeclarator<'a>>> {
+    let mut declarations = Vec::new_in(ctx.arena);
     loop {
         let dec_start_loc = ctx.start_location_node();
-        let id: Pattern = parse_binding_atom(ctx).into();
+        let id: Pattern = parse_binding_atom(ctx)?.into();
         let mut init = None;
         // TODO: check if pattern is valid
-        if ctx.eat(TokenLabel::Eq) {
-            init = Some(parse_maybe_assign(ctx));
+        if ctx.eat(TokenLabel::Eq)? {
+            init = Some(parse_maybe_assign(ctx)?);
         }
         // the const declarations must have initial value when it's not in for[in/of] loop
         else if matches!(kind, VariableKind::Const)
-            && (ctx.cur_token_is(TokenLabel::_In) || ctx.is_contextual("of"))
+            && !(ctx.cur_token_is(TokenLabel::_In) || ctx.is_contextual("of"))
         {
-            panic!("const declarations must have initial value");
+            return Err(ctx.raise(
+                ctx.cur_token_start,
+                ParseErrorKind::SyntaxError,
+                "const declarations must have initial value",
+            ));
         }
         // complex pattern can have no initial value only when it appear in for/[in/of] loop
         else if !matches!(id, Pattern::Identifier(..))
             && !(ctx.for_init.is_some()
                 && (ctx.cur_token_is(TokenLabel::_In) || ctx.is_contextual("of")))
         {
-            panic!("Complex binding patterns require an initialization value");
+            return Err(ctx.raise(
+                ctx.cur_token_start,
+                ParseErrorKind::SyntaxError,
+                "Complex binding patterns require an initialization value",
+            ));
         }
 
         declarations.push(VariableDeclarator::new(
@@ -1257,88 +1526,101 @@ pub fn parse_var_declarator(ctx: &mut Parser, kind: VariableKind) -> Vec<Variabl
             ctx.compose_loc_info(dec_start_loc),
         ));
 
-        if !ctx.eat(TokenLabel::Comma) {
+        if !ctx.eat(TokenLabel::Comma)? {
             break;
         }
     }
 
-    declarations
+    Ok(declarations)
 }
 
-pub fn parse_do_loop(ctx: &mut Parser) -> DoWhileStatement {
+pub fn parse_do_loop<'a>(ctx: &mut Parser<'a>) -> ParseResult<DoWhileStatement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
+    ctx.next()?;
     ctx.labels.push(Label {
         kind: LabelKind::Loop,
         name: None,
     });
 
     ctx.enter_stmt_ctx(StatementContext::DoWhile);
-    let body = parse_statement(ctx);
+    let body = parse_statement(ctx)?;
     ctx.exit_stmt_ctx();
     ctx.labels.pop();
-    ctx.expect(TokenLabel::_While);
-    let test = parse_paren_expression(ctx);
+    ctx.expect(TokenLabel::_While)?;
+    let test = parse_paren_expression(ctx)?;
 
-    ctx.semicolon();
+    ctx.semicolon()?;
 
-    DoWhileStatement::new(Box::new(body), test, ctx.compose_loc_info(start_loc))
+    Ok(DoWhileStatement::new(
+        Box::new_in(body, ctx.arena),
+        test,
+        ctx.compose_loc_info(start_loc),
+    ))
 }
 
-pub fn parse_debugger(ctx: &mut Parser) -> DebuggerStatement {
+pub fn parse_debugger<'a>(ctx: &mut Parser<'a>) -> ParseResult<DebuggerStatement<'a>> {
     let start_loc = ctx.start_location_node();
-    ctx.next_unwrap();
-    ctx.semicolon();
+    ctx.next()?;
+    ctx.semicolon()?;
 
-    DebuggerStatement::new(ctx.compose_loc_info(start_loc))
+    Ok(DebuggerStatement::new(ctx.compose_loc_info(start_loc)))
 }
 
-pub fn parse_break_continue(ctx: &mut Parser) -> Statement {
+pub fn parse_break_continue<'a>(ctx: &mut Parser<'a>) -> ParseResult<Statement<'a>> {
     let is_break = ctx.cur_token_is(TokenLabel::_Break);
     let start_loc = ctx.start_location_node();
     let label;
 
-    ctx.next_unwrap();
+    ctx.next()?;
 
-    if ctx.eat(TokenLabel::Semi) || can_insert_semicolon(ctx) {
+    if ctx.eat(TokenLabel::Semi)? || can_insert_semicolon(ctx) {
         label = None;
     } else if !ctx.cur_token_is(TokenLabel::Name) {
-        unexpected(ctx.cur_token.clone().unwrap());
+        return Err(unexpected(ctx));
     } else {
-        label = Some(parse_ident(ctx, false));
-        ctx.semicolon();
+        label = Some(parse_ident(ctx, false)?);
+        ctx.semicolon()?;
     }
 
     if !check_label_destination(&ctx.labels, &label, is_break) {
-        println!(
-            "Unsyntactic {}",
-            if is_break { "break" } else { "continue" }
-        );
+        ctx.raise_early(
+            start_loc.pos,
+            EarlyErrorCode::InvalidJumpTarget,
+            &format!(
+                "Unsyntactic {}",
+                if is_break { "break" } else { "continue" }
+            ),
+        )?;
     }
 
     if is_break {
-        BreakStatement::new(label, ctx.compose_loc_info(start_loc)).into()
+        Ok(BreakStatement::new(label, ctx.compose_loc_info(start_loc)).into())
     } else {
-        ContinueStatement::new(label, ctx.compose_loc_info(start_loc)).into()
+        Ok(ContinueStatement::new(label, ctx.compose_loc_info(start_loc)).into())
     }
 }
 
 // Parse a block statement
-pub fn parse_block_stmt(ctx: &mut Parser, new_lexical_scope: bool) -> BlockStatement {
-    let mut body = vec![];
+pub fn parse_block_stmt<'a>(
+    ctx: &mut Parser<'a>,
+    new_lexical_scope: bool,
+) -> ParseResult<BlockStatement<'a>> {
+    let mut body = Vec::new_in(ctx.arena);
     let start_loc = ctx.start_location_node();
     if new_lexical_scope {
         ctx.enter_scope(0);
     }
-    ctx.expect(TokenLabel::BraceL);
-    while !ctx.eat(TokenLabel::BraceR) {
-        let stmt = parse_statement(ctx);
+    ctx.expect(TokenLabel::BraceL)?;
+    ctx.enter_stmt_ctx(StatementContext::TopLevel);
+    while !ctx.eat(TokenLabel::BraceR)? {
+        let stmt = parse_recoverable(ctx, true, parse_statement)?;
         body.push(stmt);
     }
+    ctx.exit_stmt_ctx();
 
     if new_lexical_scope {
         ctx.exit_scope();
     }
 
-    BlockStatement::new(body, ctx.compose_loc_info(start_loc))
+    Ok(BlockStatement::new(body, ctx.compose_loc_info(start_loc)))
 }
@@ -1,12 +1,17 @@
-use crate::ast::expression::{Expression, Identifier, LiteralValue};
+use crate::ast::expression::{Expression, Identifier, Literal, LiteralValue};
+use crate::ast::pattern::{ObjectPatternProperty, Pattern};
 use crate::ast::statement::Statement;
+use crate::error::{EarlyErrorCode, ParseError, ParseErrorKind, ParseResult};
 use crate::{
     parser::{
         AccessorKind, Label, LabelKind, Parser, PrivateNameInfo, PrivateNameProp, StatementContext,
     },
     tokenizer::{
-        js_token::{Token, TokenLabel},
-        util::{has_break_in_range, is_identifier_char, is_identifier_start, is_keyword_token},
+        js_token::TokenLabel,
+        util::{
+            get_code_from_idx, get_keyword, has_break_in_range, is_identifier_char,
+            is_identifier_start, is_reserved_word,
+        },
     },
 };
 use regex::Regex;
@@ -16,87 +21,323 @@ lazy_static! {
     static ref keyword_relation_operator: Regex = Regex::new(r"^in(stanceof)?$").unwrap();
 }
 
-pub fn check_used_private_name(ctx: &mut Parser, private_name_info: PrivateNameInfo) {
-    let last_private_stack_op = ctx.private_name_stack.last_mut();
-    let has_last_stack = last_private_stack_op.is_some();
+pub fn check_used_private_name(
+    ctx: &mut Parser,
+    private_name_info: PrivateNameInfo,
+) -> ParseResult<()> {
+    let has_last_stack = !ctx.private_name_stack.is_empty();
     let mut unchecked = vec![];
-    for name in private_name_info.used {
+    for (name, pos) in private_name_info.used {
         if private_name_info.declared.contains_key(&name) {
             continue;
         }
         if has_last_stack {
-            unchecked.push(name);
+            unchecked.push((name, pos));
         } else {
-            println!(
-                "Private field '#${}' must be declared in an enclosing class",
-                name
-            );
+            ctx.raise_early(
+                pos,
+                EarlyErrorCode::UndeclaredPrivateName,
+                &format!(
+                    "Private field '#{}' must be declared in an enclosing class",
+                    name
+                ),
+            )?;
         }
     }
-    if unchecked.len() > 0 {
-        last_private_stack_op.unwrap().used.append(&mut unchecked);
+    if let Some(last_private_stack) = ctx.private_name_stack.last_mut() {
+        last_private_stack.used.append(&mut unchecked);
     }
+
+    Ok(())
 }
 
 pub fn check_private_name_conflicts(
-    private_info: &mut PrivateNameInfo,
+    ctx: &mut Parser,
     name: &str,
+    pos: usize,
     is_static: bool,
     kind: Option<AccessorKind>,
-) {
-    let v_op = private_info.declared.get_mut(name);
-    if v_op.is_none() {
-        private_info.declared.insert(
-            name.to_string(),
-            PrivateNameProp {
-                is_static,
-                accessor: kind,
-            },
-        );
-    } else {
-        let v = v_op.unwrap();
-        // static private key conflicts with non-static private key that has the same name
-        if is_static && !v.is_static {
-            println!("Identifier '#${}' has already been declared", name);
-            return;
+) -> ParseResult<()> {
+    let private_info = match ctx.private_name_stack.last_mut() {
+        Some(info) => info,
+        None => return Ok(()),
+    };
+    let conflict = match private_info.declared.get_mut(name) {
+        None => {
+            private_info.declared.insert(
+                name.to_string(),
+                PrivateNameProp {
+                    is_static,
+                    accessor: kind,
+                },
+            );
+            false
         }
-        if v.accessor.is_none()
-            || kind.is_none()
-            || matches!(&v.accessor, Some(AccessorKind::GetSet))
-            || v.accessor == kind
+        // static private key conflicts with non-static private key that has the same name
+        Some(v) if is_static && !v.is_static => true,
+        Some(v)
+            if v.accessor.is_none()
+                || kind.is_none()
+                || matches!(&v.accessor, Some(AccessorKind::GetSet))
+                || v.accessor == kind =>
         {
-            println!("Identifier '#${}' has already been declared", name);
-            return;
+            true
         }
-        v.accessor = Some(AccessorKind::GetSet);
+        Some(v) => {
+            v.accessor = Some(AccessorKind::GetSet);
+            false
+        }
+    };
+
+    if conflict {
+        ctx.raise_early(
+            pos,
+            EarlyErrorCode::DuplicatePrivateName,
+            &format!("Identifier '#{}' has already been declared", name),
+        )?;
     }
+
+    Ok(())
 }
 
-pub fn check_unreserved(ctx: &Parser, name: &str, start: usize, end: usize) {
+pub fn check_unreserved(ctx: &mut Parser, name: &str, start: usize) -> ParseResult<()> {
     if ctx.in_generator_scope() && name == "yield" {
-        println!("Cannot use 'yield' as identifier inside a generator");
+        ctx.raise_early(
+            start,
+            EarlyErrorCode::InvalidIdentifier,
+            "Cannot use 'yield' as identifier inside a generator",
+        )?;
     }
     if ctx.in_async_scope() && name == "await" {
-        println!("Cannot use 'await' as identifier inside an async function");
+        ctx.raise_early(
+            start,
+            EarlyErrorCode::InvalidIdentifier,
+            "Cannot use 'await' as identifier inside an async function",
+        )?;
     }
     if ctx.in_class_field_init() && name == "arguments" {
-        println!("Cannot use 'arguments' in class field initializer");
+        ctx.raise_early(
+            start,
+            EarlyErrorCode::InvalidIdentifier,
+            "Cannot use 'arguments' in class field initializer",
+        )?;
     }
     if ctx.in_class_static_block() && (name == "arguments" || name == "await") {
-        panic!("Cannot use ${} in class static initialization block", name);
+        ctx.raise_early(
+            start,
+            EarlyErrorCode::InvalidIdentifier,
+            &format!("Cannot use {} in class static initialization block", name),
+        )?;
+    }
+    if get_keyword(ctx, name).is_some() {
+        return Err(ctx.raise(
+            start,
+            ParseErrorKind::UnexpectedToken,
+            &format!("Unexpected keyword '{}'", name),
+        ));
+    }
+    if is_reserved_word(ctx, name) {
+        let message = if name == "await" {
+            "Cannot use keyword 'await' outside an async function".to_string()
+        } else {
+            format!("The keyword '{}' is reserved", name)
+        };
+        ctx.raise_early(start, EarlyErrorCode::ReservedWord, &message)?;
+    }
+
+    Ok(())
+}
+
+// check if the name has already been exported in the module
+pub fn check_export(ctx: &mut Parser, name: &str, pos: usize) -> ParseResult<()> {
+    if !ctx.exports.insert(name.to_string()) {
+        ctx.raise_early(
+            pos,
+            EarlyErrorCode::DuplicateExport,
+            &format!("Duplicate export '{}'", name),
+        )?;
+    }
+
+    Ok(())
+}
+
+// check all the names declared by an exported binding pattern
+pub fn check_pattern_export<'a>(ctx: &mut Parser<'a>, pattern: &Pattern<'a>) -> ParseResult<()> {
+    match pattern {
+        Pattern::Identifier(ident) => check_export(ctx, &ident.name, ident.start),
+        Pattern::ObjectPattern(obj) => {
+            for prop in obj.properties.iter() {
+                match prop {
+                    ObjectPatternProperty::AssignmentProperty(p) => {
+                        check_pattern_export(ctx, &p.value)?
+                    }
+                    ObjectPatternProperty::RestElement(rest) => {
+                        check_pattern_export(ctx, &rest.argument)?
+                    }
+                }
+            }
+            Ok(())
+        }
+        Pattern::ArrayPattern(arr) => {
+            for el in arr.elements.iter().flatten() {
+                check_pattern_export(ctx, el)?;
+            }
+            Ok(())
+        }
+        Pattern::AssignmentPattern(assign) => check_pattern_export(ctx, &assign.left),
+        Pattern::RestElement(rest) => check_pattern_export(ctx, &rest.argument),
+        Pattern::MemberExpression(..) => Ok(()),
+    }
+}
+
+/// 解析函数或方法中可能存在的 `*`，async generator 从 es2018 才开始支持
+pub fn eat_generator_star(ctx: &mut Parser, is_async: bool) -> ParseResult<bool> {
+    if !ctx.cur_token_is(TokenLabel::Star) {
+        return Ok(false);
+    }
+    if is_async {
+        ctx.expect_ecma_version(9, ctx.cur_token_start, "Async generator")?;
+    } else {
+        ctx.expect_ecma_version(6, ctx.cur_token_start, "Generator")?;
+    }
+    ctx.next()?;
+
+    Ok(true)
+}
+
+// check the params of a getter or setter
+pub fn check_accessor_params<'a>(
+    ctx: &mut Parser<'a>,
+    is_getter: bool,
+    params: &[Pattern<'a>],
+    pos: usize,
+) -> ParseResult<()> {
+    if is_getter {
+        if !params.is_empty() {
+            ctx.raise_early(
+                pos,
+                EarlyErrorCode::InvalidAccessorParams,
+                "getter should have no params",
+            )?;
+        }
+        return Ok(());
+    }
+    if params.len() != 1 {
+        ctx.raise_early(
+            pos,
+            EarlyErrorCode::InvalidAccessorParams,
+            "setter should have exactly one param",
+        )?;
+    }
+    if let Some(Pattern::RestElement(..)) = params.first() {
+        ctx.raise_early(
+            pos,
+            EarlyErrorCode::InvalidAccessorParams,
+            "Setter cannot use rest params",
+        )?;
+    }
+
+    Ok(())
+}
+
+/// 容错模式下前进到下一个 token，遇到词法错误时记录错误并跳过出错的字符
+pub fn advance_with_recovery(ctx: &mut Parser) {
+    loop {
+        match ctx.next() {
+            Ok(_) => return,
+            Err(err) => {
+                let resume = ctx.cursor.max(err.pos + 1);
+                ctx.record_error(err);
+                // 模板字符串出错时直接退出模板的上下文，避免一直停留在无法结束的模板中
+                if ctx.cur_token_ctx().is_some_and(|c| c.unusual) {
+                    ctx.token_context.pop();
+                }
+                ctx.reset_cursor(resume);
+            }
+        }
     }
-    if is_keyword_token(name).is_some() {
-        panic!("Unexpected keyword {}", name);
+}
+
+/// 容错模式下跳过出错语句剩余的 token，停在下一个同步点。只记录花括号的层级，
+/// 未闭合的圆括号和方括号不会吞掉后面的代码，比如 `a(; }` 中的 `}` 仍然结束外层语句块：
+/// - 与语句开始位于同一花括号层级的分号之后
+/// - 外层语句块的右花括号之前，顶层中多余的右花括号会被跳过
+/// - switch 中下一个 case/default 分支之前
+/// - 位于新一行开头的语句关键字之前
+pub fn skip_to_sync_point(ctx: &mut Parser, nested: bool) {
+    let mut depth = 0;
+    let mut first = true;
+    loop {
+        let label = ctx.cur_token.as_ref().map_or(TokenLabel::Eof, |t| t.label);
+        match label {
+            TokenLabel::Eof => return,
+            TokenLabel::BraceR if depth > 0 => {
+                depth -= 1;
+            }
+            TokenLabel::BraceR | TokenLabel::_Case | TokenLabel::_Default
+                if nested && depth == 0 && !first =>
+            {
+                return;
+            }
+            TokenLabel::BraceR | TokenLabel::Semi if depth == 0 => {
+                advance_with_recovery(ctx);
+                return;
+            }
+            TokenLabel::BraceL | TokenLabel::DollarBraceL => {
+                depth += 1;
+            }
+            _ if !first
+                && depth == 0
+                && is_statement_keyword(label)
+                && has_break_in_range(ctx, (ctx.last_token_end, ctx.cur_token_start)) =>
+            {
+                return;
+            }
+            _ => {}
+        }
+        first = false;
+        advance_with_recovery(ctx);
     }
-    // TODO: if ecamversion < 6, can return directly
-    // if has_break_in_range(ctx, (start, end)) {
-    //      return;
-    // }
-    // TODO: check reserved word list
 }
 
-pub fn unexpected(token: Token) -> ! {
-    panic!("Unexpected token {:?}", token);
+fn is_statement_keyword(label: TokenLabel) -> bool {
+    matches!(
+        label,
+        TokenLabel::_Var
+            | TokenLabel::_Const
+            | TokenLabel::_Function
+            | TokenLabel::_Class
+            | TokenLabel::_If
+            | TokenLabel::_For
+            | TokenLabel::_While
+            | TokenLabel::_Do
+            | TokenLabel::_Return
+            | TokenLabel::_Throw
+            | TokenLabel::_Try
+            | TokenLabel::_Switch
+            | TokenLabel::_Break
+            | TokenLabel::_Continue
+            | TokenLabel::_Import
+            | TokenLabel::_Export
+    )
+}
+
+/// 构造一个位于当前 token 处的 Unexpected token 错误
+pub fn unexpected(ctx: &Parser) -> ParseError {
+    let message = match &ctx.cur_token {
+        Some(t) if t.is_eof() => "Unexpected end of input".to_string(),
+        Some(t) => format!(
+            "Unexpected token '{}'",
+            t.value.as_deref().unwrap_or(t.label.as_str())
+        ),
+        None => "Unexpected token".to_string(),
+    };
+
+    ctx.raise(
+        ctx.cur_token_start,
+        ParseErrorKind::UnexpectedToken,
+        &message,
+    )
 }
 
 pub fn can_insert_semicolon(ctx: &Parser) -> bool {
@@ -106,19 +347,23 @@ pub fn can_insert_semicolon(ctx: &Parser) -> bool {
 }
 
 // check if trailing comma is followed by label
-pub fn after_trailing_comma(ctx: &mut Parser, label: TokenLabel, auto_next: bool) -> bool {
+pub fn after_trailing_comma(
+    ctx: &mut Parser,
+    label: TokenLabel,
+    auto_next: bool,
+) -> ParseResult<bool> {
     if ctx.cur_token_is(label) {
         if auto_next {
-            ctx.next_unwrap();
+            ctx.next()?;
         }
-        return true;
+        return Ok(true);
     }
-    false
+    Ok(false)
 }
 
 pub fn is_import_expr(ctx: &Parser) -> bool {
-    let skip_word_count = skip_space_comment_at_char_idx(&ctx.content, ctx.cursor, true);
-    let mut iter = ctx.content.chars().skip(ctx.cursor + skip_word_count);
+    let skip_word_count = skip_space_comment_at(ctx.content, ctx.cursor);
+    let mut iter = ctx.content[ctx.cursor + skip_word_count..].chars();
     let next_char_op = iter.next().map(|c| c as usize);
     if let Some(next_char) = next_char_op {
         // '(' or '.'
@@ -130,26 +375,17 @@ pub fn is_import_expr(ctx: &Parser) -> bool {
 
 // check if there is break charactor between 'async' and 'function'
 pub fn is_async_func(ctx: &Parser) -> bool {
-    if !ctx.is_contextual("async") {
+    if ctx.ecma_version < 8 || !ctx.is_contextual("async") {
         return false;
     }
-    let skip_word_count = skip_space_comment_at_char_idx(&ctx.content, ctx.cursor, true);
+    let skip_word_count = skip_space_comment_at(ctx.content, ctx.cursor);
     if has_break_in_range(ctx, (ctx.cursor, ctx.cursor + skip_word_count)) {
         return false;
     }
 
-    if ctx.content.starts_with("function") {
-        let byte_idx =
-            get_byte_idx_by_char_idx(&ctx.content, ctx.cursor + skip_word_count).unwrap();
-        if byte_idx + 8 == ctx.content.chars().count() {
-            return true;
-        }
-        let next_func_ch = ctx
-            .content
-            .chars()
-            .skip(ctx.cursor + skip_word_count + 8)
-            .next()
-            .map_or(0, |ch| ch as usize);
+    let func_start = ctx.cursor + skip_word_count;
+    if ctx.content[func_start..].starts_with("function") {
+        let next_func_ch = get_code_from_idx(ctx, func_start + 8);
         if !(is_identifier_char(next_func_ch) || next_func_ch > 0xffff) {
             return true;
         }
@@ -158,13 +394,18 @@ pub fn is_async_func(ctx: &Parser) -> bool {
     false
 }
 
+// check if the current token is the 'of' of a for-of statement
+pub fn is_for_of(ctx: &Parser) -> bool {
+    ctx.ecma_version >= 6 && ctx.is_contextual("of")
+}
+
 pub fn is_let(ctx: &Parser) -> bool {
-    if !ctx.is_contextual("let") {
+    if ctx.ecma_version < 6 || !ctx.is_contextual("let") {
         return false;
     }
 
-    let skip_word_count = skip_space_comment_at_char_idx(&ctx.content, ctx.cursor, true);
-    let mut iter = ctx.content.chars().skip(ctx.cursor + skip_word_count);
+    let skip_word_count = skip_space_comment_at(ctx.content, ctx.cursor);
+    let mut iter = ctx.content[ctx.cursor + skip_word_count..].chars();
     let next_char_op = iter.next().map(|c| c as usize);
     if next_char_op.is_none() {
         return false;
@@ -194,7 +435,7 @@ pub fn is_let(ctx: &Parser) -> bool {
         }
 
         if !keyword_relation_operator.is_match(&identifier) {
-            return false;
+            return true;
         }
     }
 
@@ -203,40 +444,21 @@ pub fn is_let(ctx: &Parser) -> bool {
 
 /// TODO: 优化这种使用正则的方式，其实可以复用 skip_space_comment 这个方法的，而且读取字符的效率理论上性能会更好，
 /// 只要暂时记录当前的 cursor、line、line_start 几个值，之后再写回去就行了
-/// 读取从 start_idx 开始的空白字符以及注释字符数目
-/// 支持 字节索引和字符索引两种
-/// 返回值: 跳过的字符长度
-pub fn skip_space_comment_at_char_idx(target: &str, start_idx: usize, is_char_idx: bool) -> usize {
-    let mut s_idx = start_idx;
-    if is_char_idx {
-        if let Some(idx) = get_byte_idx_by_char_idx(target, start_idx) {
-            s_idx = idx;
-        } else {
-            return 0;
-        }
-    }
-    let match_op = skip_space_commnet.find_at(target, s_idx);
-    if let Some(mat) = match_op {
-        return mat.as_str().chars().count();
+/// 读取从字节索引 start_idx 开始的空白字符以及注释
+/// 返回值: 跳过的字节长度
+pub fn skip_space_comment_at(target: &str, start_idx: usize) -> usize {
+    if !target.is_char_boundary(start_idx) {
+        return 0;
     }
-
-    0
-}
-
-/// 根据字符的索引位置获取对应的字节索引位置
-pub fn get_byte_idx_by_char_idx(target: &str, char_idx: usize) -> Option<usize> {
-    let mut iter = target.char_indices().skip(char_idx);
-    if let Some((idx, _)) = iter.next() {
-        return Some(idx);
-    }
-
-    None
+    skip_space_commnet
+        .find_at(target, start_idx)
+        .map_or(0, |mat| mat.as_str().len())
 }
 
 // check if label exists or there is valid destination to go.
-pub fn check_label_destination(
+pub fn check_label_destination<'a>(
     labels: &Vec<Label>,
-    cur_label: &Option<Identifier>,
+    cur_label: &Option<Identifier<'a>>,
     is_break: bool,
 ) -> bool {
     let mut valid = false;
@@ -272,7 +494,60 @@ pub fn check_label_destination(
     valid
 }
 
-pub fn is_directive_candidate(stmt: &Statement) -> bool {
+// check if a directive is exactly 'use strict', escape sequences are not allowed
+pub fn is_use_strict<'a>(ctx: &Parser<'a>, literal: &Literal<'a>) -> bool {
+    literal.end - literal.start == 12
+        && &ctx.content[literal.start + 1..literal.end - 1] == "use strict"
+}
+
+// 在读取 token 之前扫描从 start 开始的指令序言中是否有 'use strict'，与 acorn 的 strictDirective 相同。
+// 解析到指令时它后面的 token 已经读取了，比如 `'use strict'; 017` 中的 `017`
+pub fn strict_directive(ctx: &Parser, mut start: usize) -> bool {
+    if ctx.ecma_version < 5 {
+        return false;
+    }
+    let content = ctx.content;
+    loop {
+        start += skip_space_comment_at(content, start);
+        let Some(end) = string_literal_end(content, start) else {
+            return false;
+        };
+        if end - start == 12 && &content[start + 1..end - 1] == "use strict" {
+            let next = end + skip_space_comment_at(content, end);
+            let mut rest = content[next..].chars();
+            return match rest.next() {
+                Some(';' | '}') => true,
+                // 换行之后的内容可能与字符串组成一个表达式，比如 `'use strict'\n.length`
+                Some(c) if "(`.[+-/*%<>=,?^&".contains(c) => false,
+                Some('!') if rest.next() == Some('=') => false,
+                _ => has_break_in_range(ctx, (end, next)),
+            };
+        }
+        start = end + skip_space_comment_at(content, end);
+        if content[start..].starts_with(';') {
+            start += 1;
+        }
+    }
+}
+
+fn string_literal_end(content: &str, start: usize) -> Option<usize> {
+    let mut chars = content[start..].char_indices();
+    let quote = match chars.next() {
+        Some((_, quote @ ('\'' | '"'))) => quote,
+        _ => return None,
+    };
+    while let Some((idx, c)) = chars.next() {
+        if c == quote {
+            return Some(start + idx + 1);
+        }
+        if c == '\\' {
+            chars.next();
+        }
+    }
+    None
+}
+
+pub fn is_directive_candidate<'a>(stmt: &Statement<'a>) -> bool {
     if let Statement::ExpressionStatement(expr) = stmt {
         if let Expression::Literal(literal) = &expr.expression {
             if let LiteralValue::String(..) = &literal.value {
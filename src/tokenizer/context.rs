@@ -123,7 +123,7 @@ pub fn update_token_context(ctx: &mut Parser) {
     if cur_token.map_or(false, |t| t.keyword)
         && prev_token.map_or(false, |pt| pt.is_token_of(TokenLabel::Dot))
     {
-        ctx.expr_allowed = true;
+        ctx.expr_allowed = false;
     } else if cur_token.map_or(false, |t| t.update_ctx) {
         match cur_token.map(|t| t.label.clone()).unwrap() {
             TokenLabel::ParenR | TokenLabel::BraceR => {
@@ -166,14 +166,20 @@ fn update_parenr_bracer_ctx(ctx: &mut Parser) {
     if token_ctx
         .as_ref()
         .map_or(false, |t| t.label == TokenContextLabel::BraceStat)
-        && ctx
-            .cur_token_ctx()
-            .map_or(false, |t| t.label == TokenContextLabel::FnStat)
+        && ctx.cur_token_ctx().map_or(false, |t| {
+            matches!(
+                t.label,
+                TokenContextLabel::FnStat
+                    | TokenContextLabel::FnExpr
+                    | TokenContextLabel::FnGen
+                    | TokenContextLabel::FnExprGen
+            )
+        })
     {
         token_ctx = ctx.token_context.pop();
     }
 
-    ctx.expr_allowed = token_ctx.map_or(false, |t| t.is_expr);
+    ctx.expr_allowed = token_ctx.map_or(true, |t| !t.is_expr);
 }
 
 fn update_bracel_ctx(ctx: &mut Parser) {
@@ -222,6 +228,9 @@ fn update_fn_class_ctx(ctx: &mut Parser) {
             {
                 ctx.token_context
                     .push(get_context_by_label(TokenContextLabel::FnExpr));
+            } else {
+                ctx.token_context
+                    .push(get_context_by_label(TokenContextLabel::FnStat));
             }
         }
         None => {
@@ -268,15 +277,13 @@ fn update_star_ctx(ctx: &mut Parser) {
 }
 
 fn update_name_ctx(ctx: &mut Parser) {
-    ctx.expr_allowed = if ctx.prev_token.as_ref().map_or(false, |t| {
-        t.label != TokenLabel::Dot
-            && (t.value.as_ref().map_or(false, |v| v == "of") && !ctx.expr_allowed
-                || t.value.as_ref().map_or(false, |v| v == "yield") && ctx.in_generator_ctx())
-    }) {
-        true
-    } else {
-        false
-    }
+    let prev_is_dot = ctx
+        .prev_token
+        .as_ref()
+        .map_or(false, |t| t.label == TokenLabel::Dot);
+    ctx.expr_allowed = !prev_is_dot
+        && (ctx.cur_token_value_is("of") && !ctx.expr_allowed
+            || ctx.cur_token_value_is("yield") && ctx.in_generator_ctx());
 }
 
 pub fn get_context_by_label(label: TokenContextLabel) -> TokenContext {
@@ -323,7 +330,8 @@ fn brace_is_block(ctx: &Parser) -> bool {
             }
             _ => {}
         },
-        None => {}
+        // 与 acorn 一样，文件开头的 `{` 是语句块
+        None => return true,
     }
 
     !ctx.expr_allowed
@@ -1,3 +1,5 @@
+use crate::ast::SourceLocation;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 lazy_static! {
@@ -40,7 +42,7 @@ lazy_static! {
         TokenLabel::_Import,
         TokenLabel::_Super
     ];
-    pub static ref TokenMap: HashMap<TokenLabel, Token> = HashMap::from([
+    pub static ref TokenMap: HashMap<TokenLabel, Token<'static>> = HashMap::from([
         (TokenLabel::Number, Token::se(TokenLabel::Number)),
         (TokenLabel::Regexp, Token::se(TokenLabel::Regexp)),
         (TokenLabel::String, Token::se(TokenLabel::String)),
@@ -71,7 +73,7 @@ lazy_static! {
         // Operators Token
         (TokenLabel::Eq, Token { before_expr: true, is_assign: true, ..Token::new(TokenLabel::Eq) }),
         (TokenLabel::Assign, Token { before_expr: true, is_assign: true, ..Token::new(TokenLabel::Assign) }),
-        (TokenLabel::IncDec, Token { prefix: true, postfix: true, ..Token::new(TokenLabel::IncDec) }),
+        (TokenLabel::IncDec, Token { prefix: true, postfix: true, starts_expr: true, ..Token::new(TokenLabel::IncDec) }),
         (TokenLabel::Prefix, Token { before_expr: true, prefix: true, starts_expr: true, ..Token::new(TokenLabel::Prefix) }),
         (TokenLabel::LogicalOr, Token::binop(TokenLabel::LogicalOr, 1)),
         (TokenLabel::LogicalAnd, Token::binop(TokenLabel::LogicalAnd, 2)),
@@ -94,7 +96,7 @@ lazy_static! {
         (TokenLabel::_Catch, Token::kw(TokenLabel::_Catch)),
         (TokenLabel::_Continue, Token::kw(TokenLabel::_Continue)),
         (TokenLabel::_Debugger, Token::kw(TokenLabel::_Debugger)),
-        (TokenLabel::_Default, Token::kw(TokenLabel::_Default)),
+        (TokenLabel::_Default, Token { before_expr: true, ..Token::kw(TokenLabel::_Default) }),
         (TokenLabel::_Do, Token { is_loop: true, before_expr: true, ..Token::kw(TokenLabel::_Do) }),
         (TokenLabel::_Else, Token { before_expr: true, ..Token::kw(TokenLabel::_Else) }),
         (TokenLabel::_Finally, Token::kw(TokenLabel::_Finally)),
@@ -103,7 +105,7 @@ lazy_static! {
         (TokenLabel::_If, Token::kw(TokenLabel::_If)),
         (TokenLabel::_Return, Token { before_expr: true, ..Token::kw(TokenLabel::_Return) }),
         (TokenLabel::_Switch, Token::kw(TokenLabel::_Switch)),
-        (TokenLabel::_Throw, Token::kw(TokenLabel::_Throw)),
+        (TokenLabel::_Throw, Token { before_expr: true, ..Token::kw(TokenLabel::_Throw) }),
         (TokenLabel::_Try, Token::kw(TokenLabel::_Try)),
         (TokenLabel::_Var, Token::kw(TokenLabel::_Var)),
         (TokenLabel::_Const, Token::kw(TokenLabel::_Const)),
@@ -215,7 +217,7 @@ pub enum TokenLabel {
 }
 
 impl TokenLabel {
-    pub fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &'static str {
         match self {
             Self::Number => "num",
             Self::Regexp => "regexp",
@@ -303,11 +305,11 @@ impl TokenLabel {
 }
 
 #[derive(Debug, Clone)]
-pub struct Token {
+pub struct Token<'a> {
     /// 标识 Token 的类型
     pub label: TokenLabel,
-    /// token 的值
-    pub value: Option<String>,
+    /// token 的值，没有转义字符时直接引用源码中的文本
+    pub value: Option<Cow<'a, str>>,
     /// 标识是否是内置关键字
     pub keyword: bool,
     /// 用于区分除法和正则
@@ -325,9 +327,18 @@ pub struct Token {
     pub binop: Option<u8>,
     /// 表示是否需要更新上下文
     pub update_ctx: bool,
+    /// token 在源码中的位置
+    pub start: usize,
+    pub end: usize,
+    /// 关闭 ParserOptions::locations 时为 None
+    pub loc: Option<SourceLocation<'a>>,
+    /// 只有开启 ParserOptions::ranges 时才会有值
+    pub range: Option<[usize; 2]>,
+    /// token 在源码中对应的原始文本
+    pub raw: &'a str,
 }
 
-impl Default for Token {
+impl Default for Token<'_> {
     fn default() -> Self {
         Self {
             label: TokenLabel::Name,
@@ -341,11 +352,16 @@ impl Default for Token {
             postfix: false,
             binop: None,
             update_ctx: false,
+            start: 0,
+            end: 0,
+            loc: None,
+            range: None,
+            raw: "",
         }
     }
 }
 
-impl Token {
+impl Token<'_> {
     fn new(label: TokenLabel) -> Self {
         Self {
             label: label,
@@ -372,6 +388,7 @@ impl Token {
     fn binop(label: TokenLabel, pre: u8) -> Self {
         Self {
             label: label,
+            before_expr: true,
             binop: Some(pre),
             ..Default::default()
         }
@@ -9,8 +9,8 @@ pub mod template;
 pub mod util;
 
 use self::context::update_token_context;
-use self::js_token::{Token, TokenLabel, TokenMap};
-use self::number::{read_number_token, read_radix_int};
+use self::js_token::{Token, TokenLabel};
+use self::number::{read_number_token, read_radix_number};
 use self::opearor::{
     read_caret_token, read_dot_token, read_modulo_token, read_pipe_amp_token, read_slash_token,
     read_star_token, read_token_eq_excl, read_token_from_lt_rt, read_token_from_plus_min,
@@ -19,40 +19,27 @@ use self::opearor::{
 use self::space::skip_space_comment;
 use self::string::read_string_token;
 use self::util::{
-    get_content_len, get_cur_code_from_ctx, get_next_code_from_ctx, get_token_from_map,
-    is_identifier_char, is_keyword_token,
+    get_content_len, get_cur_code_from_ctx, get_keyword, get_next_code_from_ctx,
+    get_token_from_map, is_identifier_char,
 };
+use crate::ast::location::FinishLocations;
+use crate::ast::SourceLocation;
+use crate::error::{ParseError, ParseErrorKind, ParseResult};
+use crate::options::ParserOptions;
 use crate::parser::Parser;
 use crate::statement::util::unexpected;
 use crate::tokenizer::template::read_template_token;
-use std::{error::Error, fmt::Display, usize};
+use bumpalo::Bump;
+use std::borrow::Cow;
 
-pub type TokenResult = Result<js_token::Token, Box<dyn Error>>;
+pub type TokenResult<'a> = ParseResult<js_token::Token<'a>>;
 
-#[derive(Debug)]
-enum TokenError {
-    KeywordNotExist,
-    TokenNotFoundError,
-    UnexpectedCharactorError,
-}
-
-impl Display for TokenError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::KeywordNotExist => write!(f, "Keyword did not exist."),
-            _ => write!(f, "Others error."),
-        }
-    }
-}
-
-impl Error for TokenError {}
-
-pub fn next_token(ctx: &mut Parser) -> TokenResult {
+pub fn next_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
     if ctx
         .cur_token_ctx()
         .map_or(true, |token_ctx| !token_ctx.preserve_space)
     {
-        skip_space_comment(ctx);
+        skip_space_comment(ctx)?;
     }
 
     ctx.cur_token_start = ctx.cursor;
@@ -65,9 +52,9 @@ pub fn next_token(ctx: &mut Parser) -> TokenResult {
         return read_unusual_token(ctx).map(|t| finish_token(ctx, t));
     }
 
-    let is_end = get_cur_code_from_ctx(ctx) == 0 && ctx.cursor == get_content_len(ctx);
+    let is_end = ctx.cursor >= get_content_len(ctx);
     let result = if is_end {
-        Ok(get_token_from_map(TokenLabel::Eof).unwrap())
+        get_token_from_map(TokenLabel::Eof)
     } else {
         read_token(ctx)
     };
@@ -75,27 +62,69 @@ pub fn next_token(ctx: &mut Parser) -> TokenResult {
     result.map(|r| finish_token(ctx, r))
 }
 
-pub fn read_unusual_token(ctx: &mut Parser) -> TokenResult {
+/// 不依赖语法解析、单独使用的 token 迭代器，遇到 eof 或者第一个错误后结束。
+///
+/// 和 acorn 的 tokenizer 一样，正则和除号的歧义通过 token context 来判断。
+pub struct Tokenizer<'a> {
+    parser: Parser<'a>,
+    done: bool,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(arena: &'a Bump, source: &'a str, options: ParserOptions) -> Self {
+        Self {
+            parser: Parser::new(arena, source, options),
+            done: false,
+        }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = ParseResult<Token<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut result = self.parser.next();
+        if let Some(config) = &self.parser.location_config {
+            match &mut result {
+                Ok(token) => token.finish_locations(config),
+                Err(err) => config.finish_error(err),
+            }
+        }
+        match result {
+            Ok(token) if token.is_eof() => {
+                self.done = true;
+                None
+            }
+            Ok(token) => Some(Ok(token)),
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+pub fn read_unusual_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
     let cur_token_ctx = ctx.cur_token_ctx().unwrap();
     match cur_token_ctx.label {
         context::TokenContextLabel::QuoteTmpl => read_template_token(ctx),
-        _ => unexpected(ctx.cur_token.clone().unwrap()),
+        _ => Err(unexpected(ctx)),
     }
 }
 
-pub fn read_token(ctx: &mut Parser) -> TokenResult {
+pub fn read_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
     let first_char = get_cur_code_from_ctx(ctx);
 
     if util::is_identifier_start(first_char) {
         let word = read_word(ctx);
-        if let Some(t) = is_keyword_token(&word) {
-            return TokenMap
-                .get(t)
-                .map(|r| r.clone())
-                .ok_or(TokenError::KeywordNotExist.into());
+        if let Some(t) = get_keyword(ctx, word) {
+            return get_token_from_map(t);
         } else {
             return get_token_from_map(TokenLabel::Name).map(|mut r| {
-                r.value = Some(word);
+                r.value = Some(Cow::Borrowed(word));
                 r
             });
         }
@@ -104,7 +133,7 @@ pub fn read_token(ctx: &mut Parser) -> TokenResult {
     read_token_by_code(ctx, first_char)
 }
 
-pub fn read_token_by_code(ctx: &mut Parser, code: usize) -> TokenResult {
+pub fn read_token_by_code<'a>(ctx: &mut Parser<'a>, code: usize) -> TokenResult<'a> {
     match code {
         // '!' or '='
         c @ (33 | 61) => read_token_eq_excl(ctx, c),
@@ -140,35 +169,23 @@ pub fn read_token_by_code(ctx: &mut Parser, code: usize) -> TokenResult {
         // 0..9
         c @ 48..=57 => {
             if c == 48 {
-                let mut iter = ctx.content.chars().skip(ctx.cursor + 1);
-                if let Some(next) = iter.next().map(|v| v as usize) {
-                    if next == 120 || next == 88 {
-                        // 读取 0x 0X 等十六进制数字
-                        ctx.cursor += 2;
-                        let v = read_radix_int(ctx, 16);
-                        return get_token_from_map(TokenLabel::Number).map(|mut r| {
-                            r.value = Some(v.to_string());
-                            r
-                        });
-                    }
-                    if next == 111 || next == 79 {
-                        // 读取 0o 0O 等八进制数字
-                        ctx.cursor += 2;
-                        let v = read_radix_int(ctx, 8);
-                        return get_token_from_map(TokenLabel::Number).map(|mut r| {
-                            r.value = Some(v.to_string());
-                            r
-                        });
-                    }
-                    if next == 98 || next == 66 {
-                        // 读取 0b 0B 等二进制数字
-                        ctx.cursor += 2;
-                        let v = read_radix_int(ctx, 2);
-                        return get_token_from_map(TokenLabel::Number).map(|mut r| {
-                            r.value = Some(v.to_string());
-                            r
-                        });
-                    }
+                let next = get_next_code_from_ctx(ctx);
+                if next == 120 || next == 88 {
+                    // 读取 0x 0X 等十六进制数字
+                    ctx.cursor += 2;
+                    return read_radix_number(ctx, 16);
+                }
+                if next == 111 || next == 79 {
+                    // 读取 0o 0O 等八进制数字
+                    ctx.expect_ecma_version(6, ctx.cursor, "Octal literal")?;
+                    ctx.cursor += 2;
+                    return read_radix_number(ctx, 8);
+                }
+                if next == 98 || next == 66 {
+                    // 读取 0b 0B 等二进制数字
+                    ctx.expect_ecma_version(6, ctx.cursor, "Binary literal")?;
+                    ctx.cursor += 2;
+                    return read_radix_number(ctx, 2);
                 }
             }
 
@@ -197,6 +214,7 @@ pub fn read_token_by_code(ctx: &mut Parser, code: usize) -> TokenResult {
         94 => read_caret_token(ctx),
         // '`'
         96 => {
+            ctx.expect_ecma_version(6, ctx.cursor, "Template literal")?;
             ctx.cursor += 1;
             get_token_from_map(TokenLabel::BackQuote)
         }
@@ -212,47 +230,73 @@ pub fn read_token_by_code(ctx: &mut Parser, code: usize) -> TokenResult {
             ctx.cursor += 1;
             get_token_from_map(TokenLabel::Prefix)
         }
-        _ => Err(TokenError::UnexpectedCharactorError.into()),
+        _ => Err(unexpected_character(ctx, code)),
     }
 }
 
-fn read_token_num_sign(ctx: &mut Parser) -> TokenResult {
+fn read_token_num_sign<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
     let next_code = get_next_code_from_ctx(ctx);
     if is_identifier_char(next_code) {
+        ctx.expect_ecma_version(13, ctx.cursor, "Private name")?;
         ctx.cursor += 1;
         let word = read_word(ctx);
         return get_token_from_map(TokenLabel::PrivateId).map(|mut r| {
-            r.value = Some(word);
+            r.value = Some(Cow::Borrowed(word));
             r
         });
     }
+    // 只有文件开头的 #! 是 hashbang，允许时已经在 Parser::new 中跳过了
+    if ctx.cursor == 0 && next_code == 33 && ctx.options.allow_hash_bang.is_none() {
+        ctx.expect_ecma_version(14, ctx.cursor, "Hashbang")?;
+    }
 
-    Err(TokenError::UnexpectedCharactorError.into())
+    Err(unexpected_character(ctx, 35))
 }
 
-fn read_word(ctx: &mut Parser) -> String {
+fn unexpected_character(ctx: &Parser, code: usize) -> ParseError {
+    let ch = char::from_u32(code as u32).unwrap_or(char::REPLACEMENT_CHARACTER);
+    ctx.raise(
+        ctx.cursor,
+        ParseErrorKind::UnexpectedCharacter,
+        &format!("Unexpected character '{}'", ch),
+    )
+}
+
+/// 读取一个标识符，返回源码中对应的片段
+fn read_word<'a>(ctx: &mut Parser<'a>) -> &'a str {
+    let content = ctx.content;
     let start_pos = ctx.cursor;
     let mut new_pos = ctx.cursor;
-    let mut result = String::new();
-    for (i, c) in ctx.content.char_indices().skip(start_pos) {
-        if i == start_pos && util::is_identifier_start(c as usize)
-            || (i != start_pos && util::is_identifier_char(c as usize))
+    for c in content[start_pos..].chars() {
+        if new_pos == start_pos && util::is_identifier_start(c as usize)
+            || (new_pos != start_pos && util::is_identifier_char(c as usize))
         {
-            new_pos += 1;
-            result.push(c);
+            new_pos += c.len_utf8();
         } else {
             break;
         }
     }
 
     ctx.cursor = new_pos;
-    result
+    &content[start_pos..new_pos]
 }
 
-pub fn finish_token(ctx: &mut Parser, token: Token) -> Token {
+pub fn finish_token<'a>(ctx: &mut Parser<'a>, mut token: Token<'a>) -> Token<'a> {
+    token.raw = &ctx.content[ctx.cur_token_start..ctx.cursor];
+    // 没有设置值的 token 使用源码中对应的文本作为值
+    if token.value.is_none() && token.label != TokenLabel::Eof {
+        token.value = Some(Cow::Borrowed(token.raw));
+    }
     ctx.prev_token = ctx.cur_token.clone();
     ctx.cur_token_end = ctx.cursor;
     ctx.cur_token_end_loc = Some(ctx.get_cursor_position());
+    token.start = ctx.cur_token_start;
+    token.end = ctx.cursor;
+    token.loc = Some(SourceLocation {
+        source: ctx.source_file,
+        start: ctx.cur_token_start_loc.clone().unwrap_or_default(),
+        end: ctx.get_cursor_position(),
+    });
     ctx.cur_token = Some(token.clone());
 
     update_token_context(ctx);
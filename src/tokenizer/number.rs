@@ -1,92 +1,198 @@
 use super::{
     js_token::TokenLabel,
-    util::{get_cur_code_from_ctx, get_token_from_map},
+    util::{get_cur_code_from_ctx, get_token_from_map, is_identifier_start},
     TokenResult,
 };
+use crate::error::{ParseErrorKind, ParseResult};
 use crate::parser::Parser;
+use std::borrow::Cow;
 
 const MAX: u32 = std::u32::MAX;
 
-pub fn read_number_token(
-    ctx: &mut Parser,
+pub fn read_number_token<'a>(
+    ctx: &mut Parser<'a>,
     starts_with_dot: bool,
     starts_with_zero: bool,
-) -> TokenResult {
+) -> TokenResult<'a> {
     let start = ctx.cursor;
-    let mut result: String = String::new();
-    let mut octal = starts_with_zero && ctx.cursor - start >= 2;
     if !starts_with_dot {
-        result.push_str(&read_int(ctx, 10, true).to_string());
+        // 以 0 开头的整数部分可能是旧式八进制，不能使用数字分隔符，比如 `0_1`、`08_1`
+        read_int(ctx, 10, !starts_with_zero)?;
+        if starts_with_zero && get_cur_code_from_ctx(ctx) == 95 {
+            return Err(ctx.raise(
+                ctx.cursor,
+                ParseErrorKind::InvalidNumber,
+                "Numeric separator is not allowed in legacy octal like integer literal",
+            ));
+        }
+    }
+    let mut octal = starts_with_zero && ctx.cursor - start >= 2;
+    if octal && ctx.strict_mode {
+        return Err(ctx.raise(start, ParseErrorKind::InvalidNumber, "Invalid number"));
     }
     let mut next = get_cur_code_from_ctx(ctx);
     // The BigInt
     if !octal && !starts_with_dot && next == 110 {
         // 'n'
+        ctx.expect_ecma_version(11, start, "BigInt literal")?;
         ctx.cursor += 1;
-        return get_token_from_map(TokenLabel::Number).map(|mut r| {
-            let mut s = String::new();
-            let mut iter = ctx.content.chars().skip(start);
-            for _ in start..ctx.cursor {
-                s.push(iter.next().unwrap());
-            }
-            r.value = Some(s);
-            r
-        });
+        check_ident_after_number(ctx)?;
+        let digits = read_raw(ctx, start, ctx.cursor - 1);
+        return number_token(digits);
     }
 
-    if octal && (result.contains("8") || result.contains("9")) {
+    if octal && ctx.content[start..ctx.cursor].contains(['8', '9']) {
         octal = false;
     }
 
     // '.'
-    if next == 46 && !octal {
+    if (next == 46 && !octal) || starts_with_dot {
         ctx.cursor += 1;
-        result.push('.');
-        result.push_str(&read_int(ctx, 10, true).to_string());
+        read_int(ctx, 10, true)?;
         next = get_cur_code_from_ctx(ctx);
     }
 
-    if next == 69 || next == 101 {
+    if (next == 69 || next == 101) && !octal {
         // 'e' or 'E'
         ctx.cursor += 1;
-        result.push(if next == 69 { 'e' } else { 'E' });
         next = get_cur_code_from_ctx(ctx);
         if next == 43 || next == 45 {
             ctx.cursor += 1;
-            result.push(if next == 43 { '+' } else { '-' });
         }
-        result.push_str(&read_int(ctx, 10, true).to_string());
+        if eat_int(ctx, 10, MAX, true)?.0 == 0 {
+            return Err(ctx.raise(start, ParseErrorKind::InvalidNumber, "Invalid number"));
+        }
     }
+    check_ident_after_number(ctx)?;
+
+    let raw = read_raw(ctx, start, ctx.cursor);
+    let value = if octal {
+        // 旧式八进制，比如 `017`
+        radix_to_f64(&raw, 8)
+    } else {
+        match raw.parse::<f64>() {
+            Ok(v) => v,
+            Err(_) => {
+                return Err(ctx.raise(
+                    start,
+                    ParseErrorKind::InvalidNumber,
+                    &format!("Invalid number '{}'", raw),
+                ))
+            }
+        }
+    };
+    number_token(Cow::Owned(value.to_string()))
+}
 
+/// 读取 `0x`、`0o`、`0b` 前缀之后的数字，`ctx.cursor` 位于前缀之后
+pub fn read_radix_number<'a>(ctx: &mut Parser<'a>, radix: u32) -> TokenResult<'a> {
+    let start = ctx.cursor;
+    let (l, _) = eat_int(ctx, radix, MAX, true)?;
+    if l == 0 {
+        return Err(ctx.raise(
+            start,
+            ParseErrorKind::InvalidNumber,
+            &format!("Expected number in radix {}", radix),
+        ));
+    }
+    let digits = read_raw(ctx, start, ctx.cursor);
+    if get_cur_code_from_ctx(ctx) == 110 {
+        // 'n'
+        ctx.expect_ecma_version(11, start - 2, "BigInt literal")?;
+        ctx.cursor += 1;
+        check_ident_after_number(ctx)?;
+        // 与 acorn 一致，BigInt 的值保留进制前缀，只去掉 `n` 与数字分隔符
+        let prefix = &ctx.content[start - 2..start];
+        return number_token(Cow::Owned(format!("{}{}", prefix, digits)));
+    }
+    check_ident_after_number(ctx)?;
+
+    number_token(Cow::Owned(radix_to_f64(&digits, radix).to_string()))
+}
+
+/// Number token 的 value 是数值本身的十进制文本（BigInt 则是去掉 `n` 的文本），
+/// 原始文本保存在 `raw` 中
+fn number_token(value: Cow<str>) -> TokenResult {
     get_token_from_map(TokenLabel::Number).map(|mut r| {
-        r.value = Some(result);
+        r.value = Some(value);
         r
     })
 }
 
-pub fn read_radix_int(ctx: &mut Parser, radix: u32) -> u32 {
-    let (l, v) = eat_int(ctx, radix, MAX, true);
-    if l == 0 {
-        panic!("Expected number in radix {}", radix);
+/// 将 2、8、16 进制的数字转换为最接近的 f64（就近舍入，平局取偶）
+fn radix_to_f64(digits: &str, radix: u32) -> f64 {
+    let bits_per_digit = radix.trailing_zeros();
+    let mut bits = vec![];
+    for c in digits.chars() {
+        let d = c.to_digit(radix).unwrap();
+        for i in (0..bits_per_digit).rev() {
+            bits.push((d >> i) & 1 == 1);
+        }
+    }
+    let bits = match bits.iter().position(|b| *b) {
+        Some(i) => &bits[i..],
+        None => return 0.0,
+    };
+    let fold = |bits: &[bool]| bits.iter().fold(0u64, |acc, b| acc << 1 | *b as u64);
+    if bits.len() <= 53 {
+        return fold(bits) as f64;
+    }
+    let mut mantissa = fold(&bits[..53]);
+    let round = bits[53];
+    let sticky = bits[54..].iter().any(|b| *b);
+    if round && (sticky || mantissa & 1 == 1) {
+        mantissa += 1;
+    }
+    mantissa as f64 * 2f64.powi((bits.len() - 53) as i32)
+}
+
+/// 数字字面量后面不能紧跟标识符，比如 `3in x`
+fn check_ident_after_number(ctx: &mut Parser) -> ParseResult<()> {
+    if is_identifier_start(get_cur_code_from_ctx(ctx)) {
+        return Err(ctx.raise(
+            ctx.cursor,
+            ParseErrorKind::InvalidNumber,
+            "Identifier directly after number",
+        ));
     }
 
-    v
+    Ok(())
 }
 
-pub fn read_int(ctx: &mut Parser, radix: u32, allow_separators: bool) -> u32 {
-    let (_, v) = eat_int(ctx, radix, MAX, allow_separators);
+/// 读取数字的原始文本，不包括数字分隔符
+fn read_raw<'a>(ctx: &Parser<'a>, start: usize, end: usize) -> Cow<'a, str> {
+    let raw = &ctx.content[start..end];
+    if raw.contains('_') {
+        Cow::Owned(raw.replace('_', ""))
+    } else {
+        Cow::Borrowed(raw)
+    }
+}
 
-    v
+pub fn read_int(ctx: &mut Parser, radix: u32, allow_separators: bool) -> ParseResult<f64> {
+    let (_, v) = eat_int(ctx, radix, MAX, allow_separators)?;
+
+    Ok(v)
 }
 
 /// 读取对应进制长度最大为 len 的数字
-pub fn read_fixed_int(ctx: &mut Parser, radix: u32, len: u32, allow_separators: bool) -> u32 {
-    let (l, v) = eat_int(ctx, radix, len, allow_separators);
+pub fn read_fixed_int(
+    ctx: &mut Parser,
+    radix: u32,
+    len: u32,
+    allow_separators: bool,
+) -> ParseResult<u32> {
+    let start = ctx.cursor;
+    let (l, v) = eat_int(ctx, radix, len, allow_separators)?;
     if l != len {
-        panic!("Expected number in radix");
+        return Err(ctx.raise(
+            start,
+            ParseErrorKind::InvalidEscape,
+            "Bad character escape sequence",
+        ));
     }
 
-    v
+    Ok(v as u32)
 }
 
 pub fn read_range_int(
@@ -94,41 +200,55 @@ pub fn read_range_int(
     radix: u32,
     len_range: (u32, u32),
     allow_separators: bool,
-) -> u32 {
+) -> ParseResult<u32> {
     let (min, max) = len_range;
-    if min > max {
-        panic!("The range left must be less than right");
-    }
-    let (l, v) = eat_int(ctx, radix, max, allow_separators);
+    let start = ctx.cursor;
+    let (l, v) = eat_int(ctx, radix, max + 1, allow_separators)?;
     if l < min || l > max {
-        panic!("Expected {} to {} numbers, got {} numbers.", min, max, l);
+        return Err(ctx.raise(
+            start,
+            ParseErrorKind::InvalidEscape,
+            &format!("Expected {} to {} numbers, got {} numbers.", min, max, l),
+        ));
     }
 
-    v
+    Ok(v as u32)
 }
 
-fn eat_int(ctx: &mut Parser, radix: u32, maxlen: u32, allow_separators: bool) -> (u32, u32) {
-    let start = ctx.cursor;
+/// 读取最多 maxlen 位数字，返回读取的位数和对应的值
+fn eat_int(
+    ctx: &mut Parser,
+    radix: u32,
+    maxlen: u32,
+    allow_separators: bool,
+) -> ParseResult<(u32, f64)> {
     let mut couter = 0;
-    let mut value: u32 = 0;
+    let mut value: f64 = 0.0;
     let mut last_code = 0;
-    let mut iter = ctx.content.chars().skip(ctx.cursor);
     while couter < maxlen {
-        let code = iter.next().map(|r| r as u32).expect("Bad end of Number");
-        let val: u32;
+        let code = get_cur_code_from_ctx(ctx) as u32;
         if allow_separators && code == 95 {
             // _ 数字分割符
+            ctx.expect_ecma_version(12, ctx.cursor, "Numeric separator")?;
             if couter == 0 {
-                panic!("Numeric separator is not allowed at the first of digits");
+                return Err(ctx.raise(
+                    ctx.cursor,
+                    ParseErrorKind::InvalidNumber,
+                    "Numeric separator is not allowed at the first of digits",
+                ));
             }
             if last_code == 95 {
-                panic!("Numeric separator must be exactly one underscore");
+                return Err(ctx.raise(
+                    ctx.cursor,
+                    ParseErrorKind::InvalidNumber,
+                    "Numeric separator must be exactly one underscore",
+                ));
             }
             last_code = 95;
             ctx.cursor += 1;
             continue;
         }
-        val = match code {
+        let val = match code {
             48..=57 => code - 48,       // 0-9
             65..=90 => code - 65 + 10,  // 'A'
             97..=122 => code - 97 + 10, // 'a'
@@ -140,8 +260,15 @@ fn eat_int(ctx: &mut Parser, radix: u32, maxlen: u32, allow_separators: bool) ->
         couter += 1;
         ctx.cursor += 1;
         last_code = code;
-        value = value * radix + val;
+        value = value * radix as f64 + val as f64;
+    }
+    if last_code == 95 {
+        return Err(ctx.raise(
+            ctx.cursor - 1,
+            ParseErrorKind::InvalidNumber,
+            "Numeric separators are not allowed at the end of numeric literals",
+        ));
     }
 
-    ((ctx.cursor - start) as u32, value)
+    Ok((couter, value))
 }
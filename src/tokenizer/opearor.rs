@@ -2,36 +2,35 @@ use super::{
     js_token::TokenLabel,
     number::read_number_token,
     regex::read_regex_token,
-    util::{get_next_code_from_ctx, get_next_code_from_iter, get_token_from_map},
+    util::{get_code_from_idx, get_next_code_from_ctx, get_token_from_map},
     TokenResult,
 };
+use crate::error::ParseErrorKind;
 use crate::parser::Parser;
 
 // read token which starts with '?'
-pub fn read_token_from_question(ctx: &mut Parser) -> TokenResult {
-    let mut iter = ctx.content.chars().skip(ctx.cursor + 1);
-
-    let next_code = get_next_code_from_iter(&mut iter);
+pub fn read_token_from_question<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
+    let next_code = get_code_from_idx(ctx, ctx.cursor + 1);
+    let nnext_code = get_code_from_idx(ctx, ctx.cursor + 2);
     // .
-    if next_code == 46 {
-        let nnext_code = get_next_code_from_iter(&mut iter);
-        if nnext_code < 48 && nnext_code > 57 {
-            ctx.cursor += 2;
-            return get_token_from_map(TokenLabel::QuestionDot);
-        }
+    if next_code == 46 && !(48..=57).contains(&nnext_code) {
+        ctx.expect_ecma_version(11, ctx.cursor, "Optional chaining")?;
+        ctx.cursor += 2;
+        return get_token_from_map(TokenLabel::QuestionDot);
     }
 
     // '?'
     if next_code == 63 {
-        let nnext_code = get_next_code_from_iter(&mut iter);
         // '='
         if nnext_code == 61 {
+            ctx.expect_ecma_version(12, ctx.cursor, "Logical assignment")?;
             ctx.cursor += 3;
             return get_token_from_map(TokenLabel::Assign).map(|mut r| {
-                r.value = Some("??=".to_string());
+                r.value = Some("??=".into());
                 r
             });
         }
+        ctx.expect_ecma_version(11, ctx.cursor, "Nullish coalescing")?;
         ctx.cursor += 2;
         return get_token_from_map(TokenLabel::Coalesce);
     }
@@ -41,25 +40,32 @@ pub fn read_token_from_question(ctx: &mut Parser) -> TokenResult {
 }
 
 // read token which starts with '>' or '<'
-pub fn read_token_from_lt_rt(ctx: &mut Parser, code: usize) -> TokenResult {
-    let mut iter = ctx.content.chars().skip(ctx.cursor + 1);
-    let next_code = get_next_code_from_iter(&mut iter);
+pub fn read_token_from_lt_rt<'a>(ctx: &mut Parser<'a>, code: usize) -> TokenResult<'a> {
+    // '<!--' 在非模块代码中会被当作注释跳过，模块中不允许出现
+    if code == 60 && ctx.content[ctx.cursor + 1..].starts_with("!--") {
+        return Err(ctx.raise(
+            ctx.cursor,
+            ParseErrorKind::SyntaxError,
+            "HTML comments are not allowed in modules",
+        ));
+    }
+    let next_code = get_code_from_idx(ctx, ctx.cursor + 1);
     if next_code == code {
-        let nnext_code = get_next_code_from_iter(&mut iter);
+        let nnext_code = get_code_from_idx(ctx, ctx.cursor + 2);
         // '>>>'
         if next_code == 62 && nnext_code == 62 {
             // '>>>='
-            if get_next_code_from_iter(&mut iter) == 61 {
+            if get_code_from_idx(ctx, ctx.cursor + 3) == 61 {
                 ctx.cursor += 4;
                 return get_token_from_map(TokenLabel::Assign).map(|mut r| {
-                    r.value = Some(">>>=".to_string());
+                    r.value = Some(">>>=".into());
                     r
                 });
             }
 
             ctx.cursor += 3;
             return get_token_from_map(TokenLabel::BitShift).map(|mut r| {
-                r.value = Some(">>>".to_string());
+                r.value = Some(">>>".into());
                 r
             });
         }
@@ -69,9 +75,9 @@ pub fn read_token_from_lt_rt(ctx: &mut Parser, code: usize) -> TokenResult {
             ctx.cursor += 3;
             return get_token_from_map(TokenLabel::Assign).map(|mut r| {
                 r.value = if code == 62 {
-                    Some(">>=".to_string())
+                    Some(">>=".into())
                 } else {
-                    Some("<<=".to_string())
+                    Some("<<=".into())
                 };
                 r
             });
@@ -80,9 +86,9 @@ pub fn read_token_from_lt_rt(ctx: &mut Parser, code: usize) -> TokenResult {
         ctx.cursor += 2;
         return get_token_from_map(TokenLabel::BitShift).map(|mut r| {
             r.value = if code == 62 {
-                Some(">>".to_string())
+                Some(">>".into())
             } else {
-                Some("<<".to_string())
+                Some("<<".into())
             };
             r
         });
@@ -90,18 +96,16 @@ pub fn read_token_from_lt_rt(ctx: &mut Parser, code: usize) -> TokenResult {
 
     get_token_from_map(TokenLabel::Relational).map(|mut r| {
         let is_included = next_code == 61;
-        r.value = if code == 60 {
+        r.value = if code == 62 {
             if is_included {
-                Some(">=".to_string())
+                Some(">=".into())
             } else {
-                Some(">".to_string())
+                Some(">".into())
             }
+        } else if is_included {
+            Some("<=".into())
         } else {
-            if is_included {
-                Some("<=".to_string())
-            } else {
-                Some("<".to_string())
-            }
+            Some("<".into())
         };
         ctx.cursor += if is_included { 2 } else { 1 };
         r
@@ -109,15 +113,15 @@ pub fn read_token_from_lt_rt(ctx: &mut Parser, code: usize) -> TokenResult {
 }
 
 // read token which starts with '+' or '-'
-pub fn read_token_from_plus_min(ctx: &mut Parser, code: usize) -> TokenResult {
+pub fn read_token_from_plus_min<'a>(ctx: &mut Parser<'a>, code: usize) -> TokenResult<'a> {
     let next_code = get_next_code_from_ctx(ctx);
     if next_code == code {
         ctx.cursor += 2;
         return get_token_from_map(TokenLabel::IncDec).map(|mut r| {
             r.value = if code == 43 {
-                Some("++".to_string())
+                Some("++".into())
             } else {
-                Some("--".to_string())
+                Some("--".into())
             };
             r
         });
@@ -127,36 +131,36 @@ pub fn read_token_from_plus_min(ctx: &mut Parser, code: usize) -> TokenResult {
         ctx.cursor += 2;
         return get_token_from_map(TokenLabel::Assign).map(|mut r| {
             r.value = if code == 43 {
-                Some("+=".to_string())
+                Some("+=".into())
             } else {
-                Some("-=".to_string())
+                Some("-=".into())
             };
             r
         });
     }
 
+    ctx.cursor += 1;
     get_token_from_map(TokenLabel::PlusMin).map(|mut r| {
         r.value = if code == 43 {
-            Some("+".to_string())
+            Some("+".into())
         } else {
-            Some("-".to_string())
+            Some("-".into())
         };
         r
     })
 }
 
 // read token which starts with '/', may could be regex
-pub fn read_slash_token(ctx: &mut Parser) -> TokenResult {
+pub fn read_slash_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
     // TODO: 补充判断是否允许解析正则的实现
     if ctx.expr_allowed {
         ctx.cursor += 1;
         return read_regex_token(ctx);
     }
-    let mut chars = ctx.content.chars().skip(ctx.cursor + 1);
-    if get_next_code_from_iter(&mut chars) == 61 {
+    if get_code_from_idx(ctx, ctx.cursor + 1) == 61 {
         ctx.cursor += 2;
         return get_token_from_map(TokenLabel::Assign).map(|mut r| {
-            r.value = Some("\\=".to_string());
+            r.value = Some("/=".into());
             r
         });
     }
@@ -166,25 +170,24 @@ pub fn read_slash_token(ctx: &mut Parser) -> TokenResult {
 }
 
 // read token which starts with '!' or '='
-pub fn read_token_eq_excl(ctx: &mut Parser, c: usize) -> TokenResult {
-    let mut iter = ctx.content.chars().skip(ctx.cursor + 1);
-    let next_code = get_next_code_from_iter(&mut iter);
+pub fn read_token_eq_excl<'a>(ctx: &mut Parser<'a>, c: usize) -> TokenResult<'a> {
+    let next_code = get_code_from_idx(ctx, ctx.cursor + 1);
     if next_code == 61 {
-        let nnext_code = get_next_code_from_iter(&mut iter);
+        let nnext_code = get_code_from_idx(ctx, ctx.cursor + 2);
         return get_token_from_map(TokenLabel::Equlity).map(|mut r| {
             r.value = if nnext_code == 61 {
                 ctx.cursor += 3;
                 if c == 61 {
-                    Some("===".to_string())
+                    Some("===".into())
                 } else {
-                    Some("!==".to_string())
+                    Some("!==".into())
                 }
             } else {
                 ctx.cursor += 2;
                 if c == 61 {
-                    Some("==".to_string())
+                    Some("==".into())
                 } else {
-                    Some("!=".to_string())
+                    Some("!=".into())
                 }
             };
             r
@@ -192,6 +195,7 @@ pub fn read_token_eq_excl(ctx: &mut Parser, c: usize) -> TokenResult {
     }
 
     if c == 61 && next_code == 62 {
+        ctx.expect_ecma_version(6, ctx.cursor, "Arrow function")?;
         ctx.cursor += 2;
         return get_token_from_map(TokenLabel::Arrow);
     }
@@ -205,12 +209,14 @@ pub fn read_token_eq_excl(ctx: &mut Parser, c: usize) -> TokenResult {
 }
 
 // read token which starts with '%'
-pub fn read_modulo_token(ctx: &mut Parser) -> TokenResult {
-    let mut chars = ctx.content.chars().skip(ctx.cursor + 1);
-    let next_code = get_next_code_from_iter(&mut chars);
+pub fn read_modulo_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
+    let next_code = get_code_from_idx(ctx, ctx.cursor + 1);
     if next_code == 61 {
         ctx.cursor += 2;
-        return get_token_from_map(TokenLabel::Assign);
+        return get_token_from_map(TokenLabel::Assign).map(|mut r| {
+            r.value = Some("%=".into());
+            r
+        });
     }
 
     ctx.cursor += 1;
@@ -218,17 +224,17 @@ pub fn read_modulo_token(ctx: &mut Parser) -> TokenResult {
 }
 
 // read token which starts with '*'
-pub fn read_star_token(ctx: &mut Parser) -> TokenResult {
-    let mut chars = ctx.content.chars().skip(ctx.cursor + 1);
-    let next_code = get_next_code_from_iter(&mut chars);
-    let nnext_code = get_next_code_from_iter(&mut chars);
+pub fn read_star_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
+    let next_code = get_code_from_idx(ctx, ctx.cursor + 1);
+    let nnext_code = get_code_from_idx(ctx, ctx.cursor + 2);
     // '*'
     if next_code == 42 {
+        ctx.expect_ecma_version(7, ctx.cursor, "Exponentiation operator")?;
         // '='
         if nnext_code == 61 {
             ctx.cursor += 3;
             return get_token_from_map(TokenLabel::Assign).map(|mut r| {
-                r.value = Some("**=".to_string());
+                r.value = Some("**=".into());
                 r
             });
         }
@@ -242,12 +248,12 @@ pub fn read_star_token(ctx: &mut Parser) -> TokenResult {
 }
 
 // read token which starts with '^'
-pub fn read_caret_token(ctx: &mut Parser) -> TokenResult {
+pub fn read_caret_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
     let next_code = get_next_code_from_ctx(ctx);
     if next_code == 61 {
         ctx.cursor += 2;
         return get_token_from_map(TokenLabel::Assign).map(|mut r| {
-            r.value = Some("^=".to_string());
+            r.value = Some("^=".into());
             r
         });
     }
@@ -257,17 +263,17 @@ pub fn read_caret_token(ctx: &mut Parser) -> TokenResult {
 }
 
 // read token which starts with '|' or '&'
-pub fn read_pipe_amp_token(ctx: &mut Parser, code: usize) -> TokenResult {
-    let mut chars = ctx.content.chars().skip(ctx.cursor + 1);
-    let next_code = get_next_code_from_iter(&mut chars);
+pub fn read_pipe_amp_token<'a>(ctx: &mut Parser<'a>, code: usize) -> TokenResult<'a> {
+    let next_code = get_code_from_idx(ctx, ctx.cursor + 1);
     if next_code == code {
-        if get_next_code_from_iter(&mut chars) == 61 {
+        if get_code_from_idx(ctx, ctx.cursor + 2) == 61 {
+            ctx.expect_ecma_version(12, ctx.cursor, "Logical assignment")?;
             ctx.cursor += 3;
             return get_token_from_map(TokenLabel::Assign).map(|mut r| {
                 r.value = if code == 124 {
-                    Some("&&=".to_string())
+                    Some("||=".into())
                 } else {
-                    Some("||=".to_string())
+                    Some("&&=".into())
                 };
                 r
             });
@@ -281,13 +287,13 @@ pub fn read_pipe_amp_token(ctx: &mut Parser, code: usize) -> TokenResult {
         };
     }
 
-    if get_next_code_from_iter(&mut chars) == 61 {
+    if next_code == 61 {
         ctx.cursor += 2;
         return get_token_from_map(TokenLabel::Assign).map(|mut r| {
             r.value = if code == 124 {
-                Some("&=".to_string())
+                Some("|=".into())
             } else {
-                Some("|=".to_string())
+                Some("&=".into())
             };
             r
         });
@@ -302,18 +308,16 @@ pub fn read_pipe_amp_token(ctx: &mut Parser, code: usize) -> TokenResult {
 }
 
 // read token which starts with '.'
-pub fn read_dot_token(ctx: &mut Parser) -> TokenResult {
-    let mut iter = ctx.content.chars().skip(ctx.cursor + 1);
-    if let Some(next_code) = iter.next().map(|r| r as usize) {
-        // 0-9
-        if next_code >= 48 && next_code <= 57 {
-            return read_number_token(ctx, true, false);
-        }
-        let nnext_code = get_next_code_from_iter(&mut iter);
-        if nnext_code == 46 && next_code == 46 {
-            ctx.cursor += 3;
-            return get_token_from_map(TokenLabel::Ellipsis);
-        }
+pub fn read_dot_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
+    let next_code = get_code_from_idx(ctx, ctx.cursor + 1);
+    // 0-9
+    if (48..=57).contains(&next_code) {
+        return read_number_token(ctx, true, false);
+    }
+    if next_code == 46 && get_code_from_idx(ctx, ctx.cursor + 2) == 46 {
+        ctx.expect_ecma_version(6, ctx.cursor, "Rest/spread syntax")?;
+        ctx.cursor += 3;
+        return get_token_from_map(TokenLabel::Ellipsis);
     }
 
     ctx.cursor += 1;
@@ -1,22 +1,24 @@
 use super::js_token::TokenLabel;
-use super::util::is_new_line;
+use super::util::{
+    get_content_len, get_cur_code_from_ctx, is_identifier_char, is_new_line, move_to_next_char,
+};
 use super::{get_token_from_map, TokenResult};
+use crate::error::ParseErrorKind;
 use crate::parser::Parser;
-use regex::Regex;
+use crate::regexp::{validate_regexp_flags, validate_regexp_pattern};
 
-pub fn read_regex_token(ctx: &mut Parser) -> TokenResult {
+pub fn read_regex_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
     let mut escaped = false;
     let mut in_class = false;
-    let mut result = "/".to_string();
-    let mut iter = ctx.content.chars().skip(ctx.cursor);
+    let pattern_start = ctx.cursor;
     loop {
-        let n = iter.next().map(|c| c as usize);
-        if n.is_none() {
-            panic!("Unterminated regular expression")
-        }
-        let next = n.unwrap();
-        if is_new_line(next as u32) {
-            panic!("Unterminated regular expression")
+        let next = get_cur_code_from_ctx(ctx);
+        if ctx.cursor >= get_content_len(ctx) || is_new_line(next as u32) {
+            return Err(ctx.raise(
+                ctx.cur_token_start,
+                ParseErrorKind::Unterminated,
+                "Unterminated regular expression",
+            ));
         }
         if !escaped {
             match next {
@@ -30,8 +32,6 @@ pub fn read_regex_token(ctx: &mut Parser) -> TokenResult {
                 }
                 // '/'
                 n if n == 47 && !in_class => {
-                    ctx.cursor += 1;
-                    result.push('/');
                     break;
                 }
                 // '\'
@@ -40,29 +40,29 @@ pub fn read_regex_token(ctx: &mut Parser) -> TokenResult {
                 }
                 _ => {}
             }
-            if next != 92 {
-                result.push(char::from_u32(next as u32).unwrap());
-            }
         } else {
-            result.push(char::from_u32(next as u32).unwrap());
+            escaped = false;
         }
-        ctx.cursor += 1;
+        move_to_next_char(ctx);
     }
 
-    let re = Regex::new(r"[a-z]").unwrap();
-    for c in iter {
-        if re.is_match(&c.to_string()) {
-            ctx.cursor += 1;
-            result.push(c);
-        } else {
-            break;
-        }
+    let pattern = &ctx.content[pattern_start..ctx.cursor];
+    ctx.cursor += 1;
+    let flags_start = ctx.cursor;
+    while is_identifier_char(get_cur_code_from_ctx(ctx)) {
+        move_to_next_char(ctx);
     }
+    let flags = &ctx.content[flags_start..ctx.cursor];
 
-    // TODO: 忽略检验正则是否合法的检查，因为检查一个正则的处理过于复杂，所以暂时不处理
+    // 与 acorn 一致，错误的位置为 '/' 之后
+    let start = ctx.cur_token_start + 1;
+    if let Err(message) = validate_regexp_flags(flags, ctx.ecma_version) {
+        return Err(ctx.raise(start, ParseErrorKind::InvalidRegExp, message));
+    }
+    if let Err(message) = validate_regexp_pattern(pattern, flags, ctx.ecma_version) {
+        return Err(ctx.raise(start, ParseErrorKind::InvalidRegExp, &message));
+    }
 
-    get_token_from_map(TokenLabel::Regexp).map(|mut r| {
-        r.value = Some(result);
-        r
-    })
+    // token 的值为源码中的 `/pattern/flags`，在 finish_token 中设置
+    get_token_from_map(TokenLabel::Regexp)
 }
@@ -1,27 +1,33 @@
 use super::{
     get_cur_code_from_ctx, get_next_code_from_ctx,
-    util::{is_new_line, is_non_ascii_white_space, move_to_next_line},
+    util::{
+        has_break_in_range, is_new_line, is_non_ascii_white_space, move_to_next_char,
+        move_to_next_line,
+    },
 };
+use crate::ast::comment::{Comment, CommentKind};
+use crate::ast::{Position, SourceLocation};
+use crate::error::{ParseErrorKind, ParseResult};
 use crate::parser::Parser;
 
-pub fn skip_space_comment(ctx: &mut Parser) {
+pub fn skip_space_comment(ctx: &mut Parser) -> ParseResult<()> {
     loop {
         let code = get_cur_code_from_ctx(ctx);
         match code {
             // ' '
             32 | 160 => {
-                ctx.cursor += 1;
+                move_to_next_char(ctx);
             }
             13 => {
                 ctx.cursor += 1;
-                if get_next_code_from_ctx(ctx) == 10 {
+                if get_cur_code_from_ctx(ctx) == 10 {
                     ctx.cursor += 1;
                 }
                 move_to_next_line(ctx);
             }
             // '\r' '\n'
             10 | 8232 | 8233 => {
-                ctx.cursor += 1;
+                move_to_next_char(ctx);
                 move_to_next_line(ctx);
             }
             // '/'
@@ -29,92 +35,115 @@ pub fn skip_space_comment(ctx: &mut Parser) {
                 match get_next_code_from_ctx(ctx) {
                     // '*'
                     42 => {
-                        skip_block_comment(ctx);
+                        skip_block_comment(ctx)?;
                     }
                     // '/'
                     47 => {
-                        skip_line_comment(ctx);
+                        skip_line_comment(ctx, 2);
                     }
                     _ => break,
                 }
-                ctx.cursor += 1;
+            }
+            // '<!--', 只在非模块代码中作为单行注释
+            60 if !ctx.in_module() && next_codes_are(ctx, "!--") => {
+                skip_line_comment(ctx, 4);
+            }
+            // '-->', 只有位于行首时才是单行注释
+            45 if !ctx.in_module()
+                && next_codes_are(ctx, "->")
+                && (ctx.last_token_end == 0
+                    || has_break_in_range(ctx, (ctx.last_token_end, ctx.cursor))) =>
+            {
+                skip_line_comment(ctx, 3);
             }
             9 | 11 | 12 => {
                 ctx.cursor += 1;
             }
             c @ _ => {
                 if is_non_ascii_white_space(c) {
-                    ctx.cursor += 1;
+                    move_to_next_char(ctx);
                 } else {
                     break;
                 }
             }
         }
     }
-}
 
-fn skip_block_comment(ctx: &mut Parser) {
-    ctx.cursor += 2;
-    let mut pos = ctx.cursor;
-    let mut last_is_star = false;
-    let mut line_inc = 0;
-    let mut line_start = ctx.line_start;
+    Ok(())
+}
 
-    loop {
-        let cur_code = ctx.codes.get(pos);
-        if cur_code.is_none() {
-            break;
+fn skip_block_comment(ctx: &mut Parser) -> ParseResult<()> {
+    let start = ctx.cursor;
+    let start_loc = ctx.get_cursor_position();
+    let end = match ctx.content[start + 2..].find("*/") {
+        Some(len) => start + 2 + len,
+        None => {
+            return Err(ctx.raise(start, ParseErrorKind::Unterminated, "Unterminated comment"));
         }
-        let c = *cur_code.unwrap();
-        if c == 47 && last_is_star {
-            pos += 1;
-            break;
-        } else if c == 42 {
-            last_is_star = true;
-        } else if is_new_line(c) {
-            if c == 13 && ctx.codes.get(pos + 1).map_or(false, |next_c| *next_c == 10) {
-                pos += 1;
-            }
-            line_start = pos;
-            line_inc += 1;
+    };
+
+    // 注释中的 '\r\n' 只算一次换行
+    let mut chars = ctx.content[start + 2..end].char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if is_new_line(c as u32) && !(c == '\r' && chars.peek().map(|(_, c)| *c) == Some('\n')) {
+            ctx.line += 1;
+            ctx.line_start = start + 2 + i + c.len_utf8();
         }
-        pos += 1;
     }
 
-    ctx.cursor = pos;
-    ctx.line += line_inc;
-    ctx.line_start = line_start;
-}
+    ctx.cursor = end + 2;
+    record_comment(ctx, CommentKind::Block, (start + 2, end), start, start_loc);
 
-fn skip_line_comment(ctx: &mut Parser) {
-    ctx.cursor += 2;
-    let mut pos = ctx.cursor;
-    let mut incre_line = true;
-    loop {
-        let next_c_op = ctx.codes.get(pos);
-        if next_c_op.is_none() {
-            incre_line = false;
-            break;
-        }
-        let c = *next_c_op.unwrap();
-        if is_new_line(c) {
-            pos += 1;
-            // handle windows break line '\r\n'
-            if c == 13
-                && ctx
-                    .codes
-                    .get(pos)
-                    .map_or(false, |next_code| *next_code == 10)
-            {
-                pos += 1;
-            }
-            break;
-        }
-        pos += 1;
-    }
+    Ok(())
+}
 
+/// 跳过单行注释，start_skip 为注释开始标记的长度
+pub fn skip_line_comment(ctx: &mut Parser, start_skip: usize) {
+    let start = ctx.cursor;
+    let start_loc = ctx.get_cursor_position();
+    let value_start = start + start_skip;
+    let pos = ctx.content[value_start..]
+        .find(|c| is_new_line(c as u32))
+        .map_or(ctx.content.len(), |len| value_start + len);
     ctx.cursor = pos;
-    if incre_line {
-        move_to_next_line(ctx);
+    record_comment(ctx, CommentKind::Line, (value_start, pos), start, start_loc);
+
+    let c = get_cur_code_from_ctx(ctx);
+    if pos >= ctx.content.len() {
+        return;
+    }
+    move_to_next_char(ctx);
+    // handle windows break line '\r\n'
+    if c == 13 && get_cur_code_from_ctx(ctx) == 10 {
+        ctx.cursor += 1;
     }
+    move_to_next_line(ctx);
+}
+
+/// 记录一条结束于光标处的注释，value_range 为注释内容的字节范围
+fn record_comment(
+    ctx: &mut Parser,
+    kind: CommentKind,
+    value_range: (usize, usize),
+    start: usize,
+    start_loc: Position,
+) {
+    let comment = Comment {
+        kind,
+        value: ctx.content[value_range.0..value_range.1].into(),
+        start,
+        end: ctx.cursor,
+        loc: Some(SourceLocation {
+            source: ctx.source_file,
+            start: start_loc,
+            end: ctx.get_cursor_position(),
+        }),
+        range: None,
+    };
+    ctx.push_comment(comment);
+}
+
+// check if the characters after the cursor are the same as s
+fn next_codes_are(ctx: &Parser, s: &str) -> bool {
+    ctx.content[ctx.cursor + 1..].starts_with(s)
 }
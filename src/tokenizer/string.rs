@@ -1,40 +1,64 @@
 use super::js_token::TokenLabel;
 use super::number::{read_fixed_int, read_range_int};
-use super::util::{get_token_from_map, is_new_line, move_to_next_line};
+use super::util::{
+    get_code_from_idx, get_content_len, get_token_from_map, move_to_next_char, move_to_next_line,
+};
 use super::{get_cur_code_from_ctx, TokenResult};
+use crate::error::{ParseErrorKind, ParseResult};
 use crate::parser::Parser;
+use std::borrow::Cow;
 
-pub fn read_string_token(ctx: &mut Parser, code: usize) -> TokenResult {
-    let mut value = String::new();
-    loop {
+pub fn read_string_token<'a>(ctx: &mut Parser<'a>, code: usize) -> TokenResult<'a> {
+    // 只有包含转义字符时才需要复制，否则直接引用源码中的文本
+    let mut out: Option<String> = None;
+    let mut chunk_start = ctx.cursor;
+    let value = loop {
+        if ctx.cursor >= get_content_len(ctx) {
+            return Err(ctx.raise(
+                ctx.cur_token_start,
+                ParseErrorKind::Unterminated,
+                "Unterminated string constant",
+            ));
+        }
         let c = get_cur_code_from_ctx(ctx);
         match c {
             c if c == code => {
+                let chunk = &ctx.content[chunk_start..ctx.cursor];
                 ctx.cursor += 1;
-                break;
+                break match out {
+                    Some(mut out) => {
+                        out.push_str(chunk);
+                        Cow::Owned(out)
+                    }
+                    None => Cow::Borrowed(chunk),
+                };
             }
             // '\'
             92 => {
+                let out = out.get_or_insert_with(String::new);
+                out.push_str(&ctx.content[chunk_start..ctx.cursor]);
                 ctx.cursor += 1;
-                if let Some(e) = read_escape_char(ctx) {
-                    value.push(e);
+                if let Some(e) = read_escape_char(ctx, false)? {
+                    out.push(e);
                 }
+                chunk_start = ctx.cursor;
             }
-            0x2028 | 0x209 => {
-                ctx.cursor += 1;
+            0x2028 | 0x2029 => {
+                move_to_next_char(ctx);
                 move_to_next_line(ctx);
             }
             10 | 13 => {
-                panic!("Unterminated string constant");
+                return Err(ctx.raise(
+                    ctx.cur_token_start,
+                    ParseErrorKind::Unterminated,
+                    "Unterminated string constant",
+                ));
             }
             _ => {
-                ctx.cursor += 1;
-                unsafe {
-                    value.push(char::from_u32_unchecked(c as u32));
-                }
+                move_to_next_char(ctx);
             }
         }
-    }
+    };
 
     get_token_from_map(TokenLabel::String).map(|mut r| {
         r.value = Some(value);
@@ -43,20 +67,24 @@ pub fn read_string_token(ctx: &mut Parser, code: usize) -> TokenResult {
 }
 
 /// 当前的光标位置为 \ 的下个元素，从光标所在位置开始读取 escape char 的值
-pub fn read_escape_char(ctx: &mut Parser) -> Option<char> {
-    let mut iter = ctx.content.chars().skip(ctx.cursor);
-    let code: usize = iter
-        .next()
-        .map(|r| r as usize)
-        .expect("Unterminated string constant");
-    ctx.cursor += 1;
-    match code {
+/// 模板字符串中不允许八进制转义以及 `\8`、`\9`
+pub fn read_escape_char(ctx: &mut Parser, in_template: bool) -> ParseResult<Option<char>> {
+    if ctx.cursor >= get_content_len(ctx) {
+        return Err(ctx.raise(
+            ctx.cur_token_start,
+            ParseErrorKind::Unterminated,
+            "Unterminated string constant",
+        ));
+    }
+    let code = get_cur_code_from_ctx(ctx);
+    move_to_next_char(ctx);
+    let ch = match code {
         110 => Some('\n'),
         114 => Some('\r'),
         // 'x'，后面是两位十六进制的数
-        120 => Some(read_hex_char(ctx, 2)),
+        120 => Some(read_hex_char(ctx, 2)?),
         // 'u'
-        117 => Some(read_unicode_code_point_char(ctx)),
+        117 => Some(read_unicode_code_point_char(ctx)?),
         116 => Some('\t'),
         // '\b' -> unicode value 8
         98 => Some('\u{08}'),
@@ -73,61 +101,128 @@ pub fn read_escape_char(ctx: &mut Parser) -> Option<char> {
             None
         }
         // '\n'
-        10 => {
+        10 | 0x2028 | 0x2029 => {
             move_to_next_line(ctx);
             None
         }
         // 0-9
         n @ 48..=57 => {
             if n == 56 || n == 57 {
-                panic!("Invalid escape sequence");
+                if ctx.strict_mode || in_template {
+                    return Err(ctx.raise(
+                        ctx.cursor - 2,
+                        ParseErrorKind::InvalidEscape,
+                        if in_template {
+                            "Invalid escape sequence in template string"
+                        } else {
+                            "Invalid escape sequence"
+                        },
+                    ));
+                }
+                return Ok(char::from_u32(n as u32));
             }
             let mut oct_str = char::from_u32(n as u32).unwrap().to_string();
-            let mut times = 2;
+            // 以 0-3 开头的八进制转义最多三位，其余最多两位
+            let mut times = if n <= 51 { 2 } else { 1 };
             while times > 0 {
                 let code = get_cur_code_from_ctx(ctx);
-                if code >= 48 && code <= 55 {
+                if (48..=55).contains(&code) {
                     oct_str.push(char::from_u32(code as u32).unwrap());
+                    ctx.cursor += 1;
                 } else {
                     break;
                 }
                 times -= 1;
             }
-            ctx.cursor += oct_str.len() - 1;
 
-            let ch = usize::from_str_radix(&oct_str, 8).unwrap();
-            if ch == 56 || ch == 57 {
-                panic!("Invalid Octal literal")
-            }
-            if is_new_line(ch as u32) {
-                return None;
+            let ch = u32::from_str_radix(&oct_str, 8).unwrap();
+            let next = get_cur_code_from_ctx(ctx);
+            if (oct_str != "0" || next == 56 || next == 57) && (ctx.strict_mode || in_template) {
+                return Err(ctx.raise(
+                    ctx.cursor - oct_str.len() - 1,
+                    ParseErrorKind::InvalidEscape,
+                    if in_template {
+                        "Octal literal in template string"
+                    } else {
+                        "Octal literal in strict mode"
+                    },
+                ));
             }
 
-            Some(char::from_u32(ch as u32).unwrap())
+            char::from_u32(ch)
         }
-        _ => Some(char::from_u32(code as u32).unwrap()),
-    }
+        _ => char::from_u32(code as u32),
+    };
+
+    Ok(ch)
 }
 
 /// 读取长度为 len 的十六进制字符
-pub fn read_hex_char(ctx: &mut Parser, len: u8) -> char {
-    let code = read_fixed_int(ctx, 16, len as u32, false);
-    char::from_u32(code).expect("Not a valid Hex Value")
+pub fn read_hex_char(ctx: &mut Parser, len: u8) -> ParseResult<char> {
+    let start = ctx.cursor;
+    let code = read_fixed_int(ctx, 16, len as u32, false)?;
+    char::from_u32(code).ok_or_else(|| {
+        ctx.raise(
+            start,
+            ParseErrorKind::InvalidEscape,
+            "Bad character escape sequence",
+        )
+    })
 }
 
 /// 读取 \u{xxxxxx} 格式的字符
-pub fn read_unicode_code_point_char(ctx: &mut Parser) -> char {
+pub fn read_unicode_code_point_char(ctx: &mut Parser) -> ParseResult<char> {
     let code = get_cur_code_from_ctx(ctx);
     if code == 123 {
-        ctx.cursor += 1;
         // '{'
-        let v = read_range_int(ctx, 16, (1, 6), false);
-        if get_cur_code_from_ctx(ctx) != 123 {
-            panic!("Expected {{");
+        ctx.expect_ecma_version(6, ctx.cursor, "Unicode code point escape")?;
+        ctx.cursor += 1;
+        let start = ctx.cursor;
+        let v = read_range_int(ctx, 16, (1, 6), false)?;
+        // '}'
+        if get_cur_code_from_ctx(ctx) != 125 {
+            return Err(ctx.raise(
+                ctx.cursor,
+                ParseErrorKind::InvalidEscape,
+                "Bad character escape sequence",
+            ));
         }
         ctx.cursor += 1;
-        return char::from_u32(v).expect("Not a valid Hex Value");
+        if v > 0x10FFFF {
+            return Err(ctx.raise(
+                start,
+                ParseErrorKind::InvalidEscape,
+                "Code point out of bounds",
+            ));
+        }
+        // 单独的代理项无法用 char 表示，使用替换字符代替
+        return Ok(char::from_u32(v).unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+
+    let start = ctx.cursor;
+    let code = read_fixed_int(ctx, 16, 4, false)?;
+    // 处理 😀 这样的代理对
+    if (0xD800..=0xDBFF).contains(&code)
+        && get_cur_code_from_ctx(ctx) == 92
+        && get_code_from_idx(ctx, ctx.cursor + 1) == 117
+    {
+        let save = ctx.cursor;
+        ctx.cursor += 2;
+        if let Ok(low) = read_fixed_int(ctx, 16, 4, false) {
+            if (0xDC00..=0xDFFF).contains(&low) {
+                let c = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                return Ok(char::from_u32(c).unwrap());
+            }
+        }
+        ctx.cursor = save;
+    }
+    if char::from_u32(code).is_none() && !(0xD800..=0xDFFF).contains(&code) {
+        return Err(ctx.raise(
+            start,
+            ParseErrorKind::InvalidEscape,
+            "Bad character escape sequence",
+        ));
     }
 
-    read_hex_char(ctx, 4)
+    Ok(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
 }
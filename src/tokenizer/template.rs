@@ -1,72 +1,119 @@
 use super::{
     js_token::TokenLabel,
     string::read_escape_char,
-    util::{get_token_from_map, is_new_line, move_to_next_line, next_code_is},
+    util::{
+        get_content_len, get_cur_code_from_ctx, get_token_from_map, is_new_line, move_to_next_char,
+        move_to_next_line, next_code_is,
+    },
     TokenResult,
 };
+use crate::error::ParseErrorKind;
 use crate::parser::Parser;
+use std::borrow::Cow;
 
-pub fn read_template_token(ctx: &mut Parser) -> TokenResult {
-    let mut result = String::new();
+pub fn read_template_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
+    // 与字符串一样，只有包含转义字符或者 '\r\n' 时才需要复制
+    let mut out: Option<String> = None;
+    let mut chunk_start = ctx.cursor;
     let start_pos = ctx.cur_token_start;
     let cur_is_template = ctx.cur_token_test(|t| {
         t.label == TokenLabel::Template || t.label == TokenLabel::Invalidtemplate
     });
 
     loop {
-        let ch_op = ctx.codes.get(ctx.cursor);
-        if ch_op.is_none() {
-            panic!("Unterminated template");
+        if ctx.cursor >= get_content_len(ctx) {
+            return Err(ctx.raise(
+                start_pos,
+                ParseErrorKind::Unterminated,
+                "Unterminated template",
+            ));
         }
-        let ch = *ch_op.unwrap();
+        let ch = get_cur_code_from_ctx(ctx) as u32;
         let maybe_substitution = ctx.cursor == start_pos && cur_is_template;
-        // '`'
-        if ch == 96 {
+        // '`' or '${'
+        if ch == 96 || (ch == 36 && next_code_is(ctx, 123)) {
             if maybe_substitution {
-                ctx.cursor += 1;
-                return get_token_from_map(TokenLabel::BackQuote);
-            } else {
-                return get_token_from_map(TokenLabel::Template).map(|mut token| {
-                    token.value = Some(result);
-                    token
-                });
-            }
-        }
-        // '${'
-        else if ch == 36 && next_code_is(ctx, 123) {
-            if maybe_substitution {
-                ctx.cursor += 2;
-                return get_token_from_map(TokenLabel::DollarBraceL);
-            } else {
-                return get_token_from_map(TokenLabel::Template).map(|mut token| {
-                    token.value = Some(result);
-                    token
-                });
+                return if ch == 96 {
+                    ctx.cursor += 1;
+                    get_token_from_map(TokenLabel::BackQuote)
+                } else {
+                    ctx.cursor += 2;
+                    get_token_from_map(TokenLabel::DollarBraceL)
+                };
             }
+            let chunk = &ctx.content[chunk_start..ctx.cursor];
+            let value = match out {
+                Some(mut out) => {
+                    out.push_str(chunk);
+                    Cow::Owned(out)
+                }
+                None => Cow::Borrowed(chunk),
+            };
+            return get_token_from_map(TokenLabel::Template).map(|mut token| {
+                token.value = Some(value);
+                token
+            });
         }
         // '\'
         else if ch == 92 {
+            let buf = out.get_or_insert_with(String::new);
+            buf.push_str(&ctx.content[chunk_start..ctx.cursor]);
             ctx.cursor += 1;
-            if let Some(c) = read_escape_char(ctx) {
-                result.push(c);
+            match read_escape_char(ctx, true) {
+                Ok(Some(c)) => buf.push(c),
+                Ok(None) => {}
+                // es9 起带标签的模板允许非法转义，此时 cooked 为 null，由 parser 决定是否报错
+                Err(e) if e.kind == ParseErrorKind::InvalidEscape && ctx.ecma_version >= 9 => {
+                    return read_invalid_template_token(ctx);
+                }
+                Err(e) => return Err(e),
             }
-        } else if is_new_line(ch as u32) {
-            let v = if ch == 13
-                && ctx
-                    .codes
-                    .get(ctx.cursor + 1)
-                    .map_or(false, |next_c| *next_c == 10)
-            {
-                ctx.cursor += 1;
-                '\n'
+            chunk_start = ctx.cursor;
+        } else if is_new_line(ch) {
+            if ch == 13 && next_code_is(ctx, 10) {
+                let buf = out.get_or_insert_with(String::new);
+                buf.push_str(&ctx.content[chunk_start..ctx.cursor]);
+                buf.push('\n');
+                ctx.cursor += 2;
+                chunk_start = ctx.cursor;
             } else {
-                char::from_u32(ch as u32).unwrap()
-            };
-            result.push(v);
+                move_to_next_char(ctx);
+            }
             move_to_next_line(ctx);
         } else {
-            result.push(char::from_u32(ch as u32).unwrap());
-            ctx.cursor += 1;
+            move_to_next_char(ctx);
         }
     }
 }
+
+/// 跳过含有非法转义的模板片段，直到遇到 '`' 或者 '${'
+fn read_invalid_template_token<'a>(ctx: &mut Parser<'a>) -> TokenResult<'a> {
+    while ctx.cursor < get_content_len(ctx) {
+        match get_cur_code_from_ctx(ctx) as u32 {
+            // '\'
+            92 => ctx.cursor += 1,
+            // '`' or '${'
+            96 => break,
+            36 if next_code_is(ctx, 123) => break,
+            c if is_new_line(c) => {
+                if c == 13 && next_code_is(ctx, 10) {
+                    ctx.cursor += 1;
+                }
+                move_to_next_char(ctx);
+                move_to_next_line(ctx);
+                continue;
+            }
+            _ => {}
+        }
+        move_to_next_char(ctx);
+    }
+    if ctx.cursor >= get_content_len(ctx) {
+        return Err(ctx.raise(
+            ctx.cur_token_start,
+            ParseErrorKind::Unterminated,
+            "Unterminated template",
+        ));
+    }
+
+    get_token_from_map(TokenLabel::Invalidtemplate)
+}
@@ -1,5 +1,5 @@
 use super::js_token::{KeywordList, TokenLabel, TokenMap};
-use super::{TokenError, TokenResult};
+use super::TokenResult;
 use crate::parser::Parser;
 use std::usize;
 
@@ -717,71 +717,64 @@ pub fn move_to_next_line(ctx: &mut Parser) {
     ctx.line_start = ctx.cursor;
 }
 
+/// 将光标移动到下一个字符，非 ASCII 字符占用多个字节
+pub fn move_to_next_char(ctx: &mut Parser) {
+    ctx.cursor += char_len_at(ctx, ctx.cursor);
+}
+
+/// idx 处字符的 UTF-8 编码长度，位于结尾时为 0
+pub fn char_len_at(ctx: &Parser, idx: usize) -> usize {
+    match ctx.content.as_bytes().get(idx) {
+        Some(b) if *b < 0x80 => 1,
+        Some(_) => ctx
+            .content
+            .get(idx..)
+            .and_then(|s| s.chars().next())
+            .map_or(1, char::len_utf8),
+        None => 0,
+    }
+}
+
 pub fn has_break_in_range(ctx: &Parser, range: (usize, usize)) -> bool {
     let (start, end) = range;
     if start > end {
-        panic!("range start have to be smaller than end")
+        return false;
     }
 
-    let mut iter = ctx.content.chars().skip(start);
-    for _ in 0..=end - start {
-        if let Some(c) = iter.next() {
-            if is_new_line(c as u32) {
-                return true;
-            }
-        }
-    }
-
-    false
-}
-
-// TODO: 优化性能，每次都用迭代器的计算开销较大
-// 寻找是否可以循环利用一个迭代器的方法
-#[allow(dead_code)]
-fn is_last_char(ctx: &Parser) -> bool {
-    ctx.content.chars().count() == ctx.cursor - 1
+    ctx.content
+        .get(start..end.min(get_content_len(ctx)))
+        .map_or(false, |s| s.chars().any(|c| is_new_line(c as u32)))
+        || is_new_line(get_code_from_idx(ctx, end) as u32)
 }
 
-pub fn get_token_from_map(k: TokenLabel) -> TokenResult {
-    TokenMap
-        .get(&k)
-        .ok_or(TokenError::TokenNotFoundError.into())
-        .map(|r| r.clone())
+pub fn get_token_from_map<'a>(k: TokenLabel) -> TokenResult<'a> {
+    Ok(TokenMap[&k].clone())
 }
 
-pub fn get_next_code_from_iter<T>(iter: &mut T) -> usize
-where
-    T: Iterator<Item = char>,
-{
-    iter.next().map(|r| r as usize).unwrap_or(0)
+/// 当前字符之后的下一个字符
+pub fn get_next_code_from_ctx(ctx: &Parser) -> usize {
+    get_code_from_idx(ctx, ctx.cursor + char_len_at(ctx, ctx.cursor))
 }
 
-pub fn get_next_code_from_ctx(ctx: &mut Parser) -> usize {
-    ctx.content
-        .chars()
-        .skip(ctx.cursor + 1)
-        .next()
-        .map_or(0, |r| r as usize)
-}
-
-pub fn get_cur_code_from_ctx(ctx: &mut Parser) -> usize {
-    ctx.content
-        .chars()
-        .skip(ctx.cursor)
-        .next()
-        .map_or(0, |r| r as usize)
+pub fn get_cur_code_from_ctx(ctx: &Parser) -> usize {
+    get_code_from_idx(ctx, ctx.cursor)
 }
 
+/// 读取字节位置 idx 处的字符，超出范围或者 idx 不在字符边界上时返回 0
 pub fn get_code_from_idx(ctx: &Parser, idx: usize) -> usize {
-    ctx.content
-        .chars()
-        .skip(idx)
-        .next()
-        .map_or(0, |c| c as usize)
+    match ctx.content.as_bytes().get(idx) {
+        Some(b) if *b < 0x80 => *b as usize,
+        Some(_) => ctx
+            .content
+            .get(idx..)
+            .and_then(|s| s.chars().next())
+            .map_or(0, |c| c as usize),
+        None => 0,
+    }
 }
 
 pub fn get_content_len(ctx: &Parser) -> usize {
-    ctx.content.chars().count()
+    ctx.content.len()
 }
 
 pub fn is_keyword_token(t: &str) -> Option<&TokenLabel> {
@@ -795,5 +788,84 @@ pub fn is_keyword_token(t: &str) -> Option<&TokenLabel> {
 }
 
 pub fn next_code_is(ctx: &Parser, code: u32) -> bool {
-    ctx.codes.get(ctx.cursor + 1).map_or(false, |c| *c == code)
+    get_next_code_from_ctx(ctx) == code as usize
+}
+
+/// 根据当前的 ecma 版本判断 t 是否是关键字，在 es6 之前 class、const 等还只是保留字
+pub fn get_keyword(ctx: &Parser, t: &str) -> Option<TokenLabel> {
+    is_keyword_token(t).copied().filter(|k| {
+        ctx.ecma_version >= 6
+            || !matches!(
+                k,
+                TokenLabel::_Const
+                    | TokenLabel::_Class
+                    | TokenLabel::_Extends
+                    | TokenLabel::_Export
+                    | TokenLabel::_Import
+                    | TokenLabel::_Super
+            )
+    })
+}
+
+const RESERVED_WORDS_3: [&str; 29] = [
+    "abstract",
+    "boolean",
+    "byte",
+    "char",
+    "class",
+    "double",
+    "enum",
+    "export",
+    "extends",
+    "final",
+    "float",
+    "goto",
+    "implements",
+    "import",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "short",
+    "static",
+    "super",
+    "synchronized",
+    "throws",
+    "transient",
+    "volatile",
+];
+const RESERVED_WORDS_5: [&str; 7] = [
+    "class", "enum", "extends", "super", "const", "export", "import",
+];
+const RESERVED_WORDS_STRICT: [&str; 9] = [
+    "implements",
+    "interface",
+    "let",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "static",
+    "yield",
+];
+
+/// 判断 t 在当前的 ecma 版本以及严格模式下是否是不能作为标识符的保留字
+pub fn is_reserved_word(ctx: &Parser, t: &str) -> bool {
+    if !ctx.options.allows_reserved() {
+        let reserved = match ctx.ecma_version {
+            v if v >= 6 => t == "enum",
+            5 => RESERVED_WORDS_5.contains(&t),
+            _ => RESERVED_WORDS_3.contains(&t),
+        };
+        // await 在模块中也是保留字
+        if reserved || ctx.in_module() && t == "await" {
+            return true;
+        }
+    }
+
+    ctx.strict_mode && RESERVED_WORDS_STRICT.contains(&t)
 }
@@ -0,0 +1,387 @@
+use std::borrow::Cow;
+
+use serde_json::Value;
+use snail::{
+    ast::{expression::Identifier, visit_mut::VisitMut},
+    codegen::{self, FormatOptions, SourceMapOptions},
+    Bump, ParserOptions, PositionEncoding, SourceType,
+};
+
+fn options(source_type: SourceType) -> ParserOptions {
+    ParserOptions {
+        source_type,
+        ..Default::default()
+    }
+}
+
+// 打印出来的代码中节点的位置和注释都会变化，比较时只看树的结构
+fn strip_positions(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for key in ["start", "end", "loc", "range", "comments"] {
+                map.remove(key);
+            }
+            map.values_mut().for_each(strip_positions);
+        }
+        Value::Array(list) => list.iter_mut().for_each(strip_positions),
+        _ => {}
+    }
+}
+
+fn to_tree(arena: &Bump, source: &str, source_type: SourceType) -> Value {
+    let program = snail::parse(arena, source, options(source_type))
+        .unwrap_or_else(|err| panic!("failed to parse: {}\n{}", err, source));
+    let mut tree = serde_json::to_value(&program).unwrap();
+    strip_positions(&mut tree);
+    tree
+}
+
+fn print(source: &str, source_type: SourceType) -> String {
+    let arena = Bump::new();
+    let program = snail::parse(&arena, source, options(source_type)).unwrap();
+    codegen::print(&program)
+}
+
+fn assert_round_trip(source: &str, source_type: SourceType) {
+    let arena = Bump::new();
+    let printed = print(source, source_type);
+    assert_eq!(
+        to_tree(&arena, source, source_type),
+        to_tree(&arena, &printed, source_type),
+        "\n{}",
+        printed
+    );
+    // 打印的结果再打印一次应该保持不变
+    assert_eq!(print(&printed, source_type), printed);
+}
+
+#[test]
+fn round_trip_script() {
+    assert_round_trip(
+        include_str!("fixtures/codegen/script.js"),
+        SourceType::Script,
+    );
+}
+
+#[test]
+fn round_trip_module() {
+    assert_round_trip(
+        include_str!("fixtures/codegen/module.js"),
+        SourceType::Module,
+    );
+}
+
+#[test]
+fn parenthesizes_by_precedence() {
+    for (source, expected) in [
+        ("(a + b) * c", "(a + b) * c;\n"),
+        ("a + (b * c)", "a + b * c;\n"),
+        ("a - (b - c)", "a - (b - c);\n"),
+        ("(a ** b) ** c", "(a ** b) ** c;\n"),
+        ("(-a) ** b", "(-a) ** b;\n"),
+        ("a ?? (b || c)", "a ?? (b || c);\n"),
+        ("(a, b) ? c : d", "(a, b) ? c : d;\n"),
+        ("(a?.b).c", "(a?.b).c;\n"),
+        ("(function () {})()", "(function() {})();\n"),
+        ("({}).toString()", "({}).toString();\n"),
+        ("x = () => ({})", "x = () => ({});\n"),
+        ("a - (-b)", "a - -b;\n"),
+        ("(1).toString()", "(1).toString();\n"),
+    ] {
+        assert_eq!(print(source, SourceType::Script), expected, "{}", source);
+    }
+}
+
+#[test]
+fn source_map_records_renamed_identifiers() {
+    struct Rename;
+
+    impl<'a> VisitMut<'a> for Rename {
+        fn visit_identifier(&mut self, node: &mut Identifier<'a>) {
+            if node.name == "count" {
+                node.name = Cow::Borrowed("n");
+            }
+        }
+    }
+
+    // 列号按 UTF-16 计算，`é` 占一个单位，`😀` 占两个
+    let source = "let count = '😀';\nif (count) log(count,   'é', count);";
+    let arena = Bump::new();
+    let mut program = snail::parse(
+        &arena,
+        source,
+        ParserOptions {
+            source_file: Some("input.js".to_string()),
+            position_encoding: PositionEncoding::Char,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    Rename.visit_program(&mut program);
+    let (code, source_map) = codegen::print_with_source_map(
+        &program,
+        source,
+        SourceMapOptions {
+            file: Some("output.js".to_string()),
+            position_encoding: PositionEncoding::Char,
+            ..Default::default()
+        },
+    );
+    assert_eq!(code, "let n = '😀';\nif (n) log(n, 'é', n);\n");
+    assert_eq!(
+        source_map.to_json(),
+        r#"{"version":3,"file":"output.js","sources":["input.js"],"names":["count"],"mappings":"AAAA,IAAIA,IAAQ;AACZ,IAAIA,GAAO,IAAIA,GAAS,KAAKA"}"#
+    );
+}
+
+fn format(source: &str, source_type: SourceType, format_options: &FormatOptions) -> String {
+    let arena = Bump::new();
+    let program = snail::parse(&arena, source, options(source_type)).unwrap();
+    codegen::format(&program, format_options)
+}
+
+// 格式化会改变字符串的引号，不打印分号时语句开头加上的 `;` 会被解析为空语句
+fn strip_formatting(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.remove("raw");
+            map.remove("directive");
+            map.values_mut().for_each(strip_formatting);
+        }
+        Value::Array(list) => {
+            list.retain(|item| item["type"] != "EmptyStatement");
+            list.iter_mut().for_each(strip_formatting);
+        }
+        _ => {}
+    }
+}
+
+fn assert_format_round_trip(source: &str, source_type: SourceType, format_options: &FormatOptions) {
+    let arena = Bump::new();
+    let formatted = format(source, source_type, format_options);
+    let mut expected = to_tree(&arena, source, source_type);
+    let mut actual = to_tree(&arena, &formatted, source_type);
+    strip_formatting(&mut expected);
+    strip_formatting(&mut actual);
+    assert_eq!(expected, actual, "\n{}", formatted);
+    let count_comments = |source: &str| {
+        let arena = Bump::new();
+        let program = snail::parse(&arena, source, options(source_type)).unwrap();
+        program.comments.len()
+    };
+    assert_eq!(
+        count_comments(source),
+        count_comments(&formatted),
+        "\n{}",
+        formatted
+    );
+    // 格式化的结果再格式化一次应该保持不变
+    assert_eq!(format(&formatted, source_type, format_options), formatted);
+}
+
+#[test]
+fn format_round_trip() {
+    let no_semi = FormatOptions {
+        semicolons: false,
+        single_quote: true,
+        ..Default::default()
+    };
+    let narrow = FormatOptions {
+        print_width: 40,
+        indent_width: 4,
+        ..Default::default()
+    };
+    for format_options in [FormatOptions::default(), no_semi, narrow] {
+        assert_format_round_trip(
+            include_str!("fixtures/codegen/script.js"),
+            SourceType::Script,
+            &format_options,
+        );
+        assert_format_round_trip(
+            include_str!("fixtures/codegen/module.js"),
+            SourceType::Module,
+            &format_options,
+        );
+    }
+}
+
+#[test]
+fn format_breaks_long_lines() {
+    let source = "const result = someFunctionWithALongName(argumentNumberOne, argumentNumberTwo, argumentNumberThree)\n\
+        promise.then(res => res.json()).then(data => console.log(data)).catch(err => handle(err))\n\
+        it('works', () => { expect(value).toBe(true) })\n";
+    assert_eq!(
+        format(source, SourceType::Script, &FormatOptions::default()),
+        r#"const result = someFunctionWithALongName(
+  argumentNumberOne,
+  argumentNumberTwo,
+  argumentNumberThree,
+);
+promise
+  .then((res) => res.json())
+  .then((data) => console.log(data))
+  .catch((err) => handle(err));
+it("works", () => {
+  expect(value).toBe(true);
+});
+"#
+    );
+}
+
+#[test]
+fn format_quotes_and_semicolons() {
+    let source = "'use strict'\nlet a = 'a', b = \"it's\", c = 'say \\\"hi\\\"'\nlet x = a\n;(function () {})()\n;[1, 2].forEach(n => n)\n";
+    assert_eq!(
+        format(source, SourceType::Script, &FormatOptions::default()),
+        r#""use strict";
+let a = "a",
+  b = "it's",
+  c = 'say "hi"';
+let x = a;
+(function () {})();
+[1, 2].forEach((n) => n);
+"#
+    );
+    let options = FormatOptions {
+        semicolons: false,
+        single_quote: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        format(source, SourceType::Script, &options),
+        r#"'use strict'
+let a = 'a',
+  b = "it's",
+  c = 'say "hi"'
+let x = a
+;(function () {})()
+;[1, 2].forEach((n) => n)
+"#
+    );
+}
+
+#[test]
+fn format_keeps_semicolons_after_modifier_named_fields() {
+    let options = FormatOptions {
+        semicolons: false,
+        ..Default::default()
+    };
+    // 去掉分号后 `async` 会和下一个方法连在一起，变成 async 方法
+    assert_eq!(
+        format("class K { async; m() {} }", SourceType::Script, &options),
+        "class K {\n  async;\n  m() {}\n}\n"
+    );
+    for source in [
+        "class K { async; m() {} }",
+        "class K { static; async; \"constructor\"() {} get; set; x() {} }",
+        "class K { async; *g() {} a = b; *c() {} }",
+    ] {
+        assert_format_round_trip(source, SourceType::Script, &options);
+    }
+}
+
+#[test]
+fn format_keeps_comments() {
+    let source = "// leading
+function greet (who) { // says hi
+  return `hi ${who}` /* trailing */
+}
+
+
+/**
+   * Doc comment.
+   */
+const list = [
+  1, // one
+  2,
+  // end
+]
+f(a, b) // after the call
+";
+    assert_eq!(
+        format(source, SourceType::Script, &FormatOptions::default()),
+        r#"// leading
+function greet(who) {
+  // says hi
+  return `hi ${who}`; /* trailing */
+}
+
+/**
+ * Doc comment.
+ */
+const list = [
+  1, // one
+  2,
+  // end
+];
+f(a, b); // after the call
+"#
+    );
+}
+
+#[test]
+fn format_keeps_comments_between_items_stable() {
+    for source in [
+        "body.on('error', /* ignore */\n  err => emit('error', err))\n",
+        "function f(uri, path,\n  /* ignore */ opts = {}) {}\n",
+        "a = 1\n\n;(function () {})()\n",
+        "x()\n\n/**\n * Doc.\n */;\ny()\n",
+    ] {
+        for format_options in [
+            FormatOptions::default(),
+            FormatOptions {
+                semicolons: false,
+                ..Default::default()
+            },
+        ] {
+            let formatted = format(source, SourceType::Script, &format_options);
+            assert_eq!(
+                format(&formatted, SourceType::Script, &format_options),
+                formatted,
+                "{}",
+                source
+            );
+            assert_format_round_trip(source, SourceType::Script, &format_options);
+        }
+    }
+}
+
+#[test]
+fn format_keeps_comments_in_place() {
+    let source = "if (a) {
+  b()
+} // after b
+else {
+  c()
+}
+if (d) e(); /* after e */ else f()
+if (g) {}
+// before else
+else {}
+function h(/* no params */) {}
+i(/* no */ /* args */)
+";
+    let formatted = format(source, SourceType::Script, &FormatOptions::default());
+    assert_eq!(
+        formatted,
+        r#"if (a) {
+  b();
+} // after b
+else {
+  c();
+}
+if (d) e(); /* after e */
+else f();
+if (g) {}
+// before else
+else {}
+function h(/* no params */) {}
+i(/* no */ /* args */);
+"#
+    );
+    assert_eq!(
+        format(&formatted, SourceType::Script, &FormatOptions::default()),
+        formatted
+    );
+    assert_format_round_trip(source, SourceType::Script, &FormatOptions::default());
+}
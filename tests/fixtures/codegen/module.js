@@ -0,0 +1,16 @@
+import "side-effect";
+import def from "a";
+import * as ns from "b";
+import defb, { x as y, z, "string name" as w } from "c";
+import defc, * as nsb from "d";
+export { y as yy, z };
+export { default as other, "a b" as "c d" } from "e";
+export * from "f";
+export * as nsf from "g";
+export * as "string ns" from "h";
+export var v = 1, u;
+export function fn() {}
+export class K {}
+export default (function () {});
+const top = await import("./x.js");
+console.log(import.meta.url);
@@ -0,0 +1,92 @@
+#!/usr/bin/env node
+("not a directive");
+(let)[0] = 1;
+for ((let) in y) {}
+var a = 1, b = (c, d), e;
+let { f, g: h, i = 1, ...j } = k;
+const [l, , m = 2, ...n] = o;
+({ p } = q);
+[r, s] = [s, r];
+x = a + b * c - (d - e) - (f + g);
+x = (a + b) * c ** d ** e;
+x = (a ** b) ** c;
+x = (-a) ** b;
+x = a ?? (b || c);
+x = (a && b) ?? c;
+x = a || b && c;
+x = (a || b) && c;
+x = a ? b : c ? d : e;
+x = (a ? b : c) ? d : e;
+x = a = b = c;
+x = typeof typeof a + void 0 + !b + -(-c) + - --d + +(+e) + a++ + --b;
+x = new a.b.c();
+x = new (a, b)();
+x = (a?.b).c;
+x = a?.b.c?.(d)?.[e];
+x = (a?.b)();
+x = 1..toString() + 1.5.toFixed() + 0x10.toString() + (1).x;
+x = `a${b}c${`d${e}`}`;
+x = tag`a${b}`;
+x = (() => {}).call();
+x = () => ({});
+x = () => ({}).x;
+x = async (a, ...b) => a;
+x = async function* () { yield; yield* a; yield b, c; await d; };
+x = function () {}();
+(function () {})();
+(class {});
+(async function () {});
+({}).toString();
+x = { a, b: 1, [c]: 2, "d": 3, 1: 4, e() {}, get f() { return 1; }, set f(v) {}, async *g() {}, ...h, __proto__: null };
+x = /re/g.test(a) / 2;
+x = a in b;
+for (;;) break;
+for (const [k, v] of m) continue;
+for (x in y) {}
+for ((async) of y) {}
+label: for (;;) { break label; }
+if (a) b; else if (c) d; else { e; }
+if (a) { if (b) c; } else d;
+while (a) b();
+do a(); while (b);
+do {} while (b);
+switch (a) { case 1: b; break; default: c; }
+try { a(); } catch { b(); } finally { c(); }
+try { a(); } catch ({ message }) {}
+throw new Error("x");
+with (a) b;
+debugger;
+;
+function f(a, b = 1, { c }, [d], ...e) { "use asm"; 'use strict'; return a; }
+async function af() { for await (const x of y) {} }
+function* gen() {}
+class A extends (B, C) {
+  #x = 1;
+  static y;
+  static #z() {}
+  constructor() { super(); super.x(); }
+  get [a]() {}
+  static async *m() {}
+  static { this.x = 1; }
+  has(o) { return #x in o; }
+  'quoted'() {}
+  static = 1;
+  get;
+  set;
+  async;
+}
+class D extends E.F {}
+x = class extends (a ? b : c) {};
+x = a, b;
+x = (a, b);
+x = -1 - -1;
+x = a - -b;
+x = a + +b;
+x = !(a && b);
+x = `\n${a}ሴ`;
+x = "é中文😀";
+x = a.b.c[d].e;
+x = (a, b).c;
+x = (a = b).c;
+x = (a ? b : c).d;
+x = (function () {}).name;
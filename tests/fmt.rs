@@ -0,0 +1,36 @@
+use std::{env, fs, process::Command};
+
+fn snail_fmt(name: &str, source: &str, args: &[&str]) -> String {
+    let path = env::temp_dir().join(format!("snail-fmt-{}-{}.js", std::process::id(), name));
+    fs::write(&path, source).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_snail"))
+        .arg("fmt")
+        .arg(&path)
+        .args(args)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn formats_scripts_by_default() {
+    // 按照模块解析的话会变成 `await 1`
+    let source = "var r = await(1);\n";
+    assert_eq!(snail_fmt("script", source, &[]), source);
+    assert_eq!(snail_fmt("explicit-script", source, &["--script"]), source);
+}
+
+#[test]
+fn formats_modules_with_flag() {
+    let source = "import a from \"a\";\nexport default await(a);\n";
+    assert_eq!(
+        snail_fmt("module", source, &["--module"]),
+        "import a from \"a\";\nexport default await a;\n"
+    );
+}
@@ -0,0 +1,99 @@
+use snail::{
+    ast::{
+        fold::{fold_program, Fold},
+        statement::{FunctionBodyContent, Statement},
+        Box, ProgramNode, Vec,
+    },
+    codegen, Bump,
+};
+
+// 删除空语句和 debugger，并把语句块展开到所在的语句列表中
+struct Flatten<'a> {
+    arena: &'a Bump,
+}
+
+impl<'a> Flatten<'a> {
+    fn splice(&self, statement: Statement<'a>, out: &mut impl FnMut(Statement<'a>)) {
+        match statement {
+            Statement::EmptyStatement(_) | Statement::DebuggerStatement(_) => {}
+            Statement::BlockStatement(block) => block.body.into_iter().for_each(out),
+            statement => out(statement),
+        }
+    }
+}
+
+impl<'a> Fold<'a> for Flatten<'a> {
+    fn fold_statements(&mut self, node: Vec<'a, Statement<'a>>) -> Vec<'a, Statement<'a>> {
+        let mut body = Vec::new_in(self.arena);
+        for statement in node {
+            self.splice(statement, &mut |statement| body.push(statement));
+        }
+        body
+    }
+
+    fn fold_function_body_contents(
+        &mut self,
+        node: Vec<'a, FunctionBodyContent<'a>>,
+    ) -> Vec<'a, FunctionBodyContent<'a>> {
+        let mut body = Vec::new_in(self.arena);
+        for content in node {
+            match content {
+                FunctionBodyContent::Statement(statement) => {
+                    self.splice(Box::into_inner(statement), &mut |statement| {
+                        body.push(FunctionBodyContent::Statement(Box::new_in(
+                            statement, self.arena,
+                        )))
+                    })
+                }
+                directive => body.push(directive),
+            }
+        }
+        body
+    }
+
+    fn fold_program_nodes(&mut self, node: Vec<'a, ProgramNode<'a>>) -> Vec<'a, ProgramNode<'a>> {
+        let mut body = Vec::new_in(self.arena);
+        for item in node {
+            match item {
+                ProgramNode::Statement(statement) => {
+                    self.splice(statement, &mut |statement| body.push(statement.into()))
+                }
+                item => body.push(item),
+            }
+        }
+        body
+    }
+}
+
+fn flatten(source: &str) -> String {
+    let arena = Bump::new();
+    let program = snail::parse(&arena, source, Default::default()).unwrap();
+    let program = fold_program(&mut Flatten { arena: &arena }, program);
+    codegen::print(&program)
+}
+
+#[test]
+fn fold_splices_statement_lists() {
+    // 内层的语句块先被展开，外层的语句块展开时已经包含了内层的语句
+    assert_eq!(
+        flatten("'use strict'; debugger; { a(); { b(); }; } ;"),
+        "'use strict';\na();\nb();\n"
+    );
+    assert_eq!(
+        flatten("function f() { 'use strict'; debugger; { c(); } return; }"),
+        "function f() {\n  'use strict';\n  c();\n  return;\n}\n"
+    );
+    assert_eq!(
+        flatten("switch (x) { case 1: ; { d(); } default: { } }"),
+        "switch (x) {\n  case 1:\n    d();\n  default:\n}\n"
+    );
+    assert_eq!(
+        flatten("class A { static { debugger; { e(); f(); } } }"),
+        "class A {\n  static {\n    e();\n    f();\n  }\n}\n"
+    );
+    // 单独的语句不在语句列表中，不会被展开
+    assert_eq!(
+        flatten("if (a) { { b(); } } else debugger;"),
+        "if (a) {\n  b();\n} else debugger;\n"
+    );
+}
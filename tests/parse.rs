@@ -0,0 +1,568 @@
+use serde_json::Value;
+use snail::{
+    ast::comment::{Comment, CommentKind},
+    error::{EarlyErrorCode, ParseError, ParseErrorKind, Severity},
+    Bump, OnComment, ParserOptions, SourceType,
+};
+use std::{cell::RefCell, rc::Rc};
+
+fn options(source_type: SourceType) -> ParserOptions {
+    ParserOptions {
+        source_type,
+        ..Default::default()
+    }
+}
+
+fn parse_error(source: &str, source_type: SourceType) -> ParseError {
+    let arena = Bump::new();
+    let result = snail::parse(&arena, source, options(source_type)).map(|_| ());
+    result.expect_err(source)
+}
+
+fn assert_parses(source: &str, source_type: SourceType) {
+    let arena = Bump::new();
+    let result = snail::parse(&arena, source, options(source_type)).map(|_| ());
+    result.unwrap_or_else(|err| panic!("failed to parse: {}\n{}", err, source));
+}
+
+// 语句的类型，包含的语句放在方括号中，比如 `FunctionDeclaration[ReturnStatement]`，
+// 函数体直接展开
+fn outline(node: &Value) -> String {
+    let mut children = vec![];
+    for key in ["body", "consequent", "alternate"] {
+        match &node.get(key) {
+            Some(Value::Array(body)) => children.extend(body.iter().map(outline)),
+            Some(child @ Value::Object(_)) => match &child["body"] {
+                Value::Array(body) if key == "body" => children.extend(body.iter().map(outline)),
+                _ => children.push(outline(child)),
+            },
+            _ => {}
+        }
+    }
+    let kind = node["type"].as_str().unwrap().to_string();
+    match children.is_empty() {
+        true => kind,
+        false => format!("{}[{}]", kind, children.join(" ")),
+    }
+}
+
+// 容错解析，返回语句的结构以及记录的错误
+fn recover(source: &str) -> (String, Vec<String>) {
+    let arena = Bump::new();
+    let options = ParserOptions {
+        error_recovery: true,
+        ..Default::default()
+    };
+    let output = snail::parse_with_diagnostics(&arena, source, options).unwrap();
+    let program = serde_json::to_value(&output.program).unwrap();
+    let errors = output
+        .diagnostics
+        .iter()
+        .map(|err| err.to_string())
+        .collect();
+    (outline(&program), errors)
+}
+
+#[test]
+fn function_context_reaches_nested_blocks() {
+    for source in [
+        "function f() { if (a) { return 1; } }",
+        "function* g() { { yield 1; } }",
+        "async function h() { try { await x; } catch {} }",
+        "function f() { { var yield; } }",
+    ] {
+        assert_parses(source, SourceType::Script);
+    }
+    parse_error("{ return; }", SourceType::Script);
+}
+
+#[test]
+fn class_checks_reach_nested_blocks() {
+    for source in [
+        "class A { static { { arguments } } }",
+        "class A { static { if (a) { var await } } }",
+        "class A { x = () => arguments }",
+        "{ class A { x = arguments } }",
+        "function f() { { class A { x = () => { arguments } } } }",
+    ] {
+        let err = parse_error(source, SourceType::Script);
+        assert_eq!(
+            err.code(),
+            Some(EarlyErrorCode::InvalidIdentifier),
+            "{}",
+            source
+        );
+    }
+    for source in [
+        "class A { static { function f() { arguments; var await } } }",
+        "class A { x = function () { arguments } }",
+        "class A { x = 1; m() { { arguments } } }",
+    ] {
+        assert_parses(source, SourceType::Script);
+    }
+}
+
+#[test]
+fn regexp_after_leading_block() {
+    for source in ["{}\n/foo/.test(a)", "{} /re/g", "{ a }\n/b/g"] {
+        assert_parses(source, SourceType::Script);
+    }
+}
+
+#[test]
+fn early_errors_by_severity() {
+    let source = "class A { #a; #a; m() { a ?? b || c; } }\n\
+        switch (x) { default: default: }\n\
+        foo: while (x) break bar;\n";
+    let arena = Bump::new();
+    let warnings = ParserOptions {
+        early_errors: Severity::Warning,
+        ..Default::default()
+    };
+    let output = snail::parse_with_diagnostics(&arena, source, warnings).unwrap();
+    let diagnostics: Vec<_> = output
+        .diagnostics
+        .iter()
+        .map(|err| {
+            assert_eq!(err.severity, Severity::Warning);
+            (err.code().unwrap().as_str(), err.loc.line)
+        })
+        .collect();
+    assert_eq!(
+        diagnostics,
+        [
+            ("duplicate-private-name", 1),
+            ("mixed-coalesce", 1),
+            ("duplicate-default-clause", 2),
+            ("invalid-jump-target", 3),
+        ]
+    );
+    assert_eq!(output.program.body.len(), 3);
+
+    // 默认的 Error 在第一个早期错误处停止解析
+    let err = snail::parse_with_diagnostics(&arena, source, Default::default())
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(err.severity, Severity::Error);
+    assert_eq!(err.code(), Some(EarlyErrorCode::DuplicatePrivateName));
+    assert_eq!(err.loc.line, 1);
+}
+
+#[test]
+fn recovery_ignores_unbalanced_parens_and_brackets() {
+    let (outline, errors) = recover("function f() { a(; }\nb()");
+    assert_eq!(
+        outline,
+        "Program[FunctionDeclaration[InvalidStatement] ExpressionStatement]"
+    );
+    assert_eq!(errors, ["Unexpected token ';' (1:17)"]);
+
+    let (outline, errors) = recover("function f() { a[1; }\nb()");
+    assert_eq!(
+        outline,
+        "Program[FunctionDeclaration[InvalidStatement] ExpressionStatement]"
+    );
+    assert_eq!(errors, ["Unexpected token ';' (1:18)"]);
+
+    let (outline, _) = recover("x = [1, 2;\ny()");
+    assert_eq!(outline, "Program[InvalidStatement ExpressionStatement]");
+}
+
+#[test]
+fn recovery_in_nested_block() {
+    let (outline, errors) = recover("function f() { if (x) { a b; } c(); }\nd()");
+    assert_eq!(
+        outline,
+        "Program[FunctionDeclaration[IfStatement[BlockStatement[InvalidStatement]] ExpressionStatement] ExpressionStatement]"
+    );
+    assert_eq!(errors, ["Unexpected token 'b' (1:26)"]);
+
+    // 语句块中的错误不会跳过语句块的右花括号
+    let (outline, _) = recover("{ a(; }\nb()\n{ c[; }");
+    assert_eq!(
+        outline,
+        "Program[BlockStatement[InvalidStatement] ExpressionStatement BlockStatement[InvalidStatement]]"
+    );
+}
+
+#[test]
+fn strict_reserved_words_in_binding_names() {
+    for source in [
+        "'use strict'; function static() {}",
+        "'use strict'; (function static() {})",
+        "'use strict'; let {yield} = o",
+        "'use strict'; ({yield} = o)",
+        "class static {}",
+        "(class yield {})",
+        "(function* yield() {})",
+        "function* g() { var {yield} = o }",
+    ] {
+        parse_error(source, SourceType::Script);
+    }
+    for source in [
+        "function static() {}",
+        "function* yield() {}",
+        "let {yield, static: s} = o",
+    ] {
+        assert_parses(source, SourceType::Script);
+    }
+}
+
+#[test]
+fn await_binding_names_in_modules() {
+    for source in [
+        "function await() {}",
+        "(function await() {})",
+        "class await {}",
+        "let {await} = x",
+        "({await} = x)",
+    ] {
+        let err = parse_error(source, SourceType::Module);
+        assert_eq!(err.code(), Some(EarlyErrorCode::ReservedWord), "{}", source);
+    }
+    for source in ["function await() {}", "class await {}", "let {await} = x"] {
+        assert_parses(source, SourceType::Script);
+    }
+    let err = parse_error("async function f() { let {await} = x }", SourceType::Script);
+    assert_eq!(err.code(), Some(EarlyErrorCode::InvalidIdentifier));
+}
+
+fn attached(source: &str) -> Value {
+    let arena = Bump::new();
+    let options = ParserOptions {
+        attach_comments: true,
+        locations: false,
+        ..Default::default()
+    };
+    let program = snail::parse(&arena, source, options).unwrap();
+    serde_json::to_value(&program).unwrap()
+}
+
+fn comment_values(comments: &Value) -> Vec<&str> {
+    match comments {
+        Value::Array(comments) => comments
+            .iter()
+            .map(|comment| comment["value"].as_str().unwrap())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[test]
+fn comments_in_empty_bodies_are_inner() {
+    let program = attached("function f() { /* c */ }");
+    let function = &program["body"][0];
+    assert!(function["id"].get("trailingComments").is_none());
+    assert_eq!(comment_values(&function["body"]["innerComments"]), [" c "]);
+
+    let program = attached("class A { // c\n}\nif (a) {\n  // d\n}");
+    assert_eq!(
+        comment_values(&program["body"][0]["body"]["innerComments"]),
+        [" c"]
+    );
+    assert_eq!(
+        comment_values(&program["body"][1]["consequent"]["innerComments"]),
+        [" d"]
+    );
+
+    // 函数体中的注释挂载到函数体中的语句上，而不是同一行的函数名
+    let program = attached("function f() { // c\n  a();\n}");
+    let function = &program["body"][0];
+    assert!(function["id"].get("trailingComments").is_none());
+    assert_eq!(
+        comment_values(&function["body"]["body"][0]["leadingComments"]),
+        [" c"]
+    );
+}
+
+// 注释的类型、内容、开始和结束的位置，以及开始和结束的行列
+type CommentInfo = (
+    CommentKind,
+    String,
+    usize,
+    usize,
+    (usize, usize),
+    (usize, usize),
+);
+
+fn comment_info(comment: &Comment) -> CommentInfo {
+    let loc = comment.loc.as_ref().unwrap();
+    (
+        comment.kind,
+        comment.value.to_string(),
+        comment.start,
+        comment.end,
+        (loc.start.line, loc.start.col),
+        (loc.end.line, loc.end.col),
+    )
+}
+
+#[test]
+fn comments_are_collected_in_order() {
+    let source = "/* a */ x; // b\ny /* c\n d */;\nz = a /* e */ / 2 + /* f */ /re/g;\n// g";
+    let received = Rc::new(RefCell::new(vec![]));
+    let on_comment = {
+        let received = received.clone();
+        OnComment::new(move |comment| received.borrow_mut().push(comment_info(comment)))
+    };
+    let options = ParserOptions {
+        on_comment: Some(on_comment),
+        ..Default::default()
+    };
+    let arena = Bump::new();
+    let program = snail::parse(&arena, source, options).unwrap();
+    let comments: Vec<_> = program.comments.iter().map(comment_info).collect();
+    let expected = [
+        (CommentKind::Block, " a ", 0, 7, (1, 0), (1, 7)),
+        (CommentKind::Line, " b", 11, 15, (1, 11), (1, 15)),
+        (CommentKind::Block, " c\n d ", 18, 28, (2, 2), (3, 5)),
+        (CommentKind::Block, " e ", 36, 43, (4, 6), (4, 13)),
+        (CommentKind::Block, " f ", 50, 57, (4, 20), (4, 27)),
+        (CommentKind::Line, " g", 65, 69, (5, 0), (5, 4)),
+    ]
+    .map(|(kind, value, start, end, loc_start, loc_end)| {
+        (kind, value.to_string(), start, end, loc_start, loc_end)
+    });
+    assert_eq!(comments, expected);
+    // 回调按照注释出现的顺序调用，每个注释只调用一次
+    assert_eq!(*received.borrow(), expected);
+}
+
+fn literal(source: &str) -> Value {
+    let arena = Bump::new();
+    let options = ParserOptions {
+        locations: false,
+        ..Default::default()
+    };
+    let program = snail::parse(&arena, source, options).unwrap();
+    let mut program = serde_json::to_value(&program).unwrap();
+    program["body"][0]["expression"].take()
+}
+
+#[test]
+fn number_literal_values() {
+    for (source, value) in [
+        ("1_000_000", 1e6),
+        ("1_0.5_5e1_0", 10.55e10),
+        ("0.0_1", 0.01),
+        ("0x1_F", 31.0),
+        ("0b1_01", 5.0),
+        ("0o1_7", 15.0),
+        ("017", 15.0),
+        ("019", 19.0),
+        ("09.5", 9.5),
+        (".5e+3", 500.0),
+        ("1E-7", 1e-7),
+        ("0.5e-0_1", 0.05),
+        ("5e-324", 5e-324),
+        ("0xFFFFFFFFFFFFFFFFF", 2f64.powi(68)),
+    ] {
+        assert_eq!(literal(source)["value"].as_f64(), Some(value), "{}", source);
+    }
+    // 超出范围的数字是 Infinity，序列化为 null
+    assert_eq!(literal("2e308")["value"], Value::Null);
+
+    for (source, bigint) in [
+        ("0n", "0"),
+        ("1_0n", "10"),
+        ("0x1_Fn", "0x1F"),
+        ("0b1_1n", "0b11"),
+    ] {
+        let literal = literal(source);
+        assert_eq!(literal["value"], Value::Null);
+        assert_eq!(literal["bigint"], bigint, "{}", source);
+        assert_eq!(literal["raw"], source);
+    }
+}
+
+#[test]
+fn invalid_number_literals() {
+    for (source, message) in [
+        (
+            "0_1",
+            "Numeric separator is not allowed in legacy octal like integer literal (1:1)",
+        ),
+        (
+            "08_1",
+            "Numeric separator is not allowed in legacy octal like integer literal (1:2)",
+        ),
+        (
+            "1__0",
+            "Numeric separator must be exactly one underscore (1:2)",
+        ),
+        (
+            "1_",
+            "Numeric separators are not allowed at the end of numeric literals (1:1)",
+        ),
+        (
+            "1._5",
+            "Numeric separator is not allowed at the first of digits (1:2)",
+        ),
+        (
+            "1e_1",
+            "Numeric separator is not allowed at the first of digits (1:2)",
+        ),
+        (
+            "0x_1",
+            "Numeric separator is not allowed at the first of digits (1:2)",
+        ),
+        ("1e", "Invalid number (1:0)"),
+        ("1e+", "Invalid number (1:0)"),
+        ("1.5n", "Identifier directly after number (1:3)"),
+        ("1e1n", "Identifier directly after number (1:3)"),
+        ("017n", "Identifier directly after number (1:3)"),
+        ("3in x", "Identifier directly after number (1:1)"),
+    ] {
+        let err = parse_error(source, SourceType::Script);
+        assert_eq!(err.to_string(), message, "{}", source);
+    }
+}
+
+#[test]
+fn legacy_octal_in_strict_mode() {
+    for source in [
+        "'use strict'; 017",
+        "'use strict'; 08",
+        "'use strict'; '\\07'",
+        "'use strict'\n017",
+        "'\\07'; 'use strict';",
+        "function f() { 'a'; 'use strict'; 017 }",
+        "() => { 'use strict'; '\\1' }",
+        "class A { m() { 017 } }",
+    ] {
+        parse_error(source, SourceType::Script);
+    }
+    parse_error("017", SourceType::Module);
+    // 严格模式只到函数体或类的右花括号为止
+    for source in [
+        "017; '\\07'; '\\0'",
+        "'use strict'\n+ 017",
+        "function f() { 'use strict' }\n017",
+        "class A {}\n'\\07'",
+        "'use strict'; '\\0'",
+    ] {
+        assert_parses(source, SourceType::Script);
+    }
+}
+
+#[test]
+fn directive_keeps_the_raw_text() {
+    let arena = Bump::new();
+    let source = r"'a\'b\n'; function f() { 'use\x20strict' }";
+    let program = snail::parse(&arena, source, ParserOptions::default()).unwrap();
+    let program = serde_json::to_value(&program).unwrap();
+    let directive = &program["body"][0];
+    assert_eq!(directive["directive"], r"a\'b\n");
+    assert_eq!(directive["expression"]["value"], "a'b\n");
+    assert_eq!(
+        program["body"][1]["body"]["body"][0]["directive"],
+        r"use\x20strict"
+    );
+}
+
+// 模板中每一段的 raw 与 cooked
+fn template_values(template: &Value) -> Vec<(&str, Option<&str>)> {
+    template["quasis"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|quasi| {
+            let value = &quasi["value"];
+            (value["raw"].as_str().unwrap(), value["cooked"].as_str())
+        })
+        .collect()
+}
+
+#[test]
+fn template_raw_and_cooked_values() {
+    let template = literal("`\\u{41}\\x42\\n${a}\\`\\\n\\r\n${b}\r\n`");
+    assert_eq!(
+        template_values(&template),
+        [
+            (r"\u{41}\x42\n", Some("AB\n")),
+            ("\\`\\\n\\r\n", Some("`\r\n")),
+            // 源码中的 \r\n 在 raw 和 cooked 中都是 \n
+            ("\n", Some("\n")),
+        ]
+    );
+
+    // 带标签的模板中不合法的转义，cooked 为 null
+    let tagged = literal(r"tag`\unicode ${a} \x4 \01 \u{41}`");
+    assert_eq!(
+        template_values(&tagged["quasi"]),
+        [(r"\unicode ", None), (r" \x4 \01 \u{41}", None)]
+    );
+    let tagged = literal(r"tag`\u{41}${a}\xg`");
+    assert_eq!(
+        template_values(&tagged["quasi"]),
+        [(r"\u{41}", Some("A")), (r"\xg", None)]
+    );
+
+    for source in [r"`\unicode`", r"`${a}\x4`", r"`\01`", r"`\u{110000}`"] {
+        let err = parse_error(source, SourceType::Script);
+        assert_eq!(
+            err.code(),
+            Some(EarlyErrorCode::InvalidTemplateEscape),
+            "{}",
+            source
+        );
+    }
+    assert_eq!(
+        parse_error("a = `ok ${b}\\u{g}`", SourceType::Script)
+            .loc
+            .col,
+        12
+    );
+}
+
+fn parse_hashbang(
+    source: &str,
+    ecma_version: u32,
+    allow_hash_bang: Option<bool>,
+) -> Result<Option<String>, ParseError> {
+    let arena = Bump::new();
+    let options = ParserOptions {
+        ecma_version,
+        allow_hash_bang,
+        ..Default::default()
+    };
+    let program = snail::parse(&arena, source, options)?;
+    Ok(program.hashbang.map(str::to_string))
+}
+
+#[test]
+fn hashbang_at_the_start() {
+    let source = "#!/usr/bin/env node\nx";
+    let arena = Bump::new();
+    let program = snail::parse(&arena, source, Default::default()).unwrap();
+    assert_eq!(program.hashbang, Some("/usr/bin/env node"));
+    assert_eq!(
+        serde_json::to_value(&program).unwrap()["hashbang"],
+        "/usr/bin/env node"
+    );
+    // 同时作为第一个注释记录下来
+    let comment = &program.comments[0];
+    assert_eq!(comment.kind, CommentKind::Line);
+    assert_eq!(
+        (comment.value, comment.start, comment.end),
+        ("/usr/bin/env node", 0, 19)
+    );
+
+    assert_eq!(
+        parse_hashbang(source, 2022, Some(true)).unwrap().as_deref(),
+        Some("/usr/bin/env node")
+    );
+    assert_eq!(parse_hashbang("x", 2023, None).unwrap(), None);
+
+    let err = parse_hashbang(source, 2022, None).unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnsupportedSyntax);
+    assert_eq!(err.pos, 0);
+    let err = parse_hashbang(source, 2023, Some(false)).unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedCharacter);
+    assert_eq!(err.pos, 0);
+    // 只有文件开头的 #! 是 hashbang
+    for source in [" #!x", "x\n#!y"] {
+        let err = parse_hashbang(source, 2023, None).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedCharacter, "{}", source);
+    }
+}
@@ -0,0 +1,234 @@
+use serde_json::{json, Value};
+use snail::{ast::Position, Bump, LineIndex, ParserOptions, PositionEncoding};
+
+// 😀 在 UTF-16 中是两个单位，é 是一个；各种换行符都结束一行
+const SOURCE: &str = "\"😀é\"; a\r\nb\rc\u{2028}d; \"😀\"; x";
+
+// 每个表达式语句的 start、end，以及开始的行和列
+fn expressions(encoding: PositionEncoding) -> Vec<(u64, u64, u64, u64)> {
+    let arena = Bump::new();
+    let options = ParserOptions {
+        position_encoding: encoding,
+        ..Default::default()
+    };
+    let program = snail::parse(&arena, SOURCE, options).unwrap();
+    let program = serde_json::to_value(&program).unwrap();
+    let number = |value: &Value| value.as_u64().unwrap();
+    program["body"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|stmt| {
+            let expr = &stmt["expression"];
+            let start = &expr["loc"]["start"];
+            (
+                number(&expr["start"]),
+                number(&expr["end"]),
+                number(&start["line"]),
+                number(&start["column"]),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn utf8_positions() {
+    assert_eq!(
+        expressions(PositionEncoding::Utf8),
+        [
+            (0, 8, 1, 0),
+            (10, 11, 1, 10),
+            (13, 14, 2, 0),
+            (15, 16, 3, 0),
+            (19, 20, 4, 0),
+            (22, 28, 4, 3),
+            (30, 31, 4, 11),
+        ]
+    );
+}
+
+#[test]
+fn utf16_positions() {
+    assert_eq!(
+        expressions(PositionEncoding::Utf16),
+        [
+            (0, 5, 1, 0),
+            (7, 8, 1, 7),
+            (10, 11, 2, 0),
+            (12, 13, 3, 0),
+            (14, 15, 4, 0),
+            (17, 21, 4, 3),
+            (23, 24, 4, 9),
+        ]
+    );
+}
+
+#[test]
+fn char_positions() {
+    assert_eq!(
+        expressions(PositionEncoding::Char),
+        [
+            (0, 4, 1, 0),
+            (6, 7, 1, 6),
+            (9, 10, 2, 0),
+            (11, 12, 3, 0),
+            (13, 14, 4, 0),
+            (16, 19, 4, 3),
+            (21, 22, 4, 8),
+        ]
+    );
+}
+
+#[test]
+fn error_positions_use_the_encoding() {
+    let arena = Bump::new();
+    let options = ParserOptions {
+        position_encoding: PositionEncoding::Utf16,
+        ..Default::default()
+    };
+    let result = snail::parse(&arena, "'😀' @", options).map(|_| ());
+    let err = result.unwrap_err();
+    assert_eq!(err.pos, 5);
+    assert_eq!((err.loc.line, err.loc.col), (1, 5));
+}
+
+// 树中所有带有 start 和 end 的对象，包括注释
+fn positioned(value: &Value, found: &mut Vec<serde_json::Map<String, Value>>) {
+    match value {
+        Value::Object(map) => {
+            if map.get("start").is_some_and(Value::is_u64) && map.contains_key("end") {
+                found.push(map.clone());
+            }
+            map.values().for_each(|child| positioned(child, found));
+        }
+        Value::Array(list) => list.iter().for_each(|child| positioned(child, found)),
+        _ => {}
+    }
+}
+
+fn parse_positioned(options: ParserOptions) -> (Value, Vec<serde_json::Map<String, Value>>) {
+    let arena = Bump::new();
+    let source = "/* 😀 */ let a = [b, { c }]; // d\nf(a)";
+    let program = snail::parse(&arena, source, options).unwrap();
+    let comments = serde_json::to_value(&program.comments).unwrap();
+    let mut program = serde_json::to_value(&program).unwrap();
+    program["comments"] = comments;
+    let mut found = vec![];
+    positioned(&program, &mut found);
+    assert!(found.len() > 10);
+    (program, found)
+}
+
+#[test]
+fn locations_can_be_left_out() {
+    let options = ParserOptions {
+        locations: false,
+        ..Default::default()
+    };
+    for node in parse_positioned(options).1 {
+        assert!(!node.contains_key("loc"), "{:?}", node);
+        assert!(!node.contains_key("range"), "{:?}", node);
+    }
+    // 默认记录 loc，不记录 range
+    for node in parse_positioned(Default::default()).1 {
+        assert!(node["loc"]["start"]["line"].is_u64(), "{:?}", node);
+        assert!(!node.contains_key("range"), "{:?}", node);
+    }
+}
+
+#[test]
+fn ranges_match_start_and_end() {
+    for (encoding, program_range, comment_range) in [
+        (PositionEncoding::Utf8, [0, 40], [0, 10]),
+        (PositionEncoding::Utf16, [0, 38], [0, 8]),
+    ] {
+        let options = ParserOptions {
+            ranges: true,
+            position_encoding: encoding,
+            ..Default::default()
+        };
+        let (program, nodes) = parse_positioned(options);
+        for node in &nodes {
+            assert_eq!(node["range"], json!([node["start"], node["end"]]));
+        }
+        assert_eq!(program["range"], json!(program_range));
+        assert_eq!(program["comments"][0]["range"], json!(comment_range));
+        assert_eq!(
+            program["body"][1]["range"],
+            json!([program_range[1] - 4, program_range[1]])
+        );
+    }
+}
+
+fn position(line: usize, col: usize) -> Position {
+    Position { line, col }
+}
+
+#[test]
+fn line_index_line_terminators() {
+    // 行分别以 \r\n、\r、U+2028 和 U+2029 结束
+    let source = "a\r\nb\rc\u{2028}d\u{2029}😀e";
+    let utf16 = LineIndex::new(source, PositionEncoding::Utf16);
+    assert_eq!(utf16.line_count(), 5);
+    assert_eq!(utf16.position(0), position(1, 0));
+    // \r\n 中间仍然属于第一行
+    assert_eq!(utf16.position(2), position(1, 2));
+    assert_eq!(utf16.position(3), position(2, 0));
+    assert_eq!(utf16.position(5), position(3, 0));
+    assert_eq!(utf16.position(7), position(4, 0));
+    assert_eq!(utf16.position(9), position(5, 0));
+    // 😀 是两个 UTF-16 单位
+    assert_eq!(utf16.position(11), position(5, 2));
+    assert_eq!(utf16.position(100), position(5, 3));
+
+    let chars = LineIndex::new(source, PositionEncoding::Char);
+    assert_eq!(chars.line_count(), 5);
+    assert_eq!(chars.position(10), position(5, 1));
+    assert_eq!(chars.position(100), position(5, 2));
+
+    let utf8 = LineIndex::new(source, PositionEncoding::Utf8);
+    assert_eq!(utf8.position(source.len() - 1), position(5, 4));
+}
+
+#[test]
+fn line_index_offsets() {
+    let source = "a\r\nb\rc\u{2028}d\u{2029}😀e";
+    for encoding in [
+        PositionEncoding::Utf8,
+        PositionEncoding::Utf16,
+        PositionEncoding::Char,
+    ] {
+        let index = LineIndex::new(source, encoding);
+        let len = match encoding {
+            PositionEncoding::Utf8 => source.len(),
+            PositionEncoding::Utf16 => source.encode_utf16().count(),
+            PositionEncoding::Char => source.chars().count(),
+        };
+        for offset in 0..=len {
+            assert_eq!(index.offset(&index.position(offset)), Some(offset));
+        }
+        // 超出行尾的列、不存在的行
+        assert_eq!(index.offset(&position(1, 3)), None);
+        assert_eq!(index.offset(&position(0, 0)), None);
+        assert_eq!(index.offset(&position(6, 0)), None);
+    }
+    let utf16 = LineIndex::new(source, PositionEncoding::Utf16);
+    assert_eq!(utf16.offset(&position(5, 2)), Some(11));
+    assert_eq!(utf16.offset(&position(5, 3)), Some(12));
+    assert_eq!(utf16.offset(&position(5, 4)), None);
+}
+
+#[test]
+fn line_index_agrees_with_parser() {
+    for encoding in [
+        PositionEncoding::Utf8,
+        PositionEncoding::Utf16,
+        PositionEncoding::Char,
+    ] {
+        let index = LineIndex::new(SOURCE, encoding);
+        for (start, _, line, col) in expressions(encoding) {
+            let expected = position(line as usize, col as usize);
+            assert_eq!(index.position(start as usize), expected);
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use serde_json::{json, Value};
+use snail::{error::ParseErrorKind, Bump, ParserOptions, PositionEncoding};
+
+fn parse_regexp(source: &str, ecma_version: u32) -> Result<(), String> {
+    let arena = Bump::new();
+    let options = ParserOptions {
+        ecma_version,
+        ..Default::default()
+    };
+    let result = snail::parse(&arena, source, options).map(|_| ());
+    result.map_err(|err| {
+        assert!(
+            matches!(err.kind, ParseErrorKind::InvalidRegExp),
+            "{}: {}",
+            source,
+            err
+        );
+        // 去掉 `Invalid regular expression: /.../: ` 前缀，只比较原因
+        err.message.rsplit(": ").next().unwrap().to_string()
+    })
+}
+
+fn assert_valid(sources: &[&str]) {
+    for source in sources {
+        if let Err(message) = parse_regexp(source, 2024) {
+            panic!("{}: {}", source, message);
+        }
+    }
+}
+
+fn assert_invalid(cases: &[(&str, &str)]) {
+    for (source, message) in cases {
+        assert_eq!(
+            parse_regexp(source, 2024),
+            Err(message.to_string()),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn flags() {
+    assert_valid(&["/a/dgimsuy", "/a/v"]);
+    assert_invalid(&[
+        ("/a/gg", "Duplicate regular expression flag"),
+        ("/a/x", "Invalid regular expression flag"),
+        ("/a/uv", "Invalid regular expression flag"),
+    ]);
+    for (source, ecma_version) in [("/a/s", 2018), ("/a/d", 2022), ("/a/v", 2024)] {
+        assert_eq!(parse_regexp(source, ecma_version), Ok(()));
+        assert_eq!(
+            parse_regexp(source, ecma_version - 1),
+            Err("Invalid regular expression flag".to_string()),
+            "{} in {}",
+            source,
+            ecma_version - 1
+        );
+    }
+}
+
+#[test]
+fn annex_b_only_without_unicode_flags() {
+    // 没有 u 和 v 时按照附录 B 的宽松语法解析
+    assert_valid(&[
+        "/{/",
+        "/]/",
+        "/a{,2}/",
+        "/\\-/",
+        "/\\c/",
+        "/\\1/",
+        "/(a)\\2/",
+        "/(?=a)*/",
+        "/\\k/",
+        "/\\p{Foo}/",
+    ]);
+    for flag in ["u", "v"] {
+        for (pattern, message) in [
+            ("/{/", "Lone quantifier brackets"),
+            ("/]/", "Lone quantifier brackets"),
+            ("/a{,2}/", "Incomplete quantifier"),
+            ("/\\-/", "Invalid escape"),
+            ("/\\c/", "Invalid unicode escape"),
+            ("/\\1/", "Invalid escape"),
+            ("/(a)\\2/", "Invalid escape"),
+            ("/(?=a)*/", "Invalid quantifier"),
+            ("/\\k/", "Invalid named reference"),
+            ("/\\u{110000}/", "Invalid unicode escape"),
+        ] {
+            assert_invalid(&[(&format!("{}{}", pattern, flag), message)]);
+        }
+    }
+    assert_valid(&["/[\\-]/u", "/\\u{1F600}/u", "/\\u{1F600}/v"]);
+}
+
+#[test]
+fn class_set_syntax_only_with_v_flag() {
+    assert_valid(&[
+        "/[(]/u",
+        "/[a&&&b]/u",
+        "/[a&&b]/v",
+        "/[a--b]/v",
+        "/[\\(]/v",
+        "/[[a-z]--[aeiou]]/v",
+        "/[\\p{L}--\\p{Lu}]/v",
+        "/[\\q{ab|c}]/v",
+        "/[^\\q{a}]/v",
+    ]);
+    assert_invalid(&[
+        ("/[(]/v", "Invalid character in character class"),
+        ("/[|]/v", "Invalid character in character class"),
+        ("/[a&&&b]/v", "Invalid character in character class"),
+        ("/[a&&b--c]/v", "Unterminated character class"),
+        ("/[\\q{ab}]/u", "Invalid escape"),
+        ("/\\q{ab}/v", "Invalid escape"),
+        ("/[a-\\d]/u", "Invalid character class"),
+        ("/[z-a]/", "Range out of order in character class"),
+        // u 模式中是 `a` 到 `-` 的范围
+        ("/[a--b]/u", "Range out of order in character class"),
+        // 取反的集合不能包含字符串
+        (
+            "/[^\\q{ab}]/v",
+            "Negated character class may contain strings",
+        ),
+        (
+            "/[^\\p{RGI_Emoji}]/v",
+            "Negated character class may contain strings",
+        ),
+    ]);
+}
+
+#[test]
+fn named_groups() {
+    assert_valid(&[
+        "/(?<a>x)(?<b>y)\\k<b>/u",
+        "/(?<$_>.)/u",
+        "/(?<é>.)/u",
+        // 可以引用在后面定义的分组
+        "/\\k<a>(?<a>.)/",
+        // 没有命名分组也没有 u 时，`\k` 是普通字符
+        "/\\k<a>/",
+        "/\\k<a/",
+    ]);
+    assert_invalid(&[
+        ("/(?<a>x)(?<a>y)/", "Duplicate capture group name"),
+        ("/(?<a>x)|(?<a>y)/", "Duplicate capture group name"),
+        ("/(?<1a>.)/u", "Invalid capture group name"),
+        ("/\\k<a>/u", "Invalid named capture referenced"),
+        ("/(?<b>.)\\k<a>/", "Invalid named capture referenced"),
+        ("/(?<a>.)\\k/", "Invalid named reference"),
+        ("/(?<a>.)\\k<a/", "Invalid capture group name"),
+    ]);
+    assert_eq!(parse_regexp("/(?<a>.)/", 2018), Ok(()));
+    assert_eq!(
+        parse_regexp("/(?<a>.)/", 2017),
+        Err("Invalid group".to_string())
+    );
+}
+
+#[test]
+fn property_escapes() {
+    assert_valid(&[
+        "/\\p{L}/u",
+        "/\\p{Letter}/u",
+        "/\\P{Lu}/u",
+        "/\\p{ASCII}/u",
+        "/\\p{Any}/u",
+        "/\\p{General_Category=Lu}/u",
+        "/\\p{Script=Greek}/u",
+        "/\\p{sc=Grek}/u",
+        "/\\p{Script_Extensions=Latin}/u",
+        "/\\p{Lu}/v",
+        // 字符串的属性只能在 v 模式中使用
+        "/\\p{RGI_Emoji}/v",
+        "/\\p{Basic_Emoji}/v",
+    ]);
+    assert_invalid(&[
+        ("/\\p{Foo}/u", "Invalid property name"),
+        ("/\\p{ascii}/u", "Invalid property name"),
+        ("/\\p{Foo=Greek}/u", "Invalid property name"),
+        ("/\\p{L=Lu}/u", "Invalid property name"),
+        ("/\\p{}/u", "Invalid property name"),
+        ("/\\p{Lu/u", "Invalid property name"),
+        ("/\\p/u", "Invalid property name"),
+        ("/\\p{Script=Foo}/u", "Invalid property value"),
+        ("/\\p{RGI_Emoji}/u", "Invalid property name"),
+        ("/\\P{RGI_Emoji}/v", "Invalid property name"),
+        ("/\\P{Basic_Emoji}/v", "Invalid property name"),
+    ]);
+}
+
+#[test]
+fn quantifiers() {
+    assert_valid(&[
+        "/a{1,2}/",
+        "/a{2,}/u",
+        "/a{1}?/u",
+        "/a{2,2}/u",
+        "/a{99999999999}/u",
+    ]);
+    assert_invalid(&[
+        ("/a{2,1}/", "numbers out of order in {} quantifier"),
+        ("/a{2,1}/u", "numbers out of order in {} quantifier"),
+        ("/a{3,2}?/", "numbers out of order in {} quantifier"),
+        ("/a**/", "Nothing to repeat"),
+        ("/x{2}{3}/", "Nothing to repeat"),
+        ("/(?:a/", "Unterminated group"),
+        ("/a)/", "Unmatched ')'"),
+    ]);
+}
+
+// 源码中第一个语句的正则字面量的 pattern AST
+fn regexp_ast(source: &str, position_encoding: PositionEncoding) -> Value {
+    let arena = Bump::new();
+    let options = ParserOptions {
+        parse_regex_ast: true,
+        position_encoding,
+        ..Default::default()
+    };
+    let program = snail::parse(&arena, source, options).unwrap();
+    let mut program = serde_json::to_value(&program).unwrap();
+    program["body"][0]["expression"]["regex"]["ast"].take()
+}
+
+fn elements(source: &str) -> Vec<Value> {
+    let ast = regexp_ast(source, PositionEncoding::Utf8);
+    let alternatives = ast["alternatives"].as_array().unwrap();
+    assert_eq!(alternatives.len(), 1, "{}", source);
+    alternatives[0]["elements"].as_array().unwrap().clone()
+}
+
+// 只保留节点中给定的字段，不存在的字段为 null
+fn fields(node: &Value, keys: &[&str]) -> Value {
+    keys.iter()
+        .map(|key| (key.to_string(), node[key].clone()))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+#[test]
+fn regexp_ast_is_only_built_when_enabled() {
+    let arena = Bump::new();
+    let program = snail::parse(&arena, "/a/u", ParserOptions::default()).unwrap();
+    let program = serde_json::to_value(&program).unwrap();
+    let regex = &program["body"][0]["expression"]["regex"];
+    assert_eq!(regex, &json!({ "pattern": "a", "flags": "u" }));
+}
+
+#[test]
+fn regexp_ast_shape() {
+    let group = json!({
+        "type": "CapturingGroup", "start": 3, "end": 10, "raw": "(?<n>b)",
+        "name": "n",
+        "alternatives": [{
+            "type": "Alternative", "start": 8, "end": 9, "raw": "b",
+            "elements": [
+                { "type": "Character", "start": 8, "end": 9, "raw": "b", "value": 98 },
+            ],
+        }],
+    });
+    assert_eq!(
+        regexp_ast("/a|(?<n>b)+?\\k<n>$/u", PositionEncoding::Utf8),
+        json!({
+            "type": "Pattern", "start": 1, "end": 18, "raw": "a|(?<n>b)+?\\k<n>$",
+            "alternatives": [
+                {
+                    "type": "Alternative", "start": 1, "end": 2, "raw": "a",
+                    "elements": [
+                        { "type": "Character", "start": 1, "end": 2, "raw": "a", "value": 97 },
+                    ],
+                },
+                {
+                    "type": "Alternative", "start": 3, "end": 18, "raw": "(?<n>b)+?\\k<n>$",
+                    "elements": [
+                        {
+                            "type": "Quantifier", "start": 3, "end": 12, "raw": "(?<n>b)+?",
+                            "min": 1, "max": null, "greedy": false, "element": group,
+                        },
+                        { "type": "Backreference", "start": 12, "end": 17, "raw": "\\k<n>", "ref": "n" },
+                        { "type": "Assertion", "start": 17, "end": 18, "raw": "$", "kind": "end" },
+                    ],
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn regexp_ast_quantifiers_and_groups() {
+    let quantifiers: Vec<_> = elements("/a*b+c?d{2}e{2,}f{2,3}?/")
+        .iter()
+        .map(|node| fields(node, &["raw", "min", "max", "greedy"]))
+        .collect();
+    assert_eq!(
+        quantifiers,
+        [
+            json!({ "raw": "a*", "min": 0, "max": null, "greedy": true }),
+            json!({ "raw": "b+", "min": 1, "max": null, "greedy": true }),
+            json!({ "raw": "c?", "min": 0, "max": 1, "greedy": true }),
+            json!({ "raw": "d{2}", "min": 2, "max": 2, "greedy": true }),
+            json!({ "raw": "e{2,}", "min": 2, "max": null, "greedy": true }),
+            json!({ "raw": "f{2,3}?", "min": 2, "max": 3, "greedy": false }),
+        ]
+    );
+
+    let nodes = elements("/(?:a)(b)\\1/");
+    assert_eq!(nodes[0]["type"], "Group");
+    assert_eq!(nodes[1]["type"], "CapturingGroup");
+    assert_eq!(nodes[1]["name"], Value::Null);
+    assert_eq!(
+        fields(&nodes[2], &["type", "ref"]),
+        json!({ "type": "Backreference", "ref": 1 })
+    );
+}
+
+#[test]
+fn regexp_ast_assertions_and_character_sets() {
+    let assertions: Vec<_> = elements("/^\\b\\B(?=a)(?!b)(?<=c)(?<!d)$/")
+        .iter()
+        .map(|node| fields(node, &["type", "kind", "negate"]))
+        .collect();
+    assert_eq!(
+        assertions,
+        [
+            json!({ "type": "Assertion", "kind": "start", "negate": null }),
+            json!({ "type": "Assertion", "kind": "word", "negate": false }),
+            json!({ "type": "Assertion", "kind": "word", "negate": true }),
+            json!({ "type": "Assertion", "kind": "lookahead", "negate": false }),
+            json!({ "type": "Assertion", "kind": "lookahead", "negate": true }),
+            json!({ "type": "Assertion", "kind": "lookbehind", "negate": false }),
+            json!({ "type": "Assertion", "kind": "lookbehind", "negate": true }),
+            json!({ "type": "Assertion", "kind": "end", "negate": null }),
+        ]
+    );
+
+    let sets: Vec<_> = elements("/.\\d\\D\\s\\W\\p{L}\\p{ASCII}\\P{Script=Greek}/u")
+        .iter()
+        .map(|node| fields(node, &["type", "kind", "negate", "key", "value"]))
+        .collect();
+    assert_eq!(
+        sets,
+        [
+            json!({ "type": "CharacterSet", "kind": "any", "negate": null, "key": null, "value": null }),
+            json!({ "type": "CharacterSet", "kind": "digit", "negate": false, "key": null, "value": null }),
+            json!({ "type": "CharacterSet", "kind": "digit", "negate": true, "key": null, "value": null }),
+            json!({ "type": "CharacterSet", "kind": "space", "negate": false, "key": null, "value": null }),
+            json!({ "type": "CharacterSet", "kind": "word", "negate": true, "key": null, "value": null }),
+            json!({ "type": "CharacterSet", "kind": "property", "negate": false, "key": "General_Category", "value": "L" }),
+            json!({ "type": "CharacterSet", "kind": "property", "negate": false, "key": "ASCII", "value": null }),
+            json!({ "type": "CharacterSet", "kind": "property", "negate": true, "key": "Script", "value": "Greek" }),
+        ]
+    );
+}
+
+#[test]
+fn regexp_ast_character_classes() {
+    let class = &elements("/[^a-z\\d]/")[0];
+    assert_eq!(
+        fields(class, &["type", "negate", "unicodeSets"]),
+        json!({ "type": "CharacterClass", "negate": true, "unicodeSets": false })
+    );
+    let range = &class["elements"][0];
+    assert_eq!(range["type"], "CharacterClassRange");
+    assert_eq!(range["min"]["value"], 'a' as u32);
+    assert_eq!(range["max"]["value"], 'z' as u32);
+    assert_eq!(class["elements"][1]["kind"], "digit");
+
+    // v 模式中的集合运算与字符串
+    let class = &elements("/[\\p{L}--\\q{ab|c}]/v")[0];
+    assert_eq!(
+        fields(class, &["type", "negate"]),
+        json!({ "type": "ExpressionCharacterClass", "negate": false })
+    );
+    let expression = &class["expression"];
+    assert_eq!(expression["type"], "ClassSubtraction");
+    assert_eq!(expression["left"]["value"], "L");
+    let disjunction = &expression["right"];
+    assert_eq!(disjunction["type"], "ClassStringDisjunction");
+    let strings: Vec<_> = disjunction["alternatives"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|alternative| alternative["raw"].as_str().unwrap())
+        .collect();
+    assert_eq!(strings, ["ab", "c"]);
+
+    let class = &elements("/[[a-z]&&\\p{RGI_Emoji}]/v")[0];
+    let expression = &class["expression"];
+    assert_eq!(expression["type"], "ClassIntersection");
+    assert_eq!(
+        fields(&expression["left"], &["type", "unicodeSets"]),
+        json!({ "type": "CharacterClass", "unicodeSets": true })
+    );
+    assert_eq!(expression["right"]["strings"], true);
+}
+
+#[test]
+fn regexp_ast_positions_use_the_encoding() {
+    let source = "/😀é(a)/u";
+    for (encoding, group_start) in [
+        (PositionEncoding::Utf8, 7),
+        (PositionEncoding::Utf16, 4),
+        (PositionEncoding::Char, 3),
+    ] {
+        let ast = regexp_ast(source, encoding);
+        let elements = &ast["alternatives"][0]["elements"];
+        assert_eq!(elements[0]["value"], 0x1F600);
+        assert_eq!(elements[2]["start"], group_start, "{:?}", encoding);
+        assert_eq!(ast["end"], group_start + 3, "{:?}", encoding);
+    }
+}
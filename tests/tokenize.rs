@@ -0,0 +1,99 @@
+use snail::{Bump, ParserOptions, TokenLabel};
+
+fn tokens(source: &str) -> Vec<(TokenLabel, String)> {
+    let arena = Bump::new();
+    snail::tokenize(&arena, source, ParserOptions::default())
+        .map(|token| {
+            let token = token.unwrap();
+            (token.label, token.raw.to_string())
+        })
+        .collect()
+}
+
+fn labels(source: &str) -> Vec<TokenLabel> {
+    tokens(source).into_iter().map(|(label, _)| label).collect()
+}
+
+#[test]
+fn slash_is_division_or_regexp_by_context() {
+    use TokenLabel::{_If, _Return, Dot, Eq, IncDec, Name, Number, ParenL, ParenR, Regexp, Slash};
+
+    assert_eq!(labels("a / b / g"), [Name, Slash, Name, Slash, Name]);
+    assert_eq!(labels("f() / 2"), [Name, ParenL, ParenR, Slash, Number]);
+    assert_eq!(labels("x++ / 2"), [Name, IncDec, Slash, Number]);
+    // 换行不会让除号变成正则
+    assert_eq!(labels("a\n/b/g"), [Name, Slash, Name, Slash, Name]);
+
+    assert_eq!(
+        tokens("x = /re/g.test(s)")[2],
+        (Regexp, "/re/g".to_string())
+    );
+    assert_eq!(labels("return /x/"), [_Return, Regexp]);
+    // 语句条件的右括号之后是表达式的开始
+    assert_eq!(
+        labels("if (a) /re/.test(b)"),
+        [_If, ParenL, Name, ParenR, Regexp, Dot, Name, ParenL, Name, ParenR]
+    );
+    assert_eq!(
+        labels("x = a\n/=b/g"),
+        [Name, Eq, Name, TokenLabel::Assign, Name, Slash, Name]
+    );
+    // 文件开头的 `{` 是语句块，`}` 之后是语句的开始
+    use TokenLabel::{BraceL, BraceR};
+    assert_eq!(labels("{} /re/g"), [BraceL, BraceR, Regexp]);
+    assert_eq!(
+        labels("{}\n/foo/.test(a)"),
+        [BraceL, BraceR, Regexp, Dot, Name, ParenL, Name, ParenR]
+    );
+    assert_eq!(labels("({} / 2)")[3], Slash);
+}
+
+#[test]
+fn nested_templates() {
+    use TokenLabel::{BackQuote, BraceL, BraceR, Colon, DollarBraceL, Dot, Name, Number, Template};
+
+    let source = "`a${ `b${c}` }d${ {e: 1}.e }`";
+    assert_eq!(
+        labels(source),
+        [
+            BackQuote,
+            Template,
+            DollarBraceL,
+            BackQuote,
+            Template,
+            DollarBraceL,
+            Name,
+            BraceR,
+            Template,
+            BackQuote,
+            BraceR,
+            Template,
+            DollarBraceL,
+            BraceL,
+            Name,
+            Colon,
+            Number,
+            BraceR,
+            Dot,
+            Name,
+            BraceR,
+            Template,
+            BackQuote,
+        ]
+    );
+    let raws: Vec<_> = tokens(source)
+        .into_iter()
+        .filter(|(label, _)| *label == Template)
+        .map(|(_, raw)| raw)
+        .collect();
+    assert_eq!(raws, ["a", "b", "", "d", ""]);
+}
+
+#[test]
+fn stops_after_the_first_error() {
+    let arena = Bump::new();
+    let results: Vec<_> = snail::tokenize(&arena, "a # b c", ParserOptions::default()).collect();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+}
@@ -0,0 +1,105 @@
+use serde_json::Value;
+use snail::{
+    ast::{
+        expression::{Identifier, Literal},
+        visit::Visit,
+    },
+    Bump, ParserOptions, SourceType,
+};
+
+#[derive(Default)]
+struct Counter {
+    identifiers: Vec<(usize, usize)>,
+    literals: Vec<(usize, usize)>,
+}
+
+impl<'a> Visit<'a> for Counter {
+    fn visit_identifier(&mut self, node: &Identifier<'a>) {
+        self.identifiers.push((node.start, node.end));
+    }
+
+    fn visit_literal(&mut self, node: &Literal<'a>) {
+        self.literals.push((node.start, node.end));
+    }
+}
+
+// 序列化后的树中给定类型的所有节点的位置
+fn collect(node: &Value, kind: &str, found: &mut Vec<(usize, usize)>) {
+    match node {
+        Value::Object(map) => {
+            if map.get("type").and_then(Value::as_str) == Some(kind) {
+                let pos = |key: &str| map[key].as_u64().unwrap() as usize;
+                found.push((pos("start"), pos("end")));
+            }
+            map.values().for_each(|child| collect(child, kind, found));
+        }
+        Value::Array(list) => list.iter().for_each(|child| collect(child, kind, found)),
+        _ => {}
+    }
+}
+
+fn assert_visits_all(source: &str, source_type: SourceType) {
+    let arena = Bump::new();
+    let options = ParserOptions {
+        source_type,
+        ..Default::default()
+    };
+    let program = snail::parse(&arena, source, options).unwrap();
+    let mut counter = Counter::default();
+    counter.visit_program(&program);
+
+    let tree = serde_json::to_value(&program).unwrap();
+    for (kind, mut visited) in [
+        ("Identifier", counter.identifiers),
+        ("Literal", counter.literals),
+    ] {
+        let mut expected = vec![];
+        collect(&tree, kind, &mut expected);
+        expected.sort();
+        visited.sort();
+        assert!(!expected.is_empty());
+        assert_eq!(visited, expected, "{} in {}", kind, source);
+    }
+}
+
+#[test]
+fn visits_every_identifier_and_literal() {
+    assert_visits_all(
+        r#"
+        "use asm";
+        var a = 1, [b, c = "c"] = d, { e, f: g = null, ...h } = i;
+        for (let j in k) label: while (j) { if (j) continue label; else break label; }
+        for (const [l] of m) l;
+        for (n = 0; n < 2; n++) while (o) do p(); while (q);
+        function r(s, t = 2, ...u) { return s?.[t]?.v ?? u.w; }
+        function* x() { yield* y; }
+        async function z() { await z; }
+        class A extends B {
+            #c = 3;
+            static d;
+            ["e"] = 4;
+            constructor() { super(); new.target; this.#c; }
+            get f() { return super.f; }
+            static { A.g = 5n; }
+        }
+        const C = (D, { E }) => D + E, F = async G => G;
+        switch (H) { case 6: I; default: J`K${L}M`; }
+        try { throw /re/g; } catch ({ N }) { N } finally { O = P ? Q : typeof R; }
+        with (S) T;
+        ({ U, V: 7, "W": 8, 9: X, [Y]: Z, get a() {}, set b(v) {}, m() {} });
+        "#,
+        SourceType::Script,
+    );
+    assert_visits_all(
+        r#"
+        import a, { b as c, "d" as e } from "f";
+        import * as g from "h";
+        export { c as i, e as "j" };
+        export * as k from "l";
+        export * from "m";
+        export default class n {}
+        export const o = import("p"), q = import.meta;
+        "#,
+        SourceType::Module,
+    );
+}